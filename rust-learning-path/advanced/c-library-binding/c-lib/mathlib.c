@@ -4,11 +4,13 @@
 #include <ctype.h>
 #include <errno.h>
 
-// Global error message storage
+// Global error message and code storage
 static char last_error[256] = {0};
+static int last_error_code = MATHLIB_OK;
 
-// Helper function to set error message
-static void set_error(const char* message) {
+// Helper function to set error code and message
+static void set_error(int code, const char* message) {
+    last_error_code = code;
     strncpy(last_error, message, sizeof(last_error) - 1);
     last_error[sizeof(last_error) - 1] = '\0';
 }
@@ -24,7 +26,7 @@ int32_t multiply_numbers(int32_t a, int32_t b) {
 
 uint64_t factorial(uint32_t n) {
     if (n > 20) {
-        set_error("Factorial input too large (max 20)");
+        set_error(MATHLIB_ERR_OVERFLOW, "Factorial input too large (max 20)");
         return 0;
     }
     
@@ -38,13 +40,13 @@ uint64_t factorial(uint32_t n) {
 // String operations
 int reverse_string(const char* input, char* output, size_t output_size) {
     if (!input || !output) {
-        set_error("Null pointer passed to reverse_string");
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to reverse_string");
         return -1;
     }
     
     size_t len = strlen(input);
     if (len + 1 > output_size) {
-        set_error("Output buffer too small for reverse_string");
+        set_error(MATHLIB_ERR_INVALID_SIZE, "Output buffer too small for reverse_string");
         return -1;
     }
     
@@ -58,13 +60,13 @@ int reverse_string(const char* input, char* output, size_t output_size) {
 
 int uppercase_string(const char* input, char* output, size_t output_size) {
     if (!input || !output) {
-        set_error("Null pointer passed to uppercase_string");
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to uppercase_string");
         return -1;
     }
     
     size_t len = strlen(input);
     if (len + 1 > output_size) {
-        set_error("Output buffer too small for uppercase_string");
+        set_error(MATHLIB_ERR_INVALID_SIZE, "Output buffer too small for uppercase_string");
         return -1;
     }
     
@@ -78,7 +80,7 @@ int uppercase_string(const char* input, char* output, size_t output_size) {
 
 size_t string_length(const char* str) {
     if (!str) {
-        set_error("Null pointer passed to string_length");
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to string_length");
         return 0;
     }
     return strlen(str);
@@ -87,7 +89,7 @@ size_t string_length(const char* str) {
 // Array operations
 int32_t sum_array(const int32_t* array, size_t length) {
     if (!array) {
-        set_error("Null pointer passed to sum_array");
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to sum_array");
         return 0;
     }
     
@@ -100,12 +102,12 @@ int32_t sum_array(const int32_t* array, size_t length) {
 
 int32_t find_max(const int32_t* array, size_t length, int32_t* max_value) {
     if (!array || !max_value) {
-        set_error("Null pointer passed to find_max");
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to find_max");
         return -1;
     }
     
     if (length == 0) {
-        set_error("Empty array passed to find_max");
+        set_error(MATHLIB_ERR_INVALID_SIZE, "Empty array passed to find_max");
         return -1;
     }
     
@@ -119,11 +121,61 @@ int32_t find_max(const int32_t* array, size_t length, int32_t* max_value) {
     return 0;
 }
 
+int array_summarize(const int32_t* array, size_t length, ArraySummary* summary) {
+    if (!array || !summary) {
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to array_summarize");
+        return -1;
+    }
+    
+    if (length == 0) {
+        set_error(MATHLIB_ERR_INVALID_SIZE, "Empty array passed to array_summarize");
+        return -1;
+    }
+    
+    summary->sum = 0;
+    summary->max = array[0];
+    summary->min = array[0];
+    summary->count = length;
+    for (size_t i = 0; i < length; i++) {
+        summary->sum += array[i];
+        if (array[i] > summary->max) {
+            summary->max = array[i];
+        }
+        if (array[i] < summary->min) {
+            summary->min = array[i];
+        }
+    }
+    
+    return 0;
+}
+
+// Callbacks
+int sort_array(int* arr, size_t len, int (*cmp)(const int*, const int*)) {
+    if (!arr || !cmp) {
+        set_error(MATHLIB_ERR_NULL_POINTER, "Null pointer passed to sort_array");
+        return -1;
+    }
+    
+    // Insertion sort: only moves an element past ones that compare greater,
+    // so equal elements keep their order
+    for (size_t i = 1; i < len; i++) {
+        int value = arr[i];
+        size_t j = i;
+        while (j > 0 && cmp(&arr[j - 1], &value) > 0) {
+            arr[j] = arr[j - 1];
+            j--;
+        }
+        arr[j] = value;
+    }
+    
+    return 0;
+}
+
 // Memory allocation helpers
 char* allocate_string(size_t size) {
     char* str = malloc(size);
     if (!str) {
-        set_error("Failed to allocate memory for string");
+        set_error(MATHLIB_ERR_ALLOCATION, "Failed to allocate memory for string");
     }
     return str;
 }
@@ -137,4 +189,8 @@ void free_string(char* str) {
 // Error handling
 const char* get_last_error(void) {
     return last_error;
+}
+
+int get_last_error_code(void) {
+    return last_error_code;
 }
\ No newline at end of file
@@ -19,11 +19,36 @@ size_t string_length(const char* str);
 int32_t sum_array(const int32_t* array, size_t length);
 int32_t find_max(const int32_t* array, size_t length, int32_t* max_value);
 
+// Sum, maximum, minimum and count of an array, gathered in one pass.
+// The sum is 64-bit so it can't overflow for any array that fits in memory
+typedef struct {
+    int64_t sum;
+    int32_t max;
+    int32_t min;
+    size_t count;
+} ArraySummary;
+
+// Returns 0 on success, -1 on error
+int array_summarize(const int32_t* array, size_t length, ArraySummary* summary);
+
+// Callbacks
+// Sorts arr in place using cmp, which returns <0, 0 or >0 like qsort's comparator.
+// The sort is stable. Returns 0 on success, -1 on error
+int sort_array(int* arr, size_t len, int (*cmp)(const int*, const int*));
+
 // Memory allocation helpers
 char* allocate_string(size_t size);
 void free_string(char* str);
 
 // Error handling
+// Codes returned by get_last_error_code, for the last error set
+#define MATHLIB_OK 0
+#define MATHLIB_ERR_NULL_POINTER 1
+#define MATHLIB_ERR_OVERFLOW 2
+#define MATHLIB_ERR_INVALID_SIZE 3
+#define MATHLIB_ERR_ALLOCATION 4
+
 const char* get_last_error(void);
+int get_last_error_code(void);
 
 #endif // MATHLIB_H
\ No newline at end of file
@@ -49,9 +49,41 @@ void test_array_operations() {
     assert(find_max(arr, len, &max_val) == 0);
     assert(max_val == 5);
     
+    int32_t mixed[] = {-7, 3, INT32_MAX, -2, INT32_MAX};
+    ArraySummary summary;
+    assert(array_summarize(mixed, 5, &summary) == 0);
+    assert(summary.sum == -7 + 3 - 2 + 2 * (int64_t)INT32_MAX);
+    assert(summary.max == INT32_MAX);
+    assert(summary.min == -7);
+    assert(summary.count == 5);
+    
+    assert(array_summarize(arr, 0, &summary) == -1);
+    assert(array_summarize(NULL, len, &summary) == -1);
+    
     printf("Array operations: PASSED\n");
 }
 
+static int compare_descending(const int* a, const int* b) {
+    return (*a < *b) - (*a > *b);
+}
+
+void test_callbacks() {
+    printf("Testing callbacks...\n");
+    
+    int arr[] = {3, 1, 4, 1, 5, 9, 2, 6};
+    size_t len = sizeof(arr) / sizeof(arr[0]);
+    
+    assert(sort_array(arr, len, compare_descending) == 0);
+    for (size_t i = 1; i < len; i++) {
+        assert(arr[i - 1] >= arr[i]);
+    }
+    
+    assert(sort_array(NULL, len, compare_descending) == -1);
+    assert(sort_array(arr, len, NULL) == -1);
+    
+    printf("Callbacks: PASSED\n");
+}
+
 void test_memory_operations() {
     printf("Testing memory operations...\n");
     
@@ -71,6 +103,7 @@ void test_error_handling() {
     
     // Test factorial error
     factorial(25);
+    assert(get_last_error_code() == MATHLIB_ERR_OVERFLOW);
     const char* error = get_last_error();
     assert(strlen(error) > 0);
     printf("Last error: %s\n", error);
@@ -78,10 +111,15 @@ void test_error_handling() {
     // Test null pointer error
     char buffer[10];
     assert(reverse_string(NULL, buffer, sizeof(buffer)) == -1);
+    assert(get_last_error_code() == MATHLIB_ERR_NULL_POINTER);
     error = get_last_error();
     assert(strlen(error) > 0);
     printf("Last error: %s\n", error);
     
+    // Test undersized buffer error
+    assert(reverse_string("hello", buffer, 3) == -1);
+    assert(get_last_error_code() == MATHLIB_ERR_INVALID_SIZE);
+    
     printf("Error handling: PASSED\n");
 }
 
@@ -91,6 +129,7 @@ int main() {
     test_math_operations();
     test_string_operations();
     test_array_operations();
+    test_callbacks();
     test_memory_operations();
     test_error_handling();
     
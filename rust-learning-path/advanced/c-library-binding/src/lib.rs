@@ -10,6 +10,7 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int};
 use std::ptr;
+use std::sync::{Mutex, PoisonError};
 
 // Manual FFI declarations for the C library
 extern "C" {
@@ -23,19 +24,86 @@ extern "C" {
     
     fn sum_array(array: *const i32, length: usize) -> i32;
     fn find_max(array: *const i32, length: usize, max_value: *mut i32) -> c_int;
+    fn array_summarize(array: *const i32, length: usize, summary: *mut arrays::ArraySummary) -> c_int;
+    
+    fn sort_array(
+        arr: *mut c_int,
+        len: usize,
+        cmp: Option<unsafe extern "C" fn(*const c_int, *const c_int) -> c_int>,
+    ) -> c_int;
     
     fn allocate_string(size: usize) -> *mut c_char;
     fn free_string(str: *mut c_char);
     
     fn get_last_error() -> *const c_char;
+    fn get_last_error_code() -> c_int;
+}
+
+/// Error codes from the C library, matching the `MATHLIB_ERR_*` defines
+const MATHLIB_ERR_NULL_POINTER: c_int = 1;
+const MATHLIB_ERR_OVERFLOW: c_int = 2;
+const MATHLIB_ERR_INVALID_SIZE: c_int = 3;
+
+/// What went wrong in an FFI operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiErrorKind {
+    /// A null pointer was passed where one isn't allowed
+    NullPointer,
+    /// The result doesn't fit in the return type
+    Overflow,
+    /// A buffer or array is the wrong size, e.g. too small or empty
+    InvalidSize,
+    /// A string contains a null byte, which would cut the C string short
+    InteriorNul,
+    /// Anything else, such as a failed allocation
+    Unknown,
+}
+
+impl FfiErrorKind {
+    /// Classify an error code from the C library
+    pub fn from_code(code: c_int) -> Self {
+        match code {
+            MATHLIB_ERR_NULL_POINTER => FfiErrorKind::NullPointer,
+            MATHLIB_ERR_OVERFLOW => FfiErrorKind::Overflow,
+            MATHLIB_ERR_INVALID_SIZE => FfiErrorKind::InvalidSize,
+            _ => FfiErrorKind::Unknown,
+        }
+    }
 }
 
 /// Custom error type for FFI operations
 #[derive(Debug, Clone)]
 pub struct FfiError {
+    pub kind: FfiErrorKind,
+    /// The C library's error code, or 0 if the error was caught on the Rust side
+    pub code: i32,
     pub message: String,
 }
 
+impl FfiError {
+    /// Create an error caught on the Rust side, before calling into C
+    fn new(kind: FfiErrorKind, message: impl Into<String>) -> Self {
+        FfiError {
+            kind,
+            code: 0,
+            message: message.into(),
+        }
+    }
+
+    /// Read the error the C library last set
+    ///
+    /// Only call this with `C_ERROR_LOCK` held since the call that failed,
+    /// or the error may be another thread's.
+    fn last_c_error() -> Self {
+        let code = unsafe { get_last_error_code() };
+        FfiError {
+            kind: FfiErrorKind::from_code(code),
+            code,
+            message: get_last_error_string(),
+        }
+    }
+}
+
 impl std::fmt::Display for FfiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "FFI Error: {}", self.message)
@@ -46,6 +114,26 @@ impl std::error::Error for FfiError {}
 
 pub type FfiResult<T> = Result<T, FfiError>;
 
+/// Serializes C calls that can set the C library's error
+///
+/// The C library keeps a single error code and message for the whole
+/// process, so without this one thread's failing call could overwrite the
+/// error between another thread's call and its read of the error.
+static C_ERROR_LOCK: Mutex<()> = Mutex::new(());
+
+/// Make a C call, reading the C error if `failed` says the call failed
+///
+/// The lock is held from the call until the error has been read.
+fn call_c<T>(call: impl FnOnce() -> T, failed: impl FnOnce(&T) -> bool) -> FfiResult<T> {
+    let _guard = C_ERROR_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let result = call();
+    if failed(&result) {
+        Err(FfiError::last_c_error())
+    } else {
+        Ok(result)
+    }
+}
+
 /// Safe wrapper for mathematical operations
 pub mod math {
     use super::*;
@@ -82,14 +170,7 @@ pub mod math {
     /// assert_eq!(factorial(5).unwrap(), 120);
     /// ```
     pub fn factorial(n: u32) -> FfiResult<u64> {
-        let result = unsafe { factorial(n) };
-        if result == 0 && n > 0 {
-            Err(FfiError {
-                message: get_last_error_string(),
-            })
-        } else {
-            Ok(result)
-        }
+        call_c(|| unsafe { super::factorial(n) }, |&result| result == 0 && n > 0)
     }
 }
 
@@ -97,6 +178,9 @@ pub mod math {
 pub mod strings {
     use super::*;
 
+    /// Signature shared by the C functions that transform a string into a caller's buffer
+    type StringTransform = unsafe extern "C" fn(*const c_char, *mut c_char, usize) -> c_int;
+
     /// Reverse a string
     /// 
     /// # Examples
@@ -105,28 +189,26 @@ pub mod strings {
     /// assert_eq!(reverse("hello").unwrap(), "olleh");
     /// ```
     pub fn reverse(input: &str) -> FfiResult<String> {
-        let c_input = CString::new(input).map_err(|_| FfiError {
-            message: "Input contains null bytes".to_string(),
-        })?;
-
-        let mut buffer = vec![0u8; input.len() + 1];
-        let result = unsafe {
-            reverse_string(
-                c_input.as_ptr(),
-                buffer.as_mut_ptr() as *mut c_char,
-                buffer.len(),
-            )
-        };
-
-        if result != 0 {
-            return Err(FfiError {
-                message: get_last_error_string(),
-            });
-        }
+        transform(input, reverse_string)
+    }
 
-        // Convert back to Rust string
-        let c_str = unsafe { CStr::from_ptr(buffer.as_ptr() as *const c_char) };
-        Ok(c_str.to_string_lossy().into_owned())
+    /// Reverse a string into `output`, returning the number of bytes written
+    ///
+    /// `output` needs room for the result and a null terminator. If it's too
+    /// small, nothing useful is written and an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use c_library_binding::strings::reverse_into;
+    ///
+    /// let mut buffer = [0u8; 6];
+    /// let written = reverse_into("hello", &mut buffer).unwrap();
+    /// assert_eq!(&buffer[..written], b"olleh");
+    ///
+    /// assert!(reverse_into("hello, world", &mut buffer).is_err());
+    /// ```
+    pub fn reverse_into(input: &str, output: &mut [u8]) -> FfiResult<usize> {
+        transform_into(&to_c_string(input)?, reverse_string, output)
     }
 
     /// Convert string to uppercase
@@ -137,28 +219,52 @@ pub mod strings {
     /// assert_eq!(uppercase("hello").unwrap(), "HELLO");
     /// ```
     pub fn uppercase(input: &str) -> FfiResult<String> {
-        let c_input = CString::new(input).map_err(|_| FfiError {
-            message: "Input contains null bytes".to_string(),
-        })?;
-
-        let mut buffer = vec![0u8; input.len() + 1];
-        let result = unsafe {
-            uppercase_string(
-                c_input.as_ptr(),
-                buffer.as_mut_ptr() as *mut c_char,
-                buffer.len(),
-            )
-        };
+        transform(input, uppercase_string)
+    }
 
-        if result != 0 {
-            return Err(FfiError {
-                message: get_last_error_string(),
-            });
-        }
+    /// Convert a string to uppercase into `output`, returning the number of bytes written
+    ///
+    /// `output` needs room for the result and a null terminator. If it's too
+    /// small, nothing useful is written and an error is returned.
+    pub fn uppercase_into(input: &str, output: &mut [u8]) -> FfiResult<usize> {
+        transform_into(&to_c_string(input)?, uppercase_string, output)
+    }
 
-        // Convert back to Rust string
-        let c_str = unsafe { CStr::from_ptr(buffer.as_ptr() as *const c_char) };
-        Ok(c_str.to_string_lossy().into_owned())
+    /// Run a transform into a buffer sized for its output
+    ///
+    /// The buffer size comes from asking C how many bytes the input is,
+    /// which is what the transforms write, rather than from the Rust
+    /// string. If a transform ever needs more room, the C side reports it
+    /// and the error is returned instead of a truncated result.
+    fn transform(input: &str, function: StringTransform) -> FfiResult<String> {
+        let c_input = to_c_string(input)?;
+        let output_size = unsafe { string_length(c_input.as_ptr()) } + 1;
+
+        let mut buffer = vec![0u8; output_size];
+        let written = transform_into(&c_input, function, &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer[..written]).into_owned())
+    }
+
+    fn transform_into(input: &CStr, function: StringTransform, output: &mut [u8]) -> FfiResult<usize> {
+        call_c(
+            || unsafe {
+                function(
+                    input.as_ptr(),
+                    output.as_mut_ptr() as *mut c_char,
+                    output.len(),
+                )
+            },
+            |&result| result != 0,
+        )?;
+
+        // Only trust the output up to a terminator inside the buffer
+        CStr::from_bytes_until_nul(output)
+            .map(|c_str| c_str.to_bytes().len())
+            .map_err(|_| FfiError::new(FfiErrorKind::Unknown, "C function did not null-terminate its output"))
+    }
+
+    fn to_c_string(input: &str) -> FfiResult<CString> {
+        CString::new(input).map_err(|_| FfiError::new(FfiErrorKind::InteriorNul, "Input contains null bytes"))
     }
 
     /// Get the length of a string using the C library
@@ -169,9 +275,7 @@ pub mod strings {
     /// assert_eq!(length("hello").unwrap(), 5);
     /// ```
     pub fn length(input: &str) -> FfiResult<usize> {
-        let c_input = CString::new(input).map_err(|_| FfiError {
-            message: "Input contains null bytes".to_string(),
-        })?;
+        let c_input = to_c_string(input)?;
 
         let result = unsafe { string_length(c_input.as_ptr()) };
         Ok(result)
@@ -205,20 +309,147 @@ pub mod arrays {
     /// ```
     pub fn find_maximum(array: &[i32]) -> FfiResult<i32> {
         if array.is_empty() {
-            return Err(FfiError {
-                message: "Cannot find maximum of empty array".to_string(),
-            });
+            return Err(FfiError::new(FfiErrorKind::InvalidSize, "Cannot find maximum of empty array"));
         }
 
         let mut max_value: i32 = 0;
-        let result = unsafe { find_max(array.as_ptr(), array.len(), &mut max_value) };
+        call_c(
+            || unsafe { find_max(array.as_ptr(), array.len(), &mut max_value) },
+            |&result| result != 0,
+        )?;
+        Ok(max_value)
+    }
+
+    /// Statistics for an array, laid out like the C `ArraySummary` struct
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct ArraySummary {
+        /// Sum of the elements, which unlike `sum` can't overflow
+        pub sum: i64,
+        pub max: i32,
+        pub min: i32,
+        pub count: usize,
+    }
+
+    /// Get the sum, maximum, minimum and count of an array in one C call
+    ///
+    /// Cheaper than calling `sum` and `find_maximum` separately, as the
+    /// array crosses the FFI boundary once and is only walked once.
+    ///
+    /// # Examples
+    /// ```
+    /// use c_library_binding::arrays::summarize;
+    /// let summary = summarize(&[4, -2, 7]).unwrap();
+    /// assert_eq!((summary.sum, summary.max, summary.min, summary.count), (9, 7, -2, 3));
+    /// ```
+    pub fn summarize(array: &[i32]) -> FfiResult<ArraySummary> {
+        if array.is_empty() {
+            return Err(FfiError::new(FfiErrorKind::InvalidSize, "Cannot summarize empty array"));
+        }
+
+        let mut summary = ArraySummary::default();
+        call_c(
+            || unsafe { array_summarize(array.as_ptr(), array.len(), &mut summary) },
+            |&result| result != 0,
+        )?;
+        Ok(summary)
+    }
+}
+
+/// Safe wrapper for C functions that call back into Rust
+///
+/// C function pointers can't capture anything, and `sort_array` has no
+/// "user data" argument to pass a closure's state through. `sort_with`
+/// bridges the gap with a thread-local: it points the thread-local at the
+/// closure for the duration of the C call, and a plain `extern "C"`
+/// trampoline reads it back each time C calls the comparator. C calls the
+/// comparator on the same thread before `sort_array` returns, so any
+/// closure works, including ones that capture and mutate local state.
+pub mod callbacks {
+    use super::*;
+    use std::any::Any;
+    use std::cell::Cell;
+    use std::cmp::Ordering;
+    use std::ffi::c_void;
+    use std::panic::{self, AssertUnwindSafe};
+
+    /// The closure for the sort in progress on this thread
+    struct SortState<'a> {
+        compare: &'a mut dyn FnMut(&i32, &i32) -> Ordering,
+        /// A panic from `compare`, to be resumed once back in Rust
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    thread_local! {
+        /// Points to the current `SortState`, or is null outside `sort_with`
+        static CURRENT_SORT: Cell<*mut c_void> = const { Cell::new(ptr::null_mut()) };
+    }
+
+    /// Sort an array in place with a Rust comparison closure
+    ///
+    /// The sort is done by the C library and is stable. If `compare`
+    /// panics, the remaining comparisons are skipped and the panic is
+    /// resumed once the C function returns, leaving `array` in an
+    /// unspecified order.
+    ///
+    /// # Examples
+    /// ```
+    /// use c_library_binding::callbacks::sort_with;
+    ///
+    /// let mut numbers = [3, 1, 2];
+    /// sort_with(&mut numbers, |a, b| b.cmp(a)).unwrap();
+    /// assert_eq!(numbers, [3, 2, 1]);
+    /// ```
+    pub fn sort_with<F>(array: &mut [i32], mut compare: F) -> FfiResult<()>
+    where
+        F: FnMut(&i32, &i32) -> Ordering,
+    {
+        let mut state = SortState {
+            compare: &mut compare,
+            panic: None,
+        };
+
+        // Restoring the previous pointer afterwards lets a comparator call
+        // `sort_with` itself
+        let previous = CURRENT_SORT.with(|current| {
+            current.replace(&mut state as *mut SortState as *mut c_void)
+        });
+        let result = unsafe { sort_array(array.as_mut_ptr(), array.len(), Some(compare_trampoline)) };
+        CURRENT_SORT.with(|current| current.set(previous));
+
+        if let Some(payload) = state.panic {
+            panic::resume_unwind(payload);
+        }
 
         if result != 0 {
-            Err(FfiError {
-                message: get_last_error_string(),
-            })
+            // The lock can't be held across the sort, as the comparator may
+            // call into C itself. `sort_array` only fails on null pointers,
+            // which it's never given here, so this is not expected to race.
+            let _guard = C_ERROR_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            Err(FfiError::last_c_error())
         } else {
-            Ok(max_value)
+            Ok(())
+        }
+    }
+
+    /// The comparator handed to C, which forwards to the current closure
+    unsafe extern "C" fn compare_trampoline(a: *const c_int, b: *const c_int) -> c_int {
+        // Safety: C only calls this from inside `sort_array`, on the thread
+        // that `sort_with` set `CURRENT_SORT` on, while `state` is alive
+        let state = unsafe { &mut *(CURRENT_SORT.with(Cell::get) as *mut SortState) };
+
+        // Unwinding into C is undefined behavior, so a panic is caught here
+        // and the rest of the sort is a no-op
+        if state.panic.is_some() {
+            return 0;
+        }
+        let (a, b) = unsafe { (&*a, &*b) };
+        match panic::catch_unwind(AssertUnwindSafe(|| (state.compare)(a, b))) {
+            Ok(ordering) => ordering as c_int,
+            Err(payload) => {
+                state.panic = Some(payload);
+                0
+            }
         }
     }
 }
@@ -228,21 +459,83 @@ pub mod memory {
     use super::*;
 
     /// A safe wrapper around C-allocated strings
+    ///
+    /// The buffer is zeroed when it's allocated, so every byte of it can be
+    /// read safely, and starts out as an empty C string.
+    ///
+    /// # Examples
+    /// ```
+    /// use c_library_binding::memory::CAllocatedString;
+    ///
+    /// let mut c_string = CAllocatedString::new(8).unwrap();
+    /// c_string.write_str("hello").unwrap();
+    /// assert_eq!(c_string.as_bytes(6), b"hello\0");
+    ///
+    /// // "too long" and its terminator need 9 bytes
+    /// assert!(c_string.write_str("too long").is_err());
+    /// ```
     pub struct CAllocatedString {
         ptr: *mut c_char,
+        capacity: usize,
     }
 
     impl CAllocatedString {
         /// Allocate a new string with the given size
         pub fn new(size: usize) -> FfiResult<Self> {
-            let ptr = unsafe { allocate_string(size) };
-            if ptr.is_null() {
-                Err(FfiError {
-                    message: get_last_error_string(),
-                })
-            } else {
-                Ok(CAllocatedString { ptr })
+            let ptr = call_c(|| unsafe { allocate_string(size) }, |ptr| ptr.is_null())?;
+            // Safety: C just allocated `size` bytes at `ptr`
+            unsafe { ptr::write_bytes(ptr, 0, size) };
+            Ok(CAllocatedString { ptr, capacity: size })
+        }
+
+        /// Get the size of the buffer in bytes, null terminator included
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        /// View the first `len` bytes of the buffer
+        ///
+        /// # Panics
+        /// Panics if `len` is larger than the capacity.
+        pub fn as_bytes(&self, len: usize) -> &[u8] {
+            assert!(
+                len <= self.capacity,
+                "range end index {} out of range for C string of capacity {}",
+                len,
+                self.capacity
+            );
+            // Safety: the buffer holds `capacity` initialized bytes, and
+            // writing to it needs `&mut self`
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, len) }
+        }
+
+        /// Copy `s` into the buffer as a null-terminated C string
+        ///
+        /// Fails if `s` and its terminator don't fit, or if `s` contains a
+        /// null byte, which would cut the C string short. The buffer is
+        /// left unchanged when it fails.
+        pub fn write_str(&mut self, s: &str) -> FfiResult<()> {
+            if s.len() >= self.capacity {
+                return Err(FfiError::new(
+                    FfiErrorKind::InvalidSize,
+                    format!(
+                        "String of {} bytes plus its null terminator doesn't fit in a buffer of {} bytes",
+                        s.len(),
+                        self.capacity
+                    ),
+                ));
             }
+            if s.as_bytes().contains(&0) {
+                return Err(FfiError::new(FfiErrorKind::InteriorNul, "Input contains null bytes"));
+            }
+
+            // Safety: `s.len() + 1 <= capacity`, and `s` can't overlap a
+            // buffer we hold a unique reference to
+            unsafe {
+                ptr::copy_nonoverlapping(s.as_ptr(), self.ptr as *mut u8, s.len());
+                *self.ptr.add(s.len()) = 0;
+            }
+            Ok(())
         }
 
         /// Get a mutable pointer to the underlying C string
@@ -250,6 +543,7 @@ pub mod memory {
         /// # Safety
         /// The caller must ensure that:
         /// - The pointer is not used after this object is dropped
+        /// - Nothing is written past `capacity` bytes
         /// - The string is properly null-terminated before reading
         pub unsafe fn as_mut_ptr(&mut self) -> *mut c_char {
             self.ptr
@@ -261,9 +555,7 @@ pub mod memory {
         /// The caller must ensure the C string is properly null-terminated
         pub unsafe fn to_string(&self) -> FfiResult<String> {
             if self.ptr.is_null() {
-                return Err(FfiError {
-                    message: "Null pointer".to_string(),
-                });
+                return Err(FfiError::new(FfiErrorKind::NullPointer, "Null pointer"));
             }
 
             let c_str = CStr::from_ptr(self.ptr);
@@ -315,6 +607,80 @@ mod tests {
         assert_eq!(strings::length("").unwrap(), 0);
     }
 
+    #[test]
+    fn test_string_output_buffers() {
+        // Exactly enough room: the bytes plus the terminator
+        let mut buffer = [0xffu8; 6];
+        assert_eq!(strings::reverse_into("hello", &mut buffer).unwrap(), 5);
+        assert_eq!(&buffer, b"olleh\0");
+
+        assert_eq!(strings::uppercase_into("Rust", &mut buffer).unwrap(), 4);
+        assert_eq!(&buffer[..5], b"RUST\0");
+
+        // Multibyte input is sized by its bytes, not its characters
+        assert_eq!(strings::uppercase("café").unwrap(), "CAFé");
+        let mut buffer = [0u8; 6];
+        assert_eq!(strings::reverse_into("ab€", &mut buffer).unwrap(), 5);
+        assert_eq!(&buffer, b"\xac\x82\xe2ba\0");
+    }
+
+    #[test]
+    fn test_undersized_output_buffer_is_an_error() {
+        let mut buffer = [0u8; 5];
+
+        let error = strings::reverse_into("hello", &mut buffer).unwrap_err();
+        assert!(error.message.contains("too small"), "{}", error);
+
+        let error = strings::uppercase_into("hello", &mut []).unwrap_err();
+        assert!(error.message.contains("too small"), "{}", error);
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let error = math::factorial(25).unwrap_err();
+        assert_eq!(error.kind, FfiErrorKind::Overflow);
+        assert_eq!(error.code, MATHLIB_ERR_OVERFLOW);
+        assert!(error.message.contains("too large"), "{}", error);
+
+        let error = strings::reverse_into("hello", &mut [0u8; 3]).unwrap_err();
+        assert_eq!(error.kind, FfiErrorKind::InvalidSize);
+        assert_eq!(error.code, MATHLIB_ERR_INVALID_SIZE);
+
+        // Caught before reaching C, so there's no C error code
+        let error = arrays::find_maximum(&[]).unwrap_err();
+        assert_eq!(error.kind, FfiErrorKind::InvalidSize);
+        assert_eq!(error.code, 0);
+
+        assert_eq!(strings::reverse("a\0b").unwrap_err().kind, FfiErrorKind::InteriorNul);
+        assert_eq!(FfiErrorKind::from_code(MATHLIB_ERR_NULL_POINTER), FfiErrorKind::NullPointer);
+        assert_eq!(FfiErrorKind::from_code(4), FfiErrorKind::Unknown);
+    }
+
+    #[test]
+    fn test_concurrent_errors_are_not_mixed_up() {
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    for _ in 0..500 {
+                        if i % 2 == 0 {
+                            let error = math::factorial(25).unwrap_err();
+                            assert_eq!(error.kind, FfiErrorKind::Overflow);
+                            assert!(error.message.contains("Factorial"), "{}", error);
+                        } else {
+                            let error = strings::uppercase_into("hello", &mut [0u8; 2]).unwrap_err();
+                            assert_eq!(error.kind, FfiErrorKind::InvalidSize);
+                            assert!(error.message.contains("uppercase_string"), "{}", error);
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
     #[test]
     fn test_array_operations() {
         let arr = [1, 2, 3, 4, 5];
@@ -324,6 +690,87 @@ mod tests {
         let empty: &[i32] = &[];
         assert_eq!(arrays::sum(empty), 0);
         assert!(arrays::find_maximum(empty).is_err());
+        assert!(arrays::summarize(empty).is_err());
+    }
+
+    #[test]
+    fn test_summarize_matches_element_wise() {
+        // A fixed-seed LCG, so failures reproduce
+        let mut seed: u32 = 0x2545_f491;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            seed as i32
+        };
+
+        for len in [1, 2, 7, 100, 1000] {
+            let array: Vec<i32> = (0..len).map(|_| next()).collect();
+            assert!(array.iter().any(|&x| x < 0));
+
+            let summary = arrays::summarize(&array).unwrap();
+            assert_eq!(summary.sum, array.iter().map(|&x| x as i64).sum::<i64>());
+            assert_eq!(summary.max, arrays::find_maximum(&array).unwrap());
+            assert_eq!(summary.min, *array.iter().min().unwrap());
+            assert_eq!(summary.count, array.len());
+        }
+    }
+
+    #[test]
+    fn test_summarize_small_values_agree_with_sum() {
+        let array = [-5, 12, 0, -30, 8];
+        let summary = arrays::summarize(&array).unwrap();
+        assert_eq!(summary.sum, arrays::sum(&array) as i64);
+        assert_eq!((summary.max, summary.min, summary.count), (12, -30, 5));
+    }
+
+    #[test]
+    fn test_sort_with_ascending_and_descending() {
+        let mut numbers = [5, -3, 9, 0, 2, 2, 7];
+
+        callbacks::sort_with(&mut numbers, |a, b| a.cmp(b)).unwrap();
+        assert_eq!(numbers, [-3, 0, 2, 2, 5, 7, 9]);
+
+        callbacks::sort_with(&mut numbers, |a, b| b.cmp(a)).unwrap();
+        assert_eq!(numbers, [9, 7, 5, 2, 2, 0, -3]);
+
+        let mut empty: [i32; 0] = [];
+        callbacks::sort_with(&mut empty, |a, b| a.cmp(b)).unwrap();
+    }
+
+    #[test]
+    fn test_sort_with_capturing_comparator() {
+        // Sort by distance from a captured target, counting the comparisons
+        let target = 10;
+        let mut comparisons = 0;
+        let mut numbers = [1, 12, 8, 20, 10];
+
+        callbacks::sort_with(&mut numbers, |a, b| {
+            comparisons += 1;
+            (a - target).abs().cmp(&(b - target).abs())
+        })
+        .unwrap();
+
+        // Stable: 12 and 8 are the same distance away and keep their order
+        assert_eq!(numbers, [10, 12, 8, 1, 20]);
+        assert!(comparisons > 0);
+    }
+
+    #[test]
+    fn test_sort_with_nested_sort() {
+        let mut outer = [3, 1, 2];
+        callbacks::sort_with(&mut outer, |a, b| {
+            let mut inner = [*b, *a];
+            callbacks::sort_with(&mut inner, |x, y| y.cmp(x)).unwrap();
+            a.cmp(b)
+        })
+        .unwrap();
+        assert_eq!(outer, [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "comparator failed")]
+    fn test_sort_with_resumes_comparator_panic() {
+        let mut numbers = [2, 1];
+        let _ = callbacks::sort_with(&mut numbers, |_, _| panic!("comparator failed"));
     }
 
     #[test]
@@ -332,4 +779,43 @@ mod tests {
         // The CAllocatedString will be automatically freed when dropped
         assert!(!unsafe { c_string.as_mut_ptr() }.is_null());
     }
+
+    #[test]
+    fn test_c_allocated_string_write_and_read_back() {
+        let mut c_string = memory::CAllocatedString::new(16).unwrap();
+        assert_eq!(c_string.capacity(), 16);
+        assert_eq!(c_string.as_bytes(16), &[0; 16]);
+
+        c_string.write_str("hello, C").unwrap();
+        assert_eq!(c_string.as_bytes(9), b"hello, C\0");
+        assert_eq!(unsafe { c_string.to_string() }.unwrap(), "hello, C");
+
+        // A shorter string is terminated where it ends
+        c_string.write_str("bye").unwrap();
+        assert_eq!(unsafe { c_string.to_string() }.unwrap(), "bye");
+
+        // Exactly filling the buffer leaves room for the terminator
+        c_string.write_str(&"x".repeat(15)).unwrap();
+        assert_eq!(c_string.as_bytes(16)[15], 0);
+    }
+
+    #[test]
+    fn test_c_allocated_string_capacity_exceeded() {
+        let mut c_string = memory::CAllocatedString::new(4).unwrap();
+        c_string.write_str("abc").unwrap();
+
+        let error = c_string.write_str("abcd").unwrap_err();
+        assert!(error.message.contains("doesn't fit"), "{}", error);
+        assert_eq!(c_string.write_str("a\0b").unwrap_err().kind, FfiErrorKind::InteriorNul);
+
+        // Failed writes leave the old contents alone
+        assert_eq!(c_string.as_bytes(4), b"abc\0");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_c_allocated_string_as_bytes_past_capacity() {
+        let c_string = memory::CAllocatedString::new(4).unwrap();
+        c_string.as_bytes(5);
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use c_library_binding::{arrays, math, strings, memory};
+use c_library_binding::{arrays, callbacks, math, strings, memory};
 
 fn main() {
     println!("=== C Library Binding Demo ===\n");
@@ -48,6 +48,14 @@ fn main() {
         Err(e) => println!("Error finding maximum: {}", e),
     }
     
+    match arrays::summarize(&numbers) {
+        Ok(summary) => println!(
+            "Summary: sum {}, max {}, min {}, count {}",
+            summary.sum, summary.max, summary.min, summary.count
+        ),
+        Err(e) => println!("Error summarizing: {}", e),
+    }
+    
     let empty_array: &[i32] = &[];
     println!("Empty array sum: {}", arrays::sum(empty_array));
     match arrays::find_maximum(empty_array) {
@@ -56,11 +64,25 @@ fn main() {
     }
     println!();
 
+    // Demonstrate passing a Rust closure to C
+    println!("Callbacks:");
+    let mut sorted = numbers;
+    match callbacks::sort_with(&mut sorted, |a, b| b.cmp(a)) {
+        Ok(()) => println!("Sorted descending: {:?}", sorted),
+        Err(e) => println!("Error sorting array: {}", e),
+    }
+    println!();
+
     // Demonstrate memory management
     println!("Memory Management:");
     match memory::CAllocatedString::new(50) {
-        Ok(c_string) => {
+        Ok(mut c_string) => {
             println!("Successfully allocated C string with 50 bytes");
+            let message = "Hello from Rust";
+            match c_string.write_str(message) {
+                Ok(()) => println!("Wrote: {:?}", String::from_utf8_lossy(c_string.as_bytes(message.len()))),
+                Err(e) => println!("Error writing C string: {}", e),
+            }
             // The string will be automatically freed when it goes out of scope
         }
         Err(e) => println!("Error allocating C string: {}", e),
@@ -1,4 +1,4 @@
-use c_library_binding::{arrays, math, strings, memory, FfiError};
+use c_library_binding::{arrays, math, strings, memory, FfiError, FfiErrorKind};
 
 #[test]
 fn test_mathematical_operations() {
@@ -102,7 +102,8 @@ fn test_memory_management() {
 fn test_error_handling() {
     // Test factorial error
     match math::factorial(25) {
-        Err(FfiError { message }) => {
+        Err(FfiError { kind, message, .. }) => {
+            assert_eq!(kind, FfiErrorKind::Overflow);
             assert!(message.contains("too large") || message.contains("Factorial"));
         }
         Ok(_) => panic!("Expected error for factorial(25)"),
@@ -110,7 +111,8 @@ fn test_error_handling() {
     
     // Test empty array error
     match arrays::find_maximum(&[]) {
-        Err(FfiError { message }) => {
+        Err(FfiError { kind, message, .. }) => {
+            assert_eq!(kind, FfiErrorKind::InvalidSize);
             assert!(message.contains("empty") || message.contains("Empty"));
         }
         Ok(_) => panic!("Expected error for empty array"),
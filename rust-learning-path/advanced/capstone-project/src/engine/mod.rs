@@ -92,6 +92,11 @@ impl TaskEngine {
         self.statistics.get_statistics()
     }
 
+    /// Get per-worker execution and work-stealing statistics
+    pub fn worker_stats(&self) -> Vec<thread_pool::WorkerStat> {
+        self.thread_pool.worker_stats()
+    }
+
     /// Get current configuration
     pub fn config(&self) -> &ExecutorConfig {
         &self.config
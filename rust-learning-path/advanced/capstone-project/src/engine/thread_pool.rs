@@ -12,10 +12,33 @@ use std::thread;
 use std::time::Duration;
 use tokio::sync::oneshot;
 
+/// Snapshot of one worker's activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerStat {
+    pub worker_id: usize,
+    /// Tasks this worker has run
+    pub tasks_executed: usize,
+    /// Successful steals this worker made from other workers' local queues
+    pub steals_performed: usize,
+    /// Successful steals other workers made from this worker's local queue
+    pub steals_received: usize,
+}
+
+/// Per-worker counters, shared so thieves can credit their victims.
+/// `Relaxed` is enough because they're only read as statistics.
+#[derive(Default)]
+struct WorkerCounters {
+    tasks_executed: AtomicUsize,
+    steals_performed: AtomicUsize,
+    steals_received: AtomicUsize,
+}
+
 /// A work-stealing thread pool for task execution
 pub struct WorkStealingThreadPool {
     workers: Vec<WorkerThread>,
     global_queue: Arc<Injector<Task>>,
+    inboxes: Vec<Arc<Injector<Task>>>,
+    counters: Arc<Vec<WorkerCounters>>,
     stealers: Vec<Stealer<Task>>,
     active_tasks: Arc<AtomicUsize>,
     queued_tasks: Arc<AtomicUsize>,
@@ -36,20 +59,25 @@ impl WorkStealingThreadPool {
         let shutdown = Arc::new(AtomicBool::new(false));
         let shutdown_condvar = Arc::new((Mutex::new(false), Condvar::new()));
 
+        let counters: Arc<Vec<WorkerCounters>> =
+            Arc::new((0..num_workers).map(|_| WorkerCounters::default()).collect());
+
+        // Every worker needs every stealer, so create all the local queues first
+        let worker_queues: Vec<Worker<Task>> = (0..num_workers).map(|_| Worker::new_fifo()).collect();
+        let stealers: Vec<Stealer<Task>> = worker_queues.iter().map(Worker::stealer).collect();
+        let inboxes: Vec<Arc<Injector<Task>>> = (0..num_workers).map(|_| Arc::new(Injector::new())).collect();
+
         let mut workers = Vec::with_capacity(num_workers);
-        let mut stealers = Vec::with_capacity(num_workers);
 
         // Create workers
-        for id in 0..num_workers {
-            let worker_queue = Worker::new_fifo();
-            let stealer = worker_queue.stealer();
-            stealers.push(stealer);
-
+        for (id, worker_queue) in worker_queues.into_iter().enumerate() {
             let worker = WorkerThread::new(
                 id,
                 worker_queue,
+                inboxes[id].clone(),
                 global_queue.clone(),
                 stealers.clone(),
+                counters.clone(),
                 active_tasks.clone(),
                 queued_tasks.clone(),
                 shutdown.clone(),
@@ -62,6 +90,8 @@ impl WorkStealingThreadPool {
         Ok(Self {
             workers,
             global_queue,
+            inboxes,
+            counters,
             stealers,
             active_tasks,
             queued_tasks,
@@ -91,6 +121,45 @@ impl WorkStealingThreadPool {
         Ok(())
     }
 
+    /// Submit a task straight to one worker's local queue
+    ///
+    /// Other workers can only get at it by stealing, which makes this useful
+    /// for exercising and observing the work-stealing path.
+    pub fn submit_to<F>(&self, worker_id: usize, task: F) -> Result<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.shutdown.load(Ordering::Relaxed) {
+            return Err(EngineError::thread_pool("Thread pool is shutting down"));
+        }
+        let inbox = self.inboxes.get(worker_id).ok_or_else(|| {
+            EngineError::thread_pool(format!("No worker with id {}", worker_id))
+        })?;
+
+        inbox.push(Task::new(task));
+        self.queued_tasks.fetch_add(1, Ordering::Relaxed);
+
+        let (lock, cvar) = &*self.shutdown_condvar;
+        let _guard = lock.lock();
+        cvar.notify_all();
+
+        Ok(())
+    }
+
+    /// Get execution and stealing statistics for every worker
+    pub fn worker_stats(&self) -> Vec<WorkerStat> {
+        self.counters
+            .iter()
+            .enumerate()
+            .map(|(worker_id, counters)| WorkerStat {
+                worker_id,
+                tasks_executed: counters.tasks_executed.load(Ordering::Relaxed),
+                steals_performed: counters.steals_performed.load(Ordering::Relaxed),
+                steals_received: counters.steals_received.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
     /// Get the number of active tasks
     pub fn active_tasks(&self) -> usize {
         self.active_tasks.load(Ordering::Relaxed)
@@ -138,8 +207,10 @@ impl WorkerThread {
     fn new(
         id: usize,
         worker_queue: Worker<Task>,
+        inbox: Arc<Injector<Task>>,
         global_queue: Arc<Injector<Task>>,
         stealers: Vec<Stealer<Task>>,
+        counters: Arc<Vec<WorkerCounters>>,
         active_tasks: Arc<AtomicUsize>,
         queued_tasks: Arc<AtomicUsize>,
         shutdown: Arc<AtomicBool>,
@@ -151,8 +222,10 @@ impl WorkerThread {
                 Self::worker_loop(
                     id,
                     worker_queue,
+                    inbox,
                     global_queue,
                     stealers,
+                    counters,
                     active_tasks,
                     queued_tasks,
                     shutdown,
@@ -170,8 +243,10 @@ impl WorkerThread {
     fn worker_loop(
         id: usize,
         worker_queue: Worker<Task>,
+        inbox: Arc<Injector<Task>>,
         global_queue: Arc<Injector<Task>>,
         stealers: Vec<Stealer<Task>>,
+        counters: Arc<Vec<WorkerCounters>>,
         active_tasks: Arc<AtomicUsize>,
         queued_tasks: Arc<AtomicUsize>,
         shutdown: Arc<AtomicBool>,
@@ -184,7 +259,7 @@ impl WorkerThread {
             }
 
             // Try to find work
-            let task = Self::find_task(&worker_queue, &global_queue, &stealers);
+            let task = Self::find_task(id, &worker_queue, &inbox, &global_queue, &stealers, &counters);
 
             match task {
                 Some(task) => {
@@ -195,6 +270,7 @@ impl WorkerThread {
                     task.execute();
 
                     active_tasks.fetch_sub(1, Ordering::Relaxed);
+                    counters[id].tasks_executed.fetch_add(1, Ordering::Relaxed);
                 }
                 None => {
                     // No work available, wait for notification or timeout
@@ -210,15 +286,27 @@ impl WorkerThread {
     }
 
     fn find_task(
+        id: usize,
         worker_queue: &Worker<Task>,
+        inbox: &Injector<Task>,
         global_queue: &Arc<Injector<Task>>,
         stealers: &[Stealer<Task>],
+        counters: &[WorkerCounters],
     ) -> Option<Task> {
         // First, try to pop from local queue
         if let Some(task) = worker_queue.pop() {
             return Some(task);
         }
 
+        // Then, move tasks submitted directly to this worker into the local queue
+        loop {
+            match inbox.steal_batch_and_pop(worker_queue) {
+                crossbeam::deque::Steal::Success(task) => return Some(task),
+                crossbeam::deque::Steal::Empty => break,
+                crossbeam::deque::Steal::Retry => continue,
+            }
+        }
+
         // Then, try to steal from global queue
         loop {
             match global_queue.steal_batch_and_pop(worker_queue) {
@@ -229,10 +317,17 @@ impl WorkerThread {
         }
 
         // Finally, try to steal from other workers
-        for stealer in stealers {
+        for (victim, stealer) in stealers.iter().enumerate() {
+            if victim == id {
+                continue;
+            }
             loop {
                 match stealer.steal_batch_and_pop(worker_queue) {
-                    crossbeam::deque::Steal::Success(task) => return Some(task),
+                    crossbeam::deque::Steal::Success(task) => {
+                        counters[id].steals_performed.fetch_add(1, Ordering::Relaxed);
+                        counters[victim].steals_received.fetch_add(1, Ordering::Relaxed);
+                        return Some(task);
+                    }
                     crossbeam::deque::Steal::Empty => break,
                     crossbeam::deque::Steal::Retry => continue,
                 }
@@ -352,6 +447,38 @@ mod tests {
         assert!(pool.submit(|| {}).is_err());
     }
 
+    #[tokio::test]
+    async fn test_flooded_worker_is_stolen_from() {
+        let pool = WorkStealingThreadPool::new(4, 100).unwrap();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        // Everything lands on worker 0; the others can only get work by stealing
+        for _ in 0..200 {
+            let counter_clone = counter.clone();
+            pool.submit_to(0, move || {
+                thread::sleep(Duration::from_millis(1));
+                counter_clone.fetch_add(1, Ordering::Relaxed);
+            }).unwrap();
+        }
+
+        let start = std::time::Instant::now();
+        while counter.load(Ordering::Relaxed) < 200 && start.elapsed() < Duration::from_secs(5) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        pool.shutdown().await.unwrap();
+
+        let stats = pool.worker_stats();
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats.iter().map(|s| s.tasks_executed).sum::<usize>(), 200);
+        assert!(stats[0].steals_received > 0);
+        assert!(stats[1..].iter().any(|s| s.steals_performed > 0));
+        assert_eq!(
+            stats.iter().map(|s| s.steals_performed).sum::<usize>(),
+            stats.iter().map(|s| s.steals_received).sum::<usize>()
+        );
+        assert!(pool.submit_to(9, || {}).is_err());
+    }
+
     #[test]
     fn test_invalid_worker_count() {
         assert!(WorkStealingThreadPool::new(0, 100).is_err());
@@ -0,0 +1,32 @@
+//! Installs a `TrackingAllocator` as the global allocator and reports how
+//! much memory the program used.
+//!
+//! Run with `cargo run --example tracking_global`.
+
+use custom_allocator::TrackingAllocator;
+use std::alloc::System;
+use std::collections::HashMap;
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator<System> = TrackingAllocator::new(System);
+
+fn main() {
+    // Some ordinary work that allocates
+    let words: Vec<String> = (0..10_000).map(|i| format!("word-{}", i % 500)).collect();
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in &words {
+        *counts.entry(word.as_str()).or_default() += 1;
+    }
+    println!("Counted {} distinct words out of {}", counts.len(), words.len());
+
+    drop(counts);
+    drop(words);
+
+    let stats = GLOBAL.stats_snapshot();
+    println!("Allocations:         {}", stats.allocation_count);
+    println!("Deallocations:       {}", stats.deallocation_count);
+    println!("Total allocated:     {} bytes", stats.total_allocated);
+    println!("Still allocated:     {} bytes", stats.current_allocated);
+    println!("Peak memory usage:   {} bytes", stats.peak_allocated);
+}
@@ -5,8 +5,33 @@
 
 use std::alloc::{GlobalAlloc, Layout};
 use std::ptr::{self, NonNull};
-use std::sync::Mutex;
-use crate::{AllocatorStats, utils::*};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use crate::{AllocatorStats, AllocatorStatsSnapshot, utils::*};
+
+/// Lock an allocator's state, even if a panic poisoned the lock
+/// 
+/// `alloc` and `dealloc` must not panic. Every critical section here leaves
+/// the state consistent before anything in it can panic, so the data behind
+/// a poisoned lock is still good to use.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The memory region behind an allocator couldn't be set up
+///
+/// Either the requested sizes don't describe a valid layout or the system
+/// allocator is out of memory. Stands in for `std::alloc::AllocError`,
+/// which is only available on nightly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionError;
+
+impl std::fmt::Display for RegionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to allocate the allocator's memory region")
+    }
+}
+
+impl std::error::Error for RegionError {}
 
 /// A simple bump allocator that allocates memory sequentially
 /// 
@@ -56,14 +81,44 @@ impl BumpAllocator {
         self.stats.get_stats()
     }
 
+    /// Get allocation statistics as of a single moment
+    pub fn stats_snapshot(&self) -> AllocatorStatsSnapshot {
+        self.stats.snapshot()
+    }
+
+    /// Get the number of bytes used so far, alignment padding included
+    pub fn used(&self) -> usize {
+        *lock(&self.offset)
+    }
+
     /// Reset the allocator, effectively freeing all allocated memory
     /// 
+    /// The bump pointer goes back to the start of the region, so the next
+    /// allocations reuse the memory from the beginning. This lets one
+    /// allocator serve, say, every frame of a loop without growing.
+    /// 
+    /// In debug builds this panics if any allocation hasn't been passed to
+    /// `dealloc`, since that's usually a pointer that's still in use.
+    /// 
     /// # Safety
     /// 
     /// After calling this method, all previously allocated pointers become invalid.
-    /// Using them will result in undefined behavior.
+    /// Using them will result in undefined behavior: the memory they point to
+    /// is handed out again by later allocations. The debug check only sees
+    /// allocations that weren't deallocated, so it can't catch a pointer that
+    /// was deallocated and is still used.
     pub unsafe fn reset(&self) {
-        let mut offset = self.offset.lock().unwrap();
+        debug_assert_eq!(
+            self.stats.current_allocated.load(std::sync::atomic::Ordering::Relaxed),
+            0,
+            "BumpAllocator::reset called with live allocations"
+        );
+        self.rewind();
+    }
+
+    /// Move the bump pointer back to the start without any checks
+    pub(crate) fn rewind(&self) {
+        let mut offset = lock(&self.offset);
         *offset = 0;
         // Note: We don't update stats here as the memory is still "allocated" from the system
     }
@@ -71,20 +126,25 @@ impl BumpAllocator {
 
 unsafe impl GlobalAlloc for BumpAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        let mut offset = self.offset.lock().unwrap();
+        let mut offset = lock(&self.offset);
         
-        // Calculate the aligned offset for this allocation
+        // Calculate the aligned offset for this allocation. A huge alignment
+        // or size could wrap around, so the arithmetic is checked: wrapping
+        // would pass the capacity check below and hand out memory past the end
         let current_addr = self.memory as usize + *offset;
-        let aligned_addr = align_up(current_addr, layout.align());
+        let Some(aligned_addr) = checked_align_up(current_addr, layout.align()) else {
+            return ptr::null_mut();
+        };
         let aligned_offset = aligned_addr - self.memory as usize;
         
         // Check if we have enough space
-        if aligned_offset + layout.size() > self.size {
-            return ptr::null_mut();
-        }
+        let end = match aligned_offset.checked_add(layout.size()) {
+            Some(end) if end <= self.size => end,
+            _ => return ptr::null_mut(),
+        };
         
         // Update the offset for the next allocation
-        *offset = aligned_offset + layout.size();
+        *offset = end;
         
         // Record the allocation
         self.stats.record_allocation(layout.size());
@@ -120,6 +180,16 @@ unsafe impl Sync for BumpAllocator {}
 /// when new allocations are requested. It's more complex than a bump allocator
 /// but supports proper deallocation.
 /// 
+/// The free list is kept sorted by address, so a freed block's neighbors are
+/// found where it's inserted and adjacent free blocks are merged right away.
+/// Without this, memory freed in small pieces could never satisfy a larger
+/// allocation again.
+/// 
+/// Every block starts at a multiple of `BLOCK_SIZE` and spans a multiple of
+/// it, so whatever is left over when a block is split can always hold a
+/// `FreeBlock` header, and `dealloc` can work out a block's size from the
+/// layout alone.
+/// 
 /// # Safety
 /// 
 /// This allocator uses extensive unsafe code to manage a free list using raw pointers.
@@ -127,6 +197,7 @@ unsafe impl Sync for BumpAllocator {}
 /// - Free list pointers must always point to valid memory or be null
 /// - The free list must not contain cycles
 /// - Allocated memory must not overlap with free list nodes
+/// - Free blocks are sorted by address and never adjacent to each other
 pub struct FreeListAllocator {
     memory: *mut u8,
     size: usize,
@@ -144,6 +215,15 @@ struct FreeBlock {
     next: *mut FreeBlock,
 }
 
+/// Granularity of the free list allocator's blocks, in size and alignment
+const BLOCK_SIZE: usize = std::mem::size_of::<FreeBlock>();
+
+/// Size of the block that serves an allocation of `size` bytes, or `None`
+/// if no block could be that big
+fn block_size(size: usize) -> Option<usize> {
+    checked_align_up(size.max(BLOCK_SIZE), BLOCK_SIZE)
+}
+
 impl FreeListAllocator {
     /// Create a new free list allocator with the given memory size
     /// 
@@ -153,7 +233,7 @@ impl FreeListAllocator {
     /// when the allocator is no longer needed.
     pub fn new(size: usize) -> Result<Self, std::alloc::AllocError> {
         // Allocate memory using the system allocator
-        let layout = Layout::from_size_align(size, 8)
+        let layout = Layout::from_size_align(size, BLOCK_SIZE)
             .map_err(|_| std::alloc::AllocError)?;
         
         let memory = unsafe { std::alloc::alloc(layout) };
@@ -161,12 +241,20 @@ impl FreeListAllocator {
             return Err(std::alloc::AllocError);
         }
 
-        // Initialize the free list with the entire memory block
-        let free_list = unsafe {
-            let free_block = memory as *mut FreeBlock;
-            (*free_block).size = size;
-            (*free_block).next = ptr::null_mut();
-            free_block
+        // Initialize the free list with the entire memory block, minus any
+        // tail too short to be a block
+        let usable = size - size % BLOCK_SIZE;
+        let free_list = if usable == 0 {
+            ptr::null_mut()
+        } else {
+            unsafe {
+                let free_block = memory as *mut FreeBlock;
+                free_block.write(FreeBlock {
+                    size: usable,
+                    next: ptr::null_mut(),
+                });
+                free_block
+            }
         };
 
         Ok(FreeListAllocator {
@@ -182,93 +270,155 @@ impl FreeListAllocator {
         self.stats.get_stats()
     }
 
-    /// Find a suitable free block for the given layout
+    /// Get allocation statistics as of a single moment
+    pub fn stats_snapshot(&self) -> AllocatorStatsSnapshot {
+        self.stats.snapshot()
+    }
+
+    /// Get how scattered the free memory is
     /// 
-    /// Returns a pointer to the previous block (for unlinking) and the block itself.
-    /// If the returned previous pointer is null, the block is the first in the list.
-    unsafe fn find_free_block(&self, layout: Layout) -> Option<(*mut FreeBlock, *mut FreeBlock)> {
-        let mut current = *self.free_list.lock().unwrap();
+    /// This is the share of free bytes outside the largest free block: 0.0
+    /// when all free memory is in one block (or none is free), approaching
+    /// 1.0 as it's split into many small ones.
+    pub fn fragmentation_ratio(&self) -> f64 {
+        let free_list = lock(&self.free_list);
+        let mut total = 0;
+        let mut largest = 0;
+
+        let mut current = *free_list;
+        while !current.is_null() {
+            unsafe {
+                total += (*current).size;
+                largest = largest.max((*current).size);
+                current = (*current).next;
+            }
+        }
+
+        if total == 0 {
+            return 0.0;
+        }
+        (total - largest) as f64 / total as f64
+    }
+
+    /// Carve an allocation out of the first free block that fits it
+    /// 
+    /// Space before the aligned start stays in the list as a smaller block,
+    /// and space after the allocation becomes a new block right behind it,
+    /// so the list stays sorted. Returns null if no block fits.
+    unsafe fn take_block(free_list: &mut *mut FreeBlock, size: usize, align: usize) -> *mut u8 {
         let mut previous: *mut FreeBlock = ptr::null_mut();
+        let mut current = *free_list;
 
         while !current.is_null() {
-            let block_addr = current as usize;
-            let aligned_addr = align_up(block_addr, layout.align());
-            let padding = aligned_addr - block_addr;
-            let required_size = padding + layout.size();
-
-            if (*current).size >= required_size {
-                return Some((previous, current));
+            let block_start = current as usize;
+            let block_end = block_start + (*current).size;
+            // Blocks are BLOCK_SIZE-aligned, so any padding is a whole block.
+            // A huge size or alignment could wrap around and seem to fit, so
+            // the arithmetic is checked
+            let fits = checked_align_up(block_start, align.max(BLOCK_SIZE))
+                .and_then(|start| Some((start, start.checked_add(size)?)))
+                .filter(|(_, end)| *end <= block_end);
+
+            if let Some((start, end)) = fits {
+                let next = (*current).next;
+
+                // Whatever follows the allocation stays free
+                let rest = block_end - end;
+                let after = if rest > 0 {
+                    let tail = end as *mut FreeBlock;
+                    tail.write(FreeBlock { size: rest, next });
+                    tail
+                } else {
+                    next
+                };
+
+                if start > block_start {
+                    // Keep the padding before the allocation as a free block
+                    (*current).size = start - block_start;
+                    (*current).next = after;
+                } else if previous.is_null() {
+                    *free_list = after;
+                } else {
+                    (*previous).next = after;
+                }
+
+                return start as *mut u8;
             }
 
             previous = current;
             current = (*current).next;
         }
 
-        None
+        ptr::null_mut()
     }
 
-    /// Remove a block from the free list
-    unsafe fn remove_from_free_list(&self, previous: *mut FreeBlock, block: *mut FreeBlock) {
-        if previous.is_null() {
-            // Removing the first block
-            let mut free_list = self.free_list.lock().unwrap();
-            *free_list = (*block).next;
-        } else {
-            // Removing a block in the middle or end
-            (*previous).next = (*block).next;
+    /// Return a block to the free list, merging it with free neighbors
+    /// 
+    /// The block is inserted in address order, so its neighbors in memory
+    /// are its neighbors in the list, and it's merged with whichever of them
+    /// it touches.
+    unsafe fn insert_block(free_list: &mut *mut FreeBlock, block: *mut FreeBlock, size: usize) {
+        let start = block as usize;
+
+        // Find the free blocks on either side
+        let mut previous: *mut FreeBlock = ptr::null_mut();
+        let mut next = *free_list;
+        while !next.is_null() && (next as usize) < start {
+            previous = next;
+            next = (*next).next;
         }
-    }
 
-    /// Add a block to the free list
-    unsafe fn add_to_free_list(&self, block: *mut FreeBlock, size: usize) {
-        let mut free_list = self.free_list.lock().unwrap();
-        
-        (*block).size = size;
-        (*block).next = *free_list;
-        *free_list = block;
+        block.write(FreeBlock { size, next });
+
+        // Merge with the block right after it
+        if !next.is_null() && start + size == next as usize {
+            (*block).size += (*next).size;
+            (*block).next = (*next).next;
+        }
+
+        // Merge into the block right before it, or link it in after that one
+        if !previous.is_null() && previous as usize + (*previous).size == start {
+            (*previous).size += (*block).size;
+            (*previous).next = (*block).next;
+        } else if previous.is_null() {
+            *free_list = block;
+        } else {
+            (*previous).next = block;
+        }
     }
 }
 
 unsafe impl GlobalAlloc for FreeListAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        // Ensure minimum size for free block metadata
-        let min_size = std::mem::size_of::<FreeBlock>();
-        let alloc_size = layout.size().max(min_size);
-        let alloc_layout = Layout::from_size_align_unchecked(alloc_size, layout.align());
-
-        if let Some((previous, block)) = self.find_free_block(alloc_layout) {
-            let block_addr = block as usize;
-            let aligned_addr = align_up(block_addr, layout.align());
-            let padding = aligned_addr - block_addr;
-            let total_size = padding + alloc_size;
-
-            // Remove the block from the free list
-            self.remove_from_free_list(previous, block);
-
-            // If there's leftover space, add it back to the free list
-            let remaining_size = (*block).size - total_size;
-            if remaining_size >= min_size {
-                let new_free_block = (block as *mut u8).add(total_size) as *mut FreeBlock;
-                self.add_to_free_list(new_free_block, remaining_size);
-            }
+        let Some(alloc_size) = block_size(layout.size()) else {
+            return ptr::null_mut();
+        };
+
+        let ptr = {
+            let mut free_list = lock(&self.free_list);
+            Self::take_block(&mut free_list, alloc_size, layout.align())
+        };
 
+        if !ptr.is_null() {
             // Record the allocation
             self.stats.record_allocation(alloc_size);
-
-            return aligned_addr as *mut u8;
         }
 
-        // No suitable block found
-        ptr::null_mut()
+        ptr
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
-        let min_size = std::mem::size_of::<FreeBlock>();
-        let dealloc_size = layout.size().max(min_size);
+        // `alloc` never succeeds for a size with no block size, so there's
+        // nothing to free
+        let Some(dealloc_size) = block_size(layout.size()) else {
+            return;
+        };
 
         // Add the block back to the free list
-        let free_block = ptr as *mut FreeBlock;
-        self.add_to_free_list(free_block, dealloc_size);
+        {
+            let mut free_list = lock(&self.free_list);
+            Self::insert_block(&mut free_list, ptr as *mut FreeBlock, dealloc_size);
+        }
 
         // Record the deallocation
         self.stats.record_deallocation(dealloc_size);
@@ -278,7 +428,7 @@ unsafe impl GlobalAlloc for FreeListAllocator {
 impl Drop for FreeListAllocator {
     fn drop(&mut self) {
         unsafe {
-            let layout = Layout::from_size_align_unchecked(self.size, 8);
+            let layout = Layout::from_size_align_unchecked(self.size, BLOCK_SIZE);
             std::alloc::dealloc(self.memory, layout);
         }
     }
@@ -289,6 +439,181 @@ unsafe impl Send for FreeListAllocator {}
 // Safety: FreeListAllocator can be safely shared between threads (it uses Mutex internally)
 unsafe impl Sync for FreeListAllocator {}
 
+/// A slab allocator for objects of one fixed size
+/// 
+/// The memory is split into equal slots when the allocator is created. Free
+/// slots are linked through their own first bytes, so `alloc` pops the head
+/// of that list and `dealloc` pushes onto it, both in constant time, with
+/// no searching, splitting or merging and no fragmentation.
+/// 
+/// Any request that fits in a slot is served by a whole slot. Larger or more
+/// strictly aligned requests get null.
+/// 
+/// # Safety
+/// 
+/// The safety invariants are:
+/// - Every slot is either allocated or in the free list, never both
+/// - Free list pointers always point to the start of a slot or are null
+/// - Pointers passed to `dealloc` came from this allocator's `alloc`
+pub struct SlabAllocator {
+    memory: *mut u8,
+    slot_size: usize,
+    slot_align: usize,
+    count: usize,
+    free_list: Mutex<*mut FreeSlot>,
+    stats: AllocatorStats,
+}
+
+/// A free slot, linking to the next free one
+#[repr(C)]
+struct FreeSlot {
+    next: *mut FreeSlot,
+}
+
+impl SlabAllocator {
+    /// Create a slab of `count` slots, each fitting an object of
+    /// `object_size` bytes aligned to `object_align`
+    /// 
+    /// Slots are at least pointer-sized and pointer-aligned, since free ones
+    /// hold the link to the next. Fails if `count` is zero, the alignment
+    /// isn't a power of two, or the memory for `count` slots can't be
+    /// allocated.
+    pub fn new(object_size: usize, object_align: usize, count: usize) -> Result<Self, RegionError> {
+        if count == 0 || !object_align.is_power_of_two() {
+            return Err(RegionError);
+        }
+
+        let slot_align = object_align.max(std::mem::align_of::<FreeSlot>());
+        let slot_size = object_size
+            .max(std::mem::size_of::<FreeSlot>())
+            .checked_next_multiple_of(slot_align)
+            .ok_or(RegionError)?;
+        let size = slot_size.checked_mul(count).ok_or(RegionError)?;
+
+        let layout = Layout::from_size_align(size, slot_align)
+            .map_err(|_| RegionError)?;
+
+        let memory = unsafe { std::alloc::alloc(layout) };
+        if memory.is_null() {
+            return Err(RegionError);
+        }
+
+        // Link every slot into the free list, in address order
+        let free_list = unsafe {
+            for i in 0..count {
+                let slot = memory.add(i * slot_size) as *mut FreeSlot;
+                let next = if i + 1 < count {
+                    memory.add((i + 1) * slot_size) as *mut FreeSlot
+                } else {
+                    ptr::null_mut()
+                };
+                slot.write(FreeSlot { next });
+            }
+            memory as *mut FreeSlot
+        };
+
+        Ok(SlabAllocator {
+            memory,
+            slot_size,
+            slot_align,
+            count,
+            free_list: Mutex::new(free_list),
+            stats: AllocatorStats::new(),
+        })
+    }
+
+    /// Get allocation statistics
+    pub fn stats(&self) -> (usize, usize, usize, usize, usize) {
+        self.stats.get_stats()
+    }
+
+    /// Get allocation statistics as of a single moment
+    pub fn stats_snapshot(&self) -> AllocatorStatsSnapshot {
+        self.stats.snapshot()
+    }
+
+    /// Get the size of each slot, which is the largest allocation it serves
+    pub fn slot_size(&self) -> usize {
+        self.slot_size
+    }
+
+    /// Get the total number of slots
+    pub fn capacity(&self) -> usize {
+        self.count
+    }
+
+    /// Get the number of slots not currently allocated
+    pub fn free_slots(&self) -> usize {
+        let free_list = lock(&self.free_list);
+        let mut free = 0;
+
+        let mut current = *free_list;
+        while !current.is_null() {
+            free += 1;
+            current = unsafe { (*current).next };
+        }
+
+        free
+    }
+
+    /// Check that `ptr` is the start of one of this slab's slots
+    fn owns_slot(&self, ptr: *mut u8) -> bool {
+        let offset = (ptr as usize).wrapping_sub(self.memory as usize);
+        offset < self.slot_size * self.count && offset.is_multiple_of(self.slot_size)
+    }
+}
+
+unsafe impl GlobalAlloc for SlabAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > self.slot_size || layout.align() > self.slot_align {
+            return ptr::null_mut();
+        }
+
+        let slot = {
+            let mut free_list = lock(&self.free_list);
+            let slot = *free_list;
+            if !slot.is_null() {
+                *free_list = (*slot).next;
+            }
+            slot
+        };
+
+        if slot.is_null() {
+            return ptr::null_mut();
+        }
+
+        self.stats.record_allocation(self.slot_size);
+        slot as *mut u8
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
+        debug_assert!(self.owns_slot(ptr), "SlabAllocator::dealloc called with a foreign pointer");
+
+        {
+            let mut free_list = lock(&self.free_list);
+            let slot = ptr as *mut FreeSlot;
+            slot.write(FreeSlot { next: *free_list });
+            *free_list = slot;
+        }
+
+        self.stats.record_deallocation(self.slot_size);
+    }
+}
+
+impl Drop for SlabAllocator {
+    fn drop(&mut self) {
+        unsafe {
+            let layout = Layout::from_size_align_unchecked(self.slot_size * self.count, self.slot_align);
+            std::alloc::dealloc(self.memory, layout);
+        }
+    }
+}
+
+// Safety: SlabAllocator can be safely sent between threads
+unsafe impl Send for SlabAllocator {}
+// Safety: SlabAllocator can be safely shared between threads (it uses Mutex internally)
+unsafe impl Sync for SlabAllocator {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +659,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bump_allocator_reset_reuses_memory() {
+        let allocator = BumpAllocator::new(1024).unwrap();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        
+        unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.alloc(layout);
+            assert_eq!(allocator.used(), 128);
+            allocator.dealloc(first, layout);
+            allocator.dealloc(second, layout);
+            
+            allocator.reset();
+            assert_eq!(allocator.used(), 0);
+            
+            // The next allocation starts over at the beginning of the region
+            let reused = allocator.alloc(layout);
+            assert_eq!(reused, first);
+            allocator.dealloc(reused, layout);
+        }
+    }
+
+    #[test]
+    fn test_bump_allocator_reset_allows_refilling() {
+        let allocator = BumpAllocator::new(256).unwrap();
+        let layout = Layout::from_size_align(128, 8).unwrap();
+        
+        unsafe {
+            for _ in 0..3 {
+                let ptrs = [allocator.alloc(layout), allocator.alloc(layout)];
+                assert!(ptrs.iter().all(|ptr| !ptr.is_null()));
+                // Full until reset
+                assert!(allocator.alloc(layout).is_null());
+                
+                for ptr in ptrs {
+                    allocator.dealloc(ptr, layout);
+                }
+                allocator.reset();
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "live allocations")]
+    fn test_bump_allocator_reset_with_live_allocation_panics() {
+        let allocator = BumpAllocator::new(1024).unwrap();
+        
+        unsafe {
+            let _live = allocator.alloc(Layout::from_size_align(64, 8).unwrap());
+            allocator.reset();
+        }
+    }
+
     #[test]
     fn test_free_list_allocator_creation() {
         let allocator = FreeListAllocator::new(1024).unwrap();
@@ -365,6 +744,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_free_list_allocator_coalesces_adjacent_blocks() {
+        let allocator = FreeListAllocator::new(1024).unwrap();
+        
+        unsafe {
+            let layout = Layout::from_size_align(64, 8).unwrap();
+            let ptrs: Vec<*mut u8> = (0..3).map(|_| allocator.alloc(layout)).collect();
+            // Keeps the three apart from the rest of the free memory
+            let guard = allocator.alloc(layout);
+            assert!(ptrs.iter().chain([&guard]).all(|ptr| !ptr.is_null()));
+            
+            // Free the outer two first, then the one between them
+            allocator.dealloc(ptrs[0], layout);
+            allocator.dealloc(ptrs[2], layout);
+            assert!(allocator.fragmentation_ratio() > 0.0);
+            allocator.dealloc(ptrs[1], layout);
+            
+            // The three became one block that fits an allocation of all of them
+            let big = Layout::from_size_align(192, 8).unwrap();
+            let merged = allocator.alloc(big);
+            assert_eq!(merged, ptrs[0]);
+            
+            allocator.dealloc(merged, big);
+            allocator.dealloc(guard, layout);
+        }
+        
+        // With everything freed, the whole region is one block again
+        assert_eq!(allocator.fragmentation_ratio(), 0.0);
+        unsafe {
+            let whole = Layout::from_size_align(1024, 8).unwrap();
+            assert!(!allocator.alloc(whole).is_null());
+        }
+    }
+
+    #[test]
+    fn test_free_list_allocator_fragmentation_ratio() {
+        let allocator = FreeListAllocator::new(1024).unwrap();
+        assert_eq!(allocator.fragmentation_ratio(), 0.0);
+        
+        unsafe {
+            let layout = Layout::from_size_align(128, 8).unwrap();
+            let ptrs: Vec<*mut u8> = (0..8).map(|_| allocator.alloc(layout)).collect();
+            // Completely full: nothing free, nothing fragmented
+            assert_eq!(allocator.fragmentation_ratio(), 0.0);
+            
+            // Four separate 128-byte holes: three quarters are outside the largest
+            for ptr in ptrs.iter().step_by(2) {
+                allocator.dealloc(*ptr, layout);
+            }
+            assert_eq!(allocator.fragmentation_ratio(), 0.75);
+            
+            for ptr in ptrs.iter().skip(1).step_by(2) {
+                allocator.dealloc(*ptr, layout);
+            }
+            assert_eq!(allocator.fragmentation_ratio(), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_free_list_allocator_alignment_padding_is_reused() {
+        let allocator = FreeListAllocator::new(1024).unwrap();
+        
+        unsafe {
+            let aligned = Layout::from_size_align(64, 256).unwrap();
+            let ptr = allocator.alloc(aligned);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % 256, 0);
+            
+            allocator.dealloc(ptr, aligned);
+            // The padding before the aligned block was kept and merged back
+            assert_eq!(allocator.fragmentation_ratio(), 0.0);
+        }
+    }
+
     #[test]
     fn test_free_list_allocator_reuse() {
         let allocator = FreeListAllocator::new(1024).unwrap();
@@ -383,4 +836,128 @@ mod tests {
             assert_eq!(ptr1, ptr2);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_slab_allocator_fills_and_reuses_every_slot() {
+        let allocator = SlabAllocator::new(24, 8, 16).unwrap();
+        assert_eq!(allocator.slot_size(), 24);
+        assert_eq!(allocator.capacity(), 16);
+        let layout = Layout::from_size_align(24, 8).unwrap();
+        
+        unsafe {
+            let mut ptrs: Vec<*mut u8> = (0..16).map(|_| allocator.alloc(layout)).collect();
+            assert!(ptrs.iter().all(|ptr| !ptr.is_null() && (*ptr as usize).is_multiple_of(8)));
+            assert_eq!(allocator.free_slots(), 0);
+            // Full
+            assert!(allocator.alloc(layout).is_null());
+            
+            for ptr in &ptrs {
+                allocator.dealloc(*ptr, layout);
+            }
+            assert_eq!(allocator.free_slots(), 16);
+            
+            // Every slot is handed out again, and nothing else
+            let mut reused: Vec<*mut u8> = (0..16).map(|_| allocator.alloc(layout)).collect();
+            ptrs.sort();
+            reused.sort();
+            assert_eq!(reused, ptrs);
+        }
+        
+        let (total_alloc, total_dealloc, current_alloc, alloc_count, dealloc_count) = allocator.stats();
+        assert_eq!((total_alloc, total_dealloc, current_alloc), (32 * 24, 16 * 24, 16 * 24));
+        assert_eq!((alloc_count, dealloc_count), (32, 16));
+    }
+
+    #[test]
+    fn test_slab_allocator_reuses_last_freed_slot() {
+        let allocator = SlabAllocator::new(64, 16, 4).unwrap();
+        let layout = Layout::from_size_align(64, 16).unwrap();
+        
+        unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.alloc(layout);
+            allocator.dealloc(first, layout);
+            
+            // Smaller requests still take a whole slot
+            let small = Layout::from_size_align(8, 8).unwrap();
+            assert_eq!(allocator.alloc(small), first);
+            assert_ne!(allocator.alloc(small), second);
+        }
+    }
+
+    #[test]
+    fn test_slab_allocator_rejects_oversized_requests() {
+        let allocator = SlabAllocator::new(32, 8, 4).unwrap();
+        
+        unsafe {
+            assert!(allocator.alloc(Layout::from_size_align(33, 8).unwrap()).is_null());
+            assert!(allocator.alloc(Layout::from_size_align(8, 64).unwrap()).is_null());
+        }
+        assert_eq!(allocator.free_slots(), 4);
+    }
+
+    #[test]
+    fn test_slab_allocator_small_objects_get_pointer_sized_slots() {
+        let allocator = SlabAllocator::new(1, 1, 8).unwrap();
+        assert_eq!(allocator.slot_size(), std::mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn test_slab_allocator_creation_fails_when_slots_dont_fit() {
+        assert!(SlabAllocator::new(64, 8, 0).is_err());
+        assert!(SlabAllocator::new(64, 3, 4).is_err());
+        assert!(SlabAllocator::new(1 << 20, 8, usize::MAX / 1024).is_err());
+    }
+
+    #[test]
+    fn test_bump_allocator_exhaustion_returns_null() {
+        let allocator = BumpAllocator::new(256).unwrap();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        
+        unsafe {
+            let ptrs: Vec<*mut u8> = (0..4).map(|_| allocator.alloc(layout)).collect();
+            assert!(ptrs.iter().all(|ptr| !ptr.is_null()));
+            
+            // Full, for any request
+            assert!(allocator.alloc(layout).is_null());
+            assert!(allocator.alloc(Layout::from_size_align(1, 1).unwrap()).is_null());
+            // Requests far larger than the region don't wrap around
+            assert!(allocator.alloc(Layout::from_size_align(isize::MAX as usize - 7, 8).unwrap()).is_null());
+            assert!(allocator.alloc(Layout::from_size_align(1, 1 << 40).unwrap()).is_null());
+            assert_eq!(allocator.used(), 256);
+            
+            let snapshot = allocator.stats_snapshot();
+            assert_eq!(snapshot.allocation_count, 4);
+            assert_eq!(snapshot.current_allocated, 256);
+            assert_eq!(snapshot.current_allocated, snapshot.total_allocated - snapshot.total_deallocated);
+        }
+    }
+
+    #[test]
+    fn test_free_list_allocator_exhaustion_returns_null() {
+        let allocator = FreeListAllocator::new(256).unwrap();
+        let layout = Layout::from_size_align(32, 8).unwrap();
+        
+        unsafe {
+            let ptrs: Vec<*mut u8> = std::iter::from_fn(|| Some(allocator.alloc(layout)))
+                .take_while(|ptr| !ptr.is_null())
+                .collect();
+            assert_eq!(ptrs.len(), 8);
+            
+            // Still full on later attempts, and huge requests don't wrap around
+            assert!(allocator.alloc(layout).is_null());
+            assert!(allocator.alloc(Layout::from_size_align(isize::MAX as usize - 15, 16).unwrap()).is_null());
+            assert!(allocator.alloc(Layout::from_size_align(16, 1 << 40).unwrap()).is_null());
+            
+            let snapshot = allocator.stats_snapshot();
+            assert_eq!(snapshot.allocation_count, 8);
+            assert_eq!(snapshot.current_allocated, 256);
+            assert_eq!(snapshot.current_allocated, snapshot.total_allocated - snapshot.total_deallocated);
+            
+            // Freeing one makes room for exactly one more
+            allocator.dealloc(ptrs[3], layout);
+            assert_eq!(allocator.alloc(layout), ptrs[3]);
+            assert!(allocator.alloc(layout).is_null());
+        }
+    }
+}
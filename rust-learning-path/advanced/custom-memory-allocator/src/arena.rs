@@ -0,0 +1,140 @@
+//! A typed arena on top of the bump allocator
+//!
+//! `BumpAllocator` hands out raw pointers and leaves it to the caller to
+//! stop using them before `reset`. `Arena` wraps it in a safe API: values
+//! are borrowed from the arena, so the borrow checker makes sure none of
+//! them are still in use when it is reset or dropped.
+
+use std::alloc::{GlobalAlloc, Layout};
+use crate::allocator::{BumpAllocator, RegionError};
+
+/// An arena that allocates values out of a bump region
+///
+/// Allocating is a pointer bump, and everything is freed at once by
+/// `reset` or by dropping the arena. Values are never dropped: their memory
+/// is simply reused, so types with a `Drop` impl leak whatever they own.
+///
+/// # Examples
+///
+/// ```
+/// use custom_allocator::Arena;
+///
+/// let mut arena = Arena::new(1024).unwrap();
+///
+/// let x = arena.alloc_value(41);
+/// *x += 1;
+/// assert_eq!(*x, 42);
+///
+/// // Every value borrowed from the arena must be gone before it's reset
+/// arena.reset();
+/// assert_eq!(arena.used(), 0);
+/// ```
+pub struct Arena {
+    bump: BumpAllocator,
+}
+
+impl Arena {
+    /// Create an arena with `size` bytes of space
+    pub fn new(size: usize) -> Result<Self, RegionError> {
+        Ok(Arena {
+            bump: BumpAllocator::new(size).map_err(|_| RegionError)?,
+        })
+    }
+
+    /// Move `value` into the arena and get a reference to it
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena doesn't have room for the value.
+    #[allow(clippy::mut_from_ref)] // Every call returns a distinct allocation
+    pub fn alloc_value<T>(&self, value: T) -> &mut T {
+        match self.try_alloc_value(value) {
+            Ok(value) => value,
+            Err(_) => panic!("arena out of memory allocating {} bytes", std::mem::size_of::<T>()),
+        }
+    }
+
+    /// Move `value` into the arena, or give it back if there's no room
+    #[allow(clippy::mut_from_ref)] // Every call returns a distinct allocation
+    pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
+        let ptr = unsafe { self.bump.alloc(Layout::new::<T>()) } as *mut T;
+        if ptr.is_null() {
+            return Err(value);
+        }
+
+        // Safety: the pointer is aligned for T, points to unused memory large
+        // enough for it, and no other allocation overlaps it until `reset`,
+        // which needs `&mut self` and so can't happen while it is borrowed
+        unsafe {
+            ptr.write(value);
+            Ok(&mut *ptr)
+        }
+    }
+
+    /// Get the number of bytes used so far, alignment padding included
+    pub fn used(&self) -> usize {
+        self.bump.used()
+    }
+
+    /// Free every value at once, making the whole arena available again
+    ///
+    /// This takes `&mut self`, so no reference from `alloc_value` can still
+    /// be alive; the memory they pointed to is reused by later allocations.
+    pub fn reset(&mut self) {
+        self.bump.rewind();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc_values_of_different_types() {
+        let arena = Arena::new(1024).unwrap();
+
+        let byte = arena.alloc_value(7u8);
+        let number = arena.alloc_value(1234u64);
+        let pair = arena.alloc_value((1.5f32, 'x'));
+
+        *number += 1;
+        assert_eq!(*byte, 7);
+        assert_eq!(*number, 1235);
+        assert_eq!(*pair, (1.5, 'x'));
+        assert_eq!(number as *mut u64 as usize % std::mem::align_of::<u64>(), 0);
+    }
+
+    #[test]
+    fn test_reset_reuses_memory() {
+        let mut arena = Arena::new(1024).unwrap();
+
+        let first = arena.alloc_value([1u32; 4]) as *mut [u32; 4];
+        arena.alloc_value([2u32; 4]);
+        assert_eq!(arena.used(), 32);
+
+        arena.reset();
+        assert_eq!(arena.used(), 0);
+
+        let reused = arena.alloc_value([3u32; 4]);
+        assert_eq!(reused as *mut [u32; 4], first);
+        assert_eq!(*reused, [3; 4]);
+    }
+
+    #[test]
+    fn test_full_arena_gives_value_back() {
+        let mut arena = Arena::new(16).unwrap();
+
+        assert!(arena.try_alloc_value([0u8; 16]).is_ok());
+        assert_eq!(arena.try_alloc_value(5u8), Err(5));
+
+        arena.reset();
+        assert!(arena.try_alloc_value(5u8).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "arena out of memory")]
+    fn test_alloc_value_panics_when_full() {
+        let arena = Arena::new(8).unwrap();
+        arena.alloc_value([0u8; 16]);
+    }
+}
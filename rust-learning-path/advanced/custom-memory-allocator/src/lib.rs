@@ -5,16 +5,27 @@
 
 use std::alloc::{GlobalAlloc, Layout};
 use std::ptr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
 use std::sync::Mutex;
 
 pub mod allocator;
+pub mod arena;
+pub mod tracking;
 pub mod utils;
 
-pub use allocator::{BumpAllocator, FreeListAllocator};
-pub use utils::{align_up, is_aligned};
+pub use allocator::{BumpAllocator, FreeListAllocator, RegionError, SlabAllocator};
+pub use arena::Arena;
+pub use tracking::TrackingAllocator;
+pub use utils::{align_up, checked_align_up, is_aligned};
 
 /// Statistics for tracking allocator behavior
+///
+/// Each counter can be read on its own. Reading several of them that way can
+/// mix values from before and after a concurrent update, so use `snapshot`
+/// when they need to agree with each other.
+///
+/// Updates only touch atomics, never a lock, so the stats are safe to keep
+/// inside a `#[global_allocator]`.
 #[derive(Debug, Default)]
 pub struct AllocatorStats {
     pub total_allocated: AtomicUsize,
@@ -22,23 +33,98 @@ pub struct AllocatorStats {
     pub current_allocated: AtomicUsize,
     pub allocation_count: AtomicUsize,
     pub deallocation_count: AtomicUsize,
+    /// Highest `current_allocated` has been
+    pub peak_allocated: AtomicUsize,
+    /// Number of updates begun
+    updates_started: AtomicUsize,
+    /// Number of updates done; equal to `updates_started` when none is in progress
+    updates_finished: AtomicUsize,
+}
+
+/// All allocator statistics as of a single moment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocatorStatsSnapshot {
+    pub total_allocated: usize,
+    pub total_deallocated: usize,
+    pub current_allocated: usize,
+    pub allocation_count: usize,
+    pub deallocation_count: usize,
+    pub peak_allocated: usize,
 }
 
 impl AllocatorStats {
-    pub fn new() -> Self {
-        Self::default()
+    pub const fn new() -> Self {
+        Self {
+            total_allocated: AtomicUsize::new(0),
+            total_deallocated: AtomicUsize::new(0),
+            current_allocated: AtomicUsize::new(0),
+            allocation_count: AtomicUsize::new(0),
+            deallocation_count: AtomicUsize::new(0),
+            peak_allocated: AtomicUsize::new(0),
+            updates_started: AtomicUsize::new(0),
+            updates_finished: AtomicUsize::new(0),
+        }
     }
 
     pub fn record_allocation(&self, size: usize) {
-        self.total_allocated.fetch_add(size, Ordering::Relaxed);
-        self.current_allocated.fetch_add(size, Ordering::Relaxed);
-        self.allocation_count.fetch_add(1, Ordering::Relaxed);
+        self.update(|| {
+            self.total_allocated.fetch_add(size, Ordering::Relaxed);
+            let current = self.current_allocated.fetch_add(size, Ordering::Relaxed) + size;
+            self.peak_allocated.fetch_max(current, Ordering::Relaxed);
+            self.allocation_count.fetch_add(1, Ordering::Relaxed);
+        });
     }
 
     pub fn record_deallocation(&self, size: usize) {
-        self.total_deallocated.fetch_add(size, Ordering::Relaxed);
-        self.current_allocated.fetch_sub(size, Ordering::Relaxed);
-        self.deallocation_count.fetch_add(1, Ordering::Relaxed);
+        self.update(|| {
+            self.total_deallocated.fetch_add(size, Ordering::Relaxed);
+            self.current_allocated.fetch_sub(size, Ordering::Relaxed);
+            self.deallocation_count.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    /// Read every counter at once, consistently with each other
+    ///
+    /// Unlike `get_stats`, the values are all from a moment when no update
+    /// was half done, so `current_allocated` is always
+    /// `total_allocated - total_deallocated`. Updates never wait for this;
+    /// if one lands while the counters are being read, they're read again.
+    pub fn snapshot(&self) -> AllocatorStatsSnapshot {
+        loop {
+            // Every update begun by now has finished if the two counts agree.
+            // `finished` is read first, so it can't count an update that
+            // started after `started` was read.
+            let finished = self.updates_finished.load(Ordering::SeqCst);
+            let started = self.updates_started.load(Ordering::SeqCst);
+            if started != finished {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let snapshot = AllocatorStatsSnapshot {
+                total_allocated: self.total_allocated.load(Ordering::Relaxed),
+                total_deallocated: self.total_deallocated.load(Ordering::Relaxed),
+                current_allocated: self.current_allocated.load(Ordering::Relaxed),
+                allocation_count: self.allocation_count.load(Ordering::Relaxed),
+                deallocation_count: self.deallocation_count.load(Ordering::Relaxed),
+                peak_allocated: self.peak_allocated.load(Ordering::Relaxed),
+            };
+
+            // No update began while reading, so nothing changed under us
+            fence(Ordering::Acquire);
+            if self.updates_started.load(Ordering::Relaxed) == started {
+                return snapshot;
+            }
+        }
+    }
+
+    /// Run `apply` as one update, as far as `snapshot` can tell
+    fn update(&self, apply: impl FnOnce()) {
+        self.updates_started.fetch_add(1, Ordering::SeqCst);
+        // Keep the counter updates from becoming visible before the start
+        fence(Ordering::Release);
+        apply();
+        self.updates_finished.fetch_add(1, Ordering::SeqCst);
     }
 
     pub fn get_stats(&self) -> (usize, usize, usize, usize, usize) {
@@ -81,4 +167,81 @@ mod tests {
         assert_eq!(alloc_count, 2);
         assert_eq!(dealloc_count, 1);
     }
+
+    #[test]
+    fn test_snapshot_matches_counters() {
+        let stats = AllocatorStats::new();
+        stats.record_allocation(64);
+        stats.record_allocation(32);
+        stats.record_deallocation(64);
+
+        assert_eq!(
+            stats.snapshot(),
+            AllocatorStatsSnapshot {
+                total_allocated: 96,
+                total_deallocated: 64,
+                current_allocated: 32,
+                allocation_count: 2,
+                deallocation_count: 1,
+                peak_allocated: 96,
+            }
+        );
+    }
+
+    #[test]
+    fn test_snapshot_is_consistent_under_concurrent_allocation() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+        use std::thread;
+
+        let allocator = Arc::new(FreeListAllocator::new(64 * 1024).unwrap());
+        let done = Arc::new(AtomicBool::new(false));
+
+        let workers: Vec<_> = (0..4)
+            .map(|i| {
+                let allocator = allocator.clone();
+                thread::spawn(move || {
+                    let layout = Layout::from_size_align(16 + i * 8, 8).unwrap();
+                    for _ in 0..2000 {
+                        unsafe {
+                            let ptr = allocator.alloc(layout);
+                            if !ptr.is_null() {
+                                allocator.dealloc(ptr, layout);
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let reader = {
+            let allocator = allocator.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let mut snapshots = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let snapshot = allocator.stats_snapshot();
+                    assert_eq!(
+                        snapshot.current_allocated,
+                        snapshot.total_allocated - snapshot.total_deallocated,
+                        "torn snapshot: {:?}",
+                        snapshot
+                    );
+                    snapshots += 1;
+                }
+                snapshots
+            })
+        };
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+        assert!(reader.join().unwrap() > 0);
+
+        let snapshot = allocator.stats_snapshot();
+        assert_eq!(snapshot.current_allocated, 0);
+        assert_eq!(snapshot.allocation_count, 8000);
+        assert_eq!(snapshot.deallocation_count, 8000);
+    }
 }
\ No newline at end of file
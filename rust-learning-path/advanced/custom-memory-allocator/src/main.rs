@@ -3,7 +3,7 @@
 //! This binary demonstrates the usage of custom allocators and provides
 //! benchmarks to compare their performance characteristics.
 
-use custom_allocator::{BumpAllocator, FreeListAllocator};
+use custom_allocator::{BumpAllocator, FreeListAllocator, SlabAllocator};
 use std::alloc::{GlobalAlloc, Layout};
 use std::time::Instant;
 
@@ -177,6 +177,26 @@ fn performance_comparison() {
     }
     let free_list_duration = start.elapsed();
 
+    // Benchmark slab allocator
+    let slab_allocator = SlabAllocator::new(ALLOCATION_SIZE, 8, NUM_ALLOCATIONS)
+        .expect("Failed to create slab allocator");
+    
+    let start = Instant::now();
+    unsafe {
+        let layout = Layout::from_size_align(ALLOCATION_SIZE, 8).unwrap();
+        let mut ptrs = Vec::with_capacity(NUM_ALLOCATIONS);
+        
+        for _ in 0..NUM_ALLOCATIONS {
+            let ptr = slab_allocator.alloc(layout);
+            ptrs.push(ptr);
+        }
+        
+        for ptr in ptrs {
+            slab_allocator.dealloc(ptr, layout);
+        }
+    }
+    let slab_duration = start.elapsed();
+
     // Benchmark system allocator
     let start = Instant::now();
     unsafe {
@@ -197,11 +217,13 @@ fn performance_comparison() {
     println!("Allocating and deallocating {} blocks of {} bytes each:", NUM_ALLOCATIONS, ALLOCATION_SIZE);
     println!("  Bump allocator:      {:?}", bump_duration);
     println!("  Free list allocator: {:?}", free_list_duration);
+    println!("  Slab allocator:      {:?}", slab_duration);
     println!("  System allocator:    {:?}", system_duration);
     
     println!("\nRelative performance (lower is better):");
     println!("  Bump allocator:      {:.2}x", bump_duration.as_nanos() as f64 / system_duration.as_nanos() as f64);
     println!("  Free list allocator: {:.2}x", free_list_duration.as_nanos() as f64 / system_duration.as_nanos() as f64);
+    println!("  Slab allocator:      {:.2}x", slab_duration.as_nanos() as f64 / system_duration.as_nanos() as f64);
     println!("  System allocator:    1.00x (baseline)");
 
     println!("\nNote: These benchmarks are simplified and may not reflect real-world performance.");
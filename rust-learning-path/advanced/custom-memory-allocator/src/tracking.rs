@@ -0,0 +1,142 @@
+//! An allocator wrapper that keeps statistics
+//!
+//! `TrackingAllocator` can wrap any `GlobalAlloc`, including the system
+//! allocator, and can itself be installed with `#[global_allocator]` to
+//! watch every allocation a program makes.
+
+use std::alloc::{GlobalAlloc, Layout};
+use crate::{AllocatorStats, AllocatorStatsSnapshot};
+
+/// Delegates to an inner allocator, recording every allocation it serves
+///
+/// Recording only updates atomics, so it neither locks nor allocates, which
+/// is what an allocator serving the whole program needs: a lock could be
+/// taken again by an allocation made while it's held, and an allocation
+/// would recurse back into the allocator.
+///
+/// A `realloc` counts as freeing the old block and allocating the new one.
+///
+/// # Examples
+///
+/// ```
+/// use custom_allocator::TrackingAllocator;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static GLOBAL: TrackingAllocator<System> = TrackingAllocator::new(System);
+///
+/// let data = vec![0u8; 4096];
+/// assert!(GLOBAL.stats_snapshot().peak_allocated >= data.len());
+/// ```
+pub struct TrackingAllocator<A> {
+    inner: A,
+    stats: AllocatorStats,
+}
+
+impl<A> TrackingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        TrackingAllocator {
+            inner,
+            stats: AllocatorStats::new(),
+        }
+    }
+
+    /// Get the allocator being tracked
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Get allocation statistics
+    pub fn stats(&self) -> (usize, usize, usize, usize, usize) {
+        self.stats.get_stats()
+    }
+
+    /// Get allocation statistics as of a single moment
+    pub fn stats_snapshot(&self) -> AllocatorStatsSnapshot {
+        self.stats.snapshot()
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.stats.record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.stats.record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.stats.record_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        // On failure the old block is untouched, so nothing changed
+        if !new_ptr.is_null() {
+            self.stats.record_deallocation(layout.size());
+            self.stats.record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FreeListAllocator;
+    use std::alloc::System;
+
+    #[test]
+    fn test_tracks_system_allocations() {
+        let allocator = TrackingAllocator::new(System);
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let large = Layout::from_size_align(1024, 16).unwrap();
+
+        unsafe {
+            let a = allocator.alloc(small);
+            let b = allocator.alloc_zeroed(large);
+            assert!(!a.is_null() && !b.is_null());
+            assert!(std::slice::from_raw_parts(b, 1024).iter().all(|byte| *byte == 0));
+
+            let snapshot = allocator.stats_snapshot();
+            assert_eq!(snapshot.current_allocated, 1088);
+            assert_eq!(snapshot.allocation_count, 2);
+
+            allocator.dealloc(b, large);
+            let grown = allocator.realloc(a, small, 256);
+            assert!(!grown.is_null());
+            allocator.dealloc(grown, Layout::from_size_align(256, 8).unwrap());
+        }
+
+        let snapshot = allocator.stats_snapshot();
+        assert_eq!(snapshot.current_allocated, 0);
+        assert_eq!(snapshot.total_allocated, 64 + 1024 + 256);
+        assert_eq!(snapshot.allocation_count, snapshot.deallocation_count);
+        assert_eq!(snapshot.peak_allocated, 1088);
+    }
+
+    #[test]
+    fn test_failed_allocation_is_not_recorded() {
+        let allocator = TrackingAllocator::new(FreeListAllocator::new(256).unwrap());
+        let layout = Layout::from_size_align(512, 8).unwrap();
+
+        unsafe {
+            assert!(allocator.alloc(layout).is_null());
+        }
+        assert_eq!(allocator.stats_snapshot(), AllocatorStatsSnapshot::default());
+
+        // The inner allocator is still reachable for its own stats
+        let (total_alloc, ..) = allocator.inner().stats();
+        assert_eq!(total_alloc, 0);
+    }
+}
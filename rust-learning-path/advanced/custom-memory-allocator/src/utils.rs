@@ -25,6 +25,22 @@ pub fn align_up(value: usize, align: usize) -> usize {
     (value + align - 1) & !(align - 1)
 }
 
+/// Align a value up like `align_up`, or get `None` if the result would
+/// overflow
+/// 
+/// # Examples
+/// 
+/// ```
+/// use custom_allocator::checked_align_up;
+/// 
+/// assert_eq!(checked_align_up(10, 8), Some(16));
+/// assert_eq!(checked_align_up(usize::MAX - 2, 8), None);
+/// ```
+pub fn checked_align_up(value: usize, align: usize) -> Option<usize> {
+    debug_assert!(align.is_power_of_two(), "Alignment must be a power of 2");
+    Some(value.checked_add(align - 1)? & !(align - 1))
+}
+
 /// Check if a value is aligned to the given alignment
 /// 
 /// # Examples
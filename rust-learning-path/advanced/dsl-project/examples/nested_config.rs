@@ -128,7 +128,7 @@ fn main() {
         }
     }
     
-    let prod_config = AdvancedConfig::new();
+    let prod_config = ProductionConfig::new();
     println!("\nAdvanced Configuration:");
     println!("  Name: {}", prod_config.name);
     println!("  Port: {}", prod_config.port);
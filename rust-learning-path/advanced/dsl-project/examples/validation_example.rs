@@ -109,9 +109,10 @@ fn main() {
     
     println!();
     
-    // Example 4: Configuration with field attributes (demonstration)
+    // Example 4: Configuration with field-level validation rules
+    #[derive(ConfigValidate, Debug)]
     struct AdvancedConfig {
-        #[config_field(required = true, min_length = 1)]
+        #[config_field(required = true)]
         service_name: String,
         
         #[config_field(min = 1, max = 65535)]
@@ -120,7 +121,6 @@ fn main() {
         #[config_field(min = 1, max = 10000)]
         max_connections: u32,
         
-        #[config_field(default = true)]
         logging_enabled: bool,
     }
     
@@ -131,12 +131,15 @@ fn main() {
         logging_enabled: true,
     };
     
-    println!("Advanced Configuration (with field attributes):");
-    println!("  Service Name: {}", advanced_config.service_name);
-    println!("  Port: {}", advanced_config.port);
-    println!("  Max Connections: {}", advanced_config.max_connections);
-    println!("  Logging Enabled: {}", advanced_config.logging_enabled);
-    println!("  Note: Field attributes are parsed but not yet fully implemented");
+    validate_and_report("Advanced Configuration (with field attributes)", &advanced_config);
+    
+    let overloaded_config = AdvancedConfig {
+        max_connections: 50000,
+        ..advanced_config
+    };
+    
+    validate_and_report("Advanced Configuration (too many connections)", &overloaded_config);
+    println!("  Per-field check: {:?}", overloaded_config.validate_max_connections());
     
     println!("\n=== Configuration Validation Example Complete ===");
 }
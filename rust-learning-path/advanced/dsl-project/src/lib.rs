@@ -6,7 +6,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, Field, Type, Lit, Meta};
+use syn::{parse_macro_input, DeriveInput, Data, Fields, Field, Type};
 
 /// A simple declarative macro for basic configuration
 /// 
@@ -55,6 +55,13 @@ macro_rules! simple_config {
 /// This macro supports nested configurations and generates type-safe structs.
 /// It demonstrates advanced token parsing and recursive macro patterns.
 /// 
+/// Each field's type is inferred from its literal: integers become `i64`,
+/// `true`/`false` become `bool` and strings become `&'static str`. A nested
+/// block becomes a struct of its own, named after the block.
+/// 
+/// Every generated struct also gets `get_path`, which looks a value up by a
+/// dotted path such as `"database.port"` and returns it as a string.
+/// 
 /// # Example
 /// 
 /// ```rust
@@ -70,12 +77,19 @@ macro_rules! simple_config {
 ///         }
 ///     }
 /// }
+/// 
+/// let config = AppConfig::new();
+/// let port: i64 = config.database.port;
+/// assert_eq!(config.get_path("database.port"), Some("5432".to_string()));
 /// ```
 #[macro_export]
 macro_rules! config {
     // Entry point: app name with configuration block
     (app $app_name:literal { $($content:tt)* }) => {
-        config!(@parse_config AppConfig, $($content)*);
+        $crate::__config_check_values! {
+            { $($content)* }
+            config!(@parse_config AppConfig, $($content)*);
+        }
     };
     
     // Parse configuration content
@@ -84,14 +98,14 @@ macro_rules! config {
     };
     
     // Build struct with accumulated fields
-    (@build_struct $struct_name:ident, [$($fields:tt)*], $field:ident: $value:expr, $($rest:tt)*) => {
-        config!(@build_struct $struct_name, [$($fields)* ($field, $value, simple),], $($rest)*);
+    (@build_struct $struct_name:ident, [$($fields:tt)*], $field:ident: $value:expr $(, $($rest:tt)*)?) => {
+        config!(@build_struct $struct_name, [$($fields)* (simple, $field, $value),], $($($rest)*)?);
     };
     
     // Handle nested configuration blocks
-    (@build_struct $struct_name:ident, [$($fields:tt)*], $field:ident { $($nested:tt)* }, $($rest:tt)*) => {
+    (@build_struct $struct_name:ident, [$($fields:tt)*], $field:ident { $($nested:tt)* } $(, $($rest:tt)*)?) => {
         config!(@create_nested_struct $field, $($nested)*);
-        config!(@build_struct $struct_name, [$($fields)* ($field, $field, nested),], $($rest)*);
+        config!(@build_struct $struct_name, [$($fields)* (nested, $field, $field),], $($($rest)*)?);
     };
     
     // Final struct generation
@@ -105,42 +119,38 @@ macro_rules! config {
     };
     
     // Generate the final struct definition
-    (@generate_struct $struct_name:ident, $(($field:ident, $value:expr, simple),)*) => {
-        #[derive(Debug, Clone)]
-        pub struct $struct_name {
-            $(pub $field: &'static str,)*
-        }
-        
-        impl $struct_name {
-            pub fn new() -> Self {
-                Self {
-                    $($field: $value,)*
-                }
-            }
-        }
-        
-        impl Default for $struct_name {
-            fn default() -> Self {
-                Self::new()
-            }
-        }
-    };
-    
-    // Generate struct with nested fields
-    (@generate_struct $struct_name:ident, $(($field:ident, $field_type:ident, $field_kind:ident),)*) => {
+    (@generate_struct $struct_name:ident, $(($field_kind:ident, $field:ident, $value:tt),)*) => {
+        #[allow(non_camel_case_types)]
         #[derive(Debug, Clone)]
         pub struct $struct_name {
-            $(pub $field: config!(@field_type $field_type, $field_kind),)*
+            $(pub $field: config!(@field_type $field_kind $value),)*
         }
         
         impl $struct_name {
             pub fn new() -> Self {
                 Self {
                     $(
-                        $field: config!(@field_default $field_type, $field_kind),
+                        $field: config!(@field_default $field_kind $value),
                     )*
                 }
             }
+            
+            /// Look up a value by its dotted path, such as `"database.port"`
+            /// 
+            /// Returns `None` for unknown paths and for paths naming a
+            /// nested block rather than a value.
+            pub fn get_path(&self, path: &str) -> Option<String> {
+                let (head, rest) = match path.split_once('.') {
+                    Some((head, rest)) => (head, Some(rest)),
+                    None => (path, None),
+                };
+                $(
+                    if head == stringify!($field) {
+                        return config!(@get_path $field_kind &self.$field, rest);
+                    }
+                )*
+                None
+            }
         }
         
         impl Default for $struct_name {
@@ -151,12 +161,23 @@ macro_rules! config {
     };
     
     // Helper to determine field type
-    (@field_type $field_type:ident, simple) => { &'static str };
-    (@field_type $field_type:ident, nested) => { $field_type };
+    (@field_type simple $value:expr) => { $crate::__config_field_type!($value) };
+    (@field_type nested $nested_name:ident) => { $nested_name };
     
     // Helper to determine field default
-    (@field_default $field_type:expr, simple) => { $field_type };
-    (@field_default $field_type:ident, nested) => { $field_type::new() };
+    (@field_default simple $value:expr) => { $value };
+    (@field_default nested $nested_name:ident) => { $nested_name::new() };
+    
+    // Helper to look up the rest of a dotted path in a field
+    (@get_path simple $field:expr, $rest:expr) => {
+        match $rest {
+            None => Some($field.to_string()),
+            Some(_) => None,
+        }
+    };
+    (@get_path nested $field:expr, $rest:expr) => {
+        $rest.and_then(|rest| $field.get_path(rest))
+    };
 }
 
 /// Derive macro for configuration validation
@@ -164,51 +185,98 @@ macro_rules! config {
 /// This procedural macro generates validation methods for configuration structs.
 /// It demonstrates how to parse struct definitions and generate trait implementations.
 /// 
+/// Fields without attributes are checked with their `Validate` implementation.
+/// Fields marked with `#[config_field(...)]` get a `validate_<field>()` method
+/// that enforces the rules in the attribute instead:
+/// 
+/// - `required` (or `required = true`): a `String` or `&str` must not be empty,
+///   an `Option` must be `Some`
+/// - `min = ...` / `max = ...`: an inclusive range for a numeric field
+/// 
+/// The struct also gets `to_json()` and `from_json()`, which save and load
+/// it field by field; `from_json` validates the loaded config. The code for
+/// both is generated into the struct's crate, so it needs no JSON library.
+/// Fields may be strings, booleans, numbers, `Option`, `Vec`, or nested
+/// structs that derive `ConfigValidate` too. Structs with `&str` fields only
+/// get `to_json()`, as there's nothing for a loaded string to borrow from.
+/// 
 /// # Example
 /// 
 /// ```rust
 /// #[derive(ConfigValidate)]
 /// struct DatabaseConfig {
+///     #[config_field(required)]
 ///     host: String,
+///     #[config_field(min = 1, max = 65535)]
 ///     port: u16,
 ///     name: String,
 /// }
 /// ```
-#[proc_macro_derive(ConfigValidate)]
+#[proc_macro_derive(ConfigValidate, attributes(config_field))]
 pub fn derive_config_validate(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
     
-    let validation_methods = match &input.data {
-        Data::Struct(data_struct) => {
-            match &data_struct.fields {
-                Fields::Named(fields) => {
-                    let field_validations = fields.named.iter().map(|field| {
-                        let field_name = &field.ident;
-                        let field_type = &field.ty;
+    let mut field_validations = Vec::new();
+    let mut field_methods = Vec::new();
+    
+    if let Data::Struct(data_struct) = &input.data {
+        if let Fields::Named(fields) = &data_struct.fields {
+            for field in &fields.named {
+                let field_name = &field.ident;
+                
+                let rules = match parse_field_rules(field) {
+                    Ok(rules) => rules,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+                
+                match rules {
+                    Some(rules) => {
+                        let method = quote::format_ident!("validate_{}", field.ident.as_ref().unwrap());
+                        let checks = match field_rule_checks(field, &rules) {
+                            Ok(checks) => checks,
+                            Err(err) => return err.to_compile_error().into(),
+                        };
                         
-                        quote! {
+                        field_methods.push(quote! {
+                            /// Check this field against the rules in its `config_field` attribute
+                            pub fn #method(&self) -> Result<(), String> {
+                                #checks
+                                Ok(())
+                            }
+                        });
+                        field_validations.push(quote! {
+                            self.#method()?;
+                        });
+                    }
+                    None => {
+                        field_validations.push(quote! {
                             // Validate field based on its type
                             if !self.#field_name.is_valid() {
                                 return Err(format!("Invalid value for field '{}'", stringify!(#field_name)));
                             }
-                        }
-                    });
-                    
-                    quote! {
-                        #(#field_validations)*
+                        });
                     }
                 }
-                _ => quote! {},
             }
         }
+    }
+    
+    let json_methods = match &input.data {
+        Data::Struct(data_struct) => json_methods(name, &data_struct.fields),
         _ => quote! {},
     };
     
     let expanded = quote! {
+        impl #name {
+            #(#field_methods)*
+        }
+        
+        #json_methods
+        
         impl ConfigValidate for #name {
             fn validate(&self) -> Result<(), String> {
-                #validation_methods
+                #(#field_validations)*
                 Ok(())
             }
             
@@ -221,6 +289,480 @@ pub fn derive_config_validate(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Validation rules read from a `#[config_field(...)]` attribute
+struct FieldRules {
+    required: bool,
+    min: Option<syn::Expr>,
+    max: Option<syn::Expr>,
+}
+
+/// Read the `config_field` attribute of a field, if it has one
+fn parse_field_rules(field: &Field) -> syn::Result<Option<FieldRules>> {
+    let mut rules = None;
+    
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("config_field")) {
+        let rules = rules.get_or_insert(FieldRules {
+            required: false,
+            min: None,
+            max: None,
+        });
+        
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("required") {
+                rules.required = if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::LitBool>()?.value
+                } else {
+                    true
+                };
+            } else if meta.path.is_ident("min") {
+                rules.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                rules.max = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported config_field rule, expected `required`, `min` or `max`"));
+            }
+            Ok(())
+        })?;
+    }
+    
+    Ok(rules)
+}
+
+/// Generate the checks for a field's rules, or an error if the rules don't
+/// apply to the field's type
+fn field_rule_checks(field: &Field, rules: &FieldRules) -> syn::Result<proc_macro2::TokenStream> {
+    let field_name = &field.ident;
+    let field_type = &field.ty;
+    let mut checks = Vec::new();
+    
+    if rules.required {
+        let check = match field_kind(field_type) {
+            FieldKind::Text => quote! { self.#field_name.is_empty() },
+            FieldKind::Optional => quote! { self.#field_name.is_none() },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    field_type,
+                    "`required` is only supported on String, &str and Option fields",
+                ))
+            }
+        };
+        checks.push(quote! {
+            if #check {
+                return Err(format!("Field '{}' is required", stringify!(#field_name)));
+            }
+        });
+    }
+    
+    if rules.min.is_some() || rules.max.is_some() {
+        // Integer bounds are compared as written, so a bound that doesn't fit
+        // the field's type is a compile error; float fields also accept
+        // integer bounds
+        let bound = |expr: &syn::Expr| match field_kind(field_type) {
+            FieldKind::Integer => Ok(quote! { (#expr) }),
+            FieldKind::Float => Ok(quote! { ((#expr) as #field_type) }),
+            _ => Err(syn::Error::new_spanned(
+                field_type,
+                "`min` and `max` are only supported on numeric fields",
+            )),
+        };
+        
+        if let Some(min) = &rules.min {
+            let min = bound(min)?;
+            checks.push(quote! {
+                if self.#field_name < #min {
+                    return Err(format!(
+                        "Field '{}' must be at least {}, got {}",
+                        stringify!(#field_name), #min, self.#field_name
+                    ));
+                }
+            });
+        }
+        if let Some(max) = &rules.max {
+            let max = bound(max)?;
+            checks.push(quote! {
+                if self.#field_name > #max {
+                    return Err(format!(
+                        "Field '{}' must be at most {}, got {}",
+                        stringify!(#field_name), #max, self.#field_name
+                    ));
+                }
+            });
+        }
+    }
+    
+    Ok(quote! { #(#checks)* })
+}
+
+/// The kinds of field types that `config_field` rules know how to check
+enum FieldKind {
+    Integer,
+    Float,
+    Text,
+    Optional,
+    Other,
+}
+
+fn field_kind(ty: &Type) -> FieldKind {
+    match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => FieldKind::Text,
+            _ => FieldKind::Other,
+        },
+        Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return FieldKind::Other;
+            };
+            match segment.ident.to_string().as_str() {
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize"
+                | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => FieldKind::Integer,
+                "f32" | "f64" => FieldKind::Float,
+                "String" => FieldKind::Text,
+                "Option" => FieldKind::Optional,
+                _ => FieldKind::Other,
+            }
+        }
+        _ => FieldKind::Other,
+    }
+}
+
+/// How a field is written to and read from JSON
+enum JsonShape<'a> {
+    Text,
+    /// `&str`, which can be written but not read back
+    BorrowedText,
+    Bool,
+    Integer,
+    Float,
+    Optional(&'a Type),
+    List(&'a Type),
+    /// A struct that derives `ConfigValidate` and so has its own JSON methods
+    Nested,
+}
+
+fn json_shape(ty: &Type) -> JsonShape<'_> {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            let inner = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                    syn::GenericArgument::Type(inner) => Some(inner),
+                    _ => None,
+                }),
+                _ => None,
+            };
+            match (segment.ident.to_string().as_str(), inner) {
+                ("Option", Some(inner)) => return JsonShape::Optional(inner),
+                ("Vec", Some(inner)) => return JsonShape::List(inner),
+                ("bool", None) => return JsonShape::Bool,
+                _ => {}
+            }
+        }
+    }
+    
+    match (field_kind(ty), ty) {
+        (FieldKind::Integer, _) => JsonShape::Integer,
+        (FieldKind::Float, _) => JsonShape::Float,
+        (FieldKind::Text, Type::Reference(_)) => JsonShape::BorrowedText,
+        (FieldKind::Text, _) => JsonShape::Text,
+        _ => JsonShape::Nested,
+    }
+}
+
+/// Generate statements that append `value` (an expression of type `&ty`)
+/// to the `json` string
+fn json_write(ty: &Type, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match json_shape(ty) {
+        JsonShape::Text | JsonShape::BorrowedText => quote! { json_escape(&mut json, #value); },
+        JsonShape::Bool | JsonShape::Integer => quote! { json.push_str(&(#value).to_string()); },
+        JsonShape::Float => quote! {
+            // NaN and infinity have no JSON form
+            let number = *#value;
+            if number.is_finite() {
+                json.push_str(&number.to_string());
+            } else {
+                json.push_str("null");
+            }
+        },
+        JsonShape::Optional(inner) => {
+            let write = json_write(inner, quote! { value });
+            quote! {
+                match #value {
+                    Some(value) => { #write }
+                    None => json.push_str("null"),
+                }
+            }
+        }
+        JsonShape::List(inner) => {
+            let write = json_write(inner, quote! { item });
+            quote! {
+                json.push('[');
+                for (i, item) in (#value).iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    #write
+                }
+                json.push(']');
+            }
+        }
+        JsonShape::Nested => quote! { json.push_str(&(#value).to_json()); },
+    }
+}
+
+/// Generate an expression that reads a `Result<ty, String>` from `raw`, the
+/// `&str` text of one JSON value, or `None` if the type can't be loaded
+fn json_read(ty: &Type, raw: proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+    let read = match json_shape(ty) {
+        JsonShape::Text => quote! { json_string(#raw) },
+        JsonShape::BorrowedText => return None,
+        JsonShape::Bool => quote! {
+            match #raw {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                other => Err(json_mismatch("a boolean", other)),
+            }
+        },
+        JsonShape::Integer | JsonShape::Float => quote! {{
+            let raw = #raw;
+            if json_kind(raw) == "a number" {
+                raw.parse::<#ty>().map_err(|_| format!("expected {}, got {}", stringify!(#ty), raw))
+            } else {
+                Err(json_mismatch("a number", raw))
+            }
+        }},
+        JsonShape::Optional(inner) => {
+            let read = json_read(inner, quote! { raw })?;
+            quote! {{
+                let raw = #raw;
+                if raw == "null" { Ok(None) } else { #read.map(Some) }
+            }}
+        }
+        JsonShape::List(inner) => {
+            let read = json_read(inner, quote! { raw })?;
+            quote! {
+                json_items(#raw, '[', ']', "an array")
+                    .and_then(|items| items.into_iter().map(|raw| #read).collect::<Result<Vec<_>, String>>())
+            }
+        }
+        JsonShape::Nested => quote! { <#ty>::from_json(#raw) },
+    };
+    Some(read)
+}
+
+/// Generate `to_json` and, when every field can be loaded, `from_json`,
+/// together with the small JSON scanner they use. Everything goes in an
+/// anonymous const block so the helpers don't leak into the caller's module.
+fn json_methods(name: &syn::Ident, fields: &Fields) -> proc_macro2::TokenStream {
+    let mut writes = Vec::new();
+    let mut reads = Some(Vec::new());
+    
+    for (i, field) in fields.iter().enumerate() {
+        let Some(ident) = &field.ident else { continue };
+        let key = format!("{}\"{}\":", if i > 0 { "," } else { "" }, ident);
+        let write = json_write(&field.ty, quote! { &self.#ident });
+        writes.push(quote! {
+            json.push_str(#key);
+            #write
+        });
+        
+        let name = ident.to_string();
+        reads = reads.zip(json_read(&field.ty, quote! { field(#name) })).map(|(mut reads, read)| {
+            reads.push(quote! {
+                #ident: #read.map_err(|err| format!("Field '{}': {}", #name, err))?,
+            });
+            reads
+        });
+    }
+    
+    let reader = reads.is_some().then(json_reader);
+    let from_json = reads.map(|reads| quote! {
+        /// Load a config saved by `to_json`, rejecting it if it doesn't validate
+        pub fn from_json(json: &str) -> Result<Self, String> {
+            let fields = json_object(json.trim())?;
+            // A missing field reads as `null`, so `Option` fields may be left out
+            let field = |name: &str| {
+                fields.iter().find(|(key, _)| key == name).map_or("null", |(_, raw)| *raw)
+            };
+            let config = Self {
+                #(#reads)*
+            };
+            ConfigValidate::validate(&config)?;
+            Ok(config)
+        }
+    });
+    
+    let writer = json_writer();
+    quote! {
+        const _: () = {
+            impl #name {
+                /// Save the config as a JSON object with one entry per field
+                pub fn to_json(&self) -> String {
+                    let mut json = String::from("{");
+                    #(#writes)*
+                    json.push('}');
+                    json
+                }
+                
+                #from_json
+            }
+            
+            #writer
+            #reader
+        };
+    }
+}
+
+/// String escaping for the generated `to_json`
+fn json_writer() -> proc_macro2::TokenStream {
+    quote! {
+        fn json_escape(json: &mut String, value: &str) {
+            json.push('"');
+            for c in value.chars() {
+                match c {
+                    '"' => json.push_str("\\\""),
+                    '\\' => json.push_str("\\\\"),
+                    '\n' => json.push_str("\\n"),
+                    '\r' => json.push_str("\\r"),
+                    '\t' => json.push_str("\\t"),
+                    c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => json.push(c),
+                }
+            }
+            json.push('"');
+        }
+    }
+}
+
+/// The functions the generated `from_json` calls: a scanner that splits JSON
+/// text into the raw text of each value, which the per-field code converts
+fn json_reader() -> proc_macro2::TokenStream {
+    quote! {
+        fn json_kind(raw: &str) -> &'static str {
+            match raw.chars().next() {
+                Some('"') => "a string",
+                Some('t' | 'f') => "a boolean",
+                Some('n') => "null",
+                Some('[') => "an array",
+                Some('{') => "an object",
+                _ => "a number",
+            }
+        }
+        
+        fn json_mismatch(expected: &str, raw: &str) -> String {
+            format!("expected {}, got {}", expected, json_kind(raw))
+        }
+        
+        fn json_string(raw: &str) -> Result<String, String> {
+            let inner = raw.strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+                .ok_or_else(|| json_mismatch("a string", raw))?;
+            let mut value = String::new();
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    value.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .filter(|_| hex.len() == 4)
+                            .ok_or_else(|| format!("Invalid JSON: bad \\u escape in {}", raw))?;
+                        value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    _ => return Err(format!("Invalid JSON: bad escape in {}", raw)),
+                }
+            }
+            Ok(value)
+        }
+        
+        /// The trimmed text of each top-level value between `open` and `close`
+        fn json_items<'a>(raw: &'a str, open: char, close: char, expected: &str) -> Result<Vec<&'a str>, String> {
+            let inner = raw.strip_prefix(open)
+                .and_then(|rest| rest.strip_suffix(close))
+                .ok_or_else(|| json_mismatch(expected, raw))?;
+            let unbalanced = || format!("Invalid JSON: unbalanced brackets or quotes in {}", raw);
+            
+            let mut items = Vec::new();
+            let (mut depth, mut in_string, mut escaped, mut start) = (0usize, false, false, 0);
+            for (i, c) in inner.char_indices() {
+                if in_string {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => in_string = false,
+                        _ => {}
+                    }
+                    continue;
+                }
+                match c {
+                    '"' => in_string = true,
+                    '[' | '{' => depth += 1,
+                    ']' | '}' => depth = depth.checked_sub(1).ok_or_else(unbalanced)?,
+                    ',' if depth == 0 => {
+                        items.push(inner[start..i].trim());
+                        start = i + 1;
+                    }
+                    _ => {}
+                }
+            }
+            if in_string || depth != 0 {
+                return Err(unbalanced());
+            }
+            
+            let last = inner[start..].trim();
+            if !last.is_empty() || !items.is_empty() {
+                items.push(last);
+            }
+            if items.contains(&"") {
+                return Err(format!("Invalid JSON: empty value in {}", raw));
+            }
+            Ok(items)
+        }
+        
+        /// The name and raw value text of each field of an object
+        fn json_object(raw: &str) -> Result<Vec<(String, &str)>, String> {
+            json_items(raw, '{', '}', "an object")?
+                .into_iter()
+                .map(|item| {
+                    let mut escaped = false;
+                    let key_end = item.char_indices().skip(1).find(|&(_, c)| match c {
+                        _ if escaped => {
+                            escaped = false;
+                            false
+                        }
+                        '\\' => {
+                            escaped = true;
+                            false
+                        }
+                        c => c == '"',
+                    });
+                    let bad_field = || format!("Invalid JSON: expected \"name\": value, got {}", item);
+                    let key_end = key_end
+                        .filter(|_| item.starts_with('"'))
+                        .map(|(i, _)| i)
+                        .ok_or_else(bad_field)?;
+                    let value = item[key_end + 1..].trim_start()
+                        .strip_prefix(':')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .ok_or_else(bad_field)?;
+                    Ok((json_string(&item[..=key_end])?, value))
+                })
+                .collect()
+        }
+    }
+}
+
 /// Trait for configuration validation
 pub trait ConfigValidate {
     fn validate(&self) -> Result<(), String>;
@@ -262,25 +804,117 @@ impl Validate for bool {
     }
 }
 
-/// Attribute macro for field-level configuration
-/// 
-/// This macro demonstrates attribute parsing and code modification.
-/// It can be used to add validation rules to struct fields.
+/// A list is valid when every item is
+impl<T: Validate> Validate for Vec<T> {
+    fn is_valid(&self) -> bool {
+        self.iter().all(Validate::is_valid)
+    }
+}
+
+/// A missing optional value is valid; use `#[config_field(required)]` to require it
+impl<T: Validate> Validate for Option<T> {
+    fn is_valid(&self) -> bool {
+        self.as_ref().is_none_or(Validate::is_valid)
+    }
+}
+
+/// Field type for a simple `config!` field, inferred from its literal value
 /// 
-/// # Example
+/// Used by `config!`, which can't tell literal kinds apart on its own.
+/// Integer literals map to `i64` (or their suffix type, like `8080u16`),
+/// booleans to `bool` and strings to `&'static str`. Other values have
+/// already been reported by `__config_check_values`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __config_field_type(input: TokenStream) -> TokenStream {
+    let value = parse_macro_input!(input as syn::Expr);
+    
+    match config_literal_type(&value) {
+        Ok(field_type) => TokenStream::from(field_type),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn config_literal_type(value: &syn::Expr) -> syn::Result<proc_macro2::TokenStream> {
+    let unsupported = || {
+        syn::Error::new_spanned(
+            value,
+            "config! field values must be integer, boolean or string literals",
+        )
+    };
+    
+    match value {
+        // Values forwarded through `config!` arrive wrapped in invisible groups
+        syn::Expr::Group(group) => config_literal_type(&group.expr),
+        syn::Expr::Paren(paren) => config_literal_type(&paren.expr),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => match &**expr {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(_), .. }) => config_literal_type(expr),
+            _ => Err(unsupported()),
+        },
+        syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Int(int) if int.suffix().is_empty() => Ok(quote! { i64 }),
+            syn::Lit::Int(int) => {
+                let suffix = quote::format_ident!("{}", int.suffix());
+                Ok(quote! { #suffix })
+            }
+            syn::Lit::Bool(_) => Ok(quote! { bool }),
+            syn::Lit::Str(_) => Ok(quote! { &'static str }),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    }
+}
+
+/// Check every simple value in a `config!` block before it's expanded
 /// 
-/// ```rust
-/// struct Config {
-///     #[config_field(required = true, min = 1, max = 65535)]
-///     port: u16,
-/// }
-/// ```
-#[proc_macro_attribute]
-pub fn config_field(args: TokenStream, input: TokenStream) -> TokenStream {
-    // For now, this is a placeholder that just returns the input unchanged
-    // In a full implementation, this would parse the attributes and modify the field
-    let _args = args; // Parse validation rules from args
-    input // Return the field unchanged for now
+/// Expands to the tokens after the block if each value is a literal
+/// `config!` supports, and to only the errors otherwise: the field types
+/// can't be inferred from a bad value, so expanding the structs anyway
+/// would add a type error for every use of it.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __config_check_values(input: TokenStream) -> TokenStream {
+    let mut tokens = proc_macro2::TokenStream::from(input).into_iter();
+    let block = match tokens.next() {
+        Some(proc_macro2::TokenTree::Group(block)) => block,
+        _ => return TokenStream::new(),
+    };
+    let expansion: proc_macro2::TokenStream = tokens.collect();
+    
+    // A block that doesn't parse is left for `config!` itself to report
+    let errors = syn::parse::Parser::parse2(config_value_errors, block.stream()).unwrap_or_default();
+    if errors.is_empty() {
+        expansion.into()
+    } else {
+        errors.iter().map(syn::Error::to_compile_error).collect::<proc_macro2::TokenStream>().into()
+    }
+}
+
+/// Collect an error for each unsupported value in a `config!` block,
+/// including nested blocks
+fn config_value_errors(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::Error>> {
+    let mut errors = Vec::new();
+    
+    while !input.is_empty() {
+        input.parse::<syn::Ident>()?;
+        if input.peek(syn::Token![:]) {
+            input.parse::<syn::Token![:]>()?;
+            let value: syn::Expr = input.parse()?;
+            if let Err(err) = config_literal_type(&value) {
+                errors.push(err);
+            }
+        } else {
+            let content;
+            syn::braced!(content in input);
+            errors.extend(config_value_errors(&content)?);
+        }
+        
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+    }
+    
+    Ok(errors)
 }
 
 /// Function-like procedural macro for advanced configuration
@@ -288,43 +922,76 @@ pub fn config_field(args: TokenStream, input: TokenStream) -> TokenStream {
 /// This macro demonstrates complex token parsing and code generation.
 /// It provides more flexibility than declarative macros.
 /// 
+/// Each field is declared as `name: Type = default`. A default of
+/// `env!("VAR", fallback)` reads the environment variable `VAR` when the
+/// config is built and parses it into the field's type, using `fallback`
+/// if the variable is unset. String literal defaults are converted into
+/// `String` for `String` fields.
+/// 
+/// The generated `try_new()` returns an error naming the variable if one
+/// doesn't parse; `new()` and `Default` panic with that error instead.
+/// 
 /// # Example
 /// 
 /// ```rust
 /// advanced_config! {
 ///     struct AppConfig {
 ///         name: String = env!("APP_NAME", "DefaultApp"),
-///         port: u16 = 8080,
+///         port: u16 = env!("APP_PORT", 8080),
 ///         debug: bool = cfg!(debug_assertions),
 ///     }
 /// }
 /// ```
 #[proc_macro]
 pub fn advanced_config(input: TokenStream) -> TokenStream {
-    // This is a simplified implementation
-    // A full implementation would parse the entire configuration syntax
-    let input_str = input.to_string();
+    let AdvancedConfigInput { attrs, vis, name, fields } = parse_macro_input!(input as AdvancedConfigInput);
+    
+    let field_names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let field_values = fields.iter().map(|field| {
+        let field_type = &field.ty;
+        match &field.default {
+            FieldDefault::Value(value) => config_default_value(field_type, value),
+            FieldDefault::Env { var, fallback } => {
+                let fallback = config_default_value(field_type, fallback);
+                quote! {
+                    match ::std::env::var(#var) {
+                        Ok(value) => value.parse::<#field_type>().map_err(|err| {
+                            format!(
+                                "Environment variable {}={:?} is not a valid {}: {}",
+                                #var, value, stringify!(#field_type), err
+                            )
+                        })?,
+                        Err(::std::env::VarError::NotPresent) => #fallback,
+                        Err(err) => return Err(format!("Environment variable {}: {}", #var, err)),
+                    }
+                }
+            }
+        }
+    });
     
-    // For demonstration, generate a simple struct
     let expanded = quote! {
+        #(#attrs)*
         #[derive(Debug, Clone)]
-        pub struct AdvancedConfig {
-            pub name: String,
-            pub port: u16,
-            pub debug: bool,
+        #vis struct #name {
+            #(pub #field_names: #field_types,)*
         }
         
-        impl AdvancedConfig {
+        impl #name {
+            /// Build the config, reading each environment variable once
+            pub fn try_new() -> Result<Self, String> {
+                Ok(Self {
+                    #(#field_names: #field_values,)*
+                })
+            }
+            
+            /// Build the config, panicking if an environment variable doesn't parse
             pub fn new() -> Self {
-                Self {
-                    name: std::env::var("APP_NAME").unwrap_or_else(|_| "DefaultApp".to_string()),
-                    port: 8080,
-                    debug: cfg!(debug_assertions),
-                }
+                Self::try_new().unwrap_or_else(|err| panic!("{}", err))
             }
         }
         
-        impl Default for AdvancedConfig {
+        impl Default for #name {
             fn default() -> Self {
                 Self::new()
             }
@@ -334,6 +1001,86 @@ pub fn advanced_config(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Input to `advanced_config!`: a struct whose fields have defaults
+struct AdvancedConfigInput {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: syn::Ident,
+    fields: syn::punctuated::Punctuated<ConfigFieldDef, syn::Token![,]>,
+}
+
+/// One `name: Type = default` field of `advanced_config!`
+struct ConfigFieldDef {
+    name: syn::Ident,
+    ty: Type,
+    default: FieldDefault,
+}
+
+enum FieldDefault {
+    /// Any expression of the field's type
+    Value(syn::Expr),
+    /// `env!("VAR", fallback)`
+    Env { var: syn::LitStr, fallback: syn::Expr },
+}
+
+impl syn::parse::Parse for AdvancedConfigInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<syn::Token![struct]>()?;
+        let name = input.parse()?;
+        
+        let content;
+        syn::braced!(content in input);
+        let fields = content.parse_terminated(ConfigFieldDef::parse, syn::Token![,])?;
+        
+        Ok(Self { attrs, vis, name, fields })
+    }
+}
+
+impl syn::parse::Parse for ConfigFieldDef {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<syn::Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        
+        let default = match input.parse()? {
+            syn::Expr::Macro(expr) if expr.mac.path.is_ident("env") => {
+                let args = expr.mac.parse_body_with(
+                    syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+                )?;
+                let mut args = args.into_iter();
+                match (args.next(), args.next(), args.next()) {
+                    (Some(syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(var), .. })), Some(fallback), None) => {
+                        FieldDefault::Env { var, fallback }
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &expr.mac,
+                            "expected env!(\"VARIABLE\", fallback)",
+                        ))
+                    }
+                }
+            }
+            value => FieldDefault::Value(value),
+        };
+        
+        Ok(Self { name, ty, default })
+    }
+}
+
+/// A field's default value, turning string literals into `String` for `String` fields
+fn config_default_value(field_type: &Type, value: &syn::Expr) -> proc_macro2::TokenStream {
+    let is_string = matches!(field_type, Type::Path(path) if path.path.is_ident("String"));
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(_), .. }) if is_string => {
+            quote! { ::std::string::String::from(#value) }
+        }
+        _ => quote! { #value },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
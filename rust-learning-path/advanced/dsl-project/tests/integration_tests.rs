@@ -16,6 +16,70 @@ fn test_simple_config_generation() {
     assert_eq!(config.debug, "true");
 }
 
+mod typed_config {
+    use dsl_project::*;
+
+    config! {
+        app "TypedApp" {
+            version: "1.0.0",
+            debug: true,
+            retries: -3,
+
+            database {
+                host: "localhost",
+                port: 5432,
+                ssl: false
+            },
+
+            server {
+                workers: 4,
+                timeout_ms: 30_000u32
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_infers_field_types() {
+        let config = AppConfig::new();
+
+        // These bindings only compile if the fields have the inferred types
+        let version: &'static str = config.version;
+        let debug: bool = config.debug;
+        let retries: i64 = config.retries;
+        let host: &'static str = config.database.host;
+        let port: i64 = config.database.port;
+        let ssl: bool = config.database.ssl;
+        let workers: i64 = config.server.workers;
+        let timeout_ms: u32 = config.server.timeout_ms;
+
+        assert_eq!(version, "1.0.0");
+        assert!(debug);
+        assert_eq!(retries, -3);
+        assert_eq!(host, "localhost");
+        assert_eq!(port, 5432);
+        assert!(!ssl);
+        assert_eq!(workers, 4);
+        assert_eq!(timeout_ms, 30_000);
+    }
+
+    #[test]
+    fn test_config_get_path() {
+        let config = AppConfig::new();
+
+        assert_eq!(config.get_path("database.port"), Some("5432".to_string()));
+        assert_eq!(config.get_path("database.host"), Some("localhost".to_string()));
+        assert_eq!(config.get_path("server.timeout_ms"), Some("30000".to_string()));
+        assert_eq!(config.get_path("debug"), Some("true".to_string()));
+        assert_eq!(config.database.get_path("ssl"), Some("false".to_string()));
+
+        assert_eq!(config.get_path("database.bogus"), None);
+        assert_eq!(config.get_path("bogus.port"), None);
+        assert_eq!(config.get_path("database"), None);
+        assert_eq!(config.get_path("database.port.value"), None);
+        assert_eq!(config.get_path(""), None);
+    }
+}
+
 #[test]
 fn test_config_validation_derive() {
     #[derive(ConfigValidate)]
@@ -51,9 +115,66 @@ fn test_advanced_config_macro() {
         }
     }
     
-    let config = AdvancedConfig::new();
-    assert!(!config.name.is_empty());
-    assert!(config.port > 0);
+    let config = TestAdvancedConfig::new();
+    assert_eq!(config.name, "TestApp");
+    assert_eq!(config.port, 3000);
+    assert!(config.debug);
+}
+
+#[test]
+fn test_advanced_config_reads_env() {
+    advanced_config! {
+        struct EnvConfig {
+            name: String = env!("DSL_TEST_ENV_NAME", "DefaultApp"),
+            port: u16 = env!("DSL_TEST_ENV_PORT", 8080),
+            debug: bool = env!("DSL_TEST_ENV_DEBUG", false),
+            workers: u32 = 4,
+        }
+    }
+    
+    std::env::set_var("DSL_TEST_ENV_NAME", "FromEnv");
+    std::env::set_var("DSL_TEST_ENV_PORT", "9090");
+    std::env::set_var("DSL_TEST_ENV_DEBUG", "true");
+    
+    let config = EnvConfig::new();
+    assert_eq!(config.name, "FromEnv");
+    assert_eq!(config.port, 9090);
+    assert!(config.debug);
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn test_advanced_config_env_fallback() {
+    advanced_config! {
+        struct FallbackConfig {
+            name: String = env!("DSL_TEST_FALLBACK_NAME", "DefaultApp"),
+            port: u16 = env!("DSL_TEST_FALLBACK_PORT", 8080),
+            debug: bool = env!("DSL_TEST_FALLBACK_DEBUG", false),
+        }
+    }
+    
+    std::env::remove_var("DSL_TEST_FALLBACK_NAME");
+    std::env::remove_var("DSL_TEST_FALLBACK_PORT");
+    std::env::remove_var("DSL_TEST_FALLBACK_DEBUG");
+    
+    let config = FallbackConfig::default();
+    assert_eq!(config.name, "DefaultApp");
+    assert_eq!(config.port, 8080);
+    assert!(!config.debug);
+}
+
+#[test]
+fn test_advanced_config_invalid_env() {
+    advanced_config! {
+        struct InvalidEnvConfig {
+            port: u16 = env!("DSL_TEST_INVALID_PORT", 8080),
+        }
+    }
+    
+    std::env::set_var("DSL_TEST_INVALID_PORT", "70000");
+    let err = InvalidEnvConfig::try_new().unwrap_err();
+    assert!(err.contains("DSL_TEST_INVALID_PORT"), "unexpected error: {}", err);
+    assert!(err.contains("u16"), "unexpected error: {}", err);
 }
 
 #[test]
@@ -73,6 +194,7 @@ fn test_validation_traits() {
 
 #[test]
 fn test_config_field_attribute() {
+    #[derive(ConfigValidate)]
     struct TestFieldConfig {
         #[config_field(required = true)]
         name: String,
@@ -80,11 +202,216 @@ fn test_config_field_attribute() {
         port: u16,
     }
     
-    // This test verifies that the attribute macro doesn't break compilation
-    let _config = TestFieldConfig {
+    let config = TestFieldConfig {
+        name: "test".to_string(),
+        port: 8080,
+    };
+    assert!(config.validate_name().is_ok());
+    assert!(config.validate_port().is_ok());
+    assert!(config.is_valid());
+    
+    let missing_name = TestFieldConfig {
+        name: "".to_string(),
+        port: 8080,
+    };
+    assert_eq!(missing_name.validate_name(), Err("Field 'name' is required".to_string()));
+    assert_eq!(missing_name.validate(), Err("Field 'name' is required".to_string()));
+    
+    let zero_port = TestFieldConfig {
         name: "test".to_string(),
+        port: 0,
+    };
+    assert_eq!(zero_port.validate_port(), Err("Field 'port' must be at least 1, got 0".to_string()));
+    assert!(!zero_port.is_valid());
+}
+
+#[test]
+fn test_config_field_ranges() {
+    #[derive(ConfigValidate)]
+    struct RangeConfig {
+        #[config_field(min = 1, max = 65535)]
+        port: u32,
+        #[config_field(min = -10, max = 10)]
+        offset: i32,
+        #[config_field(min = 0, max = 1)]
+        ratio: f64,
+        #[config_field(required)]
+        region: Option<String>,
+        // Unannotated fields still use `Validate`
+        host: String,
+    }
+    
+    let config = |port, offset, ratio| RangeConfig {
+        port,
+        offset,
+        ratio,
+        region: Some("eu".to_string()),
+        host: "localhost".to_string(),
+    };
+    
+    // Bounds are inclusive
+    assert!(config(1, -10, 0.0).is_valid());
+    assert!(config(65535, 10, 1.0).is_valid());
+    
+    assert_eq!(
+        config(70000, 0, 0.5).validate(),
+        Err("Field 'port' must be at most 65535, got 70000".to_string())
+    );
+    assert!(config(0, 0, 0.5).validate_port().is_err());
+    assert!(config(80, -11, 0.5).validate_offset().is_err());
+    assert!(config(80, 0, 1.5).validate_ratio().is_err());
+    
+    let mut no_region = config(80, 0, 0.5);
+    no_region.region = None;
+    assert_eq!(no_region.validate(), Err("Field 'region' is required".to_string()));
+    
+    let mut no_host = config(80, 0, 0.5);
+    no_host.host = String::new();
+    assert_eq!(no_host.validate(), Err("Invalid value for field 'host'".to_string()));
+}
+
+#[derive(ConfigValidate, Debug, PartialEq)]
+struct SavedConfig {
+    #[config_field(required)]
+    name: String,
+    #[config_field(min = 1, max = 65535)]
+    port: u32,
+    #[config_field(min = 0, max = 1)]
+    sample_rate: f64,
+    #[config_field(required)]
+    region: Option<String>,
+    debug: bool,
+}
+
+#[test]
+fn test_config_json_round_trip() {
+    let config = SavedConfig {
+        name: "api \"edge\"\nnode".to_string(),
+        port: 8443,
+        sample_rate: 0.25,
+        region: Some("eu-west".to_string()),
+        debug: false,
+    };
+
+    let json = config.to_json();
+    assert_eq!(
+        json,
+        r#"{"name":"api \"edge\"\nnode","port":8443,"sample_rate":0.25,"region":"eu-west","debug":false}"#
+    );
+    assert_eq!(SavedConfig::from_json(&json), Ok(config));
+
+    // Whitespace and field order don't matter
+    let pretty = r#"{
+        "debug": true,
+        "region": "us-east",
+        "sample_rate": 1,
+        "port": 80,
+        "name": "web"
+    }"#;
+    let loaded = SavedConfig::from_json(pretty).unwrap();
+    assert_eq!(loaded.port, 80);
+    assert_eq!(loaded.sample_rate, 1.0);
+    assert!(loaded.debug);
+}
+
+#[test]
+fn test_config_from_json_rejects_invalid() {
+    let config = SavedConfig {
+        name: "web".to_string(),
         port: 8080,
+        sample_rate: 0.5,
+        region: Some("eu-west".to_string()),
+        debug: true,
     };
+    let json = config.to_json();
+
+    // Parses fine, but fails validation
+    let out_of_range = json.replace("8080", "70000");
+    assert_eq!(
+        SavedConfig::from_json(&out_of_range),
+        Err("Field 'port' must be at most 65535, got 70000".to_string())
+    );
+    let no_region = json.replace(r#""eu-west""#, "null");
+    assert_eq!(SavedConfig::from_json(&no_region), Err("Field 'region' is required".to_string()));
+
+    // Doesn't fit the struct at all
+    let wrong_type = json.replace("true", r#""yes""#);
+    assert_eq!(
+        SavedConfig::from_json(&wrong_type),
+        Err("Field 'debug': expected a boolean, got a string".to_string())
+    );
+    let missing_field = json.replace(r#","debug":true"#, "");
+    assert!(SavedConfig::from_json(&missing_field).unwrap_err().contains("'debug'"));
+    assert!(SavedConfig::from_json(&json[..json.len() - 1]).is_err());
+}
+
+#[derive(ConfigValidate, Debug, PartialEq)]
+struct UpstreamConfig {
+    #[config_field(required)]
+    host: String,
+    weights: Vec<u32>,
+}
+
+#[derive(ConfigValidate, Debug, PartialEq)]
+struct ProxyConfig {
+    primary: UpstreamConfig,
+    #[config_field(min = 0.5, max = 60)]
+    timeout: f64,
+    retries: Option<u32>,
+    tags: Vec<Option<String>>,
+}
+
+#[test]
+fn test_config_json_nested_and_lists() {
+    let config = ProxyConfig {
+        primary: UpstreamConfig { host: "a, [b] {c}".to_string(), weights: vec![1, 2] },
+        timeout: 2.5,
+        retries: None,
+        tags: vec![Some("x\"]".to_string()), None],
+    };
+
+    let json = config.to_json();
+    assert_eq!(
+        json,
+        r#"{"primary":{"host":"a, [b] {c}","weights":[1,2]},"timeout":2.5,"retries":null,"tags":["x\"]",null]}"#
+    );
+    assert_eq!(ProxyConfig::from_json(&json), Ok(config));
+
+    // Nested configs are validated as they load
+    let empty_host = json.replace(r#""a, [b] {c}""#, r#""""#);
+    assert_eq!(
+        ProxyConfig::from_json(&empty_host),
+        Err("Field 'primary': Field 'host' is required".to_string())
+    );
+    let bad_weight = json.replace("[1,2]", "[1,-2]");
+    assert_eq!(
+        ProxyConfig::from_json(&bad_weight),
+        Err("Field 'primary': Field 'weights': expected u32, got -2".to_string())
+    );
+    assert_eq!(
+        ProxyConfig::from_json(&json.replace("[1,2]", "[1,0]")),
+        Err("Field 'primary': Invalid value for field 'weights'".to_string())
+    );
+    assert!(ProxyConfig::from_json(&json.replace("[1,2]", "[1,2")).is_err());
+    assert!(ProxyConfig::from_json(&json.replace("null]", "]")).is_err());
+}
+
+#[derive(ConfigValidate)]
+struct BorrowedConfig {
+    name: &'static str,
+    port: u16,
+}
+
+#[test]
+fn test_borrowed_config_saves_to_json() {
+    let config = BorrowedConfig { name: "static", port: 80 };
+    assert_eq!(config.to_json(), r#"{"name":"static","port":80}"#);
+}
+
+#[test]
+fn test_config_field_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
 }
 
 // Macro expansion tests
@@ -0,0 +1,9 @@
+use dsl_project::*;
+
+#[derive(ConfigValidate)]
+struct Config {
+    #[config_field(required)]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use dsl_project::*;
+
+#[derive(ConfigValidate)]
+struct Config {
+    #[config_field(min_length = 1)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use dsl_project::*;
+
+#[derive(ConfigValidate)]
+struct Config {
+    #[config_field(min = 1, max = 10)]
+    enabled: bool,
+}
+
+fn main() {}
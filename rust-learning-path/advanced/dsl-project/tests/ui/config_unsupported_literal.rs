@@ -0,0 +1,9 @@
+use dsl_project::*;
+
+config! {
+    app "MyApp" {
+        ratio: 0.5
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use dsl_project::*;
+
+config! {
+    app "MyApp" {
+        name: "MyApp",
+        
+        limits {
+            ratio: 0.5,
+            retries: 3
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,204 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The ways a shared counter can be incremented from many threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterStrategy {
+    /// Every increment takes a lock on a shared `Mutex<usize>`
+    Mutex,
+    /// Every increment is a `fetch_add` on a shared `AtomicUsize`
+    Atomic,
+    /// Each thread counts locally and sends its total over a channel
+    Channel,
+}
+
+impl CounterStrategy {
+    pub const ALL: [CounterStrategy; 3] = [
+        CounterStrategy::Mutex,
+        CounterStrategy::Atomic,
+        CounterStrategy::Channel,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CounterStrategy::Mutex => "mutex",
+            CounterStrategy::Atomic => "atomic",
+            CounterStrategy::Channel => "channel",
+        }
+    }
+}
+
+/// Outcome of one benchmark run
+#[derive(Debug, Clone)]
+pub struct CounterBenchmarkResult {
+    pub strategy: CounterStrategy,
+    pub threads: usize,
+    pub total: usize,
+    pub elapsed: Duration,
+}
+
+impl CounterBenchmarkResult {
+    /// Increments per second
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return f64::INFINITY;
+        }
+        self.total as f64 / secs
+    }
+}
+
+/// Every thread locks the shared counter for each increment
+pub fn mutex_counter(threads: usize, increments_per_thread: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0usize));
+    let mut handles = vec![];
+
+    for _ in 0..threads {
+        let counter = Arc::clone(&counter);
+        handles.push(thread::spawn(move || {
+            for _ in 0..increments_per_thread {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let total = *counter.lock().unwrap();
+    total
+}
+
+/// Every thread does a relaxed `fetch_add` on the shared counter.
+/// `Relaxed` is enough because only the final value matters and
+/// joining the threads synchronizes with the read.
+pub fn atomic_counter(threads: usize, increments_per_thread: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut handles = vec![];
+
+    for _ in 0..threads {
+        let counter = Arc::clone(&counter);
+        handles.push(thread::spawn(move || {
+            for _ in 0..increments_per_thread {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    counter.load(Ordering::SeqCst)
+}
+
+/// Every thread counts into a local variable and sends its partial sum.
+/// The receiver reduces the partial sums, so threads never contend.
+pub fn channel_counter(threads: usize, increments_per_thread: usize) -> usize {
+    let (tx, rx) = mpsc::channel();
+
+    for _ in 0..threads {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut local = 0usize;
+            for _ in 0..increments_per_thread {
+                local = std::hint::black_box(local + 1);
+            }
+            tx.send(local).unwrap();
+        });
+    }
+    drop(tx);
+
+    rx.iter().sum()
+}
+
+/// Runs one strategy and times it
+pub fn run_counter_benchmark(
+    strategy: CounterStrategy,
+    threads: usize,
+    increments_per_thread: usize,
+) -> CounterBenchmarkResult {
+    let start = Instant::now();
+    let total = match strategy {
+        CounterStrategy::Mutex => mutex_counter(threads, increments_per_thread),
+        CounterStrategy::Atomic => atomic_counter(threads, increments_per_thread),
+        CounterStrategy::Channel => channel_counter(threads, increments_per_thread),
+    };
+
+    CounterBenchmarkResult {
+        strategy,
+        threads,
+        total,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Runs every strategy at every thread count.
+/// Panics if any strategy miscounts, so the benchmark doubles as a check.
+pub fn compare_counter_strategies(
+    thread_counts: &[usize],
+    increments_per_thread: usize,
+) -> Vec<CounterBenchmarkResult> {
+    let mut results = Vec::new();
+
+    for &threads in thread_counts {
+        let expected = threads * increments_per_thread;
+        for strategy in CounterStrategy::ALL {
+            let result = run_counter_benchmark(strategy, threads, increments_per_thread);
+            assert_eq!(
+                result.total, expected,
+                "{} counter lost increments with {} threads",
+                strategy.name(),
+                threads
+            );
+            results.push(result);
+        }
+    }
+
+    results
+}
+
+/// Demonstrates the cost of mutex, atomic, and channel-reduced counters
+pub fn counter_benchmark_example() {
+    println!("\n=== Counter Strategy Benchmark ===");
+
+    const INCREMENTS_PER_THREAD: usize = 100_000;
+    let results = compare_counter_strategies(&[1, 2, 4, 8], INCREMENTS_PER_THREAD);
+
+    println!("{:>8} {:>8} {:>12} {:>16}", "strategy", "threads", "elapsed", "ops/sec");
+    for result in &results {
+        println!(
+            "{:>8} {:>8} {:>12.2?} {:>16.0}",
+            result.strategy.name(),
+            result.threads,
+            result.elapsed,
+            result.throughput()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_strategies_reach_expected_total() {
+        let results = compare_counter_strategies(&[1, 2, 4, 8], 10_000);
+
+        assert_eq!(results.len(), 4 * CounterStrategy::ALL.len());
+        for result in &results {
+            assert_eq!(result.total, result.threads * 10_000);
+            assert!(result.throughput() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_zero_threads() {
+        for strategy in CounterStrategy::ALL {
+            assert_eq!(run_counter_benchmark(strategy, 0, 100).total, 0);
+        }
+    }
+}
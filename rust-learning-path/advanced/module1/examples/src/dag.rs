@@ -0,0 +1,270 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// Identifier of a node inside a `Dag`, assigned in insertion order
+pub type NodeId = usize;
+
+/// Errors returned when building a `Dag`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DagError {
+    /// The referenced node was never added to the graph
+    UnknownNode(NodeId),
+    /// Adding the edge `from -> to` would close a cycle
+    CycleDetected { from: NodeId, to: NodeId },
+}
+
+impl fmt::Display for DagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DagError::UnknownNode(id) => write!(f, "unknown node {}", id),
+            DagError::CycleDetected { from, to } => {
+                write!(f, "edge {} -> {} would create a cycle", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DagError {}
+
+/// A directed acyclic graph used to schedule work with dependencies.
+///
+/// An edge `from -> to` means `from` must complete before `to` can run.
+/// Edges that would introduce a cycle are rejected, so the graph is always
+/// acyclic and a topological order always exists.
+pub struct Dag<T> {
+    nodes: Vec<T>,
+    dependencies: Vec<Vec<NodeId>>,
+    dependents: Vec<Vec<NodeId>>,
+}
+
+impl<T> Dag<T> {
+    pub fn new() -> Self {
+        Dag {
+            nodes: Vec::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+
+    /// Adds a node and returns its id
+    pub fn add_node(&mut self, value: T) -> NodeId {
+        self.nodes.push(value);
+        self.dependencies.push(Vec::new());
+        self.dependents.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge meaning `from` must complete before `to`
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) -> Result<(), DagError> {
+        self.check_node(from)?;
+        self.check_node(to)?;
+
+        if from == to || self.reaches(to, from) {
+            return Err(DagError::CycleDetected { from, to });
+        }
+
+        if !self.dependencies[to].contains(&from) {
+            self.dependencies[to].push(from);
+            self.dependents[from].push(to);
+        }
+        Ok(())
+    }
+
+    /// Returns true if `to` is reachable from `from` by following edges.
+    ///
+    /// Unknown nodes reach nothing.
+    pub fn reaches(&self, from: NodeId, to: NodeId) -> bool {
+        let mut stack = vec![from];
+        let mut visited = HashSet::new();
+
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return true;
+            }
+            if visited.insert(current) {
+                if let Some(dependents) = self.dependents.get(current) {
+                    stack.extend(dependents.iter().copied());
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns every node in dependency order.
+    ///
+    /// The order is stable: among nodes that are ready at the same time,
+    /// the one added first comes first.
+    pub fn topological_sort(&self) -> Vec<NodeId> {
+        let mut remaining: Vec<usize> = self.dependencies.iter().map(Vec::len).collect();
+        let mut ready: BTreeSet<NodeId> = (0..self.nodes.len())
+            .filter(|&id| remaining[id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while let Some(id) = ready.pop_first() {
+            order.push(id);
+            for &dependent in &self.dependents[id] {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Returns the nodes that are not yet complete but whose dependencies all are
+    pub fn ready_nodes(&self, completed: &HashSet<NodeId>) -> Vec<NodeId> {
+        (0..self.nodes.len())
+            .filter(|id| !completed.contains(id))
+            .filter(|&id| self.dependencies[id].iter().all(|dep| completed.contains(dep)))
+            .collect()
+    }
+
+    /// Returns the direct dependencies of a node, or `None` if it doesn't exist
+    pub fn dependencies(&self, id: NodeId) -> Option<&[NodeId]> {
+        self.dependencies.get(id).map(Vec::as_slice)
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&T> {
+        self.nodes.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn check_node(&self, id: NodeId) -> Result<(), DagError> {
+        if id < self.nodes.len() {
+            Ok(())
+        } else {
+            Err(DagError::UnknownNode(id))
+        }
+    }
+}
+
+impl<T> Default for Dag<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Demonstrates scheduling work in waves with a DAG
+pub fn dag_scheduling_example() {
+    println!("\n=== DAG Scheduling Example ===");
+
+    let mut dag = Dag::new();
+    let fetch = dag.add_node("fetch sources");
+    let compile = dag.add_node("compile");
+    let docs = dag.add_node("build docs");
+    let package = dag.add_node("package");
+
+    dag.add_edge(fetch, compile).unwrap();
+    dag.add_edge(fetch, docs).unwrap();
+    dag.add_edge(compile, package).unwrap();
+    dag.add_edge(docs, package).unwrap();
+
+    if let Err(e) = dag.add_edge(package, fetch) {
+        println!("Rejected edge: {}", e);
+    }
+
+    let mut completed = HashSet::new();
+    let mut wave = 1;
+    loop {
+        let ready = dag.ready_nodes(&completed);
+        if ready.is_empty() {
+            break;
+        }
+
+        let names: Vec<&str> = ready.iter().map(|&id| *dag.node(id).unwrap()).collect();
+        println!("Wave {}: {:?}", wave, names);
+        completed.extend(ready);
+        wave += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> (Dag<&'static str>, [NodeId; 4]) {
+        let mut dag = Dag::new();
+        let a = dag.add_node("a");
+        let b = dag.add_node("b");
+        let c = dag.add_node("c");
+        let d = dag.add_node("d");
+        dag.add_edge(a, b).unwrap();
+        dag.add_edge(a, c).unwrap();
+        dag.add_edge(b, d).unwrap();
+        dag.add_edge(c, d).unwrap();
+        (dag, [a, b, c, d])
+    }
+
+    #[test]
+    fn test_diamond_topological_order() {
+        let (dag, [a, b, c, d]) = diamond();
+        assert_eq!(dag.topological_sort(), vec![a, b, c, d]);
+    }
+
+    #[test]
+    fn test_topological_order_is_stable() {
+        let mut dag = Dag::new();
+        let late = dag.add_node("late");
+        let early = dag.add_node("early");
+        let root = dag.add_node("root");
+        dag.add_edge(root, late).unwrap();
+        dag.add_edge(root, early).unwrap();
+
+        assert_eq!(dag.topological_sort(), vec![root, late, early]);
+        assert_eq!(dag.topological_sort(), dag.topological_sort());
+    }
+
+    #[test]
+    fn test_cycle_rejected() {
+        let (mut dag, [a, b, _, d]) = diamond();
+
+        assert_eq!(dag.add_edge(d, a), Err(DagError::CycleDetected { from: d, to: a }));
+        assert_eq!(dag.add_edge(b, b), Err(DagError::CycleDetected { from: b, to: b }));
+        assert_eq!(dag.add_edge(a, 42), Err(DagError::UnknownNode(42)));
+
+        // The graph is unchanged after the rejected edges
+        assert_eq!(dag.topological_sort().len(), 4);
+        assert_eq!(dag.dependencies(a), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_unknown_node_lookups() {
+        let (dag, [a, b, c, d]) = diamond();
+
+        assert_eq!(dag.dependencies(d), Some(&[b, c][..]));
+        assert_eq!(dag.dependencies(42), None);
+        assert!(dag.node(42).is_none());
+        assert!(!dag.reaches(42, a));
+        assert!(!dag.reaches(a, 42));
+    }
+
+    #[test]
+    fn test_ready_nodes_incremental() {
+        let (dag, [a, b, c, d]) = diamond();
+        let mut completed = HashSet::new();
+
+        assert_eq!(dag.ready_nodes(&completed), vec![a]);
+
+        completed.insert(a);
+        assert_eq!(dag.ready_nodes(&completed), vec![b, c]);
+
+        completed.insert(b);
+        assert_eq!(dag.ready_nodes(&completed), vec![c]);
+
+        completed.insert(c);
+        assert_eq!(dag.ready_nodes(&completed), vec![d]);
+
+        completed.insert(d);
+        assert!(dag.ready_nodes(&completed).is_empty());
+    }
+}
@@ -3,9 +3,13 @@ pub mod message_passing;
 pub mod shared_state;
 pub mod atomic_operations;
 pub mod lock_free_structures;
+pub mod dag;
+pub mod counter_benchmark;
 
 pub use basic_threads::*;
 pub use message_passing::*;
 pub use shared_state::*;
 pub use atomic_operations::*;
-pub use lock_free_structures::*;
\ No newline at end of file
+pub use lock_free_structures::*;
+pub use dag::*;
+pub use counter_benchmark::*;
\ No newline at end of file
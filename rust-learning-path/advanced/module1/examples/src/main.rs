@@ -16,6 +16,7 @@ fn main() {
     non_blocking_receive_example();
     timed_receive_example();
     work_distribution_example();
+    blocking_queue_example();
 
     // Shared state examples
     basic_mutex_example();
@@ -23,7 +24,9 @@ fn main() {
     condvar_example();
     producer_consumer_example();
     mutex_poisoning_example();
+    recoverable_mutex_example();
     thread_safe_counter_example();
+    object_pool_example();
 
     // Atomic operations examples
     basic_atomic_example();
@@ -40,6 +43,10 @@ fn main() {
     lock_free_queue_example();
     atomic_counter_example();
     performance_comparison();
+    counter_benchmark_example();
+
+    // Work scheduling examples
+    dag_scheduling_example();
 
     println!("\n🎉 All concurrency examples completed!");
     println!("\nKey takeaways:");
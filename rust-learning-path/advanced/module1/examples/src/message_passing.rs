@@ -1,4 +1,5 @@
-use std::sync::mpsc;
+use std::collections::VecDeque;
+use std::sync::{mpsc, Condvar, Mutex};
 use std::thread;
 use std::time::Duration;
 
@@ -213,6 +214,121 @@ pub fn work_distribution_example() {
     println!("Processed {} work items", results.len());
 }
 
+/// A bounded queue built by hand from a `Mutex` and two `Condvar`s.
+///
+/// This is a hand-built equivalent of `mpsc::sync_channel`: `push` waits
+/// on `not_full` while the queue is at capacity, and `pop` waits on
+/// `not_empty` while it has no items. Both waits sit in a `while` loop because a
+/// `Condvar` can wake up spuriously, so the condition is re-checked each time.
+pub struct BlockingQueue<T> {
+    state: Mutex<QueueState<T>>,
+    not_full: Condvar,
+    not_empty: Condvar,
+    capacity: usize,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    high_water_mark: usize,
+}
+
+impl<T> BlockingQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        BlockingQueue {
+            state: Mutex::new(QueueState {
+                items: VecDeque::with_capacity(capacity),
+                high_water_mark: 0,
+            }),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// Adds an item, blocking while the queue is full
+    pub fn push(&self, item: T) {
+        let mut state = self.state.lock().unwrap();
+        while state.items.len() >= self.capacity {
+            state = self.not_full.wait(state).unwrap();
+        }
+
+        state.items.push_back(item);
+        state.high_water_mark = state.high_water_mark.max(state.items.len());
+        self.not_empty.notify_one();
+    }
+
+    /// Removes the oldest item, blocking while the queue is empty
+    pub fn pop(&self) -> T {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.not_full.notify_one();
+                return item;
+            }
+            state = self.not_empty.wait(state).unwrap();
+        }
+    }
+
+    /// Removes the oldest item if there is one, without blocking
+    pub fn try_pop(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        let item = state.items.pop_front()?;
+        self.not_full.notify_one();
+        Some(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The largest number of items the queue has held at once
+    pub fn high_water_mark(&self) -> usize {
+        self.state.lock().unwrap().high_water_mark
+    }
+}
+
+/// Demonstrates a hand-built bounded queue with a fast producer and slow consumer
+pub fn blocking_queue_example() {
+    println!("\n=== Blocking Queue Example ===");
+
+    let queue = std::sync::Arc::new(BlockingQueue::new(3));
+
+    let producer_queue = std::sync::Arc::clone(&queue);
+    let producer = thread::spawn(move || {
+        for i in 0..8 {
+            producer_queue.push(i);
+            println!("Producer: Pushed {} (len {})", i, producer_queue.len());
+        }
+    });
+
+    let consumer_queue = std::sync::Arc::clone(&queue);
+    let consumer = thread::spawn(move || {
+        for _ in 0..8 {
+            let item = consumer_queue.pop();
+            println!("Consumer: Popped {}", item);
+            thread::sleep(Duration::from_millis(50));
+        }
+    });
+
+    producer.join().unwrap();
+    consumer.join().unwrap();
+
+    println!(
+        "Queue never held more than {} of {} slots",
+        queue.high_water_mark(),
+        queue.capacity()
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,4 +433,70 @@ mod tests {
             _ => panic!("Should have received 42"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_blocking_queue_fifo() {
+        let queue = BlockingQueue::new(2);
+        queue.push(1);
+        queue.push(2);
+
+        assert_eq!(queue.pop(), 1);
+        assert_eq!(queue.try_pop(), Some(2));
+        assert_eq!(queue.try_pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_blocking_queue_mismatched_rates() {
+        use std::sync::Arc;
+
+        const PRODUCERS: usize = 3;
+        const CONSUMERS: usize = 2;
+        const ITEMS_PER_PRODUCER: usize = 50;
+        const TOTAL: usize = PRODUCERS * ITEMS_PER_PRODUCER;
+
+        let queue = Arc::new(BlockingQueue::new(4));
+        let mut producers = vec![];
+        let mut consumers = vec![];
+
+        // Fast producers
+        for p in 0..PRODUCERS {
+            let queue = Arc::clone(&queue);
+            producers.push(thread::spawn(move || {
+                for i in 0..ITEMS_PER_PRODUCER {
+                    queue.push(p * ITEMS_PER_PRODUCER + i);
+                }
+            }));
+        }
+
+        // Slow consumers splitting the work between them
+        for c in 0..CONSUMERS {
+            let queue = Arc::clone(&queue);
+            let count = TOTAL / CONSUMERS + if c < TOTAL % CONSUMERS { 1 } else { 0 };
+            consumers.push(thread::spawn(move || {
+                let mut received = Vec::with_capacity(count);
+                for _ in 0..count {
+                    received.push(queue.pop());
+                    if received.len() % 10 == 0 {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                }
+                received
+            }));
+        }
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let mut all: Vec<usize> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        all.sort();
+
+        assert_eq!(all, (0..TOTAL).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+        assert!(queue.high_water_mark() <= queue.capacity());
+    }
+}
@@ -1,7 +1,9 @@
-use std::sync::{Arc, Mutex, RwLock, Condvar};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, Condvar};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 
 /// Demonstrates basic Mutex usage for shared state
 pub fn basic_mutex_example() {
@@ -201,6 +203,90 @@ pub fn mutex_poisoning_example() {
     }
 }
 
+/// A mutex that repairs and clears its poison flag instead of failing.
+///
+/// When a thread panics while holding a `Mutex`, the data may have been left
+/// half-updated, so every later `lock()` returns `Err`. `RecoverableMutex`
+/// takes the guard out of the error with `into_inner`, runs a repair closure
+/// on the data, clears the poison flag, and hands back a normal guard.
+///
+/// This is only safe when the repair closure can restore the invariants of
+/// `T` from whatever state the panicking thread left behind, for example
+/// resetting a cache or recomputing a derived total. If a half-finished
+/// update can't be detected or undone, keep the default poisoning behavior
+/// and propagate the failure instead.
+pub struct RecoverableMutex<T> {
+    inner: Mutex<T>,
+    repair: Box<dyn Fn(&mut T) + Send + Sync>,
+    recoveries: AtomicUsize,
+}
+
+impl<T> RecoverableMutex<T> {
+    pub fn new<F>(value: T, repair: F) -> Self
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        RecoverableMutex {
+            inner: Mutex::new(value),
+            repair: Box::new(repair),
+            recoveries: AtomicUsize::new(0),
+        }
+    }
+
+    /// Locks the mutex, repairing the data first if it was poisoned
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                let mut guard = poisoned.into_inner();
+                (self.repair)(&mut guard);
+                self.inner.clear_poison();
+                self.recoveries.fetch_add(1, Ordering::SeqCst);
+                guard
+            }
+        }
+    }
+
+    /// Number of times the data was repaired after a panic
+    pub fn recovery_count(&self) -> usize {
+        self.recoveries.load(Ordering::SeqCst)
+    }
+
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+}
+
+/// Demonstrates recovering shared state after a thread panics
+pub fn recoverable_mutex_example() {
+    println!("\n=== Recoverable Mutex Example ===");
+
+    // The invariant is that `total` equals the sum of `entries`
+    let ledger = Arc::new(RecoverableMutex::new(
+        (Vec::<i32>::new(), 0i32),
+        |(entries, total)| {
+            println!("Repair: Recomputing total from {} entries", entries.len());
+            *total = entries.iter().sum();
+        },
+    ));
+
+    let ledger_clone = Arc::clone(&ledger);
+    let handle = thread::spawn(move || {
+        let mut guard = ledger_clone.lock();
+        guard.0.push(10);
+        panic!("Oops! Panicked before updating the total");
+    });
+    let _ = handle.join();
+
+    let guard = ledger.lock();
+    println!(
+        "Entries: {:?}, total: {}, recoveries: {}",
+        guard.0,
+        guard.1,
+        ledger.recovery_count()
+    );
+}
+
 /// Thread-safe counter with multiple operations
 pub struct ThreadSafeCounter {
     value: Arc<Mutex<i32>>,
@@ -291,6 +377,180 @@ pub fn thread_safe_counter_example() {
     // Expected: (5 * 100) - (3 * 50) + (2 * 25) = 500 - 150 + 50 = 400
 }
 
+/// A bounded pool of reusable objects that are expensive to create.
+///
+/// Objects are created lazily, up to `max_size`, and lent out through a
+/// `PooledObject` guard that hands the object back when dropped. The guard
+/// borrows the pool, so the borrow checker rejects any guard that would
+/// outlive it.
+pub struct ObjectPool<T> {
+    state: Mutex<PoolState<T>>,
+    available: Condvar,
+    factory: Box<dyn Fn() -> T + Send + Sync>,
+    max_size: usize,
+}
+
+struct PoolState<T> {
+    idle: Vec<T>,
+    created: usize,
+}
+
+impl<T> ObjectPool<T> {
+    pub fn new<F>(max_size: usize, factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        ObjectPool {
+            state: Mutex::new(PoolState {
+                idle: Vec::new(),
+                created: 0,
+            }),
+            available: Condvar::new(),
+            factory: Box::new(factory),
+            max_size,
+        }
+    }
+
+    /// Borrows an object, blocking until one is available
+    pub fn get(&self) -> PooledObject<'_, T> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(object) = self.take(&mut state) {
+                drop(state);
+                return self.guard(object);
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+
+    /// Borrows an object, giving up after `timeout`
+    pub fn get_timeout(&self, timeout: Duration) -> Option<PooledObject<'_, T>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(object) = self.take(&mut state) {
+                drop(state);
+                return Some(self.guard(object));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            state = self.available.wait_timeout(state, deadline - now).unwrap().0;
+        }
+    }
+
+    /// Borrows an object only if one is available right now
+    pub fn try_get(&self) -> Option<PooledObject<'_, T>> {
+        let mut state = self.state.lock().unwrap();
+        let object = self.take(&mut state)?;
+        drop(state);
+        Some(self.guard(object))
+    }
+
+    /// Number of objects sitting idle in the pool
+    pub fn idle_count(&self) -> usize {
+        self.state.lock().unwrap().idle.len()
+    }
+
+    /// Number of objects created so far
+    pub fn created_count(&self) -> usize {
+        self.state.lock().unwrap().created
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    // Reuses an idle object or creates a new one if still under the limit.
+    // The factory runs while the lock is held, which keeps `created` exact.
+    fn take(&self, state: &mut PoolState<T>) -> Option<T> {
+        if let Some(object) = state.idle.pop() {
+            return Some(object);
+        }
+        if state.created < self.max_size {
+            state.created += 1;
+            return Some((self.factory)());
+        }
+        None
+    }
+
+    fn guard(&self, object: T) -> PooledObject<'_, T> {
+        PooledObject {
+            pool: self,
+            object: Some(object),
+        }
+    }
+
+    fn give_back(&self, object: T) {
+        let mut state = self.state.lock().unwrap();
+        state.idle.push(object);
+        self.available.notify_one();
+    }
+}
+
+/// RAII guard that returns its object to the pool when dropped
+pub struct PooledObject<'a, T> {
+    pool: &'a ObjectPool<T>,
+    object: Option<T>,
+}
+
+impl<T> Deref for PooledObject<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.object.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for PooledObject<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.object.as_mut().unwrap()
+    }
+}
+
+impl<T> Drop for PooledObject<'_, T> {
+    fn drop(&mut self) {
+        if let Some(object) = self.object.take() {
+            self.pool.give_back(object);
+        }
+    }
+}
+
+/// Demonstrates reusing buffers through an object pool
+pub fn object_pool_example() {
+    println!("\n=== Object Pool Example ===");
+
+    let pool = Arc::new(ObjectPool::new(2, || {
+        println!("Pool: Allocating a new buffer");
+        Vec::<u8>::with_capacity(1024)
+    }));
+    let mut handles = vec![];
+
+    for i in 0..4 {
+        let pool = Arc::clone(&pool);
+        let handle = thread::spawn(move || {
+            let mut buffer = pool.get();
+            buffer.clear();
+            buffer.extend_from_slice(format!("worker {}", i).as_bytes());
+            println!("Worker {}: Using buffer ({} bytes)", i, buffer.len());
+            thread::sleep(Duration::from_millis(50));
+        });
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    println!(
+        "Buffers created: {}, idle: {}",
+        pool.created_count(),
+        pool.idle_count()
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,4 +685,85 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_object_pool_exhaustion() {
+        let pool = ObjectPool::new(2, Vec::<u8>::new);
+
+        let first = pool.try_get();
+        let second = pool.try_get();
+        assert!(first.is_some());
+        assert!(second.is_some());
+        assert!(pool.try_get().is_none());
+        assert!(pool.get_timeout(Duration::from_millis(20)).is_none());
+        assert_eq!(pool.created_count(), 2);
+    }
+
+    #[test]
+    fn test_object_pool_guard_returns_object() {
+        let next_id = std::sync::atomic::AtomicUsize::new(0);
+        let pool = ObjectPool::new(1, move || {
+            next_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+        });
+
+        let id = *pool.get();
+        assert_eq!(pool.idle_count(), 1);
+
+        // The same object is handed out again instead of creating a new one
+        assert_eq!(*pool.get(), id);
+        assert_eq!(pool.created_count(), 1);
+    }
+
+    #[test]
+    fn test_object_pool_blocks_until_returned() {
+        let pool = Arc::new(ObjectPool::new(1, || String::from("connection")));
+        let held = pool.get();
+
+        let pool_clone = Arc::clone(&pool);
+        let borrower = thread::spawn(move || {
+            let start = Instant::now();
+            let object = pool_clone.get();
+            assert_eq!(*object, "connection");
+            start.elapsed()
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        drop(held);
+
+        let waited = borrower.join().unwrap();
+        assert!(waited >= Duration::from_millis(40));
+        assert_eq!(pool.created_count(), 1);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[test]
+    fn test_recoverable_mutex_repairs_after_poisoning() {
+        // Keeps the sum of the list alongside the list itself
+        let mutex = Arc::new(RecoverableMutex::new(
+            (vec![1, 2], 3),
+            |(items, sum): &mut (Vec<i32>, i32)| *sum = items.iter().sum(),
+        ));
+        let mutex_clone = Arc::clone(&mutex);
+
+        let handle = thread::spawn(move || {
+            let mut data = mutex_clone.lock();
+            data.0.push(4);
+            panic!("Intentional panic");
+        });
+        assert!(handle.join().is_err());
+        assert!(mutex.is_poisoned());
+
+        {
+            let data = mutex.lock();
+            assert_eq!(data.0, vec![1, 2, 4]);
+            assert_eq!(data.1, 7);
+        }
+        assert!(!mutex.is_poisoned());
+        assert_eq!(mutex.recovery_count(), 1);
+
+        // Later locks succeed without running the repair again
+        mutex.lock().0.push(5);
+        assert_eq!(mutex.lock().0.len(), 4);
+        assert_eq!(mutex.recovery_count(), 1);
+    }
+}
@@ -3,21 +3,19 @@
 //! A custom thread pool implementation that demonstrates advanced concurrency concepts
 //! in Rust, including thread management, work distribution, and graceful shutdown.
 
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 
+mod scope;
 mod worker;
+pub use scope::Scope;
+pub use work_queue::{PushError, WorkQueue};
 pub use worker::Worker;
 
 /// Type alias for a job that can be executed by the thread pool
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
-/// Messages that can be sent to worker threads
-pub enum Message {
-    NewJob(Job),
-    Terminate,
-}
-
 /// A thread pool for executing jobs concurrently
 ///
 /// # Examples
@@ -41,7 +39,8 @@ pub enum Message {
 /// ```
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: Option<mpsc::Sender<Message>>,
+    queue: Arc<WorkQueue<Job>>,
+    panicked_jobs: Arc<AtomicUsize>,
 }
 
 /// Errors that can occur when working with the thread pool
@@ -93,8 +92,8 @@ impl ThreadPool {
             ));
         }
 
-        let (sender, receiver) = mpsc::channel();
-        let receiver = Arc::new(Mutex::new(receiver));
+        let queue = Arc::new(WorkQueue::unbounded());
+        let panicked_jobs = Arc::new(AtomicUsize::new(0));
 
         let mut workers = Vec::with_capacity(size);
 
@@ -102,12 +101,14 @@ impl ThreadPool {
             // TODO: Create worker threads
             // Each worker should:
             // 1. Have a unique ID
-            // 2. Share the receiver to get jobs
+            // 2. Share the queue to get jobs
             // 3. Handle potential thread creation failures
             
-            match Worker::new(id, Arc::clone(&receiver)) {
+            match Worker::new(id, Arc::clone(&queue), Arc::clone(&panicked_jobs)) {
                 Ok(worker) => workers.push(worker),
                 Err(e) => {
+                    // Let the workers already started exit
+                    queue.close();
                     return Err(ThreadPoolError::CreationFailed(
                         format!("Failed to create worker {}: {}", id, e)
                     ));
@@ -117,7 +118,8 @@ impl ThreadPool {
 
         Ok(ThreadPool {
             workers,
-            sender: Some(sender),
+            queue,
+            panicked_jobs,
         })
     }
 
@@ -146,18 +148,51 @@ impl ThreadPool {
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
-
-        match &self.sender {
-            Some(sender) => {
-                sender.send(Message::NewJob(job))
-                    .map_err(|_| ThreadPoolError::ExecutionFailed(
-                        "Failed to send job to workers".to_string()
-                    ))?;
-                Ok(())
-            }
-            None => Err(ThreadPoolError::ShutDown),
-        }
+        self.send(Box::new(f))
+    }
+
+    /// Queue an already boxed job for the workers
+    fn send(&self, job: Job) -> Result<(), ThreadPoolError> {
+        self.queue.push(job).map_err(|_| ThreadPoolError::ShutDown)
+    }
+
+    /// Execute a job on the thread pool and get its return value back.
+    ///
+    /// The returned receiver yields the value once the job finishes. If the
+    /// job panics, the sender is dropped without sending, so `recv()`
+    /// returns an error instead of blocking forever.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that implements FnOnce() -> T + Send + 'static
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Receiver<T>)` - Job was successfully queued
+    /// * `Err(ThreadPoolError)` - If the job couldn't be queued
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thread_pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(2).unwrap();
+    /// let receiver = pool.execute_result(|| 2 + 2).unwrap();
+    /// assert_eq!(receiver.recv().unwrap(), 4);
+    /// ```
+    pub fn execute_result<F, T>(&self, f: F) -> Result<mpsc::Receiver<T>, ThreadPoolError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        self.execute(move || {
+            // The receiver may already be gone; the result is simply discarded then
+            let _ = sender.send(f());
+        })?;
+
+        Ok(receiver)
     }
 
     /// Get the number of worker threads in the pool
@@ -165,22 +200,25 @@ impl ThreadPool {
         self.workers.len()
     }
 
+    /// Get the number of jobs that have panicked so far
+    ///
+    /// A panicking job doesn't take its worker down with it: the worker
+    /// logs the panic and moves on to the next job.
+    pub fn panicked_job_count(&self) -> usize {
+        self.panicked_jobs.load(Ordering::Relaxed)
+    }
+
     /// Gracefully shutdown the thread pool
     ///
     /// This method will:
     /// 1. Stop accepting new jobs
-    /// 2. Signal all workers to terminate
-    /// 3. Wait for all workers to finish their current jobs
+    /// 2. Let the workers run every job already queued
+    /// 3. Wait for all workers to finish
     pub fn shutdown(&mut self) {
         println!("Shutting down thread pool...");
 
-        // Drop the sender to stop accepting new jobs
-        if let Some(sender) = self.sender.take() {
-            // Send terminate message to all workers
-            for _ in &self.workers {
-                let _ = sender.send(Message::Terminate);
-            }
-        }
+        // Closing the queue stops new jobs; workers exit once it's empty
+        self.queue.close();
 
         // Wait for all workers to finish
         for worker in &mut self.workers {
@@ -258,4 +296,90 @@ mod tests {
         let final_count = *counter.lock().unwrap();
         assert_eq!(final_count, 10);
     }
+
+    #[test]
+    fn test_execute_result_collects_sums() {
+        let pool = ThreadPool::new(4).unwrap();
+
+        let receivers: Vec<_> = (0..8u64)
+            .map(|i| {
+                pool.execute_result(move || (i * 100..(i + 1) * 100).sum::<u64>())
+                    .unwrap()
+            })
+            .collect();
+
+        let total: u64 = receivers.into_iter()
+            .map(|receiver| receiver.recv().unwrap())
+            .sum();
+        assert_eq!(total, (0..800).sum::<u64>());
+    }
+
+    #[test]
+    fn test_execute_result_panic_closes_receiver() {
+        let pool = ThreadPool::new(2).unwrap();
+
+        let failed = pool.execute_result(|| -> u32 { panic!("job failed") }).unwrap();
+        let succeeded = pool.execute_result(|| 7).unwrap();
+
+        assert!(failed.recv_timeout(Duration::from_secs(5)).is_err());
+        assert_eq!(succeeded.recv_timeout(Duration::from_secs(5)).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_panicking_job_is_counted() {
+        let pool = ThreadPool::new(1).unwrap();
+
+        pool.execute(|| panic!("job failed")).unwrap();
+        // With a single worker, this only runs if the worker survived the panic
+        let receiver = pool.execute_result(|| "still running").unwrap();
+
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), "still running");
+        assert_eq!(pool.panicked_job_count(), 1);
+    }
+
+    #[test]
+    fn test_scope_sums_borrowed_slice() {
+        let pool = ThreadPool::new(4).unwrap();
+        let numbers: Vec<i32> = (1..=1000).collect();
+        let slice: &[i32] = &numbers;
+        let mut partial_sums = [0; 10];
+
+        pool.scope(|s| {
+            for (chunk, partial) in slice.chunks(100).zip(partial_sums.iter_mut()) {
+                s.spawn(move || *partial = chunk.iter().sum()).unwrap();
+            }
+        });
+
+        assert_eq!(partial_sums.iter().sum::<i32>(), 500_500);
+    }
+
+    #[test]
+    fn test_scope_waits_for_jobs_before_propagating_panic() {
+        let pool = ThreadPool::new(2).unwrap();
+        let finished = std::sync::atomic::AtomicUsize::new(0);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            pool.scope(|s| {
+                s.spawn(|| panic!("scoped job failed")).unwrap();
+                for _ in 0..3 {
+                    s.spawn(|| {
+                        thread::sleep(Duration::from_millis(20));
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    }).unwrap();
+                }
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+        assert_eq!(pool.panicked_job_count(), 1);
+    }
+
+    #[test]
+    fn test_execute_result_after_shutdown() {
+        let mut pool = ThreadPool::new(1).unwrap();
+        pool.shutdown();
+
+        assert!(matches!(pool.execute_result(|| 1), Err(ThreadPoolError::ShutDown)));
+    }
 }
\ No newline at end of file
@@ -137,6 +137,7 @@ fn error_handling_example() {
     }
 
     thread::sleep(Duration::from_secs(1));
+    println!("Jobs that panicked: {}", pool.panicked_job_count());
     println!("Error handling example completed");
 }
 
@@ -0,0 +1,153 @@
+//! Scoped jobs that can borrow from the caller's stack
+
+use crate::{Job, ThreadPool, ThreadPoolError};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+/// A scope for spawning jobs that borrow non-`'static` data
+///
+/// Created by [`ThreadPool::scope`]. Every job spawned in the scope is
+/// finished before `scope` returns, which is what makes borrowing safe.
+pub struct Scope<'scope, 'env: 'scope> {
+    pool: &'scope ThreadPool,
+    state: Arc<ScopeState>,
+    // Invariant lifetimes, as in `std::thread::Scope`
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+/// Bookkeeping shared between a scope and its running jobs
+struct ScopeState {
+    /// Number of spawned jobs that haven't finished yet
+    pending: Mutex<usize>,
+    /// Signalled when `pending` drops to zero
+    all_done: Condvar,
+    /// Set if any job panicked
+    job_panicked: AtomicBool,
+}
+
+impl ScopeState {
+    fn job_finished(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        *pending -= 1;
+        if *pending == 0 {
+            self.all_done.notify_all();
+        }
+    }
+
+    fn wait_all(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        while *pending > 0 {
+            pending = self.all_done.wait(pending).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// Marks a job finished when dropped, whether it returned, panicked, or never ran
+struct FinishGuard(Arc<ScopeState>);
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        // Dropped during unwinding means the job panicked; the worker still
+        // catches and counts the panic as usual
+        if thread::panicking() {
+            self.0.job_panicked.store(true, Ordering::Relaxed);
+        }
+        self.0.job_finished();
+    }
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawn a job that may borrow anything that outlives the scope
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Job was successfully queued
+    /// * `Err(ThreadPoolError)` - If the job couldn't be queued
+    pub fn spawn<F>(&'scope self, f: F) -> Result<(), ThreadPoolError>
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        *self.state.pending.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+
+        let guard = FinishGuard(Arc::clone(&self.state));
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            // Declared first so it's dropped last, after `f` and its borrows
+            let _guard = guard;
+            f();
+        });
+
+        // SAFETY: `ThreadPool::scope` doesn't return until every job has
+        // dropped its `FinishGuard`, and the guard is dropped only after the
+        // closure and everything it borrows. So the job never outlives 'scope,
+        // even though the pool's queue requires 'static. A job that fails to
+        // send is dropped right here, which drops its guard too.
+        let job: Job = unsafe {
+            std::mem::transmute::<Box<dyn FnOnce() + Send + 'scope>, Job>(job)
+        };
+
+        self.pool.send(job)
+    }
+}
+
+impl ThreadPool {
+    /// Run `f` with a scope for spawning jobs that borrow from the stack
+    ///
+    /// Modeled after `std::thread::scope`: jobs spawned on the scope may
+    /// borrow anything that outlives the call, and `scope` blocks until
+    /// all of them have finished, even if `f` or one of the jobs panics.
+    /// A panic in `f` is then resumed; a panic in a job makes `scope` panic.
+    ///
+    /// Calling this from inside a job on the same pool can deadlock if every
+    /// worker ends up waiting on a scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thread_pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(4).unwrap();
+    /// let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    /// let mut sums = vec![0; 4];
+    ///
+    /// pool.scope(|s| {
+    ///     for (chunk, sum) in numbers.chunks(2).zip(sums.iter_mut()) {
+    ///         s.spawn(move || *sum = chunk.iter().sum()).unwrap();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(sums, vec![3, 7, 11, 15]);
+    /// ```
+    pub fn scope<'env, F, T>(&self, f: F) -> T
+    where
+        F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+    {
+        let scope = Scope {
+            pool: self,
+            state: Arc::new(ScopeState {
+                pending: Mutex::new(0),
+                all_done: Condvar::new(),
+                job_panicked: AtomicBool::new(false),
+            }),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+        // Borrowed data must stay alive until every job is done, so wait
+        // even when `f` panicked
+        scope.state.wait_all();
+
+        match result {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(_) if scope.state.job_panicked.load(Ordering::Relaxed) => {
+                panic!("a scoped job panicked")
+            }
+            Ok(value) => value,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! Worker thread implementation for the thread pool
 
-use crate::Message;
-use std::sync::{mpsc, Arc, Mutex};
+use crate::{Job, WorkQueue};
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
 
 /// A worker thread that processes jobs from the thread pool
@@ -34,7 +36,8 @@ impl Worker {
     /// # Arguments
     ///
     /// * `id` - Unique identifier for this worker
-    /// * `receiver` - Shared receiver for getting jobs from the thread pool
+    /// * `queue` - The thread pool's job queue, shared with the other workers
+    /// * `panicked_jobs` - Shared count of jobs that panicked, incremented by this worker
     ///
     /// # Returns
     ///
@@ -42,15 +45,16 @@ impl Worker {
     /// * `Err(WorkerError)` - If worker creation fails
     pub fn new(
         id: usize,
-        receiver: Arc<Mutex<mpsc::Receiver<Message>>>,
+        queue: Arc<WorkQueue<Job>>,
+        panicked_jobs: Arc<AtomicUsize>,
     ) -> Result<Worker, WorkerError> {
         // TODO: Implement worker thread creation
         // The worker should:
         // 1. Spawn a new thread
         // 2. In the thread loop:
-        //    - Wait for messages from the receiver
+        //    - Wait for jobs from the queue
         //    - Execute jobs when received
-        //    - Handle terminate messages
+        //    - Exit once the queue is closed and empty
         //    - Handle potential panics gracefully
         
         let thread = thread::Builder::new()
@@ -58,33 +62,25 @@ impl Worker {
             .spawn(move || {
                 println!("Worker {} starting", id);
                 
-                loop {
-                    // Lock the receiver to get the next message
-                    let message = receiver.lock().unwrap().recv();
+                // `pop` only returns `None` once the pool has closed the
+                // queue and every job in it has been taken
+                while let Some(job) = queue.pop() {
+                    println!("Worker {} got a job; executing.", id);
                     
-                    match message {
-                        Ok(Message::NewJob(job)) => {
-                            println!("Worker {} got a job; executing.", id);
-                            
-                            // Execute the job
-                            // We use std::panic::catch_unwind to prevent worker panics
-                            // from crashing the entire thread pool
-                            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                                job();
-                            }));
-                            
-                            if let Err(_) = result {
-                                eprintln!("Worker {} panicked while executing job", id);
-                            }
-                        }
-                        Ok(Message::Terminate) => {
-                            println!("Worker {} was told to terminate.", id);
-                            break;
-                        }
-                        Err(_) => {
-                            println!("Worker {} disconnected; shutting down.", id);
-                            break;
-                        }
+                    // Execute the job
+                    // We use std::panic::catch_unwind to prevent worker panics
+                    // from crashing the entire thread pool
+                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        job();
+                    }));
+                    
+                    if let Err(payload) = result {
+                        eprintln!(
+                            "Worker {} panicked while executing job: {}",
+                            id,
+                            panic_message(payload.as_ref())
+                        );
+                        panicked_jobs.fetch_add(1, Ordering::Relaxed);
                     }
                 }
                 
@@ -102,4 +98,15 @@ impl Worker {
     pub fn id(&self) -> usize {
         self.id
     }
-}
\ No newline at end of file
+}
+
+/// Get the message a panic was raised with, if it was a string
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "<non-string panic payload>"
+    }
+}
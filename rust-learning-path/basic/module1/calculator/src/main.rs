@@ -1,7 +1,22 @@
+use std::env;
 use std::io;
+use std::process;
 use calculator::{parse_input, perform_calculation, CalculatorError};
 
 fn main() {
+    // An expression on the command line is evaluated once, e.g. `calculator 5 + 3`
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        match evaluate(&args.join(" ")) {
+            Ok(result) => println!("Result: {}", result),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     println!("Welcome to the Rust Calculator!");
     println!("Enter expressions (e.g., '5 + 3', '10 * 2')");
     println!("Type 'exit' to quit");
@@ -21,14 +36,8 @@ fn main() {
                 }
                 
                 // Process the input
-                match parse_input(&input) {
-                    Ok(calculation) => {
-                        // Perform the calculation
-                        match perform_calculation(&calculation) {
-                            Ok(result) => println!("Result: {}", result),
-                            Err(err) => print_error(err),
-                        }
-                    }
+                match evaluate(&input) {
+                    Ok(result) => println!("Result: {}", result),
                     Err(err) => print_error(err),
                 }
             }
@@ -37,7 +46,11 @@ fn main() {
     }
 }
 
-
+/// Parse an expression and calculate its result
+fn evaluate(input: &str) -> Result<f64, CalculatorError> {
+    let calculation = parse_input(input)?;
+    perform_calculation(&calculation)
+}
 
 /// Print a user-friendly error message
 /// 
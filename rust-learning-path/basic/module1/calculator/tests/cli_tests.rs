@@ -0,0 +1,94 @@
+//! End-to-end tests that run the calculator binary
+//!
+//! Each case is one run of the binary: the arguments, what to send on
+//! stdin, and what it should print and exit with. Add a case to `CASES`
+//! to cover a new scenario.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+struct CliCase {
+    name: &'static str,
+    args: &'static [&'static str],
+    stdin: &'static str,
+    exit_code: i32,
+    stdout: &'static [&'static str],
+    stderr: &'static [&'static str],
+}
+
+const CASES: &[CliCase] = &[
+    CliCase {
+        name: "expression argument",
+        args: &["5", "+", "3"],
+        stdin: "",
+        exit_code: 0,
+        stdout: &["Result: 8"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "quoted expression argument",
+        args: &["10 / 4"],
+        stdin: "",
+        exit_code: 0,
+        stdout: &["Result: 2.5"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "division by zero",
+        args: &["1", "/", "0"],
+        stdin: "",
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["Division by zero"],
+    },
+    CliCase {
+        name: "unknown operator",
+        args: &["2", "^", "3"],
+        stdin: "",
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["Unknown operation: ^"],
+    },
+    CliCase {
+        name: "interactive session",
+        args: &[],
+        stdin: "6 * 7\nfoo + 1\nexit\n",
+        exit_code: 0,
+        stdout: &["Result: 42", "Could not parse 'foo' as a number", "Goodbye!"],
+        stderr: &[],
+    },
+];
+
+fn run_case(case: &CliCase) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_calculator"))
+        .args(case.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(case.stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(case.exit_code),
+        "{}: wrong exit code\nstdout:\n{}\nstderr:\n{}",
+        case.name, stdout, stderr
+    );
+    for expected in case.stdout {
+        assert!(stdout.contains(expected), "{}: stdout is missing {:?}:\n{}", case.name, expected, stdout);
+    }
+    for expected in case.stderr {
+        assert!(stderr.contains(expected), "{}: stderr is missing {:?}:\n{}", case.name, expected, stderr);
+    }
+}
+
+#[test]
+fn test_cli_cases() {
+    for case in CASES {
+        run_case(case);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the dotfile bookmarks are saved to, in the user's home directory
+pub const BOOKMARKS_FILE: &str = ".file_explorer_bookmarks";
+
+/// Named shortcuts to directories, persisted to a dotfile.
+///
+/// The file holds one `name<TAB>path` pair per line.
+#[derive(Debug)]
+pub struct Bookmarks {
+    file: PathBuf,
+    entries: BTreeMap<String, PathBuf>,
+}
+
+impl Bookmarks {
+    /// Returns `~/.file_explorer_bookmarks`, falling back to the working
+    /// directory if no home directory is set
+    pub fn default_file() -> PathBuf {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        home.join(BOOKMARKS_FILE)
+    }
+
+    /// Loads bookmarks from `file`. A missing file means no bookmarks yet.
+    pub fn load(file: &Path) -> io::Result<Bookmarks> {
+        let mut entries = BTreeMap::new();
+
+        match fs::read_to_string(file) {
+            Ok(contents) => {
+                for line in contents.lines() {
+                    if let Some((name, path)) = line.split_once('\t') {
+                        entries.insert(name.to_string(), PathBuf::from(path));
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(Bookmarks {
+            file: file.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Writes the bookmarks back to their file
+    pub fn save(&self) -> io::Result<()> {
+        let mut contents = String::new();
+        for (name, path) in &self.entries {
+            contents.push_str(&format!("{}\t{}\n", name, path.display()));
+        }
+        fs::write(&self.file, contents)
+    }
+
+    /// Bookmarks `dir` under `name`, replacing any bookmark with that name
+    pub fn add(&mut self, name: &str, dir: &Path) -> io::Result<()> {
+        if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c == '@') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid bookmark name '{}'", name),
+            ));
+        }
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not an existing directory", dir.display()),
+            ));
+        }
+
+        self.entries.insert(name.to_string(), fs::canonicalize(dir)?);
+        Ok(())
+    }
+
+    /// Removes a bookmark, returning whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Looks up the directory for `name`, failing if the bookmark is unknown
+    /// or its directory no longer exists
+    pub fn resolve(&self, name: &str) -> io::Result<PathBuf> {
+        let path = self.entries.get(name).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("no bookmark named '{}'", name))
+        })?;
+
+        if !path.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "bookmark '{}' points to {}, which no longer exists",
+                    name,
+                    path.display()
+                ),
+            ));
+        }
+        Ok(path.clone())
+    }
+
+    /// Bookmarks sorted by name
+    pub fn list(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.entries.iter().map(|(name, path)| (name.as_str(), path.as_path()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,201 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Flags shared by the copy, move, and remove commands
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OpOptions {
+    /// Allow operating on directories and their contents (`-r`)
+    pub recursive: bool,
+    /// Overwrite existing targets and skip confirmation (`-f`)
+    pub force: bool,
+}
+
+impl OpOptions {
+    /// Pulls `-r`, `-f`, and combined forms like `-rf` out of the arguments,
+    /// returning the options and the remaining positional arguments
+    pub fn parse<'a>(args: &[&'a str]) -> (OpOptions, Vec<&'a str>) {
+        let mut options = OpOptions::default();
+        let mut positional = Vec::new();
+
+        for arg in args {
+            match arg.strip_prefix('-') {
+                Some(flags) if !flags.is_empty() && flags.chars().all(|c| c == 'r' || c == 'f') => {
+                    options.recursive |= flags.contains('r');
+                    options.force |= flags.contains('f');
+                }
+                _ => positional.push(*arg),
+            }
+        }
+
+        (options, positional)
+    }
+}
+
+/// Resolves a user-supplied path against the explorer's current directory
+pub fn resolve(current_dir: &Path, input: &str) -> PathBuf {
+    let path = Path::new(input);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        current_dir.join(path)
+    }
+}
+
+/// Where `src` ends up when copied or moved to `dst`.
+/// Copying into an existing directory keeps the source's name.
+fn destination_for(src: &Path, dst: &Path) -> io::Result<PathBuf> {
+    if dst.is_dir() {
+        let name = src.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("{} has no file name", src.display()))
+        })?;
+        Ok(dst.join(name))
+    } else {
+        Ok(dst.to_path_buf())
+    }
+}
+
+fn check_overwrite(target: &Path, options: OpOptions) -> io::Result<()> {
+    if target.exists() && !options.force {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists (use -f to overwrite)", target.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Canonical form of a path that may not exist yet, resolved through its parent
+fn canonical_target(target: &Path) -> io::Result<PathBuf> {
+    if target.exists() {
+        return fs::canonicalize(target);
+    }
+    let parent = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = target.file_name().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{} has no file name", target.display()))
+    })?;
+    Ok(fs::canonicalize(parent)?.join(name))
+}
+
+/// Copies a file, or a directory tree with `-r`. Returns the number of files copied.
+///
+/// Copying something onto itself is refused even with `-f`, since opening
+/// the target for writing would truncate the source.
+pub fn copy(src: &Path, dst: &Path, options: OpOptions) -> io::Result<u64> {
+    let metadata = fs::metadata(src)?;
+    let target = destination_for(src, dst)?;
+    let src_real = fs::canonicalize(src)?;
+    let target_real = canonical_target(&target)?;
+    if target_real == src_real {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} and {} are the same file", src.display(), target.display()),
+        ));
+    }
+    check_overwrite(&target, options)?;
+
+    if metadata.is_dir() {
+        if !options.recursive {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is a directory (use -r to copy it)", src.display()),
+            ));
+        }
+        if target_real.starts_with(&src_real) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot copy a directory into itself",
+            ));
+        }
+        copy_dir_recursive(src, &target)
+    } else {
+        fs::copy(src, &target)?;
+        Ok(1)
+    }
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<u64> {
+    fs::create_dir_all(dst)?;
+    let mut copied = 0;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copied += copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+            copied += 1;
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Moves a file or directory, returning where it ended up
+pub fn move_entry(src: &Path, dst: &Path, options: OpOptions) -> io::Result<PathBuf> {
+    if !src.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist", src.display()),
+        ));
+    }
+    let target = destination_for(src, dst)?;
+    check_overwrite(&target, options)?;
+
+    if fs::rename(src, &target).is_err() {
+        // Renaming fails across file systems, so fall back to copy + delete
+        let recursive = OpOptions { recursive: true, force: true };
+        copy(src, &target, recursive)?;
+        if src.is_dir() {
+            fs::remove_dir_all(src)?;
+        } else {
+            fs::remove_file(src)?;
+        }
+    }
+
+    Ok(target)
+}
+
+/// Removes a file, or a directory tree with `-r`.
+///
+/// Unless `-f` is given, `confirm` is asked first; returns `Ok(false)` if it
+/// declines. Removing the current directory or any of its parents is refused.
+pub fn remove(
+    path: &Path,
+    current_dir: &Path,
+    options: OpOptions,
+    confirm: &mut dyn FnMut(&Path) -> bool,
+) -> io::Result<bool> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    let target = fs::canonicalize(path)?;
+    let here = fs::canonicalize(current_dir)?;
+    if here.starts_with(&target) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "refusing to remove the current directory or one of its parents",
+        ));
+    }
+
+    if metadata.is_dir() && !options.recursive {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is a directory (use -r to remove it)", path.display()),
+        ));
+    }
+
+    if !options.force && !confirm(path) {
+        return Ok(false);
+    }
+
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,153 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+/// How many leading bytes are inspected when deciding if a file is binary
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Settings for a content search
+#[derive(Debug, Clone)]
+pub struct GrepOptions {
+    /// Descend into directories (`-r`)
+    pub recursive: bool,
+    /// Stop after this many matching lines
+    pub max_matches: usize,
+}
+
+impl Default for GrepOptions {
+    fn default() -> Self {
+        GrepOptions {
+            recursive: false,
+            max_matches: 200,
+        }
+    }
+}
+
+/// A single matching line
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrepMatch {
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// The outcome of a content search
+#[derive(Debug, Default)]
+pub struct GrepResults {
+    pub matches: Vec<GrepMatch>,
+    /// Files that were skipped because they look binary or aren't UTF-8
+    pub skipped_binary: Vec<PathBuf>,
+    /// Files and directories that couldn't be read
+    pub unreadable: Vec<PathBuf>,
+    /// True if the search stopped early because `max_matches` was reached
+    pub truncated: bool,
+}
+
+/// Searches the contents of `path` for lines matching `pattern`.
+///
+/// `path` may be a file, or a directory when `options.recursive` is set.
+/// Files are read line by line, so large files are never loaded whole.
+/// Symbolic links inside directories are not followed.
+pub fn grep(path: &Path, pattern: &Regex, options: &GrepOptions) -> io::Result<GrepResults> {
+    let metadata = fs::metadata(path)?;
+    if metadata.is_dir() && !options.recursive {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is a directory (use -r to search it)", path.display()),
+        ));
+    }
+
+    let mut results = GrepResults::default();
+    if metadata.is_dir() {
+        grep_dir(path, pattern, options, &mut results);
+    } else {
+        grep_file(path, pattern, options, &mut results);
+    }
+    Ok(results)
+}
+
+fn grep_dir(dir: &Path, pattern: &Regex, options: &GrepOptions, results: &mut GrepResults) {
+    let mut entries: Vec<fs::DirEntry> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(Result::ok).collect(),
+        Err(_) => {
+            results.unreadable.push(dir.to_path_buf());
+            return;
+        }
+    };
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if results.truncated {
+            return;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            grep_dir(&entry.path(), pattern, options, results);
+        } else if file_type.is_file() {
+            grep_file(&entry.path(), pattern, options, results);
+        }
+    }
+}
+
+fn grep_file(path: &Path, pattern: &Regex, options: &GrepOptions, results: &mut GrepResults) {
+    let Ok(file) = File::open(path) else {
+        results.unreadable.push(path.to_path_buf());
+        return;
+    };
+    let mut reader = BufReader::new(file);
+
+    if looks_binary(&mut reader) {
+        results.skipped_binary.push(path.to_path_buf());
+        return;
+    }
+
+    // Matches are held back until the whole file has been read, so a file
+    // that turns out not to be UTF-8 halfway through contributes nothing
+    let mut found = Vec::new();
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                results.skipped_binary.push(path.to_path_buf());
+                return;
+            }
+            Err(_) => {
+                results.unreadable.push(path.to_path_buf());
+                return;
+            }
+        }
+        line_number += 1;
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        if pattern.is_match(text) {
+            if results.matches.len() + found.len() >= options.max_matches {
+                results.truncated = true;
+                break;
+            }
+            found.push(GrepMatch {
+                path: path.to_path_buf(),
+                line_number,
+                line: text.to_string(),
+            });
+        }
+    }
+
+    results.matches.extend(found);
+}
+
+/// Treats a file as binary if its first block contains a NUL byte
+fn looks_binary<R: Read>(reader: &mut BufReader<R>) -> bool {
+    match reader.fill_buf() {
+        Ok(buffer) => buffer[..buffer.len().min(BINARY_SNIFF_LEN)].contains(&0),
+        Err(_) => true,
+    }
+}
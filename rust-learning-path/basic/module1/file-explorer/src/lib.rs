@@ -0,0 +1,6 @@
+// Re-export modules for testing
+pub mod search;
+pub mod file_ops;
+pub mod tree;
+pub mod grep;
+pub mod bookmarks;
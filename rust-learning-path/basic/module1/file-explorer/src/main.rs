@@ -4,6 +4,12 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+use file_explorer::bookmarks::Bookmarks;
+use file_explorer::file_ops::{self, OpOptions};
+use file_explorer::grep::{self, GrepOptions};
+use file_explorer::search::{self, SearchOptions};
+use file_explorer::tree::{self, TreeOptions};
+
 /// Main function that runs the file explorer
 fn main() -> io::Result<()> {
     println!("Simple File System Explorer");
@@ -64,6 +70,13 @@ fn handle_command(command: &str, args: &[&str], current_dir: &mut PathBuf) -> io
                 return Ok(true);
             }
             
+            // Jump to a bookmarked directory with "@name"
+            if let Some(name) = args[0].strip_prefix('@') {
+                let bookmarks = Bookmarks::load(&Bookmarks::default_file())?;
+                *current_dir = bookmarks.resolve(name)?;
+                return Ok(true);
+            }
+            
             // TODO: Handle navigation to parent directory with ".."
             // TODO: Handle navigation to home directory with "~"
             // TODO: Handle navigation to absolute paths
@@ -118,6 +131,111 @@ fn handle_command(command: &str, args: &[&str], current_dir: &mut PathBuf) -> io
             Ok(true)
         },
         
+        "find" => {
+            if args.is_empty() {
+                println!("Usage: find <pattern> [--max-depth N] [--limit N]");
+                return Ok(true);
+            }
+            find_entries(current_dir, args);
+            Ok(true)
+        },
+        
+        "tree" => {
+            let (options, positional) = TreeOptions::parse(args);
+            let root = match positional.first() {
+                Some(path) => file_ops::resolve(current_dir, path),
+                None => current_dir.to_path_buf(),
+            };
+            if !root.is_dir() {
+                println!("Not a directory: {}", root.display());
+                return Ok(true);
+            }
+            println!("{}", tree::build_tree(&root, &options).render());
+            Ok(true)
+        },
+        
+        "grep" => {
+            // Drop `--limit N` before picking out the pattern and path
+            let mut rest = args.to_vec();
+            if let Some(position) = rest.iter().position(|arg| *arg == "--limit") {
+                rest.drain(position..(position + 2).min(rest.len()));
+            }
+            let (ops, positional) = OpOptions::parse(&rest);
+            if positional.len() != 2 {
+                println!("Usage: grep [-r] <pattern> <path> [--limit N]");
+                return Ok(true);
+            }
+            let options = GrepOptions {
+                recursive: ops.recursive,
+                max_matches: flag_value(args, "--limit").unwrap_or(GrepOptions::default().max_matches),
+            };
+            grep_contents(current_dir, positional[0], positional[1], &options)?;
+            Ok(true)
+        },
+        
+        "bookmark" => {
+            let mut bookmarks = Bookmarks::load(&Bookmarks::default_file())?;
+            match args {
+                ["add", name] => {
+                    bookmarks.add(name, current_dir)?;
+                    bookmarks.save()?;
+                    println!("Bookmarked {} as @{}", current_dir.display(), name);
+                },
+                ["remove", name] => {
+                    if bookmarks.remove(name) {
+                        bookmarks.save()?;
+                        println!("Removed bookmark @{}", name);
+                    } else {
+                        println!("No bookmark named '{}'", name);
+                    }
+                },
+                ["list"] | [] => {
+                    if bookmarks.is_empty() {
+                        println!("No bookmarks yet");
+                    }
+                    for (name, path) in bookmarks.list() {
+                        let note = if path.is_dir() { "" } else { "  (missing)" };
+                        println!("@{:<15} {}{}", name, path.display(), note);
+                    }
+                },
+                _ => println!("Usage: bookmark add <name> | remove <name> | list"),
+            }
+            Ok(true)
+        },
+        
+        "cp" | "mv" => {
+            let (options, paths) = OpOptions::parse(args);
+            if paths.len() != 2 {
+                println!("Usage: {} [-r] [-f] <source> <destination>", command);
+                return Ok(true);
+            }
+            let src = file_ops::resolve(current_dir, paths[0]);
+            let dst = file_ops::resolve(current_dir, paths[1]);
+            if command == "cp" {
+                let count = file_ops::copy(&src, &dst, options)?;
+                println!("Copied {} file(s)", count);
+            } else {
+                let target = file_ops::move_entry(&src, &dst, options)?;
+                println!("Moved to {}", target.display());
+            }
+            Ok(true)
+        },
+        
+        "rm" => {
+            let (options, paths) = OpOptions::parse(args);
+            if paths.len() != 1 {
+                println!("Usage: rm [-r] [-f] <path>");
+                return Ok(true);
+            }
+            let path = file_ops::resolve(current_dir, paths[0]);
+            if file_ops::remove(&path, current_dir, options, &mut confirm_removal)? {
+                println!("Removed {}", path.display());
+            } else {
+                println!("Cancelled");
+            }
+            Ok(true)
+        },
+        
         // Display help information
         "help" => {
             display_help();
@@ -149,15 +267,96 @@ fn list_directory_contents(path: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Ask the user to confirm a removal on stdin
+fn confirm_removal(path: &Path) -> bool {
+    print!("Remove {}? [y/N] ", path.display());
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Parse the value following a flag such as `--max-depth 3`
+fn flag_value(args: &[&str], flag: &str) -> Option<usize> {
+    let position = args.iter().position(|arg| *arg == flag)?;
+    args.get(position + 1)?.parse().ok()
+}
+
+/// Search below the current directory for names matching a pattern
+fn find_entries(current_dir: &Path, args: &[&str]) {
+    let pattern = args[0];
+    let defaults = SearchOptions::default();
+    let options = SearchOptions {
+        max_depth: flag_value(args, "--max-depth").unwrap_or(defaults.max_depth),
+        max_results: flag_value(args, "--limit").unwrap_or(defaults.max_results),
+    };
+
+    let results = search::find(current_dir, pattern, &options);
+
+    for path in &results.matches {
+        let shown = path.strip_prefix(current_dir).unwrap_or(path);
+        println!("{}", shown.display());
+    }
+
+    println!("{} match(es) for '{}'", results.matches.len(), pattern);
+    if results.truncated {
+        println!("Stopped after {} results (use --limit to raise)", options.max_results);
+    }
+    for dir in &results.skipped {
+        println!("Skipped unreadable directory: {}", dir.display());
+    }
+}
+
+/// Search file contents for lines matching a regex
+fn grep_contents(current_dir: &Path, pattern: &str, path: &str, options: &GrepOptions) -> io::Result<()> {
+    let regex = regex::Regex::new(pattern)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let root = file_ops::resolve(current_dir, path);
+    let results = grep::grep(&root, &regex, options)?;
+
+    for found in &results.matches {
+        let shown = found.path.strip_prefix(current_dir).unwrap_or(&found.path);
+        println!("{}:{}: {}", shown.display(), found.line_number, found.line);
+    }
+
+    println!("{} matching line(s)", results.matches.len());
+    if results.truncated {
+        println!("Stopped after {} matches (use --limit to raise)", options.max_matches);
+    }
+    if !results.skipped_binary.is_empty() {
+        println!("Skipped {} binary file(s)", results.skipped_binary.len());
+    }
+    for path in &results.unreadable {
+        println!("Skipped unreadable path: {}", path.display());
+    }
+    Ok(())
+}
+
 /// Display help information
 fn display_help() {
     println!("\nAvailable commands:");
     println!("  ls                  List directory contents");
     println!("  cd <directory>      Change to specified directory");
+    println!("  cd @<name>          Jump to a bookmarked directory");
     println!("  pwd                 Print current directory path");
     println!("  info <file>         Display file information");
     println!("  cat <file>          Display file contents");
     println!("  mkdir <directory>   Create a new directory");
+    println!("  cp [-r] [-f] <src> <dst>  Copy a file (or a directory with -r)");
+    println!("  mv [-f] <src> <dst>       Move or rename a file or directory");
+    println!("  rm [-r] [-f] <path>       Remove a file (or a directory with -r)");
+    println!("  tree [path]         Show the directory hierarchy");
+    println!("                      [--depth N] [--limit N]");
+    println!("  grep [-r] <regex> <path>  Search file contents for matching lines");
+    println!("                      [--limit N]");
+    println!("  find <pattern>      Search names below here (glob or substring)");
+    println!("                      [--max-depth N] [--limit N]");
+    println!("  bookmark add <name> Bookmark the current directory");
+    println!("  bookmark remove <name> | list");
     println!("  help                Display this help message");
     println!("  exit, quit          Exit the program");
 }
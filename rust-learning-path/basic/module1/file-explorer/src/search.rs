@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Limits for a recursive name search
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// How many directory levels below the start to descend (1 = direct children only)
+    pub max_depth: usize,
+    /// Stop after this many matches
+    pub max_results: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            max_depth: 10,
+            max_results: 100,
+        }
+    }
+}
+
+/// The outcome of a search
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    /// Paths whose file name matched the pattern, in traversal order
+    pub matches: Vec<PathBuf>,
+    /// Directories that couldn't be read (e.g. permission denied)
+    pub skipped: Vec<PathBuf>,
+    /// True if the search stopped early because `max_results` was reached
+    pub truncated: bool,
+}
+
+/// Checks a file name against a pattern.
+///
+/// Patterns containing `*` or `?` are treated as globs that must match the
+/// whole name; anything else is a case-insensitive substring match.
+pub fn matches_pattern(name: &str, pattern: &str) -> bool {
+    if pattern.contains('*') || pattern.contains('?') {
+        let name: Vec<char> = name.chars().collect();
+        let pattern: Vec<char> = pattern.chars().collect();
+        glob_match(&name, &pattern)
+    } else {
+        name.to_lowercase().contains(&pattern.to_lowercase())
+    }
+}
+
+/// Matches `*` (any run of characters) and `?` (any single character).
+///
+/// Walks both strings once, remembering only the most recent `*`. On a
+/// mismatch it retries from that star with one more character swallowed,
+/// so the work is at most `name.len() * pattern.len()` steps.
+fn glob_match(name: &[char], pattern: &[char]) -> bool {
+    let (mut n, mut p) = (0, 0);
+    // Pattern index just after the last `*`, and the name index it was tried at
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                star = Some((p, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p;
+                    n = star_n + 1;
+                    star = Some((star_p, n));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Recursively searches `root` for entries whose name matches `pattern`.
+///
+/// Directories that can't be read are recorded in `skipped` instead of
+/// aborting the search. Symbolic links are not followed.
+pub fn find(root: &Path, pattern: &str, options: &SearchOptions) -> SearchResults {
+    let mut results = SearchResults::default();
+    search_dir(root, pattern, options, 1, &mut results);
+    results
+}
+
+fn search_dir(dir: &Path, pattern: &str, options: &SearchOptions, depth: usize, results: &mut SearchResults) {
+    if depth > options.max_depth || results.truncated {
+        return;
+    }
+
+    let mut entries: Vec<fs::DirEntry> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(Result::ok).collect(),
+        Err(_) => {
+            results.skipped.push(dir.to_path_buf());
+            return;
+        }
+    };
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if matches_pattern(&entry.file_name().to_string_lossy(), pattern) {
+            if results.matches.len() >= options.max_results {
+                results.truncated = true;
+                return;
+            }
+            results.matches.push(path.clone());
+        }
+
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_dir {
+            search_dir(&path, pattern, options, depth + 1, results);
+            if results.truncated {
+                return;
+            }
+        }
+    }
+}
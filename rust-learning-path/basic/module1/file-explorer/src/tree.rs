@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Limits for rendering a directory tree
+#[derive(Debug, Clone)]
+pub struct TreeOptions {
+    /// How many directory levels below the root to show (1 = direct children only)
+    pub max_depth: usize,
+    /// Stop after printing this many entries
+    pub max_entries: usize,
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        TreeOptions {
+            max_depth: 3,
+            max_entries: 200,
+        }
+    }
+}
+
+impl TreeOptions {
+    /// Pulls `--depth N` and `--limit N` out of the arguments, in any position,
+    /// returning the options and the remaining positional arguments
+    pub fn parse<'a>(args: &[&'a str]) -> (TreeOptions, Vec<&'a str>) {
+        let mut options = TreeOptions::default();
+        let mut positional = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(&arg) = args.next() {
+            let field = match arg {
+                "--depth" => &mut options.max_depth,
+                "--limit" => &mut options.max_entries,
+                _ => {
+                    positional.push(arg);
+                    continue;
+                }
+            };
+            if let Some(value) = args.next().and_then(|value| value.parse().ok()) {
+                *field = value;
+            }
+        }
+
+        (options, positional)
+    }
+}
+
+/// A rendered tree plus some counts about what was shown
+#[derive(Debug, Default)]
+pub struct Tree {
+    /// One line per entry, starting with the root itself
+    pub lines: Vec<String>,
+    pub directories: usize,
+    pub files: usize,
+    /// Directories that couldn't be read (e.g. permission denied)
+    pub skipped: Vec<PathBuf>,
+    /// True if rendering stopped early because `max_entries` was reached
+    pub truncated: bool,
+}
+
+impl Tree {
+    /// Renders the tree followed by a `tree`-style summary line
+    pub fn render(&self) -> String {
+        let mut output = self.lines.join("\n");
+        output.push_str(&format!(
+            "\n\n{} directories, {} files",
+            self.directories, self.files
+        ));
+        output
+    }
+}
+
+/// Builds an indented tree of `root`, directories first then files,
+/// each group sorted by name. Symbolic links are listed but not followed.
+pub fn build_tree(root: &Path, options: &TreeOptions) -> Tree {
+    let mut tree = Tree::default();
+    tree.lines.push(root.display().to_string());
+    add_children(root, "", 1, options, &mut tree);
+    tree
+}
+
+fn add_children(dir: &Path, prefix: &str, depth: usize, options: &TreeOptions, tree: &mut Tree) {
+    if depth > options.max_depth {
+        return;
+    }
+
+    let mut entries: Vec<(String, bool, PathBuf)> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| {
+                let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                (entry.file_name().to_string_lossy().into_owned(), is_dir, entry.path())
+            })
+            .collect(),
+        Err(_) => {
+            tree.lines.push(format!("{}└── [unreadable, skipped]", prefix));
+            tree.skipped.push(dir.to_path_buf());
+            return;
+        }
+    };
+    // Directories first, then alphabetical
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let count = entries.len();
+    for (index, (name, is_dir, path)) in entries.into_iter().enumerate() {
+        if tree.directories + tree.files >= options.max_entries {
+            tree.truncated = true;
+            tree.lines.push(format!("{}└── ... (output truncated)", prefix));
+            return;
+        }
+
+        let last = index + 1 == count;
+        let connector = if last { "└── " } else { "├── " };
+        if is_dir {
+            tree.directories += 1;
+            tree.lines.push(format!("{}{}{}/", prefix, connector, name));
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            add_children(&path, &child_prefix, depth + 1, options, tree);
+            if tree.truncated {
+                return;
+            }
+        } else {
+            tree.files += 1;
+            tree.lines.push(format!("{}{}{}", prefix, connector, name));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use file_explorer::bookmarks::Bookmarks;
+use tempfile::tempdir;
+
+#[test]
+fn test_add_and_resolve() {
+    let temp_dir = tempdir().unwrap();
+    let projects = temp_dir.path().join("projects");
+    fs::create_dir(&projects).unwrap();
+
+    let mut bookmarks = Bookmarks::load(&temp_dir.path().join("bookmarks")).unwrap();
+    assert!(bookmarks.is_empty());
+    bookmarks.add("proj", &projects).unwrap();
+
+    assert_eq!(bookmarks.resolve("proj").unwrap(), fs::canonicalize(&projects).unwrap());
+    assert_eq!(bookmarks.resolve("nope").unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn test_reject_invalid_bookmarks() {
+    let temp_dir = tempdir().unwrap();
+    let mut bookmarks = Bookmarks::load(&temp_dir.path().join("bookmarks")).unwrap();
+
+    let missing = temp_dir.path().join("missing");
+    assert_eq!(bookmarks.add("gone", &missing).unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(bookmarks.add("two words", temp_dir.path()).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(bookmarks.add("", temp_dir.path()).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(bookmarks.is_empty());
+}
+
+#[test]
+fn test_persist_and_reload() {
+    let temp_dir = tempdir().unwrap();
+    let file = temp_dir.path().join("bookmarks");
+    fs::create_dir(temp_dir.path().join("a")).unwrap();
+    fs::create_dir(temp_dir.path().join("b")).unwrap();
+
+    let mut bookmarks = Bookmarks::load(&file).unwrap();
+    bookmarks.add("b", &temp_dir.path().join("b")).unwrap();
+    bookmarks.add("a", &temp_dir.path().join("a")).unwrap();
+    bookmarks.save().unwrap();
+
+    let reloaded = Bookmarks::load(&file).unwrap();
+    let names: Vec<&str> = reloaded.list().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["a", "b"]);
+    assert_eq!(reloaded.resolve("a").unwrap(), bookmarks.resolve("a").unwrap());
+}
+
+#[test]
+fn test_stale_bookmark() {
+    let temp_dir = tempdir().unwrap();
+    let doomed = temp_dir.path().join("doomed");
+    fs::create_dir(&doomed).unwrap();
+
+    let mut bookmarks = Bookmarks::load(&temp_dir.path().join("bookmarks")).unwrap();
+    bookmarks.add("doomed", &doomed).unwrap();
+    fs::remove_dir(&doomed).unwrap();
+
+    let err = bookmarks.resolve("doomed").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert!(err.to_string().contains("no longer exists"));
+
+    // Stale bookmarks can still be listed and removed
+    assert_eq!(bookmarks.len(), 1);
+    assert!(bookmarks.remove("doomed"));
+    assert!(bookmarks.is_empty());
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use file_explorer::file_ops::{copy, move_entry, remove, resolve, OpOptions};
+use tempfile::tempdir;
+
+const RECURSIVE: OpOptions = OpOptions { recursive: true, force: false };
+const FORCE: OpOptions = OpOptions { recursive: false, force: true };
+
+#[test]
+fn test_parse_flags() {
+    let (options, paths) = OpOptions::parse(&["-rf", "a", "b"]);
+    assert!(options.recursive && options.force);
+    assert_eq!(paths, vec!["a", "b"]);
+
+    let (options, paths) = OpOptions::parse(&["a", "-r", "-x"]);
+    assert!(options.recursive && !options.force);
+    assert_eq!(paths, vec!["a", "-x"]);
+}
+
+#[test]
+fn test_resolve_relative_to_current_dir() {
+    let temp_dir = tempdir().unwrap();
+    assert_eq!(resolve(temp_dir.path(), "file.txt"), temp_dir.path().join("file.txt"));
+    assert_eq!(resolve(temp_dir.path(), "/etc"), std::path::PathBuf::from("/etc"));
+}
+
+#[test]
+fn test_copy_file() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    fs::write(&src, "hello").unwrap();
+    fs::create_dir(temp_dir.path().join("dir")).unwrap();
+
+    copy(&src, &temp_dir.path().join("b.txt"), OpOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(temp_dir.path().join("b.txt")).unwrap(), "hello");
+
+    // Copying into a directory keeps the file name
+    copy(&src, &temp_dir.path().join("dir"), OpOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(temp_dir.path().join("dir/a.txt")).unwrap(), "hello");
+    assert!(src.exists());
+}
+
+#[test]
+fn test_copy_directory_requires_recursive() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(src.join("inner")).unwrap();
+    fs::write(src.join("inner/file.txt"), "x").unwrap();
+    let dst = temp_dir.path().join("dst");
+
+    let err = copy(&src, &dst, OpOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    assert_eq!(copy(&src, &dst, RECURSIVE).unwrap(), 1);
+    assert!(dst.join("inner/file.txt").exists());
+
+    let err = copy(&src, &src.join("inner"), RECURSIVE).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_copy_onto_itself_is_refused() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    fs::write(&src, "hello").unwrap();
+
+    for options in [OpOptions::default(), FORCE] {
+        let err = copy(&src, &src, options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        // The same file reached through a different spelling of the path
+        let err = copy(&src, &temp_dir.path().join(".").join("a.txt"), options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = copy(&src, temp_dir.path(), options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+    assert_eq!(fs::read_to_string(&src).unwrap(), "hello");
+}
+
+#[test]
+fn test_copy_directory_into_itself_is_refused() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(src.join("inner")).unwrap();
+
+    // A path that only points inside `src` once `..` is resolved
+    let sneaky = temp_dir.path().join("src/inner/../inner/copy");
+    let err = copy(&src, &sneaky, RECURSIVE).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!src.join("inner/copy").exists());
+}
+
+#[test]
+fn test_overwrite_requires_force() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    let dst = temp_dir.path().join("b.txt");
+    fs::write(&src, "new").unwrap();
+    fs::write(&dst, "old").unwrap();
+
+    let err = copy(&src, &dst, OpOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    let err = move_entry(&src, &dst, OpOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(&dst).unwrap(), "old");
+
+    copy(&src, &dst, FORCE).unwrap();
+    assert_eq!(fs::read_to_string(&dst).unwrap(), "new");
+}
+
+#[test]
+fn test_move_file() {
+    let temp_dir = tempdir().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    fs::write(&src, "moving").unwrap();
+    fs::create_dir(temp_dir.path().join("dir")).unwrap();
+
+    let target = move_entry(&src, &temp_dir.path().join("dir"), OpOptions::default()).unwrap();
+
+    assert_eq!(target, temp_dir.path().join("dir/a.txt"));
+    assert!(!src.exists());
+    assert_eq!(fs::read_to_string(target).unwrap(), "moving");
+}
+
+#[test]
+fn test_remove_directory_recursively() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path().join("doomed");
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    fs::write(dir.join("a/b/file.txt"), "x").unwrap();
+
+    let err = remove(&dir, temp_dir.path(), OpOptions::default(), &mut |_| true).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(dir.exists());
+
+    assert!(remove(&dir, temp_dir.path(), RECURSIVE, &mut |_| true).unwrap());
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_remove_confirmation() {
+    let temp_dir = tempdir().unwrap();
+    let file = temp_dir.path().join("keep.txt");
+    fs::write(&file, "x").unwrap();
+
+    // Declining the prompt leaves the file alone
+    assert!(!remove(&file, temp_dir.path(), OpOptions::default(), &mut |_| false).unwrap());
+    assert!(file.exists());
+
+    // -f skips the prompt entirely
+    let mut asked = false;
+    assert!(remove(&file, temp_dir.path(), FORCE, &mut |_| {
+        asked = true;
+        false
+    })
+    .unwrap());
+    assert!(!asked);
+    assert!(!file.exists());
+}
+
+#[test]
+fn test_remove_refuses_current_directory() {
+    let temp_dir = tempdir().unwrap();
+    let here = temp_dir.path().join("here");
+    fs::create_dir(&here).unwrap();
+    let everything = OpOptions { recursive: true, force: true };
+
+    let err = remove(&here, &here, everything, &mut |_| true).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    let err = remove(temp_dir.path(), &here, everything, &mut |_| true).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    assert!(here.exists());
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use file_explorer::grep::{grep, GrepOptions};
+use regex::Regex;
+use tempfile::tempdir;
+
+const RECURSIVE: GrepOptions = GrepOptions { recursive: true, max_matches: 200 };
+
+/// Builds:
+///   root/a.txt          three lines, two mention "error"
+///   root/image.bin      binary data that happens to contain "error"
+///   root/latin1.txt     not valid UTF-8
+///   root/sub/b.log      one "error" line
+fn build_fixture(root: &Path) {
+    fs::create_dir(root.join("sub")).unwrap();
+    fs::write(root.join("a.txt"), "error: disk full\nall good\nanother error here\n").unwrap();
+    fs::write(root.join("image.bin"), b"\x89PNG\0\0error\0").unwrap();
+    fs::write(root.join("latin1.txt"), b"caf\xe9 error\n").unwrap();
+    fs::write(root.join("sub/b.log"), "ok\nok\nfatal error\n").unwrap();
+}
+
+#[test]
+fn test_grep_single_file() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+    let path = temp_dir.path().join("a.txt");
+
+    let results = grep(&path, &Regex::new("error").unwrap(), &GrepOptions::default()).unwrap();
+
+    let found: Vec<(usize, &str)> = results.matches.iter()
+        .map(|m| (m.line_number, m.line.as_str()))
+        .collect();
+    assert_eq!(found, vec![(1, "error: disk full"), (3, "another error here")]);
+}
+
+#[test]
+fn test_grep_regex() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+    let path = temp_dir.path().join("a.txt");
+
+    let results = grep(&path, &Regex::new("^error:").unwrap(), &GrepOptions::default()).unwrap();
+    assert_eq!(results.matches.len(), 1);
+    assert_eq!(results.matches[0].line_number, 1);
+}
+
+#[test]
+fn test_grep_recursive_skips_binary() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let results = grep(temp_dir.path(), &Regex::new("error").unwrap(), &RECURSIVE).unwrap();
+
+    let found: Vec<(&Path, usize)> = results.matches.iter()
+        .map(|m| (m.path.strip_prefix(temp_dir.path()).unwrap(), m.line_number))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Path::new("a.txt"), 1),
+            (Path::new("a.txt"), 3),
+            (Path::new("sub/b.log"), 3),
+        ]
+    );
+    assert_eq!(
+        results.skipped_binary,
+        vec![temp_dir.path().join("image.bin"), temp_dir.path().join("latin1.txt")]
+    );
+}
+
+#[test]
+fn test_grep_directory_requires_recursive() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let err = grep(temp_dir.path(), &Regex::new("error").unwrap(), &GrepOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_grep_match_limit() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let options = GrepOptions { max_matches: 2, ..RECURSIVE };
+    let results = grep(temp_dir.path(), &Regex::new("error").unwrap(), &options).unwrap();
+
+    assert_eq!(results.matches.len(), 2);
+    assert!(results.truncated);
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::Path;
+
+use file_explorer::search::{find, matches_pattern, SearchOptions};
+use tempfile::tempdir;
+
+/// Builds:
+///   root/notes.txt
+///   root/src/main.rs
+///   root/src/nested/deep/notes.md
+fn build_tree(root: &Path) {
+    fs::create_dir_all(root.join("src/nested/deep")).unwrap();
+    fs::write(root.join("notes.txt"), "top").unwrap();
+    fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+    fs::write(root.join("src/nested/deep/notes.md"), "deep").unwrap();
+}
+
+#[test]
+fn test_matches_pattern() {
+    assert!(matches_pattern("main.rs", "*.rs"));
+    assert!(matches_pattern("main.rs", "ma?n.*"));
+    assert!(!matches_pattern("main.rs.bak", "*.rs"));
+    assert!(matches_pattern("README.md", "readme"));
+    assert!(!matches_pattern("main.rs", "lib"));
+}
+
+#[test]
+fn test_glob_stars() {
+    assert!(matches_pattern("main.rs", "*"));
+    assert!(matches_pattern("", "*"));
+    assert!(matches_pattern("main.rs", "**.rs"));
+    assert!(matches_pattern("a.test.rs", "*.*.rs"));
+    assert!(matches_pattern("abcbc", "*bc"));
+    assert!(!matches_pattern("main.rs", "*.rs?"));
+    assert!(!matches_pattern("main", "?*?????"));
+}
+
+#[test]
+fn test_glob_many_stars_does_not_backtrack_exponentially() {
+    // A recursive matcher tries every split of the name for every `*`
+    let name = "a".repeat(60);
+    let pattern = format!("{}b", "*a".repeat(20));
+    assert!(!matches_pattern(&name, &pattern));
+    assert!(matches_pattern(&format!("{}b", name), &pattern));
+}
+
+#[test]
+fn test_find_matches_at_depth() {
+    let temp_dir = tempdir().unwrap();
+    build_tree(temp_dir.path());
+
+    let results = find(temp_dir.path(), "notes", &SearchOptions::default());
+
+    assert_eq!(
+        results.matches,
+        vec![
+            temp_dir.path().join("notes.txt"),
+            temp_dir.path().join("src/nested/deep/notes.md"),
+        ]
+    );
+    assert!(!results.truncated);
+}
+
+#[test]
+fn test_find_respects_max_depth() {
+    let temp_dir = tempdir().unwrap();
+    build_tree(temp_dir.path());
+
+    let shallow = SearchOptions { max_depth: 2, ..SearchOptions::default() };
+    let results = find(temp_dir.path(), "*", &shallow);
+
+    assert!(results.matches.contains(&temp_dir.path().join("src/main.rs")));
+    assert!(results.matches.contains(&temp_dir.path().join("src/nested")));
+    assert!(!results.matches.contains(&temp_dir.path().join("src/nested/deep")));
+}
+
+#[test]
+fn test_find_result_cap() {
+    let temp_dir = tempdir().unwrap();
+    build_tree(temp_dir.path());
+
+    let capped = SearchOptions { max_results: 2, ..SearchOptions::default() };
+    let results = find(temp_dir.path(), "*", &capped);
+
+    assert_eq!(results.matches.len(), 2);
+    assert!(results.truncated);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_find_skips_unreadable_directories() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempdir().unwrap();
+    build_tree(temp_dir.path());
+    let locked = temp_dir.path().join("locked");
+    fs::create_dir(&locked).unwrap();
+    fs::write(locked.join("notes.secret"), "hidden").unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+    let results = find(temp_dir.path(), "notes", &SearchOptions::default());
+
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+    // The rest of the tree is still searched. Privileged users can read the
+    // locked directory anyway, so accept either outcome for it.
+    assert!(results.matches.contains(&temp_dir.path().join("notes.txt")));
+    assert!(results.matches.contains(&temp_dir.path().join("src/nested/deep/notes.md")));
+    assert!(results.skipped.contains(&locked) || results.matches.contains(&locked.join("notes.secret")));
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+
+use file_explorer::tree::{build_tree, TreeOptions};
+use tempfile::tempdir;
+
+/// Builds:
+///   root/b.txt
+///   root/a/one.txt
+///   root/a/inner/deep.txt
+///   root/z/
+fn build_fixture(root: &Path) {
+    fs::create_dir_all(root.join("a/inner")).unwrap();
+    fs::create_dir(root.join("z")).unwrap();
+    fs::write(root.join("b.txt"), "").unwrap();
+    fs::write(root.join("a/one.txt"), "").unwrap();
+    fs::write(root.join("a/inner/deep.txt"), "").unwrap();
+}
+
+#[test]
+fn test_tree_structure() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let tree = build_tree(temp_dir.path(), &TreeOptions::default());
+
+    assert_eq!(
+        tree.lines[1..],
+        [
+            "├── a/",
+            "│   ├── inner/",
+            "│   │   └── deep.txt",
+            "│   └── one.txt",
+            "├── z/",
+            "└── b.txt",
+        ]
+    );
+    assert_eq!(tree.directories, 3);
+    assert_eq!(tree.files, 3);
+    assert!(!tree.truncated);
+    assert!(tree.render().ends_with("3 directories, 3 files"));
+}
+
+#[test]
+fn test_tree_depth_limit() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let options = TreeOptions { max_depth: 1, ..TreeOptions::default() };
+    let tree = build_tree(temp_dir.path(), &options);
+    assert_eq!(tree.lines[1..], ["├── a/", "├── z/", "└── b.txt"]);
+
+    let options = TreeOptions { max_depth: 2, ..TreeOptions::default() };
+    let tree = build_tree(temp_dir.path(), &options);
+    assert!(tree.lines.iter().any(|line| line.ends_with("inner/")));
+    assert!(!tree.lines.iter().any(|line| line.contains("deep.txt")));
+}
+
+#[test]
+fn test_tree_entry_cap() {
+    let temp_dir = tempdir().unwrap();
+    build_fixture(temp_dir.path());
+
+    let options = TreeOptions { max_entries: 2, ..TreeOptions::default() };
+    let tree = build_tree(temp_dir.path(), &options);
+
+    assert!(tree.truncated);
+    assert_eq!(tree.directories + tree.files, 2);
+    assert!(tree.lines.last().unwrap().contains("truncated"));
+}
+
+#[test]
+fn test_parse_flags_in_any_order() {
+    let (options, paths) = TreeOptions::parse(&["--depth", "2", "dir"]);
+    assert_eq!(options.max_depth, 2);
+    assert_eq!(paths, vec!["dir"]);
+
+    let (options, paths) = TreeOptions::parse(&["dir", "--depth", "2", "--limit", "5"]);
+    assert_eq!((options.max_depth, options.max_entries), (2, 5));
+    assert_eq!(paths, vec!["dir"]);
+
+    let (options, paths) = TreeOptions::parse(&["--limit", "5"]);
+    assert_eq!(options.max_depth, TreeOptions::default().max_depth);
+    assert_eq!(options.max_entries, 5);
+    assert!(paths.is_empty());
+}
@@ -2,6 +2,7 @@ use std::env;
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::Path;
+use std::process;
 use text_processor::{read_text, write_text, TextProcessorError};
 use text_processor::transformations::{
     to_uppercase, to_lowercase, count_statistics, 
@@ -23,7 +24,10 @@ fn main() {
     // Process the command
     match process_command(&args) {
         Ok(_) => println!("Processing completed successfully."),
-        Err(e) => eprintln!("Error: {}", e),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
     }
 }
 
@@ -0,0 +1,110 @@
+//! End-to-end tests that run the text-processor binary
+//!
+//! Each case is one run of the binary: the arguments, what to send on
+//! stdin, and what it should print and exit with. Add a case to `CASES`
+//! to cover a new scenario.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+struct CliCase {
+    name: &'static str,
+    args: &'static [&'static str],
+    stdin: &'static str,
+    exit_code: i32,
+    stdout: &'static [&'static str],
+    stderr: &'static [&'static str],
+}
+
+const CASES: &[CliCase] = &[
+    CliCase {
+        name: "uppercase from stdin",
+        args: &["uppercase", "-"],
+        stdin: "hello, world\n",
+        exit_code: 0,
+        stdout: &["HELLO, WORLD", "Processing completed successfully."],
+        stderr: &[],
+    },
+    CliCase {
+        name: "stats from stdin",
+        args: &["stats", "-"],
+        stdin: "one two\nthree\n",
+        exit_code: 0,
+        stdout: &["Words: 3", "Lines: 2"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "replace from stdin",
+        args: &["replace", "-", "cat", "dog"],
+        stdin: "the cat sat\n",
+        exit_code: 0,
+        stdout: &["the dog sat"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "no arguments prints usage",
+        args: &[],
+        stdin: "",
+        exit_code: 0,
+        stdout: &["Usage: text-processor <command> [options]"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "unknown command",
+        args: &["reverse", "-"],
+        stdin: "",
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["Unknown command: reverse"],
+    },
+    CliCase {
+        name: "missing input path",
+        args: &["uppercase"],
+        stdin: "",
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["Missing input file path"],
+    },
+    CliCase {
+        name: "input file does not exist",
+        args: &["lowercase", "does-not-exist.txt"],
+        stdin: "",
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["I/O error"],
+    },
+];
+
+fn run_case(case: &CliCase) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_text-processor"))
+        .args(case.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(case.stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(case.exit_code),
+        "{}: wrong exit code\nstdout:\n{}\nstderr:\n{}",
+        case.name, stdout, stderr
+    );
+    for expected in case.stdout {
+        assert!(stdout.contains(expected), "{}: stdout is missing {:?}:\n{}", case.name, expected, stdout);
+    }
+    for expected in case.stderr {
+        assert!(stderr.contains(expected), "{}: stderr is missing {:?}:\n{}", case.name, expected, stderr);
+    }
+}
+
+#[test]
+fn test_cli_cases() {
+    for case in CASES {
+        run_case(case);
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use colored::Colorize;
+
+use crate::task::{Task, TaskStatus};
+
+/// Returns true if the task is still open and its due day has passed
+pub fn is_overdue(task: &Task, today: NaiveDate) -> bool {
+    task.status != TaskStatus::Completed && task.due_day().is_some_and(|day| day < today)
+}
+
+/// Returns the short marker shown in the status column
+pub fn status_marker(task: &Task, today: NaiveDate) -> &'static str {
+    if is_overdue(task, today) {
+        return "[!]";
+    }
+    match task.status {
+        TaskStatus::Pending => "[ ]",
+        TaskStatus::InProgress => "[~]",
+        TaskStatus::Completed => "[x]",
+    }
+}
+
+/// Formats tasks as a table with aligned ID, STATUS, TITLE, and DUE columns.
+///
+/// When `color` is true, completed rows are green, in-progress rows yellow,
+/// and overdue rows red. Padding is applied before coloring so the escape
+/// codes don't throw off the alignment.
+pub fn format_task_table(tasks: &[&Task], today: NaiveDate, color: bool) -> String {
+    let rows: Vec<[String; 4]> = tasks.iter()
+        .map(|task| {
+            let mut title = task.title.clone();
+            if let Some((done, total)) = task.progress() {
+                title.push_str(&format!(" ({}/{})", done, total));
+            }
+            let due = task.due_day()
+                .map(|day| day.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "-".to_string());
+            [
+                format!("#{}", task.id),
+                format!("{} {}", status_marker(task, today), task.status),
+                title,
+                due,
+            ]
+        })
+        .collect();
+
+    let header = ["ID", "STATUS", "TITLE", "DUE"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String; 4]| {
+        format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            row[0], row[1], row[2], row[3],
+            w0 = widths[0], w1 = widths[1], w2 = widths[2],
+        )
+    };
+
+    let mut lines = vec![format_row(&header)];
+    for (task, row) in tasks.iter().zip(&rows) {
+        let line = format_row(row);
+        let line = if !color {
+            line
+        } else if task.status == TaskStatus::Completed {
+            line.green().to_string()
+        } else if is_overdue(task, today) {
+            line.red().to_string()
+        } else if task.status == TaskStatus::InProgress {
+            line.yellow().to_string()
+        } else {
+            line
+        };
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
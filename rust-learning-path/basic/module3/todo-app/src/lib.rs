@@ -1,3 +1,4 @@
 // Re-export modules for testing
 pub mod task;
-pub mod task_list;
\ No newline at end of file
+pub mod task_list;
+pub mod display;
@@ -1,12 +1,39 @@
-mod task;
-mod task_list;
+use std::io::{self, IsTerminal, Write};
+use chrono::{Local, NaiveDate, TimeZone};
+use todo_app::display::format_task_table;
+use todo_app::task::{Priority, Task};
+use todo_app::task_list::TaskList;
 
-use std::io::{self, Write};
-use chrono::Local;
-use task::{Task, TaskStatus};
-use task_list::TaskList;
+/// Splits "<id> <value>" command arguments
+fn parse_id_and_value(args: Option<String>) -> Result<(usize, String), String> {
+    let args = args.ok_or("Task ID and value required")?;
+    let mut parts = args.splitn(2, ' ');
+    let id = parts.next()
+        .and_then(|id| id.parse::<usize>().ok())
+        .ok_or("Invalid task ID")?;
+    let value = parts.next()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or("Value required")?;
+    Ok((id, value))
+}
+
+/// Prints a heading followed by the given tasks, or nothing if there are none
+fn print_section(heading: &str, tasks: &[&Task]) {
+    if tasks.is_empty() {
+        return;
+    }
+    println!("{}:", heading);
+    for task in tasks {
+        println!("{}", task);
+    }
+}
 
 fn main() {
+    // Color is on for interactive terminals unless --no-color is passed
+    let use_color = io::stdout().is_terminal() && !std::env::args().any(|arg| arg == "--no-color");
+    colored::control::set_override(use_color);
+
     println!("Welcome to the Rust To-Do List Application!");
     println!("Type 'help' to see available commands.");
 
@@ -39,14 +66,12 @@ fn main() {
                 }
             },
             "list" => {
-                let tasks = task_list.list_all();
+                let tasks: Vec<&Task> = task_list.list_all().iter().collect();
                 if tasks.is_empty() {
                     println!("No tasks found.");
                 } else {
                     println!("Tasks:");
-                    for task in tasks {
-                        println!("{}", task);
-                    }
+                    println!("{}", format_task_table(&tasks, Local::now().date_naive(), use_color));
                 }
             },
             "pending" => {
@@ -55,9 +80,7 @@ fn main() {
                     println!("No pending tasks found.");
                 } else {
                     println!("Pending Tasks:");
-                    for task in tasks {
-                        println!("{}", task);
-                    }
+                    println!("{}", format_task_table(&tasks, Local::now().date_naive(), use_color));
                 }
             },
             "inprogress" => {
@@ -66,9 +89,7 @@ fn main() {
                     println!("No in-progress tasks found.");
                 } else {
                     println!("In-Progress Tasks:");
-                    for task in tasks {
-                        println!("{}", task);
-                    }
+                    println!("{}", format_task_table(&tasks, Local::now().date_naive(), use_color));
                 }
             },
             "completed" => {
@@ -77,16 +98,13 @@ fn main() {
                     println!("No completed tasks found.");
                 } else {
                     println!("Completed Tasks:");
-                    for task in tasks {
-                        println!("{}", task);
-                    }
+                    println!("{}", format_task_table(&tasks, Local::now().date_naive(), use_color));
                 }
             },
             "start" => {
                 if let Some(id_str) = args {
                     if let Ok(id) = id_str.parse::<usize>() {
-                        if let Some(task) = task_list.get_task_mut(id) {
-                            task.mark_in_progress();
+                        if task_list.start_task(id) {
                             println!("Task #{} marked as in progress", id);
                         } else {
                             println!("Error: Task not found");
@@ -99,13 +117,14 @@ fn main() {
                 }
             },
             "complete" => {
-                if let Some(id_str) = args {
+                if let Some(args) = args {
+                    let mut parts = args.split_whitespace();
+                    let id_str = parts.next().unwrap_or("");
+                    let force = parts.any(|flag| flag == "--force" || flag == "-f");
                     if let Ok(id) = id_str.parse::<usize>() {
-                        if let Some(task) = task_list.get_task_mut(id) {
-                            task.mark_completed();
-                            println!("Task #{} marked as completed", id);
-                        } else {
-                            println!("Error: Task not found");
+                        match task_list.complete_task(id, force) {
+                            Ok(()) => println!("Task #{} marked as completed", id),
+                            Err(e) => println!("Error: {}", e),
                         }
                     } else {
                         println!("Error: Invalid task ID");
@@ -129,6 +148,109 @@ fn main() {
                     println!("Error: Task ID required");
                 }
             },
+            "due" => {
+                match parse_id_and_value(args) {
+                    Ok((id, date_str)) => {
+                        match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
+                            Ok(date) => {
+                                let due = date.and_hms_opt(23, 59, 0)
+                                    .and_then(|end_of_day| Local.from_local_datetime(&end_of_day).earliest());
+                                if task_list.set_due_date(id, due) {
+                                    println!("Task #{} is due {}", id, date);
+                                } else {
+                                    println!("Error: Task not found");
+                                }
+                            },
+                            Err(_) => println!("Error: Invalid date, expected YYYY-MM-DD"),
+                        }
+                    },
+                    Err(e) => println!("Error: {}", e),
+                }
+            },
+            "priority" => {
+                match parse_id_and_value(args) {
+                    Ok((id, priority_str)) => {
+                        match priority_str.parse::<Priority>() {
+                            Ok(priority) => {
+                                if task_list.set_priority(id, priority) {
+                                    println!("Task #{} priority set to {}", id, priority);
+                                } else {
+                                    println!("Error: Task not found");
+                                }
+                            },
+                            Err(e) => println!("Error: {}", e),
+                        }
+                    },
+                    Err(e) => println!("Error: {}", e),
+                }
+            },
+            "undo" => {
+                if task_list.undo() {
+                    println!("Undid last change");
+                } else {
+                    println!("Nothing to undo");
+                }
+            },
+            "redo" => {
+                if task_list.redo() {
+                    println!("Redid last change");
+                } else {
+                    println!("Nothing to redo");
+                }
+            },
+            "subtask" => {
+                let args = args.unwrap_or_default();
+                let (action, rest) = args.split_once(' ').unwrap_or((args.as_str(), ""));
+                match (action, parse_id_and_value(Some(rest.to_string()))) {
+                    ("add", Ok((id, text))) => {
+                        match task_list.add_subtask(id, text) {
+                            Ok(index) => println!("Added subtask {} to task #{}", index, id),
+                            Err(e) => println!("Error: {}", e),
+                        }
+                    },
+                    ("done", Ok((id, index_str))) => {
+                        match index_str.parse::<usize>() {
+                            Ok(index) => match task_list.complete_subtask(id, index) {
+                                Ok(()) => println!("Subtask {} of task #{} marked as done", index, id),
+                                Err(e) => println!("Error: {}", e),
+                            },
+                            Err(_) => println!("Error: Invalid subtask index"),
+                        }
+                    },
+                    ("add", Err(e)) | ("done", Err(e)) => println!("Error: {}", e),
+                    _ => println!("Error: Usage: subtask add <id> <text> | subtask done <id> <index>"),
+                }
+            },
+            "today" => {
+                let report = task_list.due_report(Local::now().date_naive());
+                if report.overdue.is_empty() && report.due_today.is_empty() {
+                    println!("Nothing due today.");
+                } else {
+                    print_section("Overdue", &report.overdue);
+                    print_section("Due Today", &report.due_today);
+                }
+            },
+            "summary" => {
+                if task_list.count() == 0 {
+                    println!("No tasks found.");
+                } else {
+                    let summary = task_list.summary(Local::now().date_naive());
+                    println!("Status:   {} pending, {} in progress, {} completed",
+                        summary.pending, summary.in_progress, summary.completed);
+                    println!("Priority: {} high, {} medium, {} low",
+                        summary.high_priority, summary.medium_priority, summary.low_priority);
+                    if summary.next_due.is_empty() {
+                        println!("No upcoming due dates.");
+                    } else {
+                        println!("Next due:");
+                        for task in summary.next_due {
+                            if let Some(day) = task.due_day() {
+                                println!("  {} - Task #{}: {}", day, task.id, task.title);
+                            }
+                        }
+                    }
+                }
+            },
             "help" => {
                 println!("Available commands:");
                 println!("  add <title>       - Add a new task");
@@ -137,8 +259,16 @@ fn main() {
                 println!("  inprogress        - List in-progress tasks");
                 println!("  completed         - List completed tasks");
                 println!("  start <id>        - Mark a task as in progress");
-                println!("  complete <id>     - Mark a task as completed");
+                println!("  complete <id> [--force] - Mark a task as completed");
+                println!("  subtask add <id> <text>    - Add a checklist item to a task");
+                println!("  subtask done <id> <index>  - Mark a checklist item as done");
                 println!("  remove <id>       - Remove a task");
+                println!("  undo              - Undo the last change to the list or a task");
+                println!("  redo              - Redo the last undone change");
+                println!("  due <id> <date>   - Set a due date (YYYY-MM-DD)");
+                println!("  priority <id> <p> - Set priority (low, medium, high)");
+                println!("  today             - Show overdue tasks and tasks due today");
+                println!("  summary           - Show counts and the next due dates");
                 println!("  help              - Show this help message");
                 println!("  exit              - Exit the application");
             },
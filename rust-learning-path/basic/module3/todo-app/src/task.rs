@@ -1,5 +1,6 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use std::fmt;
+use std::str::FromStr;
 
 /// Represents the status of a task
 #[derive(Debug, Clone, PartialEq)]
@@ -19,6 +20,45 @@ impl fmt::Display for TaskStatus {
     }
 }
 
+/// Represents how urgent a task is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "Low"),
+            Priority::Medium => write!(f, "Medium"),
+            Priority::High => write!(f, "High"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            _ => Err(format!("Unknown priority '{}', expected low, medium, or high", s)),
+        }
+    }
+}
+
+/// A checklist item inside a task
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subtask {
+    pub text: String,
+    pub completed: bool,
+}
+
 /// Represents a single task in the to-do list
 #[derive(Debug, Clone)]
 pub struct Task {
@@ -26,8 +66,10 @@ pub struct Task {
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    pub priority: Priority,
     pub due_date: Option<DateTime<Local>>,
     pub created_at: DateTime<Local>,
+    pub subtasks: Vec<Subtask>,
 }
 
 impl Task {
@@ -38,8 +80,10 @@ impl Task {
             title,
             description,
             status: TaskStatus::Pending,
+            priority: Priority::default(),
             due_date,
             created_at: Local::now(),
+            subtasks: Vec::new(),
         }
     }
 
@@ -63,6 +107,57 @@ impl Task {
         self.due_date = due_date;
     }
 
+    /// Updates the priority of the task
+    pub fn update_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
+    /// Returns the calendar day the task is due, if it has a due date
+    pub fn due_day(&self) -> Option<NaiveDate> {
+        self.due_date.map(|due| due.date_naive())
+    }
+
+    /// Adds a checklist item and returns its 1-based index
+    pub fn add_subtask(&mut self, text: String) -> usize {
+        self.subtasks.push(Subtask { text, completed: false });
+        self.subtasks.len()
+    }
+
+    /// Marks the checklist item at the given 1-based index as done
+    pub fn complete_subtask(&mut self, index: usize) -> Result<(), String> {
+        match index.checked_sub(1).and_then(|i| self.subtasks.get_mut(i)) {
+            Some(subtask) => {
+                subtask.completed = true;
+                Ok(())
+            }
+            None => Err(format!("Task #{} has no subtask {}", self.id, index)),
+        }
+    }
+
+    /// Returns (done, total) for the checklist, or None if there are no subtasks
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        if self.subtasks.is_empty() {
+            return None;
+        }
+        let done = self.subtasks.iter().filter(|subtask| subtask.completed).count();
+        Some((done, self.subtasks.len()))
+    }
+
+    /// Completes the task, refusing while subtasks are open unless forced
+    pub fn complete(&mut self, force: bool) -> Result<(), String> {
+        if let Some((done, total)) = self.progress() {
+            if done < total && !force {
+                return Err(format!(
+                    "Task #{} has {} unfinished subtask(s); use --force to complete anyway",
+                    self.id,
+                    total - done
+                ));
+            }
+        }
+        self.mark_completed();
+        Ok(())
+    }
+
     /// Marks the task as completed
     pub fn mark_completed(&mut self) {
         self.status = TaskStatus::Completed;
@@ -81,7 +176,11 @@ impl Task {
 
 impl fmt::Display for Task {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Task #{}: {} [{}]", self.id, self.title, self.status)?;
+        write!(f, "Task #{}: {} [{}] ({} priority)", self.id, self.title, self.status, self.priority)?;
+
+        if let Some((done, total)) = self.progress() {
+            write!(f, " {}/{}", done, total)?;
+        }
         
         if let Some(desc) = &self.description {
             write!(f, "\n  Description: {}", desc)?;
@@ -91,6 +190,13 @@ impl fmt::Display for Task {
             write!(f, "\n  Due: {}", due.format("%Y-%m-%d %H:%M"))?;
         }
         
-        write!(f, "\n  Created: {}", self.created_at.format("%Y-%m-%d %H:%M"))
+        write!(f, "\n  Created: {}", self.created_at.format("%Y-%m-%d %H:%M"))?;
+
+        for (i, subtask) in self.subtasks.iter().enumerate() {
+            let mark = if subtask.completed { "x" } else { " " };
+            write!(f, "\n  [{}] {}. {}", mark, i + 1, subtask.text)?;
+        }
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,17 +1,66 @@
-use crate::task::{Task, TaskStatus};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::VecDeque;
+
+use crate::task::{Priority, Subtask, Task, TaskStatus};
+
+/// Default number of operations that can be undone
+pub const DEFAULT_HISTORY_DEPTH: usize = 50;
+
+/// A mutating operation, recorded so it can be undone and redone
+#[derive(Debug, Clone)]
+enum Change {
+    Added(Task),
+    Removed { task: Task, position: usize },
+    StatusChanged { id: usize, before: TaskStatus, after: TaskStatus },
+    PriorityChanged { id: usize, before: Priority, after: Priority },
+    DueDateChanged { id: usize, before: Option<DateTime<Local>>, after: Option<DateTime<Local>> },
+    SubtaskAdded { id: usize, subtask: Subtask },
+    SubtaskCompleted { id: usize, index: usize },
+}
+
+/// Open tasks with a due date, split relative to a given day
+#[derive(Debug)]
+pub struct DueReport<'a> {
+    pub overdue: Vec<&'a Task>,
+    pub due_today: Vec<&'a Task>,
+    pub upcoming: Vec<&'a Task>,
+}
+
+/// Counts by status and priority, plus the next few due tasks
+#[derive(Debug)]
+pub struct TaskSummary<'a> {
+    pub pending: usize,
+    pub in_progress: usize,
+    pub completed: usize,
+    pub low_priority: usize,
+    pub medium_priority: usize,
+    pub high_priority: usize,
+    pub next_due: Vec<&'a Task>,
+}
 
 /// Manages a collection of tasks
 pub struct TaskList {
     tasks: Vec<Task>,
     next_id: usize,
+    undo_stack: VecDeque<Change>,
+    redo_stack: Vec<Change>,
+    history_depth: usize,
 }
 
 impl TaskList {
     /// Creates a new, empty task list
     pub fn new() -> Self {
+        Self::with_history_depth(DEFAULT_HISTORY_DEPTH)
+    }
+
+    /// Creates a new, empty task list that remembers at most `depth` operations for undo
+    pub fn with_history_depth(depth: usize) -> Self {
         TaskList {
             tasks: Vec::new(),
             next_id: 1,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            history_depth: depth,
         }
     }
 
@@ -21,6 +70,7 @@ impl TaskList {
         self.next_id += 1;
         
         let task = Task::new(id, title, description, due_date);
+        self.record(Change::Added(task.clone()));
         self.tasks.push(task);
         
         // Return a reference to the newly added task
@@ -42,13 +92,173 @@ impl TaskList {
         let position = self.tasks.iter().position(|task| task.id == id);
         
         if let Some(pos) = position {
-            self.tasks.remove(pos);
+            let task = self.tasks.remove(pos);
+            self.record(Change::Removed { task, position: pos });
             true
         } else {
             false
         }
     }
 
+    /// Marks a task as in progress, returning false if it doesn't exist
+    pub fn start_task(&mut self, id: usize) -> bool {
+        let Some(task) = self.get_task_mut(id) else {
+            return false;
+        };
+        let before = task.status.clone();
+        task.mark_in_progress();
+        self.record_status_change(id, before, TaskStatus::InProgress);
+        true
+    }
+
+    /// Completes a task, subject to the subtask guard unless forced
+    pub fn complete_task(&mut self, id: usize, force: bool) -> Result<(), String> {
+        let task = self.get_task_mut(id).ok_or(format!("Task #{} not found", id))?;
+        let before = task.status.clone();
+        task.complete(force)?;
+        self.record_status_change(id, before, TaskStatus::Completed);
+        Ok(())
+    }
+
+    /// Sets a task's priority, returning false if it doesn't exist
+    pub fn set_priority(&mut self, id: usize, priority: Priority) -> bool {
+        let Some(task) = self.get_task_mut(id) else {
+            return false;
+        };
+        let before = task.priority;
+        task.update_priority(priority);
+        if before != priority {
+            self.record(Change::PriorityChanged { id, before, after: priority });
+        }
+        true
+    }
+
+    /// Sets or clears a task's due date, returning false if it doesn't exist
+    pub fn set_due_date(&mut self, id: usize, due_date: Option<DateTime<Local>>) -> bool {
+        let Some(task) = self.get_task_mut(id) else {
+            return false;
+        };
+        let before = task.due_date;
+        task.update_due_date(due_date);
+        if before != due_date {
+            self.record(Change::DueDateChanged { id, before, after: due_date });
+        }
+        true
+    }
+
+    /// Adds a checklist item to a task, returning its 1-based index
+    pub fn add_subtask(&mut self, id: usize, text: String) -> Result<usize, String> {
+        let task = self.get_task_mut(id).ok_or(format!("Task #{} not found", id))?;
+        let index = task.add_subtask(text);
+        let subtask = task.subtasks[index - 1].clone();
+        self.record(Change::SubtaskAdded { id, subtask });
+        Ok(index)
+    }
+
+    /// Marks a task's checklist item at the given 1-based index as done
+    pub fn complete_subtask(&mut self, id: usize, index: usize) -> Result<(), String> {
+        let task = self.get_task_mut(id).ok_or(format!("Task #{} not found", id))?;
+        let was_done = index.checked_sub(1)
+            .and_then(|i| task.subtasks.get(i))
+            .is_some_and(|subtask| subtask.completed);
+        task.complete_subtask(index)?;
+        if !was_done {
+            self.record(Change::SubtaskCompleted { id, index });
+        }
+        Ok(())
+    }
+
+    /// Reverts the most recent operation. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop_back() {
+            Some(change) => {
+                self.revert(&change);
+                self.redo_stack.push(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reapplies the most recently undone operation. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(change) => {
+                self.apply(&change);
+                self.undo_stack.push_back(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of operations that can currently be undone
+    pub fn undo_depth(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Returns the number of operations that can currently be redone
+    pub fn redo_depth(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    fn record_status_change(&mut self, id: usize, before: TaskStatus, after: TaskStatus) {
+        if before != after {
+            self.record(Change::StatusChanged { id, before, after });
+        }
+    }
+
+    /// Pushes a new operation onto the undo stack, dropping the oldest past the depth limit
+    fn record(&mut self, change: Change) {
+        self.redo_stack.clear();
+        if self.history_depth == 0 {
+            return;
+        }
+        if self.undo_stack.len() == self.history_depth {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(change);
+    }
+
+    fn apply(&mut self, change: &Change) {
+        match change {
+            Change::Added(task) => self.tasks.push(task.clone()),
+            Change::Removed { task, .. } => self.tasks.retain(|t| t.id != task.id),
+            Change::StatusChanged { id, after, .. } => self.set_status(*id, after.clone()),
+            Change::PriorityChanged { id, after, .. } => self.edit(*id, |task| task.update_priority(*after)),
+            Change::DueDateChanged { id, after, .. } => self.edit(*id, |task| task.update_due_date(*after)),
+            Change::SubtaskAdded { id, subtask } => self.edit(*id, |task| task.subtasks.push(subtask.clone())),
+            Change::SubtaskCompleted { id, index } => self.edit(*id, |task| task.subtasks[index - 1].completed = true),
+        }
+    }
+
+    fn revert(&mut self, change: &Change) {
+        match change {
+            Change::Added(task) => self.tasks.retain(|t| t.id != task.id),
+            Change::Removed { task, position } => {
+                let position = (*position).min(self.tasks.len());
+                self.tasks.insert(position, task.clone());
+            }
+            Change::StatusChanged { id, before, .. } => self.set_status(*id, before.clone()),
+            Change::PriorityChanged { id, before, .. } => self.edit(*id, |task| task.update_priority(*before)),
+            Change::DueDateChanged { id, before, .. } => self.edit(*id, |task| task.update_due_date(*before)),
+            Change::SubtaskAdded { id, .. } => self.edit(*id, |task| {
+                task.subtasks.pop();
+            }),
+            Change::SubtaskCompleted { id, index } => self.edit(*id, |task| task.subtasks[index - 1].completed = false),
+        }
+    }
+
+    fn set_status(&mut self, id: usize, status: TaskStatus) {
+        self.edit(id, |task| task.update_status(status));
+    }
+
+    fn edit(&mut self, id: usize, change: impl FnOnce(&mut Task)) {
+        if let Some(task) = self.get_task_mut(id) {
+            change(task);
+        }
+    }
+
     /// Returns all tasks
     pub fn list_all(&self) -> &[Task] {
         &self.tasks
@@ -80,4 +290,65 @@ impl TaskList {
     pub fn count(&self) -> usize {
         self.tasks.len()
     }
+
+    /// Returns open tasks with a due date, sorted by due date
+    fn open_tasks_by_due_date(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.iter()
+            .filter(|task| task.status != TaskStatus::Completed && task.due_date.is_some())
+            .collect();
+        tasks.sort_by_key(|task| task.due_date);
+        tasks
+    }
+
+    /// Splits open tasks with due dates into overdue, due today, and upcoming
+    pub fn due_report(&self, today: NaiveDate) -> DueReport<'_> {
+        let mut report = DueReport {
+            overdue: Vec::new(),
+            due_today: Vec::new(),
+            upcoming: Vec::new(),
+        };
+
+        for task in self.open_tasks_by_due_date() {
+            match task.due_day() {
+                Some(day) if day < today => report.overdue.push(task),
+                Some(day) if day == today => report.due_today.push(task),
+                Some(_) => report.upcoming.push(task),
+                None => {}
+            }
+        }
+
+        report
+    }
+
+    /// Summarizes the list as of the given day
+    pub fn summary(&self, today: NaiveDate) -> TaskSummary<'_> {
+        let count_status = |status: TaskStatus| {
+            self.tasks.iter().filter(|task| task.status == status).count()
+        };
+        let count_priority = |priority: Priority| {
+            self.tasks.iter().filter(|task| task.priority == priority).count()
+        };
+
+        let next_due = self.open_tasks_by_due_date()
+            .into_iter()
+            .filter(|task| task.due_day().is_some_and(|day| day >= today))
+            .take(3)
+            .collect();
+
+        TaskSummary {
+            pending: count_status(TaskStatus::Pending),
+            in_progress: count_status(TaskStatus::InProgress),
+            completed: count_status(TaskStatus::Completed),
+            low_priority: count_priority(Priority::Low),
+            medium_priority: count_priority(Priority::Medium),
+            high_priority: count_priority(Priority::High),
+            next_due,
+        }
+    }
+}
+
+impl Default for TaskList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
-use todo_app::task::{Task, TaskStatus};
+use chrono::{Local, NaiveDate, TimeZone};
+use todo_app::display::{format_task_table, status_marker};
+use todo_app::task::{Priority, Task, TaskStatus};
 use todo_app::task_list::TaskList;
 
 // Note: For these tests to work, we need to make the modules public in main.rs
@@ -86,4 +88,317 @@ fn test_filter_by_status() {
     assert_eq!(task_list.list_pending().len(), 1);
     assert_eq!(task_list.list_in_progress().len(), 1);
     assert_eq!(task_list.list_completed().len(), 1);
-}
\ No newline at end of file
+}
+
+fn due_on(year: i32, month: u32, day: u32) -> Option<chrono::DateTime<Local>> {
+    Local.with_ymd_and_hms(year, month, day, 12, 0, 0).single()
+}
+
+fn ids(tasks: &[&Task]) -> Vec<usize> {
+    tasks.iter().map(|task| task.id).collect()
+}
+
+#[test]
+fn test_due_report_partitions_by_day() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let mut task_list = TaskList::new();
+    task_list.add_task("Overdue".to_string(), None, due_on(2024, 3, 10));
+    task_list.add_task("Due today".to_string(), None, due_on(2024, 3, 15));
+    task_list.add_task("Upcoming".to_string(), None, due_on(2024, 3, 20));
+    task_list.add_task("No due date".to_string(), None, None);
+    task_list.add_task("Done and overdue".to_string(), None, due_on(2024, 3, 1));
+    task_list.add_task("Very overdue".to_string(), None, due_on(2024, 2, 1));
+    task_list.get_task_mut(5).unwrap().mark_completed();
+
+    let report = task_list.due_report(today);
+
+    // Completed tasks and tasks without a due date are left out
+    assert_eq!(ids(&report.overdue), vec![6, 1]);
+    assert_eq!(ids(&report.due_today), vec![2]);
+    assert_eq!(ids(&report.upcoming), vec![3]);
+}
+
+#[test]
+fn test_summary_counts_and_next_due() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let mut task_list = TaskList::new();
+    task_list.add_task("A".to_string(), None, due_on(2024, 3, 25));
+    task_list.add_task("B".to_string(), None, due_on(2024, 3, 15));
+    task_list.add_task("C".to_string(), None, due_on(2024, 3, 18));
+    task_list.add_task("D".to_string(), None, due_on(2024, 3, 16));
+    task_list.add_task("E".to_string(), None, due_on(2024, 3, 1));
+    task_list.add_task("F".to_string(), None, None);
+
+    task_list.get_task_mut(1).unwrap().update_priority(Priority::High);
+    task_list.get_task_mut(2).unwrap().update_priority(Priority::High);
+    task_list.get_task_mut(3).unwrap().update_priority(Priority::Low);
+    task_list.get_task_mut(3).unwrap().mark_in_progress();
+    task_list.get_task_mut(4).unwrap().mark_completed();
+
+    let summary = task_list.summary(today);
+
+    assert_eq!(summary.pending, 4);
+    assert_eq!(summary.in_progress, 1);
+    assert_eq!(summary.completed, 1);
+    assert_eq!(summary.high_priority, 2);
+    assert_eq!(summary.medium_priority, 3);
+    assert_eq!(summary.low_priority, 1);
+
+    // Completed and overdue tasks don't count as upcoming
+    assert_eq!(ids(&summary.next_due), vec![2, 3, 1]);
+}
+
+#[test]
+fn test_summary_without_tasks_or_due_dates() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let mut task_list = TaskList::new();
+
+    let summary = task_list.summary(today);
+    assert_eq!(summary.pending + summary.in_progress + summary.completed, 0);
+    assert!(summary.next_due.is_empty());
+
+    task_list.add_task("No due date".to_string(), None, None);
+    let report = task_list.due_report(today);
+    assert!(report.overdue.is_empty() && report.due_today.is_empty() && report.upcoming.is_empty());
+    assert!(task_list.summary(today).next_due.is_empty());
+}
+
+#[test]
+fn test_priority_parsing() {
+    assert_eq!("HIGH".parse::<Priority>(), Ok(Priority::High));
+    assert_eq!("low".parse::<Priority>(), Ok(Priority::Low));
+    assert!("urgent".parse::<Priority>().is_err());
+}
+
+#[test]
+fn test_subtask_progress() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("Plan trip".to_string(), None, None);
+    let task = task_list.get_task_mut(1).unwrap();
+
+    assert_eq!(task.progress(), None);
+
+    assert_eq!(task.add_subtask("Book flights".to_string()), 1);
+    assert_eq!(task.add_subtask("Book hotel".to_string()), 2);
+    assert_eq!(task.add_subtask("Pack".to_string()), 3);
+    assert_eq!(task.progress(), Some((0, 3)));
+
+    task.complete_subtask(1).unwrap();
+    task.complete_subtask(3).unwrap();
+    assert_eq!(task.progress(), Some((2, 3)));
+    assert!(task.to_string().contains("2/3"));
+
+    assert!(task.complete_subtask(0).is_err());
+    assert!(task.complete_subtask(4).is_err());
+}
+
+#[test]
+fn test_parent_completion_guard() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("Release".to_string(), None, None);
+    let task = task_list.get_task_mut(1).unwrap();
+    task.add_subtask("Write changelog".to_string());
+    task.add_subtask("Tag version".to_string());
+    task.complete_subtask(1).unwrap();
+
+    assert!(task.complete(false).is_err());
+    assert_eq!(task.status, TaskStatus::Pending);
+
+    task.complete_subtask(2).unwrap();
+    assert!(task.complete(false).is_ok());
+    assert_eq!(task.status, TaskStatus::Completed);
+}
+
+#[test]
+fn test_parent_completion_forced() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("Release".to_string(), None, None);
+    let task = task_list.get_task_mut(1).unwrap();
+    task.add_subtask("Write changelog".to_string());
+
+    assert!(task.complete(true).is_ok());
+    assert_eq!(task.status, TaskStatus::Completed);
+    assert_eq!(task.progress(), Some((0, 1)));
+}
+
+fn titles(task_list: &TaskList) -> Vec<&str> {
+    task_list.list_all().iter().map(|task| task.title.as_str()).collect()
+}
+
+#[test]
+fn test_undo_redo_sequence() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("A".to_string(), None, None);
+    task_list.add_task("B".to_string(), None, None);
+    task_list.add_task("C".to_string(), None, None);
+    assert!(task_list.start_task(1));
+    task_list.complete_task(2, false).unwrap();
+    assert!(task_list.remove_task(1));
+    assert_eq!(titles(&task_list), vec!["B", "C"]);
+
+    // Undo the removal: A comes back in its original position, still in progress
+    assert!(task_list.undo());
+    assert_eq!(titles(&task_list), vec!["A", "B", "C"]);
+    assert_eq!(task_list.get_task(1).unwrap().status, TaskStatus::InProgress);
+
+    // Undo the completion and the start
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(2).unwrap().status, TaskStatus::Pending);
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(1).unwrap().status, TaskStatus::Pending);
+
+    // Undo the last add
+    assert!(task_list.undo());
+    assert_eq!(titles(&task_list), vec!["A", "B"]);
+
+    // Redo replays in order
+    assert!(task_list.redo());
+    assert_eq!(titles(&task_list), vec!["A", "B", "C"]);
+    assert!(task_list.redo());
+    assert_eq!(task_list.get_task(1).unwrap().status, TaskStatus::InProgress);
+    assert_eq!(task_list.redo_depth(), 2);
+
+    // A new change discards the redo history
+    task_list.add_task("D".to_string(), None, None);
+    assert_eq!(task_list.redo_depth(), 0);
+    assert!(!task_list.redo());
+    assert_eq!(titles(&task_list), vec!["A", "B", "C", "D"]);
+    assert_eq!(task_list.get_task(2).unwrap().status, TaskStatus::Pending);
+}
+
+#[test]
+fn test_undo_ignores_non_mutating_commands() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("A".to_string(), None, None);
+
+    task_list.list_all();
+    task_list.list_pending();
+    task_list.get_task(1);
+    assert!(!task_list.remove_task(999));
+    assert!(!task_list.start_task(999));
+    assert!(task_list.complete_task(999, false).is_err());
+
+    assert_eq!(task_list.undo_depth(), 1);
+    assert!(task_list.undo());
+    assert!(!task_list.undo());
+    assert_eq!(task_list.count(), 0);
+}
+
+#[test]
+fn test_undo_redo_task_edits() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("A".to_string(), None, due_on(2024, 3, 10));
+    assert!(task_list.set_priority(1, Priority::High));
+    assert!(task_list.set_due_date(1, due_on(2024, 3, 20)));
+    assert_eq!(task_list.add_subtask(1, "Draft".to_string()), Ok(1));
+    task_list.complete_subtask(1, 1).unwrap();
+
+    // Each edit is undone separately, newest first
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(1).unwrap().progress(), Some((0, 1)));
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(1).unwrap().progress(), None);
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(1).unwrap().due_date, due_on(2024, 3, 10));
+    assert!(task_list.undo());
+    assert_eq!(task_list.get_task(1).unwrap().priority, Priority::Medium);
+
+    // Redo replays them in order
+    while task_list.redo() {}
+    let task = task_list.get_task(1).unwrap();
+    assert_eq!(task.priority, Priority::High);
+    assert_eq!(task.due_date, due_on(2024, 3, 20));
+    assert_eq!(task.progress(), Some((1, 1)));
+}
+
+#[test]
+fn test_edits_that_change_nothing_are_not_recorded() {
+    let mut task_list = TaskList::new();
+    task_list.add_task("A".to_string(), None, None);
+    task_list.add_subtask(1, "Draft".to_string()).unwrap();
+    task_list.complete_subtask(1, 1).unwrap();
+    assert_eq!(task_list.undo_depth(), 3);
+
+    assert!(task_list.set_priority(1, Priority::Medium));
+    assert!(task_list.set_due_date(1, None));
+    task_list.complete_subtask(1, 1).unwrap();
+    assert!(!task_list.set_priority(999, Priority::High));
+    assert!(task_list.add_subtask(999, "Draft".to_string()).is_err());
+    assert!(task_list.complete_subtask(1, 2).is_err());
+    assert_eq!(task_list.undo_depth(), 3);
+}
+
+#[test]
+fn test_undo_history_depth_is_bounded() {
+    let mut task_list = TaskList::with_history_depth(2);
+    task_list.add_task("A".to_string(), None, None);
+    task_list.add_task("B".to_string(), None, None);
+    task_list.add_task("C".to_string(), None, None);
+
+    assert_eq!(task_list.undo_depth(), 2);
+    assert!(task_list.undo());
+    assert!(task_list.undo());
+    assert!(!task_list.undo());
+    assert_eq!(titles(&task_list), vec!["A"]);
+}
+
+#[test]
+fn test_task_table_alignment_and_markers() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let mut task_list = TaskList::new();
+    task_list.add_task("Short".to_string(), None, due_on(2024, 3, 20));
+    task_list.add_task("A much longer title".to_string(), None, due_on(2024, 3, 1));
+    task_list.add_task("Working".to_string(), None, None);
+    task_list.add_task("Done".to_string(), None, due_on(2024, 3, 1));
+    task_list.start_task(3);
+    task_list.complete_task(4, false).unwrap();
+
+    let tasks: Vec<&Task> = task_list.list_all().iter().collect();
+    let table = format_task_table(&tasks, today, false);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 5);
+    assert!(!table.contains('\u{1b}'));
+
+    // Every column starts at the same offset on every line
+    for column in ["STATUS", "TITLE", "DUE"] {
+        let offset = lines[0].find(column).unwrap();
+        for line in &lines[1..] {
+            assert_eq!(&line[offset - 2..offset], "  ", "misaligned {} in {:?}", column, line);
+            assert_ne!(line.as_bytes()[offset], b' ', "misaligned {} in {:?}", column, line);
+        }
+    }
+
+    assert!(lines[1].starts_with("#1  [ ] Pending"));
+    assert!(lines[2].contains("[!] Pending"));
+    assert!(lines[3].contains("[~] In Progress"));
+    assert!(lines[4].contains("[x] Completed"));
+    assert!(lines[1].ends_with("2024-03-20"));
+    assert!(lines[3].ends_with('-'));
+
+    assert_eq!(status_marker(tasks[1], today), "[!]");
+    // A completed task is never overdue
+    assert_eq!(status_marker(tasks[3], today), "[x]");
+}
+
+#[test]
+fn test_task_table_color_codes_when_forced() {
+    colored::control::set_override(true);
+
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let mut task_list = TaskList::new();
+    task_list.add_task("Done".to_string(), None, None);
+    task_list.add_task("Late".to_string(), None, due_on(2024, 3, 1));
+    task_list.add_task("Busy".to_string(), None, None);
+    task_list.complete_task(1, false).unwrap();
+    task_list.start_task(3);
+
+    let tasks: Vec<&Task> = task_list.list_all().iter().collect();
+    let table = format_task_table(&tasks, today, true);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert!(!lines[0].contains('\u{1b}'));
+    assert!(lines[1].starts_with("\u{1b}[32m"), "completed should be green: {:?}", lines[1]);
+    assert!(lines[2].starts_with("\u{1b}[31m"), "overdue should be red: {:?}", lines[2]);
+    assert!(lines[3].starts_with("\u{1b}[33m"), "in progress should be yellow: {:?}", lines[3]);
+}
@@ -1,11 +1,50 @@
 use tokio::net::TcpStream;
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, AsyncBufReadExt};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, BufWriter, AsyncBufReadExt};
 use tracing::{debug, warn};
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::handler::HttpHandler;
+/// How long to keep discarding input after rejecting a request
+const LINGER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How much input to discard at most after rejecting a request
+const LINGER_MAX_BYTES: usize = 64 * 1024;
+
+use crate::handler::{HttpHandler, RequestLimits, ScanStatus};
+use crate::middleware::Middleware;
+use crate::server::ServerMetrics;
 use crate::error::{ServerError, ServerResult};
 
+/// Per-connection I/O settings
+/// 
+/// Small buffers and `nodelay` favor latency; larger buffers with Nagle's
+/// algorithm left on favor throughput.
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    /// Capacity of the read buffer
+    pub read_buffer_size: usize,
+    /// Capacity of the write buffer. Responses smaller than this go out in
+    /// a single write; larger ones are written in chunks of this size
+    pub write_buffer_size: usize,
+    /// Set `TCP_NODELAY`, disabling Nagle's algorithm so small responses are
+    /// sent immediately instead of being coalesced with later writes
+    pub nodelay: bool,
+    /// Limits on the request line and headers; requests exceeding them are
+    /// rejected without reading the rest
+    pub request_limits: RequestLimits,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            read_buffer_size: 4096,
+            write_buffer_size: 8192,
+            nodelay: false,
+            request_limits: RequestLimits::default(),
+        }
+    }
+}
+
 /// Handles individual TCP connections
 /// 
 /// This demonstrates async I/O patterns:
@@ -15,12 +54,62 @@ use crate::error::{ServerError, ServerResult};
 /// - Error propagation in async contexts
 pub struct ConnectionHandler {
     id: u64,
-    stream: TcpStream,
+    stream: BufReader<BufWriter<TcpStream>>,
+    config: ConnectionConfig,
+    metrics: Arc<ServerMetrics>,
+    middleware: Vec<Arc<dyn Middleware>>,
 }
 
 impl ConnectionHandler {
     pub fn new(id: u64, stream: TcpStream) -> Self {
-        Self { id, stream }
+        let config = ConnectionConfig::default();
+        Self {
+            id,
+            stream: buffered(stream, &config),
+            config,
+            metrics: Arc::new(ServerMetrics::new()),
+            middleware: Vec::new(),
+        }
+    }
+    
+    /// Create a handler with the given buffer sizes and socket options
+    pub fn with_config(id: u64, stream: TcpStream, config: &ConnectionConfig) -> ServerResult<Self> {
+        if config.read_buffer_size == 0 || config.write_buffer_size == 0 {
+            return Err(ServerError::Configuration(
+                "Connection buffer sizes must be greater than 0".to_string()
+            ));
+        }
+        
+        stream.set_nodelay(config.nodelay)
+            .map_err(|e| ServerError::from_io_error(e, "Failed to set TCP_NODELAY"))?;
+        
+        Ok(Self {
+            id,
+            stream: buffered(stream, config),
+            config: config.clone(),
+            metrics: Arc::new(ServerMetrics::new()),
+            middleware: Vec::new(),
+        })
+    }
+    
+    /// Record traffic in, and serve `/stats` from, the given counters
+    /// 
+    /// Without this, the handler counts into metrics of its own.
+    pub fn with_metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    
+    /// Run `middleware` on every request before routing it
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+    
+    /// Check whether `TCP_NODELAY` is set on the underlying socket
+    pub fn nodelay(&self) -> ServerResult<bool> {
+        self.stream.get_ref().get_ref().nodelay()
+            .map_err(|e| ServerError::from_io_error(e, "Failed to read TCP_NODELAY"))
     }
     
     /// Handle the connection lifecycle
@@ -54,45 +143,138 @@ impl ConnectionHandler {
     /// - Generating appropriate responses
     /// - Connection persistence (HTTP/1.1 keep-alive)
     async fn handle_requests(&mut self) -> ServerResult<()> {
-        let mut buffer = vec![0; 4096];
+        let mut handler = HttpHandler::with_limits(self.config.request_limits.clone())
+            .with_metrics(Arc::clone(&self.metrics));
+        for middleware in &self.middleware {
+            handler = handler.with_middleware(Arc::clone(middleware));
+        }
         
         loop {
-            // Read data from the connection
-            match self.stream.read(&mut buffer).await {
-                Ok(0) => {
+            let (request, status) = match self.read_request(&handler).await? {
+                Some(read) => read,
+                None => {
                     // Connection closed by client
                     debug!("Connection {} closed by client", self.id);
                     break;
                 }
-                Ok(bytes_read) => {
-                    debug!("Connection {} received {} bytes", self.id, bytes_read);
-                    
-                    // Parse the HTTP request
-                    let request_data = &buffer[..bytes_read];
-                    let request_str = String::from_utf8_lossy(request_data);
-                    
-                    // Create HTTP handler and process the request
-                    let handler = HttpHandler::new();
-                    let response = handler.handle_request(&request_str).await?;
-                    
-                    // Send the response
-                    self.stream.write_all(response.as_bytes()).await
-                        .map_err(|e| ServerError::from_io_error(e, "Failed to write response"))?;
-                    
-                    // For simplicity, we'll close the connection after each request
-                    // TODO: Implement HTTP/1.1 keep-alive support
-                    debug!("Response sent for connection {}", self.id);
-                    break;
+            };
+            
+            // Process the request; oversized ones get their rejection instead
+            let rejected = matches!(status, ScanStatus::Rejected(_));
+            let response = match status {
+                ScanStatus::Rejected(rejection) => rejection.to_string(),
+                ScanStatus::Incomplete | ScanStatus::Complete => handler.handle_request(&request).await?,
+            };
+            
+            // Send the response
+            write_response(&mut self.stream, response.as_bytes(), self.config.write_buffer_size).await
+                .map_err(|e| ServerError::from_io_error(e, "Failed to write response"))?;
+            self.metrics.record_bytes_out(response.len());
+            
+            if rejected {
+                self.linger().await;
+            }
+            
+            // For simplicity, we'll close the connection after each request
+            // TODO: Implement HTTP/1.1 keep-alive support
+            debug!("Response sent for connection {}", self.id);
+            break;
+        }
+        
+        Ok(())
+    }
+    
+    /// Read until the end of the request (its headers and any body they
+    /// announce), the end of the stream, or until the data read so far
+    /// exceeds the handler's limits
+    /// 
+    /// Limits are checked after every read, so an oversized request is cut
+    /// off after at most one more buffer's worth of data. The request is
+    /// returned as the raw bytes read, with where the scan ended.
+    /// Returns `None` if the client closed the connection without sending anything.
+    async fn read_request(&mut self, handler: &HttpHandler) -> ServerResult<Option<(Vec<u8>, ScanStatus)>> {
+        let mut request = Vec::new();
+        let mut scanner = handler.scanner();
+        let mut status = ScanStatus::Incomplete;
+        
+        loop {
+            let request_data = self.stream.fill_buf().await.map_err(|e| {
+                warn!("Error reading from connection {}: {}", self.id, e);
+                ServerError::from_io_error(e, "Failed to read from connection")
+            })?;
+            
+            if request_data.is_empty() {
+                if request.is_empty() {
+                    return Ok(None);
                 }
-                Err(e) => {
-                    warn!("Error reading from connection {}: {}", self.id, e);
-                    return Err(ServerError::from_io_error(e, "Failed to read from connection"));
+                break;
+            }
+            
+            debug!("Connection {} received {} bytes", self.id, request_data.len());
+            request.extend_from_slice(request_data);
+            let bytes_read = request_data.len();
+            self.stream.consume(bytes_read);
+            self.metrics.record_bytes_in(bytes_read);
+            
+            status = scanner.scan(&request);
+            match status {
+                ScanStatus::Incomplete => {}
+                ScanStatus::Complete => break,
+                ScanStatus::Rejected(_) => {
+                    warn!("Connection {} exceeded request limits", self.id);
+                    break;
                 }
             }
         }
         
-        Ok(())
+        Ok(Some((request, status)))
+    }
+    
+    /// Close the write side, then discard input for a short while
+    /// 
+    /// After rejecting a request there is usually unread data left. Closing
+    /// the socket with unread data makes the OS send a reset, which can
+    /// destroy the rejection before the client reads it.
+    async fn linger(&mut self) {
+        if self.stream.shutdown().await.is_err() {
+            return;
+        }
+        
+        let mut discarded = 0;
+        let mut scratch = [0; 1024];
+        let drain = async {
+            while discarded < LINGER_MAX_BYTES {
+                match self.stream.read(&mut scratch).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => discarded += n,
+                }
+            }
+        };
+        let _ = tokio::time::timeout(LINGER_TIMEOUT, drain).await;
+    }
+}
+
+/// Wrap a stream in read and write buffers sized by `config`
+fn buffered(stream: TcpStream, config: &ConnectionConfig) -> BufReader<BufWriter<TcpStream>> {
+    BufReader::with_capacity(
+        config.read_buffer_size,
+        BufWriter::with_capacity(config.write_buffer_size, stream),
+    )
+}
+
+/// Write `response` in chunks of at most `chunk_size` bytes, then flush
+/// 
+/// Given a `BufWriter` of capacity `chunk_size`, a small response is
+/// buffered and sent in one write on flush, while a large one bypasses the
+/// buffer and reaches the socket in `chunk_size` pieces.
+pub async fn write_response<W>(writer: &mut W, response: &[u8], chunk_size: usize) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    for chunk in response.chunks(chunk_size.max(1)) {
+        writer.write_all(chunk).await?;
     }
+    writer.flush().await
 }
 
 // TODO: Implement connection pooling
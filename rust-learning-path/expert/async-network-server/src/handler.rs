@@ -1,8 +1,11 @@
 use serde_json::json;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::debug;
 
 use crate::error::{ServerError, ServerResult};
+use crate::middleware::Middleware;
+use crate::server::ServerMetrics;
 
 /// HTTP request structure
 /// 
@@ -16,6 +19,14 @@ pub struct HttpRequest {
     pub path: String,
     pub headers: HashMap<String, String>,
     pub body: String,
+    pub context: RequestContext,
+}
+
+/// Information attached to a request by middleware, for the route handlers
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    /// Who the request was authenticated as, if anyone
+    pub principal: Option<String>,
 }
 
 /// HTTP response structure
@@ -53,6 +64,33 @@ impl HttpResponse {
     }
 }
 
+/// Limits on the size of a request, applied before parsing
+/// 
+/// Without them a client could send an endless request line, endless
+/// headers, or an endless body and make the server buffer all of it.
+#[derive(Debug, Clone)]
+pub struct RequestLimits {
+    /// Maximum length of the request line, without the line ending
+    pub max_request_line: usize,
+    /// Maximum number of header lines
+    pub max_headers: usize,
+    /// Maximum total size of the header lines, line endings included
+    pub max_header_bytes: usize,
+    /// Maximum size of the body; for chunked bodies, of all chunks together
+    pub max_body_bytes: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_request_line: 8192,
+            max_headers: 100,
+            max_header_bytes: 16384,
+            max_body_bytes: 1024 * 1024,
+        }
+    }
+}
+
 /// Handles HTTP request processing
 /// 
 /// This demonstrates async request handling patterns:
@@ -60,11 +98,77 @@ impl HttpResponse {
 /// - Route matching and dispatch
 /// - Response generation
 /// - Error handling for malformed requests
-pub struct HttpHandler;
+pub struct HttpHandler {
+    limits: RequestLimits,
+    metrics: Arc<ServerMetrics>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
 
 impl HttpHandler {
     pub fn new() -> Self {
-        Self
+        Self::with_limits(RequestLimits::default())
+    }
+    
+    /// Create a handler that rejects requests exceeding `limits`
+    pub fn with_limits(limits: RequestLimits) -> Self {
+        Self {
+            limits,
+            metrics: Arc::new(ServerMetrics::new()),
+            middleware: Vec::new(),
+        }
+    }
+    
+    /// Serve `/stats` from the given counters instead of empty ones
+    pub fn with_metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    
+    /// Run `middleware` on every request before routing it
+    /// 
+    /// Middleware runs in the order it was added, and the first one to
+    /// answer a request stops the rest from running.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+    
+    /// Check a request, possibly only partially received, against the limits
+    /// 
+    /// Returns the rejection to send if a limit is already exceeded: `400`
+    /// for an over-long request line, `431` for too many or too large
+    /// headers, `413` for a body that is or is declared too large. A
+    /// connection reading a request in pieces uses a `RequestScanner`
+    /// instead, which checks each piece as it arrives.
+    pub fn check_limits<R: AsRef<[u8]> + ?Sized>(&self, data: &R) -> Option<HttpResponse> {
+        match self.scanner().scan(data.as_ref()) {
+            ScanStatus::Rejected(rejection) => Some(rejection),
+            ScanStatus::Incomplete | ScanStatus::Complete => None,
+        }
+    }
+    
+    /// Create a scanner for a request that will arrive in pieces
+    pub fn scanner(&self) -> RequestScanner {
+        RequestScanner::new(self.limits.clone())
+    }
+    
+    /// Build an error response for a request that was rejected before routing
+    pub(crate) fn reject(status_code: u16, status_text: &str, message: &str) -> HttpResponse {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("Connection".to_string(), "close".to_string());
+        
+        let body = json!({
+            "error": status_text,
+            "message": message
+        }).to_string();
+        
+        HttpResponse {
+            status_code,
+            status_text: status_text.to_string(),
+            headers,
+            body,
+        }
     }
     
     /// Handle an HTTP request asynchronously
@@ -74,11 +178,24 @@ impl HttpHandler {
     /// - Route matching and method dispatch
     /// - Generating appropriate responses
     /// - Error handling for invalid requests
-    pub async fn handle_request(&self, request_data: &str) -> ServerResult<String> {
+    pub async fn handle_request<R: AsRef<[u8]> + ?Sized>(&self, request_data: &R) -> ServerResult<String> {
         debug!("Processing HTTP request");
+        let request_data = request_data.as_ref();
+        
+        // Reject oversized requests before parsing them
+        if let Some(rejection) = self.check_limits(request_data) {
+            return Ok(rejection.to_string());
+        }
         
         // Parse the HTTP request
-        let request = self.parse_request(request_data)?;
+        let mut request = self.parse_request(request_data)?;
+        
+        // Let the middleware authenticate or answer the request
+        for middleware in &self.middleware {
+            if let Err(response) = middleware.handle(&mut request) {
+                return Ok(response.to_string());
+            }
+        }
         
         // Route the request based on method and path
         let response = self.route_request(request).await?;
@@ -92,8 +209,13 @@ impl HttpHandler {
     /// - String parsing and manipulation
     /// - HTTP protocol understanding
     /// - Error handling for malformed requests
-    fn parse_request(&self, data: &str) -> ServerResult<HttpRequest> {
-        let lines: Vec<&str> = data.lines().collect();
+    /// 
+    /// The head is read as text, but the body stays bytes until it is
+    /// decoded, so chunk sizes count the bytes that were actually sent.
+    fn parse_request(&self, data: &[u8]) -> ServerResult<HttpRequest> {
+        let body_start = find_body_start(data);
+        let head = String::from_utf8_lossy(&data[..body_start.unwrap_or(data.len())]);
+        let lines: Vec<&str> = head.lines().collect();
         
         if lines.is_empty() {
             return Err(ServerError::HttpParsing("Empty request".to_string()));
@@ -110,26 +232,44 @@ impl HttpHandler {
         
         // Parse headers
         let mut headers = HashMap::new();
-        let mut body_start = 1;
+        let mut framed = false;
         
-        for (i, line) in lines.iter().enumerate().skip(1) {
+        for line in lines.iter().skip(1) {
             if line.is_empty() {
-                body_start = i + 1;
                 break;
             }
             
             if let Some(colon_pos) = line.find(':') {
                 let key = line[..colon_pos].trim().to_string();
                 let value = line[colon_pos + 1..].trim().to_string();
+                if is_framing_header(&key) {
+                    // A second framing header could frame the body differently
+                    if framed {
+                        return Err(ServerError::HttpParsing(CONFLICTING_FRAMING.to_string()));
+                    }
+                    framed = true;
+                }
                 headers.insert(key, value);
             }
         }
         
         // Extract body (if any)
-        let body = if body_start < lines.len() {
-            lines[body_start..].join("\n")
+        let chunked = is_chunked(
+            headers.iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("Transfer-Encoding"))
+                .map(|(_, value)| value.as_str())
+        );
+        let raw_body = body_start.map_or(&[][..], |start| &data[start..]);
+        let body = if chunked {
+            let body = decode_chunked(raw_body, self.limits.max_body_bytes)
+                .map_err(|e| ServerError::HttpParsing(e.to_string()))?;
+            
+            // Trailers are headers that were sent after the body; they
+            // can't change how the body was framed
+            headers.extend(body.trailers.into_iter().filter(|(key, _)| !is_framing_header(key)));
+            String::from_utf8_lossy(&body.data).into_owned()
         } else {
-            String::new()
+            String::from_utf8_lossy(raw_body).lines().collect::<Vec<_>>().join("\n")
         };
         
         Ok(HttpRequest {
@@ -137,6 +277,7 @@ impl HttpHandler {
             path,
             headers,
             body,
+            context: RequestContext::default(),
         })
     }
     
@@ -151,6 +292,7 @@ impl HttpHandler {
             ("GET", "/") => self.handle_root().await,
             ("GET", "/health") => self.handle_health().await,
             ("GET", "/api/status") => self.handle_api_status().await,
+            ("GET", "/stats") => self.handle_stats().await,
             ("POST", "/api/echo") => self.handle_echo(request).await,
             _ => self.handle_not_found().await,
         }
@@ -214,7 +356,30 @@ impl HttpHandler {
             "api_version": "v1",
             "server": "async-network-server",
             "features": ["async", "tokio", "http"],
-            "endpoints": ["/", "/health", "/api/status", "/api/echo"]
+            "endpoints": ["/", "/health", "/api/status", "/api/echo", "/stats"]
+        }).to_string();
+        
+        Ok(HttpResponse {
+            status_code: 200,
+            status_text: "OK".to_string(),
+            headers,
+            body,
+        })
+    }
+    
+    /// Handle connection and traffic statistics requests
+    async fn handle_stats(&self) -> ServerResult<HttpResponse> {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        
+        let stats = self.metrics.snapshot();
+        let body = json!({
+            "total_accepted": stats.total_connections,
+            "total_closed": stats.total_closed,
+            "active_connections": stats.active_connections,
+            "uptime_seconds": stats.uptime.as_secs_f64(),
+            "bytes_in": stats.bytes_in,
+            "bytes_out": stats.bytes_out
         }).to_string();
         
         Ok(HttpResponse {
@@ -235,6 +400,7 @@ impl HttpHandler {
             "path": request.path,
             "headers": request.headers,
             "body": request.body,
+            "principal": request.context.principal,
             "echo": "Request received and processed asynchronously"
         }).to_string();
         
@@ -265,10 +431,349 @@ impl HttpHandler {
     }
 }
 
-// TODO: Add middleware support
+/// Where a request stands after a `RequestScanner` has seen the latest piece
+#[derive(Debug, Clone)]
+pub enum ScanStatus {
+    /// More of the request is needed
+    Incomplete,
+    /// The head, and any body it announced, has arrived
+    /// 
+    /// A malformed chunked body counts as complete, since reading more won't
+    /// fix it; parsing then reports the error.
+    Complete,
+    /// A limit was exceeded; this is the response to send
+    Rejected(HttpResponse),
+}
+
+/// Checks a request against the limits while it arrives in pieces
+/// 
+/// Each `scan` picks up where the last one stopped, so a request read in
+/// many small pieces is still only looked at once, and a connection can
+/// stop reading as soon as a limit is hit instead of buffering the rest.
+pub struct RequestScanner {
+    limits: RequestLimits,
+    /// Offset of the first head line not scanned yet
+    scanned: usize,
+    /// Set once the request line has been scanned
+    seen_request_line: bool,
+    header_count: usize,
+    header_bytes: usize,
+    /// Where the body starts, once the blank line ending the head has arrived
+    body_start: Option<usize>,
+    transfer_encoding: Option<String>,
+    content_length: Option<String>,
+    chunked: ChunkedDecoder,
+}
+
+impl RequestScanner {
+    fn new(limits: RequestLimits) -> Self {
+        Self {
+            limits,
+            scanned: 0,
+            seen_request_line: false,
+            header_count: 0,
+            header_bytes: 0,
+            body_start: None,
+            transfer_encoding: None,
+            content_length: None,
+            chunked: ChunkedDecoder::default(),
+        }
+    }
+    
+    /// Scan the request received so far
+    /// 
+    /// `data` is the request from its start, and must extend the data given
+    /// on earlier calls.
+    pub fn scan(&mut self, data: &[u8]) -> ScanStatus {
+        let body_start = match self.body_start {
+            Some(start) => start,
+            None => match self.scan_head(data) {
+                Ok(Some(start)) => start,
+                Ok(None) => return ScanStatus::Incomplete,
+                Err(rejection) => return ScanStatus::Rejected(rejection),
+            },
+        };
+        
+        self.scan_body(&data[body_start..])
+    }
+    
+    /// Scan the head lines that have arrived, returning where the body
+    /// starts once the head is complete
+    fn scan_head(&mut self, data: &[u8]) -> Result<Option<usize>, HttpResponse> {
+        while let Some(end) = data[self.scanned..].iter().position(|&byte| byte == b'\n') {
+            let line = &data[self.scanned..self.scanned + end];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            self.scanned += end + 1;
+            
+            if !self.seen_request_line {
+                self.seen_request_line = true;
+                self.check_request_line(line.len())?;
+                continue;
+            }
+            if line.is_empty() {
+                // Blank line: end of the headers
+                self.body_start = Some(self.scanned);
+                return Ok(Some(self.scanned));
+            }
+            
+            self.header_count += 1;
+            self.header_bytes += end + 1;
+            self.check_headers(self.header_bytes)?;
+            
+            let line = String::from_utf8_lossy(line);
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                if is_framing_header(key) && (self.transfer_encoding.is_some() || self.content_length.is_some()) {
+                    return Err(HttpHandler::reject(400, "Bad Request", CONFLICTING_FRAMING));
+                }
+                if key.eq_ignore_ascii_case("Transfer-Encoding") {
+                    self.transfer_encoding = Some(value.trim().to_string());
+                } else if key.eq_ignore_ascii_case("Content-Length") {
+                    self.content_length = Some(value.trim().to_string());
+                }
+            }
+        }
+        
+        // A line still arriving already counts toward its limit
+        let partial = &data[self.scanned..];
+        if self.seen_request_line {
+            self.check_headers(self.header_bytes + partial.len())?;
+        } else {
+            self.check_request_line(partial.strip_suffix(b"\r").unwrap_or(partial).len())?;
+        }
+        
+        Ok(None)
+    }
+    
+    fn check_request_line(&self, len: usize) -> Result<(), HttpResponse> {
+        if len > self.limits.max_request_line {
+            return Err(HttpHandler::reject(400, "Bad Request", "Request line too long"));
+        }
+        Ok(())
+    }
+    
+    fn check_headers(&self, header_bytes: usize) -> Result<(), HttpResponse> {
+        if self.header_count > self.limits.max_headers {
+            return Err(HttpHandler::reject(431, "Request Header Fields Too Large", "Too many headers"));
+        }
+        if header_bytes > self.limits.max_header_bytes {
+            return Err(HttpHandler::reject(431, "Request Header Fields Too Large", "Headers too large"));
+        }
+        Ok(())
+    }
+    
+    /// Check the body that has arrived against the limit and the length
+    /// the head announced
+    fn scan_body(&mut self, body: &[u8]) -> ScanStatus {
+        let max = self.limits.max_body_bytes;
+        let declared = self.content_length.as_deref().and_then(|len| len.parse::<usize>().ok());
+        let too_large = || ScanStatus::Rejected(HttpHandler::reject(413, "Payload Too Large", "Body too large"));
+        
+        if is_chunked(self.transfer_encoding.as_deref()) {
+            return match self.chunked.advance(body, max, |_| {}, |_| Ok(())) {
+                Ok(()) | Err(ChunkedError::Malformed(_)) => ScanStatus::Complete,
+                Err(ChunkedError::Incomplete) => ScanStatus::Incomplete,
+                Err(ChunkedError::TooLarge) => too_large(),
+            };
+        }
+        
+        if declared.is_some_and(|len| len > max) || body.len() > max {
+            return too_large();
+        }
+        match declared {
+            Some(len) if body.len() < len => ScanStatus::Incomplete,
+            _ => ScanStatus::Complete,
+        }
+    }
+}
+
+/// Longest chunk-size or trailer line accepted in a chunked body
+const MAX_CHUNK_LINE: usize = 1024;
+
+/// A decoded chunked body
+struct ChunkedBody {
+    /// The chunks' data, concatenated
+    data: Vec<u8>,
+    /// Header lines sent after the last chunk
+    trailers: Vec<(String, String)>,
+}
+
+/// Why a chunked body couldn't be decoded
+#[derive(Debug, PartialEq)]
+enum ChunkedError {
+    /// The terminating chunk or trailers haven't arrived yet
+    Incomplete,
+    /// The chunks together exceed the body limit
+    TooLarge,
+    /// The encoding is invalid
+    Malformed(&'static str),
+}
+
+impl std::fmt::Display for ChunkedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChunkedError::Incomplete => write!(f, "Incomplete chunked body"),
+            ChunkedError::TooLarge => write!(f, "Chunked body too large"),
+            ChunkedError::Malformed(reason) => write!(f, "Malformed chunked body: {}", reason),
+        }
+    }
+}
+
+/// Decode a `Transfer-Encoding: chunked` body into its data and trailers
+fn decode_chunked(data: &[u8], max_body: usize) -> Result<ChunkedBody, ChunkedError> {
+    let mut body = Vec::new();
+    let mut trailers = Vec::new();
+    
+    ChunkedDecoder::default().advance(
+        data,
+        max_body,
+        |chunk| body.extend_from_slice(chunk),
+        |line| {
+            let line = String::from_utf8_lossy(line);
+            let (key, value) = line.split_once(':')
+                .ok_or(ChunkedError::Malformed("invalid trailer"))?;
+            trailers.push((key.trim().to_string(), value.trim().to_string()));
+            Ok(())
+        },
+    )?;
+    
+    Ok(ChunkedBody { data: body, trailers })
+}
+
+/// Walks a chunked body, remembering where it stopped so decoding can go
+/// on once more of the body has arrived
+/// 
+/// Each chunk is a hexadecimal size line (optionally with `;extensions`),
+/// that many bytes of data, and a line ending. A zero-size chunk ends the
+/// body; trailer header lines and a blank line follow it. The size limit
+/// is checked against each chunk's size before its data is needed, so an
+/// oversized body is caught as early as possible.
+#[derive(Debug, Default)]
+struct ChunkedDecoder {
+    /// Offset of the first chunk or trailer line not decoded yet
+    pos: usize,
+    /// Data bytes in the chunks decoded so far
+    len: usize,
+    /// Set once the zero-size chunk has been seen
+    in_trailers: bool,
+    /// Set once the blank line after the trailers has been seen
+    done: bool,
+}
+
+impl ChunkedDecoder {
+    /// Decode the whole chunks and trailer lines in `data` past the ones
+    /// already decoded, passing each to `on_chunk` or `on_trailer`
+    /// 
+    /// `data` is the body from its start, and must extend the data given
+    /// on earlier calls. Returns `Incomplete` if the body hasn't ended yet.
+    fn advance(
+        &mut self,
+        data: &[u8],
+        max_body: usize,
+        mut on_chunk: impl FnMut(&[u8]),
+        mut on_trailer: impl FnMut(&[u8]) -> Result<(), ChunkedError>,
+    ) -> Result<(), ChunkedError> {
+        while !self.done {
+            let mut pos = self.pos;
+            let line = next_line(data, &mut pos)?;
+            
+            if self.in_trailers {
+                if line.is_empty() {
+                    self.done = true;
+                } else {
+                    on_trailer(line)?;
+                }
+                self.pos = pos;
+                continue;
+            }
+            
+            let size_line = std::str::from_utf8(line)
+                .map_err(|_| ChunkedError::Malformed("chunk size is not ASCII"))?;
+            let size_hex = size_line.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size_hex, 16)
+                .map_err(|_| ChunkedError::Malformed("invalid chunk size"))?;
+            
+            if size == 0 {
+                self.in_trailers = true;
+                self.pos = pos;
+                continue;
+            }
+            if size > max_body - self.len {
+                return Err(ChunkedError::TooLarge);
+            }
+            
+            let chunk = data.get(pos..pos + size).ok_or(ChunkedError::Incomplete)?;
+            pos += size;
+            
+            // The data is followed directly by a line ending
+            if !next_line(data, &mut pos)?.is_empty() {
+                return Err(ChunkedError::Malformed("chunk data longer than its size"));
+            }
+            
+            on_chunk(chunk);
+            self.len += size;
+            self.pos = pos;
+        }
+        
+        Ok(())
+    }
+}
+
+/// Take the line starting at `pos`, without its line ending, and move past it
+fn next_line<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a [u8], ChunkedError> {
+    let rest = &data[*pos..];
+    let Some(end) = rest.iter().position(|&byte| byte == b'\n') else {
+        // Don't wait forever for the end of an endless line
+        return Err(if rest.len() > MAX_CHUNK_LINE {
+            ChunkedError::Malformed("line too long")
+        } else {
+            ChunkedError::Incomplete
+        });
+    };
+    
+    *pos += end + 1;
+    let line = &rest[..end];
+    Ok(line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// Find where the body starts: just past the blank line ending the headers
+fn find_body_start(data: &[u8]) -> Option<usize> {
+    let mut pos = 0;
+    let mut first = true;
+    while let Some(end) = data[pos..].iter().position(|&byte| byte == b'\n') {
+        let line = &data[pos..pos + end];
+        pos += end + 1;
+        if !first && line.strip_suffix(b"\r").unwrap_or(line).is_empty() {
+            return Some(pos);
+        }
+        first = false;
+    }
+    None
+}
+
+/// Why a request with more than one way of finding its body's end is refused
+const CONFLICTING_FRAMING: &str = "Only one Transfer-Encoding or Content-Length header is allowed";
+
+/// Check whether a header decides where the body ends
+/// 
+/// Requests may carry at most one of these. If the scanner and the parser
+/// could each pick a different one, they would disagree on where the
+/// request ends, which lets a client smuggle a second request in the body.
+fn is_framing_header(name: &str) -> bool {
+    name.eq_ignore_ascii_case("Transfer-Encoding") || name.eq_ignore_ascii_case("Content-Length")
+}
+
+/// Check whether a `Transfer-Encoding` value ends with `chunked`
+fn is_chunked(transfer_encoding: Option<&str>) -> bool {
+    transfer_encoding
+        .and_then(|value| value.rsplit(',').next())
+        .is_some_and(|last| last.trim().eq_ignore_ascii_case("chunked"))
+}
+
+// TODO: Add more middleware
 // This would include:
-// 1. Request/response middleware chain
-// 2. Authentication and authorization
+// 1. Response middleware, run after routing
+// 2. Authorization based on the principal
 // 3. Request logging and metrics
 // 4. CORS handling
 // 5. Rate limiting per endpoint
@@ -1,9 +1,11 @@
 pub mod server;
 pub mod handler;
 pub mod connection;
+pub mod middleware;
 pub mod error;
 
-pub use server::Server;
-pub use handler::{HttpHandler, HttpRequest, HttpResponse};
-pub use connection::ConnectionHandler;
+pub use server::{Server, ServerConfig, ServerMetrics, ServerStats};
+pub use handler::{HttpHandler, HttpRequest, HttpResponse, RequestContext, RequestLimits, RequestScanner, ScanStatus};
+pub use middleware::{BearerAuth, Middleware};
+pub use connection::{ConnectionConfig, ConnectionHandler};
 pub use error::{ServerError, ServerResult};
\ No newline at end of file
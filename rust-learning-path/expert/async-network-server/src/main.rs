@@ -1,13 +1,14 @@
 use std::net::SocketAddr;
-use tokio::net::TcpListener;
+use observability::LogFormat;
 use tracing::{info, error};
 
 pub mod server;
 pub mod handler;
 pub mod connection;
+pub mod middleware;
 pub mod error;
 
-use server::Server;
+use server::{Server, ServerConfig};
 use error::ServerError;
 
 /// Entry point for the async network server
@@ -19,8 +20,10 @@ use error::ServerError;
 /// - Handling shutdown signals gracefully
 #[tokio::main]
 async fn main() -> Result<(), ServerError> {
-    // Initialize tracing for observability
-    tracing_subscriber::fmt::init();
+    // Initialize tracing for observability; RUST_LOG overrides the level
+    observability::init("info", LogFormat::Plain)
+        .map_err(|e| ServerError::Configuration(format!("Logging setup failed: {}", e)))?;
+    observability::install_panic_hook();
     
     info!("Starting async network server...");
     
@@ -28,14 +31,12 @@ async fn main() -> Result<(), ServerError> {
     let addr: SocketAddr = "127.0.0.1:8080".parse()
         .map_err(|e| ServerError::Configuration(format!("Invalid address: {}", e)))?;
     
-    // Create TCP listener - this is the foundation of our server
-    let listener = TcpListener::bind(&addr).await
-        .map_err(|e| ServerError::Network(format!("Failed to bind to {}: {}", addr, e)))?;
+    // Create the TCP listener - this is the foundation of our server.
+    // SO_REUSEADDR (on by default) lets a restart rebind the address immediately
+    let config = ServerConfig::default();
+    let server = Server::bind(addr, &config)?;
     
-    info!("Server listening on {}", addr);
-    
-    // Create and configure the server
-    let server = Server::new(listener);
+    info!("Server listening on {} (backlog {})", addr, config.backlog);
     
     // TODO: Set up graceful shutdown handling
     // For now, we'll run the server indefinitely
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use tracing::debug;
+
+use crate::handler::{HttpHandler, HttpRequest, HttpResponse};
+
+/// A step run on every parsed request before it is routed
+///
+/// Middleware can inspect the request, attach information to its context
+/// for the route handlers, or answer it right away by returning the
+/// response to send instead of routing it.
+pub trait Middleware: Send + Sync {
+    fn handle(&self, request: &mut HttpRequest) -> Result<(), HttpResponse>;
+}
+
+/// Requires an `Authorization: Bearer <token>` header with a known token
+///
+/// Each token maps to a principal, which is attached to the request context
+/// once the token checks out. Requests without a valid token get `401`,
+/// except for paths on the public allowlist, which are passed through
+/// unauthenticated.
+#[derive(Debug, Clone, Default)]
+pub struct BearerAuth {
+    tokens: Vec<(String, String)>,
+    public_paths: HashSet<String>,
+}
+
+impl BearerAuth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept `token`, authenticating requests that carry it as `principal`
+    pub fn with_token(mut self, token: impl Into<String>, principal: impl Into<String>) -> Self {
+        self.tokens.push((token.into(), principal.into()));
+        self
+    }
+
+    /// Let requests for `path` through without a token
+    ///
+    /// Paths are matched exactly, ignoring any query string.
+    pub fn allow_path(mut self, path: impl Into<String>) -> Self {
+        self.public_paths.insert(path.into());
+        self
+    }
+
+    /// Find the principal for a token
+    ///
+    /// Every configured token is compared in full, so the time taken doesn't
+    /// reveal how much of a guess was right.
+    fn authenticate(&self, token: &str) -> Option<&str> {
+        let mut principal = None;
+        for (known, name) in &self.tokens {
+            if constant_time_eq(known.as_bytes(), token.as_bytes()) {
+                principal = Some(name.as_str());
+            }
+        }
+        principal
+    }
+}
+
+impl Middleware for BearerAuth {
+    fn handle(&self, request: &mut HttpRequest) -> Result<(), HttpResponse> {
+        let path = request.path.split('?').next().unwrap_or("");
+        if self.public_paths.contains(path) {
+            return Ok(());
+        }
+
+        let token = request.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("Authorization"))
+            .and_then(|(_, value)| value.strip_prefix("Bearer "))
+            .map(str::trim);
+
+        match token.and_then(|token| self.authenticate(token)) {
+            Some(principal) => {
+                debug!("Authenticated request for {} as {}", request.path, principal);
+                request.context.principal = Some(principal.to_string());
+                Ok(())
+            }
+            None => {
+                let message = if token.is_some() { "Invalid token" } else { "Missing bearer token" };
+                let mut response = HttpHandler::reject(401, "Unauthorized", message);
+                response.headers.insert("WWW-Authenticate".to_string(), "Bearer".to_string());
+                Err(response)
+            }
+        }
+    }
+}
+
+/// Compare two byte strings in time that depends only on their lengths
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -1,10 +1,58 @@
 use tokio::net::{TcpListener, TcpStream};
 use tracing::{info, warn, error, debug};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::connection::ConnectionHandler;
+use crate::connection::{ConnectionConfig, ConnectionHandler};
 use crate::error::{ServerError, ServerResult};
+use crate::middleware::Middleware;
+
+/// Settings for the server's listening socket
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Set `SO_REUSEADDR`, so a restarted server can bind its address while
+    /// connections from the previous run are still in `TIME_WAIT`
+    pub reuse_address: bool,
+    /// Maximum number of connections the OS queues for `accept`.
+    /// Connection attempts beyond this are dropped until the server catches up
+    pub backlog: u32,
+    /// Buffer sizes and socket options for each accepted connection
+    pub connection: ConnectionConfig,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            reuse_address: true,
+            backlog: 1024,
+            connection: ConnectionConfig::default(),
+        }
+    }
+}
+
+/// Create a listener bound to `addr` with the given socket options
+/// 
+/// `TcpListener::bind` always uses the OS defaults; building the socket
+/// with `socket2` lets us set options before `bind` and `listen`.
+/// Must be called from within a Tokio runtime.
+pub fn bind_listener(addr: SocketAddr, config: &ServerConfig) -> ServerResult<TcpListener> {
+    let bind_error = |e| ServerError::from_io_error(e, &format!("Failed to bind to {}", addr));
+    
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))
+        .map_err(bind_error)?;
+    socket.set_reuse_address(config.reuse_address).map_err(bind_error)?;
+    socket.set_nonblocking(true).map_err(bind_error)?;
+    socket.bind(&addr.into()).map_err(bind_error)?;
+    
+    // The OS caps the backlog anyway (somaxconn on Linux), so clamp rather than fail
+    let backlog = i32::try_from(config.backlog).unwrap_or(i32::MAX);
+    socket.listen(backlog).map_err(bind_error)?;
+    
+    TcpListener::from_std(socket.into()).map_err(bind_error)
+}
 
 /// The main server struct that manages the TCP listener and connection handling
 /// 
@@ -15,8 +63,9 @@ use crate::error::{ServerError, ServerResult};
 /// - Proper resource management and cleanup
 pub struct Server {
     listener: TcpListener,
-    connection_count: Arc<AtomicU64>,
-    active_connections: Arc<AtomicU64>,
+    metrics: Arc<ServerMetrics>,
+    connection_config: ConnectionConfig,
+    middleware: Vec<Arc<dyn Middleware>>,
 }
 
 impl Server {
@@ -24,11 +73,33 @@ impl Server {
     pub fn new(listener: TcpListener) -> Self {
         Self {
             listener,
-            connection_count: Arc::new(AtomicU64::new(0)),
-            active_connections: Arc::new(AtomicU64::new(0)),
+            metrics: Arc::new(ServerMetrics::new()),
+            connection_config: ConnectionConfig::default(),
+            middleware: Vec::new(),
         }
     }
     
+    /// Create a server listening on `addr`, configured by `config`
+    pub fn bind(addr: SocketAddr, config: &ServerConfig) -> ServerResult<Self> {
+        let mut server = Self::new(bind_listener(addr, config)?);
+        server.connection_config = config.connection.clone();
+        Ok(server)
+    }
+    
+    /// Run `middleware` on every request before routing it
+    /// 
+    /// Middleware runs in the order it was added.
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+    
+    /// Get the address the server is listening on
+    pub fn local_addr(&self) -> ServerResult<SocketAddr> {
+        self.listener.local_addr()
+            .map_err(|e| ServerError::from_io_error(e, "Failed to get local address"))
+    }
+    
     /// Run the server, accepting connections in a loop
     /// 
     /// This is the main server loop that demonstrates:
@@ -44,26 +115,31 @@ impl Server {
             // The ? operator propagates errors up to the caller
             match self.listener.accept().await {
                 Ok((stream, addr)) => {
-                    let conn_id = self.connection_count.fetch_add(1, Ordering::Relaxed);
-                    self.active_connections.fetch_add(1, Ordering::Relaxed);
+                    let conn_id = self.metrics.connection_accepted();
                     
                     info!("New connection {} from {}", conn_id, addr);
                     
                     // Clone Arc references for the spawned task
-                    let active_connections = Arc::clone(&self.active_connections);
+                    let metrics = Arc::clone(&self.metrics);
+                    let connection_config = self.connection_config.clone();
+                    let middleware = self.middleware.clone();
                     
                     // Spawn a new task to handle this connection
                     // This allows the server to handle multiple connections concurrently
                     tokio::spawn(async move {
-                        let handler = ConnectionHandler::new(conn_id, stream);
-                        
                         // Handle the connection and log any errors
-                        if let Err(e) = handler.handle().await {
+                        let result = match ConnectionHandler::with_config(conn_id, stream, &connection_config) {
+                            Ok(handler) => middleware.into_iter()
+                                .fold(handler.with_metrics(Arc::clone(&metrics)), ConnectionHandler::with_middleware)
+                                .handle().await,
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = result {
                             warn!("Connection {} error: {}", conn_id, e);
                         }
                         
                         // Decrement active connection count when done
-                        let remaining = active_connections.fetch_sub(1, Ordering::Relaxed) - 1;
+                        let remaining = metrics.connection_closed();
                         debug!("Connection {} closed, {} active connections remaining", conn_id, remaining);
                     });
                 }
@@ -78,18 +154,98 @@ impl Server {
     
     /// Get current server statistics
     pub fn stats(&self) -> ServerStats {
-        ServerStats {
-            total_connections: self.connection_count.load(Ordering::Relaxed),
-            active_connections: self.active_connections.load(Ordering::Relaxed),
-        }
+        self.metrics.snapshot()
+    }
+    
+    /// Get the live counters shared with the connection handlers
+    pub fn metrics(&self) -> Arc<ServerMetrics> {
+        Arc::clone(&self.metrics)
     }
 }
 
 /// Server statistics structure
 #[derive(Debug, Clone)]
 pub struct ServerStats {
+    /// Connections accepted since the server started
     pub total_connections: u64,
+    /// Connections that have been closed
+    pub total_closed: u64,
     pub active_connections: u64,
+    /// Bytes read from clients
+    pub bytes_in: u64,
+    /// Bytes written to clients
+    pub bytes_out: u64,
+    pub uptime: Duration,
+}
+
+/// Counters for connection churn and traffic, updated as the server runs
+/// 
+/// Shared through an `Arc` between the accept loop, the connection
+/// handlers, and the `/stats` endpoint. Each counter is a separate atomic,
+/// so a snapshot taken while connections come and go may be slightly
+/// inconsistent (say, `active` not quite matching `accepted - closed`).
+#[derive(Debug)]
+pub struct ServerMetrics {
+    accepted: AtomicU64,
+    closed: AtomicU64,
+    active: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    started: Instant,
+}
+
+impl ServerMetrics {
+    /// Create zeroed counters, with the uptime starting now
+    pub fn new() -> Self {
+        Self {
+            accepted: AtomicU64::new(0),
+            closed: AtomicU64::new(0),
+            active: AtomicU64::new(0),
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+    
+    /// Count a newly accepted connection, returning its id
+    pub fn connection_accepted(&self) -> u64 {
+        self.active.fetch_add(1, Ordering::Relaxed);
+        self.accepted.fetch_add(1, Ordering::Relaxed)
+    }
+    
+    /// Count a closed connection, returning how many remain active
+    pub fn connection_closed(&self) -> u64 {
+        self.closed.fetch_add(1, Ordering::Relaxed);
+        self.active.fetch_sub(1, Ordering::Relaxed) - 1
+    }
+    
+    /// Count bytes read from a client
+    pub fn record_bytes_in(&self, bytes: usize) {
+        self.bytes_in.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+    
+    /// Count bytes written to a client
+    pub fn record_bytes_out(&self, bytes: usize) {
+        self.bytes_out.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+    
+    /// Read every counter
+    pub fn snapshot(&self) -> ServerStats {
+        ServerStats {
+            total_connections: self.accepted.load(Ordering::Relaxed),
+            total_closed: self.closed.load(Ordering::Relaxed),
+            active_connections: self.active.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            uptime: self.started.elapsed(),
+        }
+    }
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // TODO: Implement graceful shutdown
@@ -122,6 +122,486 @@ async fn test_empty_request() {
     assert!(response.is_err(), "Should reject empty requests");
 }
 
+#[tokio::test]
+async fn test_too_many_headers_rejected() {
+    use async_network_server::handler::{HttpHandler, RequestLimits};
+    
+    let handler = HttpHandler::with_limits(RequestLimits { max_headers: 10, ..RequestLimits::default() });
+    
+    let mut request_data = String::from("GET / HTTP/1.1\r\n");
+    for i in 0..11 {
+        request_data.push_str(&format!("X-Header-{}: value\r\n", i));
+    }
+    request_data.push_str("\r\n");
+    
+    let response = handler.handle_request(&request_data).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large"), "Should return 431");
+    assert!(response.contains("Too many headers"));
+    
+    // One header fewer is fine
+    let within_limit = request_data.replacen("X-Header-0: value\r\n", "", 1);
+    let response = handler.handle_request(&within_limit).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Should accept 10 headers");
+}
+
+#[tokio::test]
+async fn test_long_request_line_rejected() {
+    use async_network_server::handler::{HttpHandler, RequestLimits};
+    
+    let handler = HttpHandler::with_limits(RequestLimits { max_request_line: 64, ..RequestLimits::default() });
+    let request_data = format!("GET /{} HTTP/1.1\r\nHost: localhost:8080\r\n\r\n", "a".repeat(100));
+    
+    let response = handler.handle_request(&request_data).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"), "Should return 400");
+    assert!(response.contains("Request line too long"));
+}
+
+#[tokio::test]
+async fn test_oversized_request_closes_connection_early() {
+    use async_network_server::connection::{ConnectionConfig, ConnectionHandler};
+    use async_network_server::handler::RequestLimits;
+    use tokio::net::TcpListener;
+    
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    
+    let config = ConnectionConfig {
+        request_limits: RequestLimits { max_request_line: 1024, ..RequestLimits::default() },
+        ..ConnectionConfig::default()
+    };
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        ConnectionHandler::with_config(0, stream, &config).unwrap().handle().await
+    });
+    
+    // A request line that never ends: the server must answer without
+    // waiting for the rest, or this would hit the connection timeout
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(b"GET /").await.unwrap();
+    client.write_all(&[b'a'; 4096]).await.unwrap();
+    
+    let mut response = String::new();
+    timeout(Duration::from_secs(5), client.read_to_string(&mut response)).await
+        .expect("server should respond without waiting for the rest of the request")
+        .unwrap();
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"), "Got: {}", response);
+    
+    let result = timeout(Duration::from_secs(5), server).await.unwrap().unwrap();
+    assert!(result.is_ok(), "Connection should be handled cleanly: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_chunked_body_is_reassembled() {
+    use async_network_server::handler::HttpHandler;
+    
+    let handler = HttpHandler::new();
+    let request_data = "POST /api/echo HTTP/1.1\r\n\
+        Host: localhost:8080\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        7\r\nHello, \r\n\
+        6;name=value\r\nchunke\r\n\
+        A\r\nd world!!!\r\n\
+        0\r\n\
+        X-Checksum: abc\r\n\
+        \r\n";
+    
+    let response = handler.handle_request(request_data).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+    
+    let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+    let echoed: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(echoed["body"], "Hello, chunked world!!!");
+    assert_eq!(echoed["headers"]["X-Checksum"], "abc", "Trailers should be merged into the headers");
+}
+
+#[tokio::test]
+async fn test_chunked_body_limit_applies_across_chunks() {
+    use async_network_server::handler::{HttpHandler, RequestLimits};
+    
+    let handler = HttpHandler::with_limits(RequestLimits { max_body_bytes: 16, ..RequestLimits::default() });
+    let head = "POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+    
+    // Each chunk is within the limit, but together they aren't
+    let too_large = format!("{}8\r\n12345678\r\n8\r\n12345678\r\n1\r\n9\r\n0\r\n\r\n", head);
+    let response = handler.handle_request(&too_large).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"), "Got: {}", response);
+    
+    let within_limit = format!("{}8\r\n12345678\r\n8\r\n12345678\r\n0\r\n\r\n", head);
+    let response = handler.handle_request(&within_limit).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+}
+
+#[tokio::test]
+async fn test_malformed_chunked_body_rejected() {
+    use async_network_server::handler::HttpHandler;
+    
+    let handler = HttpHandler::new();
+    let head = "POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+    
+    for body in ["zz\r\nhello\r\n0\r\n\r\n", "3\r\nhello\r\n0\r\n\r\n", "5\r\nhello\r\n"] {
+        let response = handler.handle_request(&format!("{}{}", head, body)).await;
+        assert!(response.is_err(), "Should reject chunked body {:?}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_chunked_body_with_non_utf8_data() {
+    use async_network_server::handler::HttpHandler;
+    
+    // Chunk sizes count the bytes sent, not the characters they decode to
+    let handler = HttpHandler::new();
+    let request_data = b"POST /api/echo HTTP/1.1\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        2\r\n\xff\xfe\r\n\
+        3\r\nabc\r\n\
+        0\r\n\r\n";
+    
+    let response = handler.handle_request(&request_data[..]).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+    
+    let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+    let echoed: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(echoed["body"], "\u{FFFD}\u{FFFD}abc");
+}
+
+#[tokio::test]
+async fn test_conflicting_framing_headers_rejected() {
+    use async_network_server::handler::{HttpHandler, ScanStatus};
+    
+    let handler = HttpHandler::new();
+    let requests = [
+        // Two Transfer-Encodings, the second in a different case
+        "POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\ntransfer-encoding: identity\r\n\r\n0\r\n\r\n",
+        // Two Content-Lengths that disagree
+        "POST /api/echo HTTP/1.1\r\nContent-Length: 5\r\ncontent-length: 0\r\n\r\nhello",
+        // Two identical Content-Lengths
+        "POST /api/echo HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello",
+        // Both kinds at once, in either order
+        "POST /api/echo HTTP/1.1\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n",
+        "POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 5\r\n\r\n0\r\n\r\n",
+    ];
+    
+    for request in requests {
+        let response = handler.handle_request(request).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request"), "Request {:?} got: {}", request, response);
+        
+        match handler.scanner().scan(request.as_bytes()) {
+            ScanStatus::Rejected(rejection) => assert_eq!(rejection.status_code, 400),
+            other => panic!("Scanner accepted {:?}: {:?}", request, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_trailers_cannot_replace_framing_headers() {
+    use async_network_server::handler::HttpHandler;
+    
+    let handler = HttpHandler::new();
+    let request_data = "POST /api/echo HTTP/1.1\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        5\r\nhello\r\n\
+        0\r\n\
+        Transfer-Encoding: identity\r\n\
+        Content-Length: 100\r\n\
+        X-Checksum: abc\r\n\
+        \r\n";
+    
+    let response = handler.handle_request(request_data).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+    
+    let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+    let echoed: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(echoed["body"], "hello");
+    assert_eq!(echoed["headers"]["Transfer-Encoding"], "chunked");
+    assert!(echoed["headers"].get("Content-Length").is_none(), "Got: {}", echoed["headers"]);
+    assert_eq!(echoed["headers"]["X-Checksum"], "abc");
+}
+
+#[test]
+fn test_request_scanner_resumes_across_pieces() {
+    use async_network_server::handler::{HttpHandler, RequestLimits, ScanStatus};
+    
+    let handler = HttpHandler::with_limits(RequestLimits { max_body_bytes: 16, ..RequestLimits::default() });
+    let mut scanner = handler.scanner();
+    let mut request = Vec::new();
+    
+    let parts: [&[u8]; 4] = [
+        b"POST /api/echo HTTP/1.1\r\nTransfer-",
+        b"Encoding: chunked\r\n\r\n3\r\n\xff",
+        b"ab\r\n0\r\n",
+        b"\r\n",
+    ];
+    for (i, part) in parts.iter().enumerate() {
+        request.extend_from_slice(part);
+        let status = scanner.scan(&request);
+        if i + 1 < parts.len() {
+            assert!(matches!(status, ScanStatus::Incomplete), "Part {}: {:?}", i, status);
+        } else {
+            assert!(matches!(status, ScanStatus::Complete), "Got: {:?}", status);
+        }
+    }
+    
+    // An oversized chunk is rejected from its size line, before its data arrives
+    let mut scanner = handler.scanner();
+    let status = scanner.scan(b"POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n20\r\n");
+    match status {
+        ScanStatus::Rejected(rejection) => assert_eq!(rejection.status_code, 413),
+        other => panic!("Expected a rejection, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_chunked_body_read_across_writes() {
+    use async_network_server::connection::ConnectionHandler;
+    use tokio::net::TcpListener;
+    
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        ConnectionHandler::new(0, stream).handle().await
+    });
+    
+    // Send the head and each chunk separately, so they arrive in separate reads
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.set_nodelay(true).unwrap();
+    let parts = [
+        "POST /api/echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+        "5\r\nfirst\r\n",
+        "1\r\n \r\n",
+        "6\r\nsecond\r\n",
+        "0\r\n\r\n",
+    ];
+    for part in parts {
+        client.write_all(part.as_bytes()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    let mut response = String::new();
+    timeout(Duration::from_secs(5), client.read_to_string(&mut response)).await.unwrap().unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+    assert!(response.contains(r#""body":"first second""#), "Got: {}", response);
+    
+    assert!(server.await.unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_stats_endpoint_tracks_connections() {
+    let addr = start_test_server().await;
+    
+    // Three connections that each make a request and close
+    let request = "GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    let mut bytes_in = 0;
+    let mut bytes_out = 0;
+    for _ in 0..3 {
+        let response = make_http_request(addr, request).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        bytes_in += request.len() as u64;
+        bytes_out += response.len() as u64;
+    }
+    
+    // One that closes without sending anything
+    drop(TcpStream::connect(addr).await.unwrap());
+    
+    // A connection is counted as closed just after its socket is, so give
+    // the server a moment to catch up
+    let stats_request = "GET /stats HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    let mut stats = serde_json::Value::Null;
+    let mut stats_requests = 0;
+    for _ in 0..50 {
+        stats_requests += 1;
+        let response = make_http_request(addr, stats_request).await.unwrap();
+        let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+        stats = serde_json::from_str(body).unwrap();
+        if stats["total_closed"] == 4 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    // The connection asking for the stats is the only active one
+    assert_eq!(stats["total_closed"], 4, "Stats: {}", stats);
+    assert_eq!(stats["active_connections"], 1, "Stats: {}", stats);
+    assert_eq!(stats["total_accepted"], 4 + stats_requests, "Stats: {}", stats);
+    assert!(stats["uptime_seconds"].as_f64().unwrap() > 0.0);
+    
+    // The /stats requests add to the traffic too; only the last response
+    // isn't counted yet, since it was still being written
+    assert_eq!(stats["bytes_in"].as_u64().unwrap(), bytes_in + stats_requests * stats_request.len() as u64);
+    assert!(stats["bytes_out"].as_u64().unwrap() >= bytes_out);
+}
+
+#[tokio::test]
+async fn test_rebind_with_reuse_address() {
+    use async_network_server::server::{bind_listener, ServerConfig};
+    
+    let config = ServerConfig { reuse_address: true, ..ServerConfig::default() };
+    let listener = bind_listener("127.0.0.1:0".parse().unwrap(), &config).unwrap();
+    let addr = listener.local_addr().unwrap();
+    
+    // Close a connection from the server side first, which leaves it in
+    // TIME_WAIT on the listening port, just like a server being restarted
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (accepted, _) = listener.accept().await.unwrap();
+    drop(accepted);
+    drop(client);
+    drop(listener);
+    
+    let rebound = bind_listener(addr, &config);
+    assert!(rebound.is_ok(), "Second bind should succeed: {:?}", rebound.err());
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_backlog_is_applied() {
+    use async_network_server::server::{bind_listener, ServerConfig};
+    
+    // Count how many connections complete while nothing calls accept
+    async fn queued_connections(backlog: u32, attempts: usize) -> usize {
+        let config = ServerConfig { backlog, ..ServerConfig::default() };
+        let listener = bind_listener("127.0.0.1:0".parse().unwrap(), &config).unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        let mut connected = Vec::new();
+        for _ in 0..attempts {
+            // A full accept queue makes Linux drop the SYN, so the connect stalls
+            if let Ok(Ok(stream)) = timeout(Duration::from_millis(200), TcpStream::connect(addr)).await {
+                connected.push(stream);
+            }
+        }
+        connected.len()
+    }
+    
+    // Linux queues one more connection than the backlog
+    assert_eq!(queued_connections(1, 4).await, 2);
+    assert_eq!(queued_connections(16, 10).await, 10);
+}
+
+#[tokio::test]
+async fn test_nodelay_is_applied() {
+    use async_network_server::connection::{ConnectionConfig, ConnectionHandler};
+    use tokio::net::TcpListener;
+    
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    
+    for nodelay in [true, false] {
+        let _client = TcpStream::connect(addr).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        
+        let config = ConnectionConfig { nodelay, ..ConnectionConfig::default() };
+        let handler = ConnectionHandler::with_config(0, stream, &config).unwrap();
+        assert_eq!(handler.nodelay().unwrap(), nodelay);
+    }
+}
+
+#[tokio::test]
+async fn test_large_response_written_in_buffer_sized_chunks() {
+    use async_network_server::connection::write_response;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncWrite, BufWriter};
+    
+    /// Records the size of every write that reaches it
+    #[derive(Default)]
+    struct RecordingWriter {
+        writes: Vec<usize>,
+        data: Vec<u8>,
+    }
+    
+    impl AsyncWrite for RecordingWriter {
+        fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            self.writes.push(buf.len());
+            self.data.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+        
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+    
+    let chunk_size = 1024;
+    let response: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    
+    let mut writer = BufWriter::with_capacity(chunk_size, RecordingWriter::default());
+    write_response(&mut writer, &response, chunk_size).await.unwrap();
+    let inner = writer.into_inner();
+    
+    // Nine full chunks, then the 784-byte remainder
+    assert_eq!(inner.writes.len(), 10);
+    assert!(inner.writes[..9].iter().all(|&size| size == chunk_size));
+    assert_eq!(inner.writes[9], 10_000 - 9 * chunk_size);
+    assert_eq!(inner.data, response);
+    
+    // A small response is coalesced into a single write
+    let mut writer = BufWriter::with_capacity(chunk_size, RecordingWriter::default());
+    write_response(&mut writer, b"HTTP/1.1 204 No Content\r\n\r\n", chunk_size).await.unwrap();
+    assert_eq!(writer.into_inner().writes, vec![27]);
+}
+
+#[tokio::test]
+async fn test_bearer_auth_protects_routes() {
+    use async_network_server::middleware::BearerAuth;
+    use async_network_server::server::{Server, ServerConfig};
+    
+    let auth = BearerAuth::new()
+        .with_token("s3cret", "alice")
+        .allow_path("/health");
+    let server = Server::bind("127.0.0.1:0".parse().unwrap(), &ServerConfig::default())
+        .unwrap()
+        .with_middleware(auth);
+    let addr = server.local_addr().unwrap();
+    tokio::spawn(server.run());
+    
+    // No token: rejected before reaching the route
+    let response = make_http_request(addr, "GET /api/status HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 401 Unauthorized"), "Got: {}", response);
+    assert!(response.contains("WWW-Authenticate: Bearer"));
+    
+    // Valid token: routed, with the principal visible to the handler
+    let request = "POST /api/echo HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\nContent-Length: 2\r\n\r\nhi";
+    let response = make_http_request(addr, request).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "Got: {}", response);
+    let body: serde_json::Value = serde_json::from_str(&response[response.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+    assert_eq!(body["principal"], "alice");
+    
+    // Allowlisted path: no token needed, with or without a query string
+    for path in ["/health", "/health?verbose=1"] {
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        let response = make_http_request(addr, &request).await.unwrap();
+        assert!(!response.starts_with("HTTP/1.1 401"), "{} should skip auth, got: {}", path, response);
+    }
+}
+
+#[tokio::test]
+async fn test_bearer_auth_rejects_invalid_tokens() {
+    use async_network_server::handler::HttpHandler;
+    use async_network_server::middleware::BearerAuth;
+    use std::sync::Arc;
+    
+    let handler = HttpHandler::new()
+        .with_middleware(Arc::new(BearerAuth::new().with_token("s3cret", "alice")));
+    
+    for authorization in ["Bearer wrong", "Bearer s3cre", "Basic s3cret", "s3cret"] {
+        let request = format!("GET / HTTP/1.1\r\nAuthorization: {}\r\n\r\n", authorization);
+        let response = handler.handle_request(&request).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 401 Unauthorized"), "{:?} was accepted", authorization);
+    }
+    
+    // Header names are case-insensitive
+    let response = handler.handle_request("GET / HTTP/1.1\r\nauthorization: Bearer s3cret\r\n\r\n").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+}
+
 // TODO: Add more comprehensive integration tests
 // These would include:
 // 1. Testing with a real running server instance
@@ -130,14 +610,17 @@ async fn test_empty_request() {
 // 4. Timeout and error condition testing
 // 5. WebSocket upgrade testing (if implemented)
 
-/// Helper function to start a test server (placeholder)
+/// Helper function to start a test server on an available port
+/// 
+/// The listener is bound before this returns, so the server is ready for
+/// connections right away; it runs in a background task until the test ends.
 async fn start_test_server() -> SocketAddr {
-    // In a real implementation, this would:
-    // 1. Find an available port
-    // 2. Start the server in a background task
-    // 3. Wait for it to be ready to accept connections
-    // 4. Return the address it's listening on
-    "127.0.0.1:0".parse().unwrap()
+    use async_network_server::server::{Server, ServerConfig};
+    
+    let server = Server::bind("127.0.0.1:0".parse().unwrap(), &ServerConfig::default()).unwrap();
+    let addr = server.local_addr().unwrap();
+    tokio::spawn(server.run());
+    addr
 }
 
 /// Helper function to make HTTP requests for testing
@@ -0,0 +1,261 @@
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use shared::{AnalysisResult, IssueSeverity, JobId};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinSet;
+use tracing::info;
+
+use crate::config::AggregatorConfig;
+use crate::storage::Storage;
+
+mod shard;
+
+use shard::{Shard, ShardMessage};
+
+/// Totals over every result the aggregator has recorded
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AggregateSummary {
+    pub jobs: u64,
+    pub files_analyzed: u64,
+    pub lines_of_code: u64,
+    pub total_duration_ms: u64,
+    pub issues_by_severity: BTreeMap<IssueSeverity, u64>,
+    pub performance_insights: u64,
+    pub security_findings: u64,
+}
+
+impl AggregateSummary {
+    fn add(&mut self, result: &AnalysisResult) {
+        self.jobs += 1;
+        self.files_analyzed += result.files_analyzed as u64;
+        self.lines_of_code += result.lines_of_code;
+        self.total_duration_ms += result.duration_ms;
+        for issue in &result.issues {
+            *self.issues_by_severity.entry(issue.severity.clone()).or_default() += 1;
+        }
+        self.performance_insights += result.performance_insights.len() as u64;
+        self.security_findings += result.security_findings.len() as u64;
+    }
+
+    /// Fold another summary over disjoint jobs into this one
+    pub fn merge(&mut self, other: &AggregateSummary) {
+        self.jobs += other.jobs;
+        self.files_analyzed += other.files_analyzed;
+        self.lines_of_code += other.lines_of_code;
+        self.total_duration_ms += other.total_duration_ms;
+        for (severity, count) in &other.issues_by_severity {
+            *self.issues_by_severity.entry(severity.clone()).or_default() += count;
+        }
+        self.performance_insights += other.performance_insights;
+        self.security_findings += other.security_findings;
+    }
+}
+
+/// Stores analysis results and keeps running totals over them
+///
+/// The work is split across `shard_count` shards, each a task of its own,
+/// so results for different jobs are stored and counted in parallel. Each
+/// shard owns a disjoint range of job ids; `summary` asks every shard for
+/// its totals and merges them. A job's results always go to the same shard,
+/// so a result recorded twice is only counted once.
+pub struct Aggregator {
+    storage: Arc<Storage>,
+    shards: Vec<mpsc::Sender<ShardMessage>>,
+    /// The shards' receiving ends, until `run` takes them
+    receivers: Mutex<Option<Vec<mpsc::Receiver<ShardMessage>>>>,
+}
+
+impl Aggregator {
+    pub async fn new(storage: Arc<Storage>, config: AggregatorConfig) -> Result<Self> {
+        if config.shard_count == 0 {
+            anyhow::bail!("Aggregator shard_count cannot be 0");
+        }
+
+        // Each shard buffers up to a batch of results
+        let (shards, receivers) = (0..config.shard_count)
+            .map(|_| mpsc::channel(config.batch_size.max(1) as usize))
+            .unzip();
+
+        Ok(Self {
+            storage,
+            shards,
+            receivers: Mutex::new(Some(receivers)),
+        })
+    }
+
+    /// Get the shard that owns `job_id`
+    ///
+    /// Shards split the range of the id's top 64 bits into equal parts.
+    pub fn shard_for(&self, job_id: JobId) -> usize {
+        let key = (job_id.as_u128() >> 64) as u64;
+        ((key as u128 * self.shards.len() as u128) >> 64) as usize
+    }
+
+    /// Queue a result to be stored and counted by its shard
+    pub async fn record(&self, result: AnalysisResult) -> Result<()> {
+        let shard = self.shard_for(result.job_id);
+        self.shards[shard]
+            .send(ShardMessage::Record(result))
+            .await
+            .map_err(|_| anyhow::anyhow!("Aggregator shard {} has stopped", shard))
+    }
+
+    /// Get the totals over every result recorded so far
+    ///
+    /// Each shard answers once it has handled everything queued to it
+    /// before the request.
+    pub async fn summary(&self) -> Result<AggregateSummary> {
+        let mut summary = AggregateSummary::default();
+
+        for (index, shard) in self.shards.iter().enumerate() {
+            let (reply, response) = oneshot::channel();
+            shard
+                .send(ShardMessage::Summary(reply))
+                .await
+                .map_err(|_| anyhow::anyhow!("Aggregator shard {} has stopped", index))?;
+            let shard_summary = response
+                .await
+                .with_context(|| format!("Aggregator shard {} has stopped", index))?;
+            summary.merge(&shard_summary);
+        }
+
+        Ok(summary)
+    }
+
+    /// Run every shard until the task is aborted
+    ///
+    /// Can only be called once. Dropping the returned future stops the shards.
+    pub async fn run(&self) -> Result<()> {
+        let receivers = self
+            .receivers
+            .lock()
+            .take()
+            .context("Aggregator is already running")?;
+
+        info!("Starting {} aggregator shards", receivers.len());
+        let mut tasks = JoinSet::new();
+        for (index, receiver) in receivers.into_iter().enumerate() {
+            tasks.spawn(Shard::default().run(index, self.storage.clone(), receiver));
+        }
+
+        while let Some(finished) = tasks.join_next().await {
+            finished.context("Aggregator shard panicked")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoordinatorConfig;
+    use shared::{CodeIssue, CodeMetrics, IssueCategory};
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    async fn test_storage(dir: &tempfile::TempDir) -> Arc<Storage> {
+        let mut config = CoordinatorConfig::default().database;
+        config.url = format!("sqlite://{}", dir.path().join("results.db").display());
+        Arc::new(Storage::new(&config).await.unwrap())
+    }
+
+    async fn start_aggregator(storage: Arc<Storage>, shard_count: usize) -> Arc<Aggregator> {
+        let mut config = CoordinatorConfig::default().aggregator;
+        config.shard_count = shard_count;
+        let aggregator = Arc::new(Aggregator::new(storage, config).await.unwrap());
+
+        let running = aggregator.clone();
+        tokio::spawn(async move { running.run().await });
+        aggregator
+    }
+
+    fn test_result(n: u64) -> AnalysisResult {
+        let severities = [IssueSeverity::Info, IssueSeverity::Warning, IssueSeverity::Error, IssueSeverity::Critical];
+        AnalysisResult {
+            job_id: Uuid::new_v4(),
+            completed_at: chrono::Utc::now(),
+            duration_ms: 100 + n,
+            files_analyzed: n as u32 % 7 + 1,
+            lines_of_code: 1000 * n,
+            metrics: CodeMetrics {
+                cyclomatic_complexity: 2.0,
+                maintainability_index: 80.0,
+                technical_debt_ratio: 0.1,
+                test_coverage: None,
+                documentation_coverage: 0.5,
+                dependency_count: 3,
+            },
+            issues: (0..n % 5)
+                .map(|i| CodeIssue {
+                    severity: severities[(n + i) as usize % severities.len()].clone(),
+                    category: IssueCategory::Correctness,
+                    message: "Possible panic".to_string(),
+                    file_path: "src/lib.rs".to_string(),
+                    line_number: i as u32,
+                    column: 1,
+                    suggestion: None,
+                    rule_id: "correctness::unwrap".to_string(),
+                })
+                .collect(),
+            performance_insights: Vec::new(),
+            security_findings: Vec::new(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sharded_summary_matches_single_aggregator() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(&dir).await;
+        let sharded = start_aggregator(storage.clone(), 4).await;
+        let single = start_aggregator(storage, 1).await;
+
+        let results: Vec<AnalysisResult> = (0..40).map(test_result).collect();
+        let shards: HashSet<usize> = results.iter().map(|result| sharded.shard_for(result.job_id)).collect();
+        assert!(shards.len() > 1, "results all landed on shard {:?}", shards);
+
+        for result in &results {
+            sharded.record(result.clone()).await.unwrap();
+            single.record(result.clone()).await.unwrap();
+        }
+
+        let summary = sharded.summary().await.unwrap();
+        assert_eq!(summary, single.summary().await.unwrap());
+        assert_eq!(summary.jobs, 40);
+        assert_eq!(summary.lines_of_code, (0..40).map(|n| 1000 * n).sum::<u64>());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_results_are_counted_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregator = start_aggregator(test_storage(&dir).await, 4).await;
+
+        let results: Vec<AnalysisResult> = (0..10).map(test_result).collect();
+        for result in results.iter().chain(&results) {
+            aggregator.record(result.clone()).await.unwrap();
+        }
+
+        let summary = aggregator.summary().await.unwrap();
+        assert_eq!(summary.jobs, 10);
+        assert_eq!(summary.total_duration_ms, (0..10).map(|n| 100 + n).sum::<u64>());
+    }
+
+    #[tokio::test]
+    async fn test_shards_own_disjoint_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(&dir).await;
+        let mut config = CoordinatorConfig::default().aggregator;
+        config.shard_count = 4;
+        let aggregator = Aggregator::new(storage, config).await.unwrap();
+
+        let id = |top: u64| Uuid::from_u128((top as u128) << 64);
+        assert_eq!(aggregator.shard_for(id(0)), 0);
+        assert_eq!(aggregator.shard_for(id(u64::MAX / 4)), 0);
+        assert_eq!(aggregator.shard_for(id(u64::MAX / 4 + 1)), 1);
+        assert_eq!(aggregator.shard_for(id(u64::MAX / 2 + 1)), 2);
+        assert_eq!(aggregator.shard_for(id(u64::MAX)), 3);
+    }
+}
@@ -0,0 +1,64 @@
+use shared::{AnalysisResult, JobId};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, warn};
+
+use super::AggregateSummary;
+use crate::storage::Storage;
+
+/// Work sent to a shard's task
+pub(super) enum ShardMessage {
+    Record(AnalysisResult),
+    Summary(oneshot::Sender<AggregateSummary>),
+}
+
+/// One shard's state, owned by its task
+///
+/// Every result for a given job goes to the same shard, so remembering the
+/// jobs seen here is enough to count each job once overall.
+#[derive(Default)]
+pub(super) struct Shard {
+    seen: HashSet<JobId>,
+    summary: AggregateSummary,
+}
+
+impl Shard {
+    /// Add a result to the summary, unless its job was already counted
+    pub(super) fn record(&mut self, result: &AnalysisResult) -> bool {
+        if !self.seen.insert(result.job_id) {
+            return false;
+        }
+        self.summary.add(result);
+        true
+    }
+
+    pub(super) fn summary(&self) -> AggregateSummary {
+        self.summary.clone()
+    }
+
+    /// Handle messages until every sender is gone
+    ///
+    /// Results are stored before they're counted, so one that fails to store
+    /// isn't counted and can be recorded again.
+    pub(super) async fn run(mut self, index: usize, storage: Arc<Storage>, mut messages: mpsc::Receiver<ShardMessage>) {
+        while let Some(message) = messages.recv().await {
+            match message {
+                ShardMessage::Record(result) => {
+                    if self.seen.contains(&result.job_id) {
+                        debug!("Shard {} already has a result for job {}", index, result.job_id);
+                        continue;
+                    }
+                    if let Err(e) = storage.store_result(&result).await {
+                        warn!("Shard {} failed to store result for job {}: {}", index, result.job_id, e);
+                        continue;
+                    }
+                    self.record(&result);
+                }
+                ShardMessage::Summary(reply) => {
+                    let _ = reply.send(self.summary());
+                }
+            }
+        }
+    }
+}
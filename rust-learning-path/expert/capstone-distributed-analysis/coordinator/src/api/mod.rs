@@ -0,0 +1,485 @@
+use anyhow::Result;
+use axum::extract::{FromRef, Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use shared::http::{ErrorResponse, JobStatusResponse, SubmitJobRequest, SubmitJobResponse, SubmitResultRequest};
+use shared::{AnalysisJob, JobId, JobPriority};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tower_http::cors::CorsLayer;
+use tracing::info;
+
+use crate::aggregator::Aggregator;
+use crate::config::ServerConfig;
+use crate::scheduler::{JobEntry, Scheduler, SubmitError};
+use crate::storage::Storage;
+
+/// The coordinator's HTTP API
+pub struct Server {
+    state: AppState,
+    config: ServerConfig,
+}
+
+/// What the handlers share; each one extracts just the part it needs
+#[derive(Clone)]
+struct AppState {
+    scheduler: Arc<Scheduler>,
+    aggregator: Arc<Aggregator>,
+    storage: Arc<Storage>,
+}
+
+impl FromRef<AppState> for Arc<Scheduler> {
+    fn from_ref(state: &AppState) -> Self {
+        state.scheduler.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Aggregator> {
+    fn from_ref(state: &AppState) -> Self {
+        state.aggregator.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Storage> {
+    fn from_ref(state: &AppState) -> Self {
+        state.storage.clone()
+    }
+}
+
+impl Server {
+    pub fn new(
+        scheduler: Arc<Scheduler>,
+        aggregator: Arc<Aggregator>,
+        storage: Arc<Storage>,
+        config: ServerConfig,
+    ) -> Self {
+        Self {
+            state: AppState {
+                scheduler,
+                aggregator,
+                storage,
+            },
+            config,
+        }
+    }
+
+    /// Build the router serving every endpoint
+    pub fn router(&self) -> Router {
+        let router = Router::new()
+            .route("/jobs", post(submit_job))
+            .route("/jobs/:id", get(get_job).delete(cancel_job))
+            .route("/jobs/:id/result", get(get_result).post(submit_result))
+            .route("/summary", get(get_summary))
+            .with_state(self.state.clone());
+
+        if self.config.enable_cors {
+            router.layer(CorsLayer::permissive())
+        } else {
+            router
+        }
+    }
+
+    /// Bind the configured address and serve the API on it
+    pub async fn run(&self) -> Result<()> {
+        let address = format!("{}:{}", self.config.bind_address, self.config.port);
+        let listener = TcpListener::bind(&address).await?;
+        info!("API listening on {}", address);
+
+        self.serve(listener).await
+    }
+
+    /// Serve the API on an already bound listener
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        axum::serve(listener, self.router()).await?;
+        Ok(())
+    }
+}
+
+/// POST /jobs
+///
+/// Jobs without a priority are queued as normal priority. A job whose
+/// deadline has already passed is rejected.
+async fn submit_job(State(scheduler): State<Arc<Scheduler>>, Json(request): Json<SubmitJobRequest>) -> Response {
+    let job = AnalysisJob {
+        id: uuid::Uuid::new_v4(),
+        project_path: request.project_path,
+        analysis_config: request.config,
+        priority: request.priority.unwrap_or(JobPriority::Normal),
+        created_at: chrono::Utc::now(),
+        timeout_seconds: None,
+        deadline: request.deadline,
+    };
+
+    match scheduler.submit(job) {
+        Ok(job_id) => {
+            let body = SubmitJobResponse {
+                job_id,
+                estimated_duration_seconds: None,
+            };
+            (StatusCode::CREATED, Json(body)).into_response()
+        }
+        Err(error) => {
+            let status = match error {
+                SubmitError::QueueFull(_) => StatusCode::SERVICE_UNAVAILABLE,
+                SubmitError::Duplicate(_) => StatusCode::CONFLICT,
+                SubmitError::DeadlinePassed { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            };
+            error_response(status, error.to_string())
+        }
+    }
+}
+
+/// GET /jobs/:id
+async fn get_job(State(scheduler): State<Arc<Scheduler>>, Path(job_id): Path<JobId>) -> Response {
+    match scheduler.job(job_id) {
+        Some(entry) => Json(status_response(job_id, entry)).into_response(),
+        None => job_not_found(job_id),
+    }
+}
+
+/// DELETE /jobs/:id
+///
+/// Cancels a queued or running job. Cancelling a job that already finished
+/// changes nothing; either way the response is the job's resulting state.
+async fn cancel_job(State(scheduler): State<Arc<Scheduler>>, Path(job_id): Path<JobId>) -> Response {
+    match scheduler.cancel(job_id) {
+        Some(entry) => Json(status_response(job_id, entry)).into_response(),
+        None => job_not_found(job_id),
+    }
+}
+
+/// POST /jobs/:id/result
+///
+/// A worker reports the result of a job it ran. The job is marked completed
+/// and the result goes to the aggregator to be stored and counted. A result
+/// for a job that isn't running on that worker anymore, because it was
+/// cancelled or timed out, is rejected as stale and not counted.
+async fn submit_result(
+    State(state): State<AppState>,
+    Path(job_id): Path<JobId>,
+    Json(request): Json<SubmitResultRequest>,
+) -> Response {
+    if request.result.job_id != job_id {
+        let error = format!("Result is for job {}, not {}", request.result.job_id, job_id);
+        return error_response(StatusCode::BAD_REQUEST, error);
+    }
+
+    if !state.scheduler.complete(job_id, &request.worker_id, request.result.clone()) {
+        return match state.scheduler.job(job_id) {
+            Some(_) => error_response(
+                StatusCode::CONFLICT,
+                format!("Job {} is not running on worker {}", job_id, request.worker_id),
+            ),
+            None => job_not_found(job_id),
+        };
+    }
+
+    if let Err(e) = state.aggregator.record(request.result).await {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, e.to_string());
+    }
+
+    match state.scheduler.job(job_id) {
+        Some(entry) => Json(status_response(job_id, entry)).into_response(),
+        None => job_not_found(job_id),
+    }
+}
+
+/// GET /jobs/:id/result
+///
+/// Results are read back from storage, so they outlive the scheduler's
+/// record of the job. A submitted result can be read once its aggregator
+/// shard has stored it.
+async fn get_result(State(storage): State<Arc<Storage>>, Path(job_id): Path<JobId>) -> Response {
+    match storage.load_result(job_id).await {
+        Ok(Some(result)) => Json(result).into_response(),
+        Ok(None) => error_response(StatusCode::NOT_FOUND, format!("No result for job {}", job_id)),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// GET /summary
+///
+/// Totals over every result recorded so far
+async fn get_summary(State(aggregator): State<Arc<Aggregator>>) -> Response {
+    match aggregator.summary().await {
+        Ok(summary) => Json(summary).into_response(),
+        Err(e) => error_response(StatusCode::SERVICE_UNAVAILABLE, e.to_string()),
+    }
+}
+
+fn status_response(job_id: JobId, entry: JobEntry) -> JobStatusResponse {
+    JobStatusResponse {
+        job_id,
+        status: entry.status,
+        created_at: entry.job.created_at,
+        updated_at: entry.updated_at,
+    }
+}
+
+fn job_not_found(job_id: JobId) -> Response {
+    error_response(StatusCode::NOT_FOUND, format!("No job with id {}", job_id))
+}
+
+fn error_response(status: StatusCode, error: String) -> Response {
+    let body = ErrorResponse {
+        error,
+        code: status.as_u16() as u32,
+        details: None,
+    };
+    (status, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoordinatorConfig;
+    use crate::storage::Storage;
+    use shared::{AnalysisConfig, AnalysisJob, AnalysisResult, CodeMetrics, CoordinatorMessage, JobPriority, JobStatus};
+    use std::net::SocketAddr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use uuid::Uuid;
+
+    /// Start the API on a free local port, storing results under a
+    /// temporary directory that lives as long as the returned one
+    async fn test_server() -> (SocketAddr, Arc<Scheduler>, tempfile::TempDir) {
+        let mut config = CoordinatorConfig::default();
+        let dir = tempfile::tempdir().unwrap();
+        config.database.url = format!("sqlite://{}", dir.path().join("results.db").display());
+
+        let storage = Arc::new(Storage::new(&config.database).await.unwrap());
+        let aggregator = Arc::new(Aggregator::new(storage.clone(), config.aggregator).await.unwrap());
+        let running = aggregator.clone();
+        tokio::spawn(async move { running.run().await });
+
+        let scheduler = Arc::new(Scheduler::new(config.scheduler));
+        let server = Server::new(scheduler.clone(), aggregator, storage, config.server);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { server.serve(listener).await });
+
+        (address, scheduler, dir)
+    }
+
+    fn test_job() -> AnalysisJob {
+        AnalysisJob {
+            id: Uuid::new_v4(),
+            project_path: "test_project".to_string(),
+            analysis_config: AnalysisConfig::default(),
+            priority: JobPriority::Normal,
+            created_at: chrono::Utc::now(),
+            timeout_seconds: None,
+            deadline: None,
+        }
+    }
+
+    fn test_result(job_id: JobId) -> AnalysisResult {
+        AnalysisResult {
+            job_id,
+            completed_at: chrono::Utc::now(),
+            duration_ms: 10,
+            files_analyzed: 1,
+            lines_of_code: 100,
+            metrics: CodeMetrics {
+                cyclomatic_complexity: 1.0,
+                maintainability_index: 100.0,
+                technical_debt_ratio: 0.0,
+                test_coverage: None,
+                documentation_coverage: 1.0,
+                dependency_count: 0,
+            },
+            issues: Vec::new(),
+            performance_insights: Vec::new(),
+            security_findings: Vec::new(),
+        }
+    }
+
+    /// Send a request and decode the response's status and body
+    async fn send(address: SocketAddr, method: &str, path: &str, body: Option<serde_json::Value>) -> (u16, serde_json::Value) {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    async fn delete_job(address: SocketAddr, job_id: JobId) -> (u16, serde_json::Value) {
+        send(address, "DELETE", &format!("/jobs/{}", job_id), None).await
+    }
+
+    fn submit_request(priority: &str, deadline: chrono::DateTime<chrono::Utc>) -> serde_json::Value {
+        serde_json::json!({
+            "project_path": "test_project",
+            "config": AnalysisConfig::default(),
+            "priority": priority,
+            "deadline": deadline,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_with_priority_and_deadline() {
+        let (address, scheduler, _dir) = test_server().await;
+        let deadline = chrono::Utc::now() + chrono::Duration::minutes(10);
+
+        let (status, body) = send(address, "POST", "/jobs", Some(submit_request("High", deadline))).await;
+        assert_eq!(status, 201);
+
+        let job_id: JobId = body["job_id"].as_str().unwrap().parse().unwrap();
+        let job = scheduler.job(job_id).unwrap().job;
+        assert_eq!(job.priority, JobPriority::High);
+        assert_eq!(job.deadline, Some(deadline));
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_past_deadline_is_rejected() {
+        let (address, scheduler, _dir) = test_server().await;
+        let deadline = chrono::Utc::now() - chrono::Duration::minutes(1);
+
+        let (status, body) = send(address, "POST", "/jobs", Some(submit_request("Critical", deadline))).await;
+        assert_eq!(status, 422);
+        assert!(body["error"].as_str().unwrap().contains("deadline"), "{}", body);
+        assert_eq!(scheduler.queued_jobs(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job_removes_it_from_queue() {
+        let (address, scheduler, _dir) = test_server().await;
+        let job_id = scheduler.submit(test_job()).unwrap();
+        let other = scheduler.submit(test_job()).unwrap();
+
+        let (status, body) = delete_job(address, job_id).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["job_id"], job_id.to_string());
+        assert_eq!(body["status"], "Cancelled");
+
+        // Only the other job is left to hand out
+        assert_eq!(scheduler.queued_jobs(), 1);
+        assert_eq!(scheduler.dispatch(&"worker-1".to_string()).unwrap().id, other);
+        assert!(scheduler.dispatch(&"worker-1".to_string()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_job_signals_worker() {
+        let (address, scheduler, _dir) = test_server().await;
+        let worker = "worker-1".to_string();
+        let mut messages = scheduler.register_worker(worker.clone());
+        let job_id = scheduler.submit(test_job()).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        let (status, body) = delete_job(address, job_id).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "Cancelled");
+
+        match messages.try_recv() {
+            Ok(CoordinatorMessage::CancelJob { job_id: cancelled }) => assert_eq!(cancelled, job_id),
+            other => panic!("expected a cancel message, got {:?}", other),
+        }
+
+        // Whatever the worker reports afterwards doesn't undo the cancel
+        assert!(!scheduler.complete(job_id, &worker, test_result(job_id)));
+        assert!(matches!(scheduler.status(job_id), Some(JobStatus::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_completed_job_is_a_no_op() {
+        let (address, scheduler, _dir) = test_server().await;
+        let worker = "worker-1".to_string();
+        let mut messages = scheduler.register_worker(worker.clone());
+        let job_id = scheduler.submit(test_job()).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+        assert!(scheduler.complete(job_id, &worker, test_result(job_id)));
+
+        let (status, body) = delete_job(address, job_id).await;
+        assert_eq!(status, 200);
+        assert!(body["status"]["Completed"].is_object(), "{}", body);
+        assert!(messages.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unknown_job_is_not_found() {
+        let (address, _, _dir) = test_server().await;
+
+        let (status, body) = delete_job(address, Uuid::new_v4()).await;
+        assert_eq!(status, 404);
+        assert_eq!(body["code"], 404);
+    }
+
+    async fn submit_result(address: SocketAddr, worker: &str, result: &AnalysisResult) -> (u16, serde_json::Value) {
+        let body = serde_json::json!({ "worker_id": worker, "result": result });
+        send(address, "POST", &format!("/jobs/{}/result", result.job_id), Some(body)).await
+    }
+
+    #[tokio::test]
+    async fn test_submitted_result_is_counted_in_summary() {
+        let (address, scheduler, _dir) = test_server().await;
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job()).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        let (status, body) = submit_result(address, &worker, &test_result(job_id)).await;
+        assert_eq!(status, 200);
+        assert!(body["status"]["Completed"].is_object(), "{}", body);
+
+        let (status, summary) = send(address, "GET", "/summary", None).await;
+        assert_eq!(status, 200);
+        assert_eq!(summary["jobs"], 1);
+        assert_eq!(summary["lines_of_code"], 100);
+    }
+
+    #[tokio::test]
+    async fn test_result_is_read_back_from_storage() {
+        let (address, scheduler, _dir) = test_server().await;
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job()).unwrap();
+
+        let (status, _) = send(address, "GET", &format!("/jobs/{}/result", job_id), None).await;
+        assert_eq!(status, 404);
+
+        scheduler.dispatch(&worker).unwrap();
+        assert_eq!(submit_result(address, &worker, &test_result(job_id)).await.0, 200);
+        // The summary is answered after the shard has stored the result
+        send(address, "GET", "/summary", None).await;
+
+        let (status, body) = send(address, "GET", &format!("/jobs/{}/result", job_id), None).await;
+        assert_eq!(status, 200);
+        let result: AnalysisResult = serde_json::from_value(body).unwrap();
+        assert_eq!(result.job_id, job_id);
+        assert_eq!(result.lines_of_code, 100);
+    }
+
+    #[tokio::test]
+    async fn test_stale_result_is_rejected_and_not_counted() {
+        let (address, scheduler, _dir) = test_server().await;
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job()).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+        let result = test_result(job_id);
+
+        // Only the worker running the job can report it, and only once
+        let (status, _) = submit_result(address, "worker-2", &result).await;
+        assert_eq!(status, 409);
+        assert_eq!(submit_result(address, &worker, &result).await.0, 200);
+        assert_eq!(submit_result(address, &worker, &result).await.0, 409);
+
+        let (_, summary) = send(address, "GET", "/summary", None).await;
+        assert_eq!(summary["jobs"], 1);
+
+        let (status, _) = submit_result(address, &worker, &test_result(Uuid::new_v4())).await;
+        assert_eq!(status, 404);
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Source of the current time for the coordinator's timeouts and deadlines
+///
+/// Production code uses `SystemClock`; tests inject a `ManualClock` so they
+/// can move past a timeout without actually waiting for it.
+pub trait Clock: Send + Sync {
+    /// Monotonic time, for measuring how long something has run
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, for comparing against deadlines clients give
+    fn utc_now(&self) -> DateTime<Utc>;
+}
+
+/// The real, monotonic clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[cfg(test)]
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<(Instant, DateTime<Utc>)>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Mutex::new((Instant::now(), Utc::now())),
+        }
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock();
+        now.0 += duration;
+        now.1 += chrono::Duration::from_std(duration).expect("duration out of range");
+    }
+}
+
+#[cfg(test)]
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.lock().0
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.now.lock().1
+    }
+}
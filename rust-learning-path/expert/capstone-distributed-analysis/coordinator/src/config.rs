@@ -27,16 +27,35 @@ pub struct DatabaseConfig {
     pub max_connections: u32,
     pub connection_timeout_seconds: u64,
     pub enable_migrations: bool,
+    /// Result payloads smaller than this are stored uncompressed
+    #[serde(default = "default_compression_threshold_bytes")]
+    pub compression_threshold_bytes: usize,
+}
+
+fn default_compression_threshold_bytes() -> usize {
+    4096
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchedulerConfig {
     pub max_queue_size: u32,
     pub worker_timeout_seconds: u64,
+    /// Longest a job may run before it's reaped, unless the job sets its own
     pub job_timeout_seconds: u64,
     pub heartbeat_interval_seconds: u64,
+    /// How often timed out jobs are reaped
     pub cleanup_interval_seconds: u64,
     pub load_balancing_strategy: LoadBalancingStrategy,
+    /// Put reaped jobs back in the queue instead of failing them
+    #[serde(default)]
+    pub requeue_timed_out_jobs: bool,
+    /// How many times a job may be dispatched before a timeout fails it for good
+    #[serde(default = "default_max_job_attempts")]
+    pub max_job_attempts: u32,
+}
+
+fn default_max_job_attempts() -> u32 {
+    3
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +64,13 @@ pub struct AggregatorConfig {
     pub result_retention_days: u32,
     pub batch_size: u32,
     pub flush_interval_seconds: u64,
+    /// How many tasks results are spread across, by job id
+    #[serde(default = "default_shard_count")]
+    pub shard_count: usize,
+}
+
+fn default_shard_count() -> usize {
+    4
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +105,7 @@ impl Default for CoordinatorConfig {
                 max_connections: 10,
                 connection_timeout_seconds: 30,
                 enable_migrations: true,
+                compression_threshold_bytes: default_compression_threshold_bytes(),
             },
             scheduler: SchedulerConfig {
                 max_queue_size: 10000,
@@ -87,12 +114,15 @@ impl Default for CoordinatorConfig {
                 heartbeat_interval_seconds: 30,
                 cleanup_interval_seconds: 300,
                 load_balancing_strategy: LoadBalancingStrategy::LeastLoaded,
+                requeue_timed_out_jobs: false,
+                max_job_attempts: default_max_job_attempts(),
             },
             aggregator: AggregatorConfig {
                 result_cache_size: 1000,
                 result_retention_days: 30,
                 batch_size: 100,
                 flush_interval_seconds: 60,
+                shard_count: default_shard_count(),
             },
             metrics: MetricsConfig {
                 enabled: true,
@@ -141,6 +171,22 @@ impl CoordinatorConfig {
             anyhow::bail!("Scheduler max_queue_size cannot be 0");
         }
 
+        if self.scheduler.job_timeout_seconds == 0 {
+            anyhow::bail!("Scheduler job_timeout_seconds cannot be 0");
+        }
+
+        if self.scheduler.cleanup_interval_seconds == 0 {
+            anyhow::bail!("Scheduler cleanup_interval_seconds cannot be 0");
+        }
+
+        if self.scheduler.max_job_attempts == 0 {
+            anyhow::bail!("Scheduler max_job_attempts cannot be 0");
+        }
+
+        if self.aggregator.shard_count == 0 {
+            anyhow::bail!("Aggregator shard_count cannot be 0");
+        }
+
         if self.metrics.enabled && self.metrics.port == 0 {
             anyhow::bail!("Metrics port cannot be 0 when metrics are enabled");
         }
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use clap::Parser;
 use std::sync::Arc;
+use shared::observability::{self, LogFormat};
 use tracing::{info, error};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod api;
 mod scheduler;
@@ -10,6 +10,7 @@ mod aggregator;
 mod storage;
 mod metrics;
 mod config;
+mod clock;
 
 use crate::config::CoordinatorConfig;
 
@@ -23,6 +24,10 @@ struct Args {
     #[arg(short, long, default_value = "info")]
     log_level: String,
     
+    /// Log line format: plain or json
+    #[arg(long, default_value = "plain")]
+    log_format: LogFormat,
+    
     #[arg(long)]
     bind_address: Option<String>,
     
@@ -35,13 +40,8 @@ async fn main() -> Result<()> {
     let args = Args::parse();
     
     // Initialize tracing
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| format!("coordinator={}", args.log_level).into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    observability::init(&format!("coordinator={}", args.log_level), args.log_format)?;
+    observability::install_panic_hook();
 
     info!("Starting Distributed Code Analysis Coordinator");
 
@@ -67,10 +67,7 @@ async fn main() -> Result<()> {
     info!("Initialized metrics collection");
 
     // Initialize scheduler
-    let scheduler = Arc::new(scheduler::Scheduler::new(
-        storage.clone(),
-        config.scheduler.clone(),
-    ).await?);
+    let scheduler = Arc::new(scheduler::Scheduler::new(config.scheduler.clone()));
     info!("Initialized job scheduler");
 
     // Initialize results aggregator
@@ -132,12 +129,4 @@ async fn main() -> Result<()> {
 
     info!("Coordinator shutdown complete");
     Ok(())
-}
-
-/// Initialize panic handler for better error reporting
-fn init_panic_handler() {
-    std::panic::set_hook(Box::new(|panic_info| {
-        let backtrace = std::backtrace::Backtrace::capture();
-        error!("Panic occurred: {}\nBacktrace:\n{}", panic_info, backtrace);
-    }));
 }
\ No newline at end of file
@@ -0,0 +1,418 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use shared::{AnalysisJob, AnalysisResult, CoordinatorMessage, JobId, JobPriority, JobStatus, WorkerId};
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::SchedulerConfig;
+
+mod reaper;
+
+/// Why a job couldn't be submitted
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    #[error("Job queue is full ({0} jobs)")]
+    QueueFull(usize),
+    #[error("Job {0} was already submitted")]
+    Duplicate(JobId),
+    #[error("Job {job_id} deadline {deadline} has already passed")]
+    DeadlinePassed { job_id: JobId, deadline: DateTime<Utc> },
+}
+
+/// Queues submitted jobs, hands them out to workers, and tracks their status
+///
+/// Queued jobs are dispatched highest priority first, then earliest
+/// deadline first, with jobs that have no deadline last; jobs that tie are
+/// dispatched in the order they were queued. A job still queued when its
+/// deadline passes is failed instead of dispatched.
+///
+/// All state sits behind one lock, so a job's status and its place in the
+/// queue always change together. Status updates from workers are only
+/// recorded while the job is still running on the worker sending them; see
+/// `complete`.
+pub struct Scheduler {
+    config: SchedulerConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<SchedulerState>,
+}
+
+struct SchedulerState {
+    /// Jobs waiting to be dispatched, in dispatch order
+    queue: VecDeque<JobId>,
+    /// Every job the scheduler knows about, whatever its status
+    jobs: HashMap<JobId, JobEntry>,
+    /// Channels to the connected workers
+    workers: HashMap<WorkerId, mpsc::UnboundedSender<CoordinatorMessage>>,
+}
+
+impl SchedulerState {
+    /// Put a job in the queue behind every job that goes before or with it
+    fn enqueue(&mut self, job_id: JobId) {
+        let jobs = &self.jobs;
+        let key = dispatch_key(&jobs[&job_id].job);
+        let position = self.queue.partition_point(|queued| dispatch_key(&jobs[queued].job) <= key);
+        self.queue.insert(position, job_id);
+    }
+}
+
+/// Sort key for the queue: higher priority first, then earlier deadline,
+/// then no deadline
+fn dispatch_key(job: &AnalysisJob) -> (Reverse<JobPriority>, bool, Option<DateTime<Utc>>) {
+    (Reverse(job.priority), job.deadline.is_none(), job.deadline)
+}
+
+/// Reason recorded on a job that was still queued at its deadline
+fn deadline_exceeded(deadline: DateTime<Utc>) -> String {
+    format!("Deadline exceeded: job was still queued at {}", deadline)
+}
+
+/// A job and what the scheduler knows about it
+#[derive(Debug, Clone)]
+pub struct JobEntry {
+    pub job: AnalysisJob,
+    pub status: JobStatus,
+    /// How many times the job has been dispatched to a worker
+    pub attempts: u32,
+    /// Why the previous attempt failed, if it did and the job was requeued
+    pub last_error: Option<String>,
+    /// When the status last changed
+    pub updated_at: DateTime<Utc>,
+    /// When the current attempt started, while the job is running
+    started_at: Option<Instant>,
+}
+
+impl Scheduler {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Create a scheduler that measures job run times with `clock`
+    pub fn with_clock(config: SchedulerConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            clock,
+            state: Mutex::new(SchedulerState {
+                queue: VecDeque::new(),
+                jobs: HashMap::new(),
+                workers: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Queue a job for dispatch
+    ///
+    /// A job whose deadline has already passed is rejected rather than
+    /// queued, since it could never start in time.
+    pub fn submit(&self, job: AnalysisJob) -> Result<JobId, SubmitError> {
+        let mut state = self.state.lock();
+
+        if state.queue.len() >= self.config.max_queue_size as usize {
+            return Err(SubmitError::QueueFull(state.queue.len()));
+        }
+        if state.jobs.contains_key(&job.id) {
+            return Err(SubmitError::Duplicate(job.id));
+        }
+        if let Some(deadline) = job.deadline.filter(|deadline| *deadline <= self.clock.utc_now()) {
+            return Err(SubmitError::DeadlinePassed { job_id: job.id, deadline });
+        }
+
+        let job_id = job.id;
+        state.jobs.insert(job_id, JobEntry {
+            job,
+            status: JobStatus::Queued,
+            attempts: 0,
+            last_error: None,
+            updated_at: self.clock.utc_now(),
+            started_at: None,
+        });
+        state.enqueue(job_id);
+
+        debug!("Queued job {}", job_id);
+        Ok(job_id)
+    }
+
+    /// Hand the next queued job to `worker_id`, marking it running
+    ///
+    /// Jobs found past their deadline on the way are failed and skipped.
+    pub fn dispatch(&self, worker_id: &WorkerId) -> Option<AnalysisJob> {
+        let now = self.clock.now();
+        let utc_now = self.clock.utc_now();
+        let mut state = self.state.lock();
+
+        while let Some(job_id) = state.queue.pop_front() {
+            let Some(entry) = state.jobs.get_mut(&job_id) else {
+                continue;
+            };
+            if !matches!(entry.status, JobStatus::Queued) {
+                continue;
+            }
+            if let Some(deadline) = entry.job.deadline.filter(|deadline| *deadline <= utc_now) {
+                warn!("Job {} missed its deadline {}", job_id, deadline);
+                entry.status = JobStatus::Failed { error: deadline_exceeded(deadline) };
+                entry.updated_at = utc_now;
+                continue;
+            }
+
+            entry.status = JobStatus::Running {
+                worker_id: worker_id.clone(),
+                progress: 0.0,
+            };
+            entry.attempts += 1;
+            entry.updated_at = utc_now;
+            entry.started_at = Some(now);
+
+            info!("Dispatched job {} to worker {} (attempt {})", job_id, worker_id, entry.attempts);
+            return Some(entry.job.clone());
+        }
+
+        None
+    }
+
+    /// Record a job's result, as reported by the worker that ran it
+    ///
+    /// Returns false, recording nothing, if the job isn't running on
+    /// `worker_id` anymore: a completion that arrives after the reaper timed
+    /// the job out is stale, and recording it would count the job twice.
+    pub fn complete(&self, job_id: JobId, worker_id: &WorkerId, result: AnalysisResult) -> bool {
+        self.finish(job_id, worker_id, JobStatus::Completed { result })
+    }
+
+    /// Record that a job failed, as reported by the worker that ran it
+    ///
+    /// Returns false, recording nothing, if the job isn't running on
+    /// `worker_id` anymore; see `complete`.
+    pub fn fail(&self, job_id: JobId, worker_id: &WorkerId, error: String) -> bool {
+        self.finish(job_id, worker_id, JobStatus::Failed { error })
+    }
+
+    fn finish(&self, job_id: JobId, worker_id: &WorkerId, status: JobStatus) -> bool {
+        let mut state = self.state.lock();
+        let Some(entry) = state.jobs.get_mut(&job_id) else {
+            warn!("Worker {} reported on unknown job {}", worker_id, job_id);
+            return false;
+        };
+
+        match &entry.status {
+            JobStatus::Running { worker_id: assigned, .. } if assigned == worker_id => {
+                entry.status = status;
+                entry.updated_at = self.clock.utc_now();
+                entry.started_at = None;
+                true
+            }
+            current => {
+                warn!(
+                    "Ignoring stale report for job {} from worker {}; job is {:?}",
+                    job_id, worker_id, current
+                );
+                false
+            }
+        }
+    }
+
+    /// Cancel a job, returning what the scheduler knows about it afterwards
+    ///
+    /// A queued job is taken out of the queue. A running job is marked
+    /// cancelled and its worker is told to stop; whatever the worker reports
+    /// afterwards is ignored. A job that already finished is left as it is.
+    /// Returns `None` if there's no such job.
+    pub fn cancel(&self, job_id: JobId) -> Option<JobEntry> {
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        let entry = state.jobs.get_mut(&job_id)?;
+
+        match &entry.status {
+            JobStatus::Queued => {
+                state.queue.retain(|queued| *queued != job_id);
+                info!("Cancelled queued job {}", job_id);
+            }
+            JobStatus::Running { worker_id, .. } => {
+                let signalled = state.workers.get(worker_id)
+                    .is_some_and(|worker| worker.send(CoordinatorMessage::CancelJob { job_id }).is_ok());
+                if signalled {
+                    info!("Cancelled job {}, asked worker {} to stop", job_id, worker_id);
+                } else {
+                    warn!("Cancelled job {}, but worker {} isn't connected", job_id, worker_id);
+                }
+                entry.started_at = None;
+            }
+            _ => return Some(entry.clone()),
+        }
+
+        entry.status = JobStatus::Cancelled;
+        entry.updated_at = self.clock.utc_now();
+        Some(entry.clone())
+    }
+
+    /// Connect a worker, returning the channel of messages for it
+    ///
+    /// Registering a worker again replaces its previous channel.
+    pub fn register_worker(&self, worker_id: WorkerId) -> mpsc::UnboundedReceiver<CoordinatorMessage> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.state.lock().workers.insert(worker_id, sender);
+        receiver
+    }
+
+    /// Disconnect a worker
+    pub fn unregister_worker(&self, worker_id: &WorkerId) {
+        self.state.lock().workers.remove(worker_id);
+    }
+
+    /// Get a job's current status
+    pub fn status(&self, job_id: JobId) -> Option<JobStatus> {
+        self.state.lock().jobs.get(&job_id).map(|entry| entry.status.clone())
+    }
+
+    /// Get everything the scheduler knows about a job
+    pub fn job(&self, job_id: JobId) -> Option<JobEntry> {
+        self.state.lock().jobs.get(&job_id).cloned()
+    }
+
+    /// Get the number of jobs waiting to be dispatched
+    pub fn queued_jobs(&self) -> usize {
+        self.state.lock().queue.len()
+    }
+
+    /// The longest a job may run before the reaper times it out
+    ///
+    /// A job's own `timeout_seconds` takes precedence over the configured
+    /// default.
+    pub fn job_timeout(&self, job: &AnalysisJob) -> Duration {
+        Duration::from_secs(job.timeout_seconds.unwrap_or(self.config.job_timeout_seconds))
+    }
+
+    /// Run the scheduler's background work until the task is aborted
+    ///
+    /// Every cleanup interval, jobs that have run longer than their timeout
+    /// are reaped. This is independent of heartbeats, so it also catches
+    /// jobs on workers that died without anyone noticing. Queued jobs past
+    /// their deadline are failed at the same time, rather than waiting for
+    /// a dispatch to come across them.
+    pub async fn run(&self) -> Result<()> {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.cleanup_interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            let reaped = self.reap_expired();
+            if !reaped.is_empty() {
+                info!("Reaped {} timed out jobs", reaped.len());
+            }
+
+            let missed = self.fail_missed_deadlines();
+            if !missed.is_empty() {
+                info!("Failed {} jobs past their deadline", missed.len());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::config::CoordinatorConfig;
+    use shared::AnalysisConfig;
+    use uuid::Uuid;
+
+    fn test_job(priority: JobPriority, deadline: Option<DateTime<Utc>>) -> AnalysisJob {
+        AnalysisJob {
+            id: Uuid::new_v4(),
+            project_path: "test_project".to_string(),
+            analysis_config: AnalysisConfig::default(),
+            priority,
+            created_at: Utc::now(),
+            timeout_seconds: None,
+            deadline,
+        }
+    }
+
+    fn test_scheduler() -> (Scheduler, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new());
+        (Scheduler::with_clock(CoordinatorConfig::default().scheduler, clock.clone()), clock)
+    }
+
+    #[test]
+    fn test_dispatch_order_by_priority_then_deadline() {
+        let (scheduler, clock) = test_scheduler();
+        let in_minutes = |minutes| Some(clock.utc_now() + chrono::Duration::minutes(minutes));
+
+        let low = scheduler.submit(test_job(JobPriority::Low, in_minutes(1))).unwrap();
+        let normal_late = scheduler.submit(test_job(JobPriority::Normal, in_minutes(30))).unwrap();
+        let normal_none = scheduler.submit(test_job(JobPriority::Normal, None)).unwrap();
+        let normal_soon = scheduler.submit(test_job(JobPriority::Normal, in_minutes(5))).unwrap();
+        let normal_none_2 = scheduler.submit(test_job(JobPriority::Normal, None)).unwrap();
+        let critical = scheduler.submit(test_job(JobPriority::Critical, None)).unwrap();
+        let high = scheduler.submit(test_job(JobPriority::High, in_minutes(60))).unwrap();
+
+        let worker = "worker-1".to_string();
+        let order: Vec<JobId> = std::iter::from_fn(|| scheduler.dispatch(&worker).map(|job| job.id)).collect();
+
+        assert_eq!(order, vec![critical, high, normal_soon, normal_late, normal_none, normal_none_2, low]);
+    }
+
+    #[test]
+    fn test_job_past_deadline_is_rejected() {
+        let (scheduler, clock) = test_scheduler();
+        let deadline = clock.utc_now() - chrono::Duration::seconds(1);
+
+        let result = scheduler.submit(test_job(JobPriority::Critical, Some(deadline)));
+        assert!(matches!(result, Err(SubmitError::DeadlinePassed { .. })));
+        assert_eq!(scheduler.queued_jobs(), 0);
+    }
+
+    #[test]
+    fn test_queued_job_fails_at_deadline() {
+        let (scheduler, clock) = test_scheduler();
+        let deadline = clock.utc_now() + chrono::Duration::seconds(30);
+        let urgent = scheduler.submit(test_job(JobPriority::High, Some(deadline))).unwrap();
+        let other = scheduler.submit(test_job(JobPriority::Normal, None)).unwrap();
+
+        clock.advance(Duration::from_secs(10));
+        assert!(scheduler.fail_missed_deadlines().is_empty());
+
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(scheduler.fail_missed_deadlines(), vec![urgent]);
+        match scheduler.status(urgent) {
+            Some(JobStatus::Failed { error }) => assert!(error.contains("Deadline exceeded"), "{}", error),
+            other => panic!("expected a failed job, got {:?}", other),
+        }
+        assert_eq!(scheduler.dispatch(&"worker-1".to_string()).unwrap().id, other);
+    }
+
+    #[test]
+    fn test_dispatch_skips_job_past_deadline() {
+        let (scheduler, clock) = test_scheduler();
+        let deadline = clock.utc_now() + chrono::Duration::seconds(30);
+        let urgent = scheduler.submit(test_job(JobPriority::High, Some(deadline))).unwrap();
+        let other = scheduler.submit(test_job(JobPriority::Normal, None)).unwrap();
+
+        // No sweep has run yet, but the job isn't handed out late
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(scheduler.dispatch(&"worker-1".to_string()).unwrap().id, other);
+        assert!(matches!(scheduler.status(urgent), Some(JobStatus::Failed { .. })));
+    }
+
+    #[test]
+    fn test_updates_are_stamped_with_the_injected_clock() {
+        let (scheduler, clock) = test_scheduler();
+        let updated_at = |job_id| scheduler.job(job_id).unwrap().updated_at;
+
+        clock.advance(Duration::from_secs(60));
+        let job_id = scheduler.submit(test_job(JobPriority::Normal, None)).unwrap();
+        assert_eq!(updated_at(job_id), clock.utc_now());
+
+        clock.advance(Duration::from_secs(5));
+        scheduler.dispatch(&"worker-1".to_string()).unwrap();
+        assert_eq!(updated_at(job_id), clock.utc_now());
+
+        clock.advance(Duration::from_secs(5));
+        scheduler.cancel(job_id).unwrap();
+        assert_eq!(updated_at(job_id), clock.utc_now());
+    }
+}
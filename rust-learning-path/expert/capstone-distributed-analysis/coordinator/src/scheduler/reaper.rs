@@ -0,0 +1,263 @@
+use shared::{JobId, JobStatus, WorkerId};
+use tracing::warn;
+
+use super::{deadline_exceeded, Scheduler};
+
+/// A running job that the reaper timed out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReapedJob {
+    pub job_id: JobId,
+    /// The worker the job was running on
+    pub worker_id: WorkerId,
+    /// Whether the job went back into the queue rather than being failed
+    pub requeued: bool,
+}
+
+impl Scheduler {
+    /// Time out every running job that has run longer than its timeout
+    ///
+    /// A timed out job is failed with a timeout reason, or, if requeueing is
+    /// enabled and it has attempts left, put back in the queue behind the
+    /// jobs of the same priority, with the reason kept in `last_error`.
+    /// Either way it no longer counts as running on its worker, so a result
+    /// the worker sends afterwards is ignored rather than recorded on top of
+    /// the timeout.
+    pub fn reap_expired(&self) -> Vec<ReapedJob> {
+        let now = self.clock.now();
+        let mut state = self.state.lock();
+        let mut reaped = Vec::new();
+
+        for (job_id, entry) in state.jobs.iter_mut() {
+            let (JobStatus::Running { worker_id, .. }, Some(started_at)) = (&entry.status, entry.started_at) else {
+                continue;
+            };
+
+            let timeout = self.job_timeout(&entry.job);
+            let elapsed = now.saturating_duration_since(started_at);
+            if elapsed < timeout {
+                continue;
+            }
+
+            let worker_id = worker_id.clone();
+            let reason = format!(
+                "Job timed out after {}s on worker {} (limit {}s)",
+                elapsed.as_secs(),
+                worker_id,
+                timeout.as_secs()
+            );
+            warn!("Reaping job {}: {}", job_id, reason);
+
+            let requeued = self.config.requeue_timed_out_jobs
+                && entry.attempts < self.config.max_job_attempts;
+            entry.started_at = None;
+            entry.updated_at = self.clock.utc_now();
+            if requeued {
+                entry.status = JobStatus::Queued;
+                entry.last_error = Some(reason);
+            } else {
+                entry.status = JobStatus::Failed { error: reason };
+            }
+
+            reaped.push(ReapedJob {
+                job_id: *job_id,
+                worker_id,
+                requeued,
+            });
+        }
+
+        for job in reaped.iter().filter(|job| job.requeued) {
+            state.enqueue(job.job_id);
+        }
+
+        reaped
+    }
+
+    /// Fail every queued job whose deadline has passed, returning their ids
+    pub fn fail_missed_deadlines(&self) -> Vec<JobId> {
+        let now = self.clock.utc_now();
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        let mut missed = Vec::new();
+
+        state.queue.retain(|job_id| {
+            let Some(entry) = state.jobs.get_mut(job_id) else {
+                return false;
+            };
+            match entry.job.deadline {
+                Some(deadline) if deadline <= now => {
+                    warn!("Job {} missed its deadline {}", job_id, deadline);
+                    entry.status = JobStatus::Failed { error: deadline_exceeded(deadline) };
+                    entry.updated_at = now;
+                    missed.push(*job_id);
+                    false
+                }
+                _ => true,
+            }
+        });
+
+        missed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::config::CoordinatorConfig;
+    use shared::{AnalysisConfig, AnalysisJob, AnalysisResult, CodeMetrics, JobPriority};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn test_job(timeout_seconds: Option<u64>) -> AnalysisJob {
+        AnalysisJob {
+            id: Uuid::new_v4(),
+            project_path: "test_project".to_string(),
+            analysis_config: AnalysisConfig::default(),
+            priority: JobPriority::Normal,
+            created_at: chrono::Utc::now(),
+            timeout_seconds,
+            deadline: None,
+        }
+    }
+
+    fn test_result(job_id: JobId) -> AnalysisResult {
+        AnalysisResult {
+            job_id,
+            completed_at: chrono::Utc::now(),
+            duration_ms: 10,
+            files_analyzed: 1,
+            lines_of_code: 100,
+            metrics: CodeMetrics {
+                cyclomatic_complexity: 1.0,
+                maintainability_index: 100.0,
+                technical_debt_ratio: 0.0,
+                test_coverage: None,
+                documentation_coverage: 1.0,
+                dependency_count: 0,
+            },
+            issues: Vec::new(),
+            performance_insights: Vec::new(),
+            security_findings: Vec::new(),
+        }
+    }
+
+    fn test_scheduler(requeue: bool) -> (Scheduler, Arc<ManualClock>) {
+        let mut config = CoordinatorConfig::default().scheduler;
+        config.job_timeout_seconds = 60;
+        config.requeue_timed_out_jobs = requeue;
+        config.max_job_attempts = 2;
+
+        let clock = Arc::new(ManualClock::new());
+        (Scheduler::with_clock(config, clock.clone()), clock)
+    }
+
+    #[test]
+    fn test_job_past_timeout_is_failed() {
+        let (scheduler, clock) = test_scheduler(false);
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job(None)).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        // Not reaped before the timeout
+        clock.advance(Duration::from_secs(59));
+        assert!(scheduler.reap_expired().is_empty());
+
+        clock.advance(Duration::from_secs(1));
+        let reaped = scheduler.reap_expired();
+        assert_eq!(reaped, vec![ReapedJob { job_id, worker_id: worker, requeued: false }]);
+
+        match scheduler.status(job_id) {
+            Some(JobStatus::Failed { error }) => assert!(error.contains("timed out"), "{}", error),
+            other => panic!("expected a failed job, got {:?}", other),
+        }
+        assert_eq!(scheduler.queued_jobs(), 0);
+
+        // Reaping again finds nothing more to do
+        clock.advance(Duration::from_secs(600));
+        assert!(scheduler.reap_expired().is_empty());
+    }
+
+    #[test]
+    fn test_job_timeout_overrides_config() {
+        let (scheduler, clock) = test_scheduler(false);
+        let worker = "worker-1".to_string();
+        let short = scheduler.submit(test_job(Some(5))).unwrap();
+        let default = scheduler.submit(test_job(None)).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        clock.advance(Duration::from_secs(10));
+        let reaped: Vec<JobId> = scheduler.reap_expired().into_iter().map(|job| job.job_id).collect();
+
+        assert_eq!(reaped, vec![short]);
+        assert!(matches!(scheduler.status(default), Some(JobStatus::Running { .. })));
+    }
+
+    #[test]
+    fn test_timed_out_job_is_requeued_until_attempts_run_out() {
+        let (scheduler, clock) = test_scheduler(true);
+        let job_id = scheduler.submit(test_job(None)).unwrap();
+
+        scheduler.dispatch(&"worker-1".to_string()).unwrap();
+        clock.advance(Duration::from_secs(61));
+        assert!(scheduler.reap_expired()[0].requeued);
+
+        let entry = scheduler.job(job_id).unwrap();
+        assert!(matches!(entry.status, JobStatus::Queued));
+        assert!(entry.last_error.unwrap().contains("worker-1"));
+        assert_eq!(scheduler.queued_jobs(), 1);
+
+        // The second attempt is the last one
+        let job = scheduler.dispatch(&"worker-2".to_string()).unwrap();
+        assert_eq!(job.id, job_id);
+        clock.advance(Duration::from_secs(61));
+        assert!(!scheduler.reap_expired()[0].requeued);
+
+        let entry = scheduler.job(job_id).unwrap();
+        assert_eq!(entry.attempts, 2);
+        assert!(matches!(entry.status, JobStatus::Failed { .. }));
+        assert_eq!(scheduler.queued_jobs(), 0);
+    }
+
+    #[test]
+    fn test_completion_after_reap_is_not_recorded() {
+        let (scheduler, clock) = test_scheduler(true);
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job(None)).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        clock.advance(Duration::from_secs(61));
+        scheduler.reap_expired();
+
+        // The original worker finishes just after being timed out
+        assert!(!scheduler.complete(job_id, &worker, test_result(job_id)));
+        assert!(matches!(scheduler.status(job_id), Some(JobStatus::Queued)));
+
+        // The retry's completion is the one that counts
+        let retry_worker = "worker-2".to_string();
+        scheduler.dispatch(&retry_worker).unwrap();
+        assert!(!scheduler.complete(job_id, &worker, test_result(job_id)));
+        assert!(scheduler.complete(job_id, &retry_worker, test_result(job_id)));
+        assert!(matches!(scheduler.status(job_id), Some(JobStatus::Completed { .. })));
+
+        // Nothing running, nothing to reap
+        clock.advance(Duration::from_secs(600));
+        assert!(scheduler.reap_expired().is_empty());
+    }
+
+    #[test]
+    fn test_completed_job_is_not_reaped() {
+        let (scheduler, clock) = test_scheduler(false);
+        let worker = "worker-1".to_string();
+        let job_id = scheduler.submit(test_job(None)).unwrap();
+        scheduler.dispatch(&worker).unwrap();
+
+        clock.advance(Duration::from_secs(30));
+        assert!(scheduler.complete(job_id, &worker, test_result(job_id)));
+
+        clock.advance(Duration::from_secs(60));
+        assert!(scheduler.reap_expired().is_empty());
+        assert!(matches!(scheduler.status(job_id), Some(JobStatus::Completed { .. })));
+    }
+}
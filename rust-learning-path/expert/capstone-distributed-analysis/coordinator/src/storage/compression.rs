@@ -0,0 +1,93 @@
+use anyhow::Result;
+use shared::utils::{compress_data, decompress_data};
+use shared::CompressionType;
+
+/// How a stored payload is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadEncoding {
+    Raw,
+    Gzip,
+}
+
+impl PayloadEncoding {
+    /// Name stored alongside the payload
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayloadEncoding::Raw => "raw",
+            PayloadEncoding::Gzip => "gzip",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "raw" => Ok(PayloadEncoding::Raw),
+            "gzip" => Ok(PayloadEncoding::Gzip),
+            other => anyhow::bail!("Unknown payload encoding: {}", other),
+        }
+    }
+}
+
+/// Compress `data` if it's at least `threshold` bytes and compression helps
+///
+/// Small payloads aren't worth the CPU time, and some data doesn't shrink;
+/// both are stored as they are.
+pub fn encode_payload(data: &[u8], threshold: usize) -> Result<(PayloadEncoding, Vec<u8>)> {
+    if data.len() < threshold {
+        return Ok((PayloadEncoding::Raw, data.to_vec()));
+    }
+
+    let compressed = compress_data(data, CompressionType::Gzip)?;
+    if compressed.len() >= data.len() {
+        return Ok((PayloadEncoding::Raw, data.to_vec()));
+    }
+
+    Ok((PayloadEncoding::Gzip, compressed))
+}
+
+/// Undo `encode_payload`
+pub fn decode_payload(encoding: PayloadEncoding, data: &[u8]) -> Result<Vec<u8>> {
+    match encoding {
+        PayloadEncoding::Raw => Ok(data.to_vec()),
+        PayloadEncoding::Gzip => decompress_data(data, CompressionType::Gzip),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_payload_is_not_compressed() {
+        let data = b"{\"files_analyzed\":1}".repeat(10);
+        let (encoding, stored) = encode_payload(&data, 4096).unwrap();
+
+        assert_eq!(encoding, PayloadEncoding::Raw);
+        assert_eq!(stored, data);
+    }
+
+    #[test]
+    fn test_incompressible_payload_is_stored_raw() {
+        // Pseudo-random bytes don't shrink
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let data: Vec<u8> = (0..8192)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+
+        let (encoding, stored) = encode_payload(&data, 1024).unwrap();
+        assert_eq!(encoding, PayloadEncoding::Raw);
+        assert_eq!(stored, data);
+    }
+
+    #[test]
+    fn test_encoding_names_round_trip() {
+        for encoding in [PayloadEncoding::Raw, PayloadEncoding::Gzip] {
+            assert_eq!(PayloadEncoding::parse(encoding.as_str()).unwrap(), encoding);
+        }
+        assert!(PayloadEncoding::parse("zstd").is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use shared::{AnalysisResult, JobId};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::DatabaseConfig;
+
+mod compression;
+
+pub use compression::PayloadEncoding;
+use compression::{decode_payload, encode_payload};
+
+/// Persists analysis results in the coordinator's database
+///
+/// Results are stored as JSON. Payloads of at least
+/// `compression_threshold_bytes` are gzip-compressed first, and the
+/// encoding is stored with each row, so reading a result back works the
+/// same whether or not it was compressed.
+pub struct Storage {
+    pool: SqlitePool,
+    compression_threshold: usize,
+}
+
+impl Storage {
+    pub async fn new(config: &DatabaseConfig) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(&config.url)
+            .with_context(|| format!("Invalid database URL {}", config.url))?
+            .create_if_missing(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .acquire_timeout(Duration::from_secs(config.connection_timeout_seconds))
+            .connect_with(options)
+            .await
+            .with_context(|| format!("Failed to connect to {}", config.url))?;
+
+        let storage = Self {
+            pool,
+            compression_threshold: config.compression_threshold_bytes,
+        };
+
+        if config.enable_migrations {
+            storage.migrate().await?;
+        }
+
+        Ok(storage)
+    }
+
+    /// Create the tables if they don't exist yet
+    async fn migrate(&self) -> Result<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS results (
+                job_id TEXT PRIMARY KEY,
+                encoding TEXT NOT NULL,
+                raw_size INTEGER NOT NULL,
+                payload BLOB NOT NULL,
+                stored_at TEXT NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store a job's result, replacing any result stored for it before
+    pub async fn store_result(&self, result: &AnalysisResult) -> Result<()> {
+        let json = serde_json::to_vec(result)?;
+        let (encoding, payload) = encode_payload(&json, self.compression_threshold)?;
+
+        metrics::counter!("coordinator_result_bytes_raw").increment(json.len() as u64);
+        metrics::counter!("coordinator_result_bytes_stored").increment(payload.len() as u64);
+        if encoding != PayloadEncoding::Raw {
+            let ratio = payload.len() as f64 / json.len() as f64;
+            metrics::histogram!("coordinator_result_compression_ratio").record(ratio);
+            debug!(
+                "Compressed result for job {} from {} to {} bytes ({:.2})",
+                result.job_id, json.len(), payload.len(), ratio
+            );
+        }
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO results (job_id, encoding, raw_size, payload, stored_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(result.job_id.to_string())
+        .bind(encoding.as_str())
+        .bind(json.len() as i64)
+        .bind(payload)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Load a job's result, if one was stored
+    pub async fn load_result(&self, job_id: JobId) -> Result<Option<AnalysisResult>> {
+        let row = sqlx::query("SELECT encoding, payload FROM results WHERE job_id = ?")
+            .bind(job_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let encoding = PayloadEncoding::parse(row.try_get("encoding")?)?;
+        let payload: Vec<u8> = row.try_get("payload")?;
+        let json = decode_payload(encoding, &payload)
+            .with_context(|| format!("Corrupt result payload for job {}", job_id))?;
+
+        Ok(Some(serde_json::from_slice(&json)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoordinatorConfig;
+    use shared::{CodeIssue, CodeMetrics, IssueCategory, IssueSeverity};
+    use uuid::Uuid;
+
+    async fn test_storage(dir: &tempfile::TempDir) -> Storage {
+        let mut config = CoordinatorConfig::default().database;
+        config.url = format!("sqlite://{}", dir.path().join("results.db").display());
+        Storage::new(&config).await.unwrap()
+    }
+
+    fn test_result(issue_count: usize) -> AnalysisResult {
+        AnalysisResult {
+            job_id: Uuid::new_v4(),
+            completed_at: chrono::Utc::now(),
+            duration_ms: 1500,
+            files_analyzed: 40,
+            lines_of_code: 12000,
+            metrics: CodeMetrics {
+                cyclomatic_complexity: 4.2,
+                maintainability_index: 71.0,
+                technical_debt_ratio: 0.05,
+                test_coverage: Some(0.8),
+                documentation_coverage: 0.6,
+                dependency_count: 12,
+            },
+            issues: (0..issue_count)
+                .map(|i| CodeIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Style,
+                    message: "Variable name should be snake_case".to_string(),
+                    file_path: format!("src/module_{}.rs", i % 10),
+                    line_number: i as u32,
+                    column: 5,
+                    suggestion: Some("Rename the variable".to_string()),
+                    rule_id: "naming::snake_case".to_string(),
+                })
+                .collect(),
+            performance_insights: Vec::new(),
+            security_findings: Vec::new(),
+        }
+    }
+
+    /// Read a row's encoding and payload size as they are in the database
+    async fn stored_row(storage: &Storage, job_id: JobId) -> (String, i64, i64) {
+        let row = sqlx::query("SELECT encoding, length(payload) AS stored, raw_size FROM results WHERE job_id = ?")
+            .bind(job_id.to_string())
+            .fetch_one(&storage.pool)
+            .await
+            .unwrap();
+        (row.get("encoding"), row.get("stored"), row.get("raw_size"))
+    }
+
+    #[tokio::test]
+    async fn test_large_result_is_compressed_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(&dir).await;
+        let result = test_result(1000);
+
+        storage.store_result(&result).await.unwrap();
+
+        let (encoding, stored, raw_size) = stored_row(&storage, result.job_id).await;
+        assert_eq!(encoding, "gzip");
+        assert_eq!(raw_size as usize, serde_json::to_vec(&result).unwrap().len());
+        assert!(stored * 4 < raw_size, "stored {} of {} bytes", stored, raw_size);
+
+        // Reading it back gives exactly what was stored
+        let loaded = storage.load_result(result.job_id).await.unwrap().unwrap();
+        assert_eq!(serde_json::to_vec(&loaded).unwrap(), serde_json::to_vec(&result).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_small_result_is_stored_raw() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(&dir).await;
+        let result = test_result(0);
+
+        storage.store_result(&result).await.unwrap();
+
+        let (encoding, stored, raw_size) = stored_row(&storage, result.job_id).await;
+        assert_eq!(encoding, "raw");
+        assert_eq!(stored, raw_size);
+
+        let loaded = storage.load_result(result.job_id).await.unwrap().unwrap();
+        assert_eq!(serde_json::to_vec(&loaded).unwrap(), serde_json::to_vec(&result).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_missing_result_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(&dir).await;
+
+        assert!(storage.load_result(Uuid::new_v4()).await.unwrap().is_none());
+    }
+}
@@ -2,5 +2,6 @@ pub mod protocol;
 pub mod types;
 pub mod utils;
 
+pub use observability;
 pub use protocol::*;
 pub use types::*;
\ No newline at end of file
@@ -197,6 +197,8 @@ pub mod http {
         pub project_path: String,
         pub config: AnalysisConfig,
         pub priority: Option<JobPriority>,
+        #[serde(default)]
+        pub deadline: Option<chrono::DateTime<chrono::Utc>>,
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -205,6 +207,13 @@ pub mod http {
         pub estimated_duration_seconds: Option<u64>,
     }
 
+    /// A worker reporting the result of a job it ran
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SubmitResultRequest {
+        pub worker_id: WorkerId,
+        pub result: AnalysisResult,
+    }
+
     #[derive(Debug, Serialize, Deserialize)]
     pub struct JobStatusResponse {
         pub job_id: JobId,
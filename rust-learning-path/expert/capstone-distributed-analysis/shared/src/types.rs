@@ -18,6 +18,9 @@ pub struct AnalysisJob {
     pub priority: JobPriority,
     pub created_at: DateTime<Utc>,
     pub timeout_seconds: Option<u64>,
+    /// Latest time the job may start; it fails if it is still queued by then
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
 }
 
 /// Configuration for analysis parameters
@@ -27,6 +27,7 @@ async fn test_basic_job_submission_and_processing() -> Result<()> {
         priority: JobPriority::Normal,
         created_at: chrono::Utc::now(),
         timeout_seconds: Some(300),
+        deadline: None,
     };
 
     // TODO: Implement test logic
@@ -88,6 +89,7 @@ async fn test_job_priority_and_scheduling() -> Result<()> {
         priority: JobPriority::High,
         created_at: chrono::Utc::now(),
         timeout_seconds: Some(300),
+        deadline: None,
     };
 
     let normal_priority_job = AnalysisJob {
@@ -97,6 +99,7 @@ async fn test_job_priority_and_scheduling() -> Result<()> {
         priority: JobPriority::Normal,
         created_at: chrono::Utc::now(),
         timeout_seconds: Some(300),
+        deadline: None,
     };
 
     // TODO: Implement test logic
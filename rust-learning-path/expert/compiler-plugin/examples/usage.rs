@@ -80,7 +80,9 @@ struct badDataStructure {  // Bad naming
     ProfilePicture: Option<Vec<u8>>,  // Bad field naming
     Settings: std::collections::HashMap<String, String>,  // Bad field naming
     Permissions: Vec<String>,  // Bad field naming
-    Metadata: serde_json::Value,  // Too many fields + bad naming
+    Metadata: serde_json::Value,  // Bad field naming
+    Tags: Vec<String>,     // Bad field naming
+    Notes: Option<String>,  // Too many fields + bad naming
 }
 
 impl UserAccount {
@@ -175,6 +177,8 @@ fn main() {
         Settings: std::collections::HashMap::new(),
         Permissions: vec!["read".to_string(), "write".to_string()],
         Metadata: serde_json::Value::Null,
+        Tags: vec!["beta".to_string()],
+        Notes: None,
     };
     
     println!("Bad data: {:?}", bad_data);
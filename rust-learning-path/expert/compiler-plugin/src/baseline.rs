@@ -0,0 +1,140 @@
+//! # Lint Baseline
+//!
+//! This module lets the linter be adopted gradually on an existing codebase.
+//! A baseline file records the findings that are already known, so only new
+//! findings get reported.
+//!
+//! The file format is one `lint-name item-name` pair per line. Blank lines
+//! and lines starting with `#` are ignored; any other line is an error.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Set of known (lint name, item name) findings to suppress
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    entries: HashSet<(String, String)>,
+}
+
+impl Baseline {
+    /// Create an empty baseline that suppresses nothing
+    pub fn new() -> Self {
+        Self {
+            entries: HashSet::new(),
+        }
+    }
+
+    /// Parse a baseline from the contents of a baseline file
+    ///
+    /// Fails with `io::ErrorKind::InvalidData` on a line that isn't exactly
+    /// a lint name and an item name.
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        let mut baseline = Self::new();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(lint), Some(item), None) => baseline.insert(lint, item),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: expected `<lint-name> <item-name>`, found `{}`", number + 1, line),
+                    ))
+                }
+            }
+        }
+
+        Ok(baseline)
+    }
+
+    /// Load a baseline from a file on disk
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents)
+    }
+
+    /// Write the baseline to a file, one sorted entry per line
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_file_contents())
+    }
+
+    /// Render the baseline in the on-disk format
+    pub fn to_file_contents(&self) -> String {
+        let mut entries: Vec<&(String, String)> = self.entries.iter().collect();
+        entries.sort();
+
+        let mut contents = String::from("# Known lint findings: <lint-name> <item-name>\n");
+        for (lint, item) in entries {
+            contents.push_str(&format!("{} {}\n", lint, item));
+        }
+        contents
+    }
+
+    /// Record a finding as known
+    pub fn insert(&mut self, lint: &str, item: &str) {
+        self.entries.insert((lint.to_string(), item.to_string()));
+    }
+
+    /// Check whether a finding is already known
+    pub fn contains(&self, lint: &str, item: &str) -> bool {
+        self.entries.contains(&(lint.to_string(), item.to_string()))
+    }
+
+    /// Number of entries in the baseline
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the baseline has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        let baseline = Baseline::parse(
+            "# header\n\nnaming-convention BadName\nunused-variable helper\n",
+        )
+        .unwrap();
+
+        assert_eq!(baseline.len(), 2);
+        assert!(baseline.contains("naming-convention", "BadName"));
+        assert!(baseline.contains("unused-variable", "helper"));
+        assert!(!baseline.contains("naming-convention", "helper"));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        for contents in ["naming-convention\n", "naming-convention BadName extra\n"] {
+            let error = Baseline::parse(contents).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert!(error.to_string().contains("line 1"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lint-baseline.txt");
+
+        let mut baseline = Baseline::new();
+        baseline.insert("unused-variable", "helper");
+        baseline.insert("naming-convention", "BadName");
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded, baseline);
+    }
+}
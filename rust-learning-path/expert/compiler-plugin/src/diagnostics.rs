@@ -7,10 +7,12 @@
 use std::fmt;
 
 /// Represents the severity level of a diagnostic message
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DiagnosticLevel {
     /// Informational message
     Info,
+    /// Recommendation that is worth following but not a problem
+    Note,
     /// Warning that doesn't prevent compilation
     Warning,
     /// Error that prevents compilation
@@ -21,6 +23,7 @@ impl fmt::Display for DiagnosticLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DiagnosticLevel::Info => write!(f, "info"),
+            DiagnosticLevel::Note => write!(f, "note"),
             DiagnosticLevel::Warning => write!(f, "warning"),
             DiagnosticLevel::Error => write!(f, "error"),
         }
@@ -38,7 +41,7 @@ pub struct Diagnostic {
 }
 
 /// Information about the location of a diagnostic in source code
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SpanInfo {
     pub file: String,
     pub line: usize,
@@ -96,6 +99,7 @@ impl Diagnostic {
     pub fn emit(&self) {
         let level_str = match self.level {
             DiagnosticLevel::Info => "info",
+            DiagnosticLevel::Note => "note",
             DiagnosticLevel::Warning => "warning",
             DiagnosticLevel::Error => "error",
         };
@@ -121,6 +125,30 @@ impl Diagnostic {
         }
     }
 
+    /// Render as a single-line JSON object for editors and other tools:
+    /// `{"level":"warning","message":...,"span_line":...,"span_col":...,"suggestion":...}`
+    ///
+    /// The level is lowercase; a missing span or suggestion is `null`.
+    pub fn to_json(&self) -> String {
+        let (span_line, span_col) = match &self.span_info {
+            Some(span) => (span.line.to_string(), span.column.to_string()),
+            None => ("null".to_string(), "null".to_string()),
+        };
+        let suggestion = match &self.suggestion {
+            Some(suggestion) => json_string(suggestion),
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{{\"level\":{},\"message\":{},\"span_line\":{},\"span_col\":{},\"suggestion\":{}}}",
+            json_string(&self.level.to_string()),
+            json_string(&self.message),
+            span_line,
+            span_col,
+            suggestion
+        )
+    }
+
     /// Convert to a structured format for testing
     pub fn to_structured(&self) -> StructuredDiagnostic {
         StructuredDiagnostic {
@@ -133,6 +161,25 @@ impl Diagnostic {
     }
 }
 
+/// Quote and escape a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
 /// Structured representation of a diagnostic for testing and serialization
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructuredDiagnostic {
@@ -392,6 +439,54 @@ mod tests {
         assert_eq!(complexity_diag.code(), Some("high-complexity"));
     }
 
+    #[test]
+    fn test_warning_to_json_with_suggestion() {
+        let diagnostic = Diagnostic::new(
+            DiagnosticLevel::Warning,
+            "Variable 'count' appears to be unused".to_string(),
+            Some("Consider prefixing with underscore: '_count'".to_string()),
+        )
+        .with_code("unused-variable".to_string())
+        .with_span(SpanInfo {
+            file: "src/main.rs".to_string(),
+            line: 12,
+            column: 8,
+            length: 5,
+        });
+
+        let json = diagnostic.to_json();
+        assert_eq!(
+            json,
+            r#"{"level":"warning","message":"Variable 'count' appears to be unused","span_line":12,"span_col":8,"suggestion":"Consider prefixing with underscore: '_count'"}"#
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["level"], "warning");
+        assert_eq!(value["span_line"], 12);
+        assert_eq!(value["span_col"], 8);
+    }
+
+    #[test]
+    fn test_warning_to_json_without_suggestion() {
+        let diagnostic = Diagnostic::new(
+            DiagnosticLevel::Warning,
+            "Struct \"Config\" is\tlarge\n".to_string(),
+            None,
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&diagnostic.to_json()).unwrap();
+        let object = value.as_object().unwrap();
+        let mut keys: Vec<&str> = object.keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        assert_eq!(keys, ["level", "message", "span_col", "span_line", "suggestion"]);
+
+        assert_eq!(value["level"], "warning");
+        assert_eq!(value["message"], "Struct \"Config\" is\tlarge\n");
+        assert!(value["suggestion"].is_null());
+        assert!(value["span_line"].is_null());
+        assert!(value["span_col"].is_null());
+    }
+
     #[test]
     fn test_span_info() {
         let span = SpanInfo {
@@ -13,11 +13,14 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Item, ItemFn};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, DeriveInput, Ident, Item, ItemFn, Token};
 
+mod baseline;
 mod diagnostics;
 mod lint;
 
+use baseline::Baseline;
 pub use diagnostics::*;
 pub use lint::*;
 
@@ -103,30 +106,148 @@ pub trait LintableStruct {
 
 /// Procedural macro for analyzing entire modules
 /// This demonstrates more advanced compiler plugin capabilities
+///
+/// Options can come before the items, ending with a `;`. With
+/// `baseline = "path"`, only findings missing from that baseline file are
+/// reported; adding the `--update-baseline` flag regenerates the file from
+/// the current findings. The path is relative to the crate's manifest.
+/// A baseline file that is missing, unreadable or malformed is a compile
+/// error, except that a missing one is created by `--update-baseline`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// analyze_module! {
+///     baseline = "lint-baseline.txt", --update-baseline;
+///
+///     fn helper() {}
+/// }
+/// ```
 #[proc_macro]
 pub fn analyze_module(input: TokenStream) -> TokenStream {
-    let items = syn::parse_macro_input!(input with syn::Block);
+    let ModuleInput { options, items } = parse_macro_input!(input as ModuleInput);
     
-    let mut module_linter = ModuleLinter::new();
+    let loaded = options
+        .baseline
+        .as_ref()
+        .map(|path| load_baseline(path, options.update_baseline))
+        .transpose();
+    let (baseline_path, baseline) = match loaded {
+        Ok(loaded) => loaded.unzip(),
+        Err(e) => {
+            let error = e.to_compile_error();
+            return quote! {
+                #error
+                #(#items)*
+            }.into();
+        }
+    };
+    let mut module_linter = ModuleLinter::new().with_baseline(baseline.unwrap_or_default());
     
     // Analyze each item in the module
-    for stmt in &items.stmts {
-        if let syn::Stmt::Item(item) = stmt {
-            let diagnostics = module_linter.analyze_item(item);
-            for diagnostic in diagnostics {
-                diagnostic.emit();
-            }
+    for item in &items {
+        let diagnostics = module_linter.analyze_item(item);
+        for diagnostic in diagnostics {
+            diagnostic.emit();
+        }
+    }
+
+    if let Some(path) = baseline_path.filter(|_| options.update_baseline) {
+        if let Err(e) = module_linter.update_baseline(&path) {
+            eprintln!("warning: failed to update lint baseline '{}': {}", path.display(), e);
         }
     }
     
     // Return the original module
     quote! {
-        {
-            #items
-        }
+        #(#items)*
     }.into()
 }
 
+/// Load the baseline file named by `analyze_module!`'s options
+///
+/// The path is relative to the crate's manifest. A missing file is only
+/// expected when `--update-baseline` is about to create it; any other
+/// failure is an error, since treating the baseline as empty would report
+/// every known finding again without saying why.
+fn load_baseline(path: &syn::LitStr, update_baseline: bool) -> syn::Result<(std::path::PathBuf, Baseline)> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&manifest_dir).join(path.value());
+    
+    match Baseline::load(&full_path) {
+        Ok(baseline) => Ok((full_path, baseline)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && update_baseline => Ok((full_path, Baseline::new())),
+        Err(e) => Err(syn::Error::new(
+            path.span(),
+            format!("failed to load lint baseline '{}': {}", full_path.display(), e),
+        )),
+    }
+}
+
+/// Options given to `analyze_module!` before its items
+#[derive(Default)]
+struct ModuleOptions {
+    baseline: Option<syn::LitStr>,
+    update_baseline: bool,
+}
+
+impl Parse for ModuleOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        loop {
+            if input.peek(Token![-]) {
+                input.parse::<Token![-]>()?;
+                input.parse::<Token![-]>()?;
+                let flag: Ident = input.parse()?;
+                input.parse::<Token![-]>()?;
+                let rest: Ident = input.parse()?;
+                if flag != "update" || rest != "baseline" {
+                    return Err(syn::Error::new(flag.span(), "unknown flag, expected `--update-baseline`"));
+                }
+                options.update_baseline = true;
+            } else {
+                let name: Ident = input.parse()?;
+                if name != "baseline" {
+                    return Err(syn::Error::new(name.span(), "unknown option, expected `baseline`"));
+                }
+                input.parse::<Token![=]>()?;
+                options.baseline = Some(input.parse()?);
+            }
+
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        input.parse::<Token![;]>()?;
+
+        if options.update_baseline && options.baseline.is_none() {
+            return Err(input.error("`--update-baseline` needs a `baseline = \"path\"` to write to"));
+        }
+        Ok(options)
+    }
+}
+
+/// The input of `analyze_module!`: optional options, then the items
+struct ModuleInput {
+    options: ModuleOptions,
+    items: Vec<Item>,
+}
+
+impl Parse for ModuleInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Options start with a flag or with `name =`, which no item does
+        let has_options = input.peek(Token![-]) || (input.peek(Ident) && input.peek2(Token![=]));
+        let options = if has_options { input.parse()? } else { ModuleOptions::default() };
+
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(Self { options, items })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +276,47 @@ mod tests {
         assert_eq!(diagnostic.level(), DiagnosticLevel::Warning);
         assert_eq!(diagnostic.message(), "Test warning");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_module_input_options() {
+        let input: ModuleInput = syn::parse_str(
+            r#"baseline = "lint-baseline.txt", --update-baseline; fn helper() {} struct Helper;"#,
+        )
+        .unwrap();
+        assert_eq!(input.options.baseline.unwrap().value(), "lint-baseline.txt");
+        assert!(input.options.update_baseline);
+        assert_eq!(input.items.len(), 2);
+
+        let input: ModuleInput = syn::parse_str("fn helper() {}").unwrap();
+        assert!(input.options.baseline.is_none());
+        assert!(!input.options.update_baseline);
+        assert_eq!(input.items.len(), 1);
+
+        assert!(syn::parse_str::<ModuleInput>("--update-baseline; fn helper() {}").is_err());
+        assert!(syn::parse_str::<ModuleInput>("--update-everything, baseline = \"b\"; fn helper() {}").is_err());
+    }
+
+    #[test]
+    fn test_load_baseline_reports_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_literal = |name: &str| -> syn::LitStr {
+            let path = dir.path().join(name).display().to_string();
+            parse_quote!(#path)
+        };
+
+        std::fs::write(dir.path().join("malformed.txt"), "naming-convention\n").unwrap();
+        for update_baseline in [false, true] {
+            let error = load_baseline(&path_literal("malformed.txt"), update_baseline).unwrap_err();
+            assert!(error.to_string().contains("line 1"), "{}", error);
+        }
+
+        // A missing baseline is only fine when it's about to be written
+        assert!(load_baseline(&path_literal("missing.txt"), false).is_err());
+        let (_, baseline) = load_baseline(&path_literal("missing.txt"), true).unwrap();
+        assert!(baseline.is_empty());
+
+        std::fs::write(dir.path().join("valid.txt"), "naming-convention BadName\n").unwrap();
+        let (_, baseline) = load_baseline(&path_literal("valid.txt"), false).unwrap();
+        assert!(baseline.contains("naming-convention", "BadName"));
+    }
+}
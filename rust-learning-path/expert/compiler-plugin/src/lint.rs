@@ -3,14 +3,25 @@
 //! This module contains the core linting logic for the compiler plugin.
 //! It demonstrates how to traverse and analyze Rust code using the syn crate.
 
-use syn::{visit::Visit, DeriveInput, Expr, ItemFn, Local, Pat, Stmt, Item};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use syn::{visit::Visit, Attribute, DeriveInput, Expr, ItemFn, Local, Pat, Stmt, Item, Visibility};
+use crate::baseline::Baseline;
 use crate::diagnostics::{Diagnostic, DiagnosticLevel};
 
+/// Statement count above which a function is reported as too long
+pub const DEFAULT_MAX_STATEMENTS: usize = 50;
+
 /// Linter for analyzing functions
 pub struct FunctionLinter {
     diagnostics: Vec<Diagnostic>,
     unused_variables: Vec<String>,
     complex_expressions: usize,
+    statement_count: usize,
+    max_statements: usize,
 }
 
 impl FunctionLinter {
@@ -19,20 +30,31 @@ impl FunctionLinter {
             diagnostics: Vec::new(),
             unused_variables: Vec::new(),
             complex_expressions: 0,
+            statement_count: 0,
+            max_statements: DEFAULT_MAX_STATEMENTS,
         }
     }
 
+    /// Report functions with more than `max` statements instead of
+    /// `DEFAULT_MAX_STATEMENTS`
+    pub fn with_max_statements(mut self, max: usize) -> Self {
+        self.max_statements = max;
+        self
+    }
+
     /// Analyze a function for potential issues
     pub fn analyze_function(&mut self, func: &ItemFn) -> Vec<Diagnostic> {
         self.diagnostics.clear();
         self.unused_variables.clear();
         self.complex_expressions = 0;
+        self.statement_count = 0;
 
         // Visit the function to collect information
         self.visit_item_fn(func);
 
         // Apply linting rules
         self.check_function_complexity(func);
+        self.check_function_length(func);
         self.check_naming_conventions(func);
         self.check_unused_variables();
 
@@ -49,7 +71,27 @@ impl FunctionLinter {
                     self.complex_expressions
                 ),
                 Some("Consider extracting some logic into separate functions".to_string()),
-            );
+            )
+            .with_code("high-complexity".to_string());
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Statements in nested blocks (loops, closures, match arms) count too,
+    /// since they make the function just as long to read
+    fn check_function_length(&mut self, func: &ItemFn) {
+        if self.statement_count > self.max_statements {
+            let diagnostic = Diagnostic::new(
+                DiagnosticLevel::Warning,
+                format!(
+                    "Function '{}' is too long ({} statements, limit is {})",
+                    func.sig.ident,
+                    self.statement_count,
+                    self.max_statements
+                ),
+                Some("Consider extracting parts of it into helper functions".to_string()),
+            )
+            .with_code("long-function".to_string());
             self.diagnostics.push(diagnostic);
         }
     }
@@ -57,13 +99,15 @@ impl FunctionLinter {
     fn check_naming_conventions(&mut self, func: &ItemFn) {
         let name = func.sig.ident.to_string();
         
-        // Check for snake_case convention
-        if !name.chars().all(|c| c.is_lowercase() || c == '_' || c.is_numeric()) {
+        // Check for snake_case convention. All-caps names such as `FFI`
+        // are usually deliberate acronyms, so they're left alone.
+        if !is_snake_case(&name) && !is_acronym(&name) {
             let diagnostic = Diagnostic::new(
                 DiagnosticLevel::Warning,
                 format!("Function '{}' should use snake_case naming convention", name),
                 Some(format!("Consider renaming to '{}'", to_snake_case(&name))),
-            );
+            )
+            .with_code("naming-convention".to_string());
             self.diagnostics.push(diagnostic);
         }
 
@@ -73,7 +117,8 @@ impl FunctionLinter {
                 DiagnosticLevel::Info,
                 format!("Function '{}' has a very long name ({} characters)", name, name.len()),
                 Some("Consider using a shorter, more concise name".to_string()),
-            );
+            )
+            .with_code("long-name".to_string());
             self.diagnostics.push(diagnostic);
         }
     }
@@ -85,7 +130,8 @@ impl FunctionLinter {
                     DiagnosticLevel::Warning,
                     format!("Variable '{}' appears to be unused", var),
                     Some(format!("Consider prefixing with underscore: '_{}'", var)),
-                );
+                )
+                .with_code("unused-variable".to_string());
                 self.diagnostics.push(diagnostic);
             }
         }
@@ -107,6 +153,11 @@ impl<'ast> Visit<'ast> for FunctionLinter {
         syn::visit::visit_local(self, local);
     }
 
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        self.statement_count += 1;
+        syn::visit::visit_stmt(self, stmt);
+    }
+
     fn visit_expr(&mut self, expr: &'ast Expr) {
         // Count complex expressions
         match expr {
@@ -127,18 +178,30 @@ impl<'ast> Visit<'ast> for FunctionLinter {
     }
 }
 
+/// Field count above which a struct is reported as too wide
+pub const DEFAULT_MAX_FIELDS: usize = 12;
+
 /// Linter for analyzing struct definitions
 pub struct StructLinter {
     diagnostics: Vec<Diagnostic>,
+    max_fields: usize,
 }
 
 impl StructLinter {
     pub fn new() -> Self {
         Self {
             diagnostics: Vec::new(),
+            max_fields: DEFAULT_MAX_FIELDS,
         }
     }
 
+    /// Report structs with more than `max` fields instead of
+    /// `DEFAULT_MAX_FIELDS`
+    pub fn with_max_fields(mut self, max: usize) -> Self {
+        self.max_fields = max;
+        self
+    }
+
     /// Analyze a struct for potential issues
     pub fn analyze_struct(&mut self, input: &DeriveInput) -> Vec<Diagnostic> {
         self.diagnostics.clear();
@@ -146,13 +209,34 @@ impl StructLinter {
         self.check_struct_naming(&input.ident.to_string());
         
         if let syn::Data::Struct(data_struct) = &input.data {
-            self.check_field_count(&data_struct.fields);
+            self.check_field_count(&input.ident, &data_struct.fields);
             self.check_field_naming(&data_struct.fields);
+            self.check_missing_debug(input);
         }
 
         self.diagnostics.clone()
     }
 
+    /// Public structs should derive `Debug` so users can print them.
+    /// Structs that implement it by hand can opt out with
+    /// `#[allow(missing_debug_implementations)]`.
+    fn check_missing_debug(&mut self, input: &DeriveInput) {
+        if !matches!(input.vis, Visibility::Public(_)) {
+            return;
+        }
+        if derives(&input.attrs, "Debug") || allows(&input.attrs, "missing_debug_implementations") {
+            return;
+        }
+
+        let diagnostic = Diagnostic::new(
+            DiagnosticLevel::Note,
+            format!("Public struct '{}' does not derive Debug", input.ident),
+            Some("Consider adding #[derive(Debug)]".to_string()),
+        )
+        .with_code("missing-debug".to_string());
+        self.diagnostics.push(diagnostic);
+    }
+
     fn check_struct_naming(&mut self, name: &str) {
         // Check for PascalCase convention
         if !name.chars().next().unwrap_or('a').is_uppercase() {
@@ -160,19 +244,25 @@ impl StructLinter {
                 DiagnosticLevel::Error,
                 format!("Struct '{}' should use PascalCase naming convention", name),
                 Some(format!("Consider renaming to '{}'", to_pascal_case(name))),
-            );
+            )
+            .with_code("naming-convention".to_string());
             self.diagnostics.push(diagnostic);
         }
     }
 
-    fn check_field_count(&mut self, fields: &syn::Fields) {
+    /// Named and tuple fields both count; unit structs have none
+    fn check_field_count(&mut self, name: &syn::Ident, fields: &syn::Fields) {
         let field_count = fields.len();
-        if field_count > 10 {
+        if field_count > self.max_fields {
             let diagnostic = Diagnostic::new(
                 DiagnosticLevel::Warning,
-                format!("Struct has many fields ({}). Consider breaking it down.", field_count),
-                Some("Consider using composition or grouping related fields".to_string()),
-            );
+                format!(
+                    "Struct '{}' has {} fields (limit is {}). Consider breaking it down.",
+                    name, field_count, self.max_fields
+                ),
+                Some("Consider grouping related fields into smaller structs".to_string()),
+            )
+            .with_code("too-many-fields".to_string());
             self.diagnostics.push(diagnostic);
         }
     }
@@ -186,7 +276,8 @@ impl StructLinter {
                         DiagnosticLevel::Warning,
                         format!("Field '{}' should use snake_case naming convention", name),
                         Some(format!("Consider renaming to '{}'", to_snake_case(&name))),
-                    );
+                    )
+                    .with_code("naming-convention".to_string());
                     self.diagnostics.push(diagnostic);
                 }
             }
@@ -203,6 +294,10 @@ pub struct ModuleLinter {
     diagnostics: Vec<Diagnostic>,
     function_count: usize,
     struct_count: usize,
+    item_findings: Vec<(String, usize)>,
+    baseline: Baseline,
+    observed: Baseline,
+    suppressed_count: usize,
 }
 
 impl ModuleLinter {
@@ -211,9 +306,20 @@ impl ModuleLinter {
             diagnostics: Vec::new(),
             function_count: 0,
             struct_count: 0,
+            item_findings: Vec::new(),
+            baseline: Baseline::new(),
+            observed: Baseline::new(),
+            suppressed_count: 0,
         }
     }
 
+    /// Suppress findings that are already recorded in the given baseline.
+    /// Only findings missing from the baseline are returned and counted.
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
     /// Analyze a module item
     pub fn analyze_item(&mut self, item: &Item) -> Vec<Diagnostic> {
         let mut current_diagnostics = Vec::new();
@@ -236,10 +342,20 @@ impl ModuleLinter {
                 DiagnosticLevel::Info,
                 format!("Module has many functions ({}). Consider splitting into submodules.", self.function_count),
                 Some("Consider organizing functions into logical submodules".to_string()),
-            );
+            )
+            .with_code("too-many-functions".to_string());
             current_diagnostics.push(diagnostic);
         }
 
+        let name = item_name(item);
+        let before = current_diagnostics.len();
+        for diagnostic in &current_diagnostics {
+            self.observed.insert(lint_name(diagnostic), &name);
+        }
+        current_diagnostics.retain(|d| !self.baseline.contains(lint_name(d), &name));
+        self.suppressed_count += before - current_diagnostics.len();
+
+        self.item_findings.push((name, current_diagnostics.len()));
         self.diagnostics.extend(current_diagnostics.clone());
         current_diagnostics
     }
@@ -247,18 +363,169 @@ impl ModuleLinter {
     pub fn lint_count(&self) -> usize {
         self.diagnostics.len()
     }
+
+    /// Every diagnostic reported so far as a JSON object (see
+    /// `Diagnostic::to_json`), without emitting anything to stderr
+    pub fn collect_json(&self) -> Vec<String> {
+        self.diagnostics.iter().map(Diagnostic::to_json).collect()
+    }
+
+    /// Number of findings hidden because they were in the baseline
+    pub fn suppressed_count(&self) -> usize {
+        self.suppressed_count
+    }
+
+    /// Baseline covering every finding seen so far, including suppressed ones
+    pub fn current_baseline(&self) -> Baseline {
+        self.observed.clone()
+    }
+
+    /// Regenerate the baseline file from every finding seen so far
+    pub fn update_baseline<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.observed.save(path)
+    }
+
+    /// Build an aggregate report over every item analyzed so far
+    pub fn summary(&self) -> LintSummary {
+        let mut by_level = HashMap::new();
+        let mut by_lint = HashMap::new();
+
+        for diagnostic in &self.diagnostics {
+            *by_level.entry(diagnostic.level()).or_insert(0) += 1;
+            *by_lint.entry(lint_name(diagnostic).to_string()).or_insert(0) += 1;
+        }
+
+        LintSummary {
+            total: self.diagnostics.len(),
+            by_level,
+            by_lint,
+            item_findings: self.item_findings.clone(),
+        }
+    }
+}
+
+/// Aggregate view of the diagnostics produced by a `ModuleLinter`
+#[derive(Debug, Clone, Default)]
+pub struct LintSummary {
+    total: usize,
+    by_level: HashMap<DiagnosticLevel, usize>,
+    by_lint: HashMap<String, usize>,
+    item_findings: Vec<(String, usize)>,
+}
+
+impl LintSummary {
+    /// Total number of diagnostics across all items
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of diagnostics at the given level
+    pub fn count_by_level(&self, level: DiagnosticLevel) -> usize {
+        self.by_level.get(&level).copied().unwrap_or(0)
+    }
+
+    /// Number of diagnostics produced by the named lint
+    pub fn count_by_lint(&self, lint: &str) -> usize {
+        self.by_lint.get(lint).copied().unwrap_or(0)
+    }
+
+    /// Items with the most findings, highest first.
+    /// Ties keep the order in which the items were analyzed.
+    pub fn worst_offenders(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut offenders: Vec<(&str, usize)> = self
+            .item_findings
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        offenders.sort_by_key(|&(_, count)| Reverse(count));
+        offenders.truncate(n);
+        offenders
+    }
+}
+
+/// Name used to group findings of the same lint
+fn lint_name(diagnostic: &Diagnostic) -> &str {
+    diagnostic.code().unwrap_or("uncategorized")
+}
+
+/// Name used to attribute findings to a module item
+fn item_name(item: &Item) -> String {
+    match item {
+        Item::Fn(func) => func.sig.ident.to_string(),
+        Item::Struct(item_struct) => item_struct.ident.to_string(),
+        Item::Enum(item_enum) => item_enum.ident.to_string(),
+        Item::Mod(item_mod) => item_mod.ident.to_string(),
+        Item::Trait(item_trait) => item_trait.ident.to_string(),
+        _ => "<anonymous>".to_string(),
+    }
+}
+
+/// Whether any `#[derive(...)]` attribute lists the named trait
+fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attribute_lists(attrs, "derive", name)
+}
+
+/// Whether any `#[allow(...)]` attribute lists the named lint
+fn allows(attrs: &[Attribute], lint: &str) -> bool {
+    attribute_lists(attrs, "allow", lint)
+}
+
+/// Whether any `#[attribute(...)]` lists a path ending in `name`,
+/// so both `Debug` and `std::fmt::Debug` count
+fn attribute_lists(attrs: &[Attribute], attribute: &str, name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(attribute))
+        .any(|attr| {
+            let mut found = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.segments.last().is_some_and(|segment| segment.ident == name) {
+                    found = true;
+                }
+                Ok(())
+            });
+            found
+        })
 }
 
 // Helper functions for name conversion
+fn is_snake_case(name: &str) -> bool {
+    !name.chars().any(char::is_uppercase)
+}
+
+/// Whether the name has no lowercase letters, like `HTTP` or `IO_2`
+fn is_acronym(name: &str) -> bool {
+    !name.chars().any(char::is_lowercase)
+}
+
+/// Convert camelCase or PascalCase to snake_case
+///
+/// A new word starts at an uppercase letter that follows a lowercase letter
+/// or digit, and at the last capital of an acronym followed by lowercase
+/// letters, so `XMLParser` becomes `xml_parser`. Digits stay with the word
+/// before them: `parseHTTP2` becomes `parse_http2`.
 fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
     let mut result = String::new();
-    let mut chars = name.chars().peekable();
     
-    while let Some(ch) = chars.next() {
-        if ch.is_uppercase() && !result.is_empty() {
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_uppercase() {
+            result.push(ch);
+            continue;
+        }
+        
+        let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+        let next = chars.get(i + 1);
+        let starts_word = match prev {
+            Some(prev) if prev.is_lowercase() || prev.is_numeric() => true,
+            Some(prev) if prev.is_uppercase() => next.is_some_and(|next| next.is_lowercase()),
+            _ => false,
+        };
+        if starts_word {
             result.push('_');
         }
-        result.push(ch.to_lowercase().next().unwrap_or(ch));
+        result.extend(ch.to_lowercase());
     }
     
     result
@@ -290,7 +557,14 @@ mod tests {
     #[test]
     fn test_snake_case_conversion() {
         assert_eq!(to_snake_case("CamelCase"), "camel_case");
-        assert_eq!(to_snake_case("XMLParser"), "x_m_l_parser");
+        assert_eq!(to_snake_case("camelCase"), "camel_case");
+        assert_eq!(to_snake_case("XMLParser"), "xml_parser");
+        assert_eq!(to_snake_case("getHTTPResponse"), "get_http_response");
+        assert_eq!(to_snake_case("parseHTTP2"), "parse_http2");
+        assert_eq!(to_snake_case("utf8Decode"), "utf8_decode");
+        assert_eq!(to_snake_case("Vec3D"), "vec3_d");
+        assert_eq!(to_snake_case("_privateHelper"), "_private_helper");
+        assert_eq!(to_snake_case("mixed_upCase"), "mixed_up_case");
         assert_eq!(to_snake_case("simple"), "simple");
     }
 
@@ -322,6 +596,91 @@ mod tests {
         assert!(diagnostics.iter().any(|d| d.message().contains("snake_case")));
     }
 
+    fn naming_suggestion(func: ItemFn) -> Option<String> {
+        let mut linter = FunctionLinter::new();
+        linter
+            .analyze_function(&func)
+            .into_iter()
+            .find(|d| d.code() == Some("naming-convention"))
+            .map(|d| {
+                assert_eq!(d.level(), DiagnosticLevel::Warning);
+                d.suggestion().unwrap().to_string()
+            })
+    }
+
+    #[test]
+    fn test_snake_case_lint() {
+        assert_eq!(
+            naming_suggestion(parse_quote! { fn myFunction() {} }),
+            Some("Consider renaming to 'my_function'".to_string())
+        );
+        assert_eq!(
+            naming_suggestion(parse_quote! { fn MyFunc() {} }),
+            Some("Consider renaming to 'my_func'".to_string())
+        );
+        assert_eq!(
+            naming_suggestion(parse_quote! { fn parseHTTP2() {} }),
+            Some("Consider renaming to 'parse_http2'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_snake_case_lint_ignores_snake_case_and_acronyms() {
+        assert_eq!(naming_suggestion(parse_quote! { fn already_snake_case() {} }), None);
+        assert_eq!(naming_suggestion(parse_quote! { fn parse_http2() {} }), None);
+        assert_eq!(naming_suggestion(parse_quote! { fn _private() {} }), None);
+        assert_eq!(naming_suggestion(parse_quote! { fn FFI() {} }), None);
+        assert_eq!(naming_suggestion(parse_quote! { fn HTTP2_INIT() {} }), None);
+    }
+
+    /// A function whose body is `count` statements, one per line
+    fn function_with_statements(count: usize) -> ItemFn {
+        let stmts = (0..count).map(|i| -> Stmt { parse_quote! { let _value = #i; } });
+        parse_quote! {
+            fn long_function() {
+                #(#stmts)*
+            }
+        }
+    }
+
+    fn has_long_function_lint(diagnostics: &[Diagnostic]) -> bool {
+        diagnostics.iter().any(|d| d.code() == Some("long-function"))
+    }
+
+    #[test]
+    fn test_long_function_default_threshold() {
+        let mut linter = FunctionLinter::new();
+        assert!(!has_long_function_lint(&linter.analyze_function(&function_with_statements(50))));
+
+        let diagnostics = linter.analyze_function(&function_with_statements(51));
+        let lint = diagnostics.iter().find(|d| d.code() == Some("long-function")).unwrap();
+        assert_eq!(lint.level(), DiagnosticLevel::Warning);
+        assert!(lint.message().contains("51 statements"));
+        assert!(lint.suggestion().unwrap().contains("helper functions"));
+    }
+
+    #[test]
+    fn test_long_function_custom_threshold() {
+        let func: ItemFn = parse_quote! {
+            fn process(items: &[u32]) -> u32 {
+                let mut total = 0;
+                for item in items {
+                    total += item;
+                }
+                total
+            }
+        };
+
+        // `let`, `for`, the `+=` inside the loop, and the tail expression
+        let mut strict = FunctionLinter::new().with_max_statements(3);
+        let diagnostics = strict.analyze_function(&func);
+        assert!(has_long_function_lint(&diagnostics));
+        assert!(diagnostics[0].message().contains("4 statements, limit is 3"));
+
+        let mut lenient = FunctionLinter::new().with_max_statements(4);
+        assert!(!has_long_function_lint(&lenient.analyze_function(&func)));
+    }
+
     #[test]
     fn test_struct_linter() {
         let input: DeriveInput = parse_quote! {
@@ -337,4 +696,219 @@ mod tests {
         assert!(!diagnostics.is_empty());
         assert!(diagnostics.iter().any(|d| d.message().contains("PascalCase")));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_struct_linter_public_struct_without_debug() {
+        let input: DeriveInput = parse_quote! {
+            #[derive(Clone)]
+            pub struct Config {
+                name: String,
+            }
+        };
+
+        let mut linter = StructLinter::new();
+        let diagnostics = linter.analyze_struct(&input);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), Some("missing-debug"));
+        assert_eq!(diagnostics[0].level(), DiagnosticLevel::Note);
+        assert!(diagnostics[0].suggestion().unwrap().contains("#[derive(Debug)]"));
+    }
+
+    #[test]
+    fn test_struct_linter_public_struct_with_debug() {
+        let derived: DeriveInput = parse_quote! {
+            #[derive(Clone, Debug)]
+            pub struct Config {
+                name: String,
+            }
+        };
+        let qualified: DeriveInput = parse_quote! {
+            #[derive(std::fmt::Debug)]
+            pub struct Config(u32);
+        };
+        let private: DeriveInput = parse_quote! {
+            struct Config {
+                name: String,
+            }
+        };
+
+        let mut linter = StructLinter::new();
+        for input in [&derived, &qualified, &private] {
+            assert!(linter.analyze_struct(input).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_struct_linter_too_many_fields() {
+        let wide: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            pub struct Settings {
+                a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8,
+                h: u8, i: u8, j: u8, k: u8, l: u8, m: u8,
+            }
+        };
+
+        let mut linter = StructLinter::new();
+        let diagnostics = linter.analyze_struct(&wide);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), Some("too-many-fields"));
+        assert_eq!(diagnostics[0].level(), DiagnosticLevel::Warning);
+        assert!(diagnostics[0].message().contains("13 fields"));
+        assert!(diagnostics[0].suggestion().unwrap().contains("smaller structs"));
+
+        // Exactly at the default limit is fine
+        let at_limit: DeriveInput = parse_quote! {
+            struct Settings(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+        };
+        assert!(linter.analyze_struct(&at_limit).is_empty());
+    }
+
+    #[test]
+    fn test_struct_linter_field_limit_counts_every_struct_kind() {
+        let named: DeriveInput = parse_quote! {
+            struct Point { x: f64, y: f64, z: f64 }
+        };
+        let tuple: DeriveInput = parse_quote! {
+            struct Point(f64, f64, f64);
+        };
+        let unit: DeriveInput = parse_quote! {
+            struct Marker;
+        };
+
+        let mut linter = StructLinter::new().with_max_fields(2);
+        for input in [&named, &tuple] {
+            let diagnostics = linter.analyze_struct(input);
+            assert_eq!(diagnostics.len(), 1);
+            assert!(diagnostics[0].message().contains("3 fields (limit is 2)"));
+        }
+
+        let mut strictest = StructLinter::new().with_max_fields(0);
+        assert!(strictest.analyze_struct(&unit).is_empty());
+    }
+
+    #[test]
+    fn test_struct_linter_missing_debug_can_be_allowed() {
+        let input: DeriveInput = parse_quote! {
+            #[allow(dead_code, missing_debug_implementations)]
+            pub struct Handle {
+                raw: *mut u8,
+            }
+        };
+
+        let mut linter = StructLinter::new();
+        assert!(linter.analyze_struct(&input).is_empty());
+    }
+
+    #[test]
+    fn test_module_linter_summary() {
+        let items: Vec<Item> = vec![
+            parse_quote! {
+                fn BadName() {
+                    let unused_one = 1;
+                    let unused_two = 2;
+                }
+            },
+            parse_quote! {
+                fn well_named() {
+                    let _ignored = 1;
+                }
+            },
+            parse_quote! {
+                fn AlsoBad() {}
+            },
+            parse_quote! {
+                fn helper() {
+                    let leftover = 3;
+                }
+            },
+        ];
+
+        let mut linter = ModuleLinter::new();
+        for item in &items {
+            linter.analyze_item(item);
+        }
+
+        let summary = linter.summary();
+        assert_eq!(summary.total(), 5);
+        assert_eq!(summary.count_by_level(DiagnosticLevel::Warning), 5);
+        assert_eq!(summary.count_by_level(DiagnosticLevel::Error), 0);
+        assert_eq!(summary.count_by_lint("naming-convention"), 2);
+        assert_eq!(summary.count_by_lint("unused-variable"), 3);
+        assert_eq!(summary.count_by_lint("high-complexity"), 0);
+
+        assert_eq!(
+            summary.worst_offenders(2),
+            vec![("BadName", 3), ("AlsoBad", 1)]
+        );
+        assert_eq!(summary.worst_offenders(10).len(), 3);
+    }
+
+    #[test]
+    fn test_module_linter_collect_json() {
+        let items: Vec<Item> = vec![
+            parse_quote! {
+                fn BadName() {}
+            },
+            parse_quote! {
+                fn helper() {
+                    let leftover = 3;
+                }
+            },
+        ];
+
+        let mut linter = ModuleLinter::new();
+        for item in &items {
+            linter.analyze_item(item);
+        }
+
+        let json = linter.collect_json();
+        assert_eq!(json.len(), 2);
+        assert_eq!(
+            json[0],
+            r#"{"level":"warning","message":"Function 'BadName' should use snake_case naming convention","span_line":null,"span_col":null,"suggestion":"Consider renaming to 'bad_name'"}"#
+        );
+        assert!(json[1].contains(r#""message":"Variable 'leftover' appears to be unused""#));
+    }
+
+    #[test]
+    fn test_module_linter_baseline_hides_known_findings() {
+        let known: Item = parse_quote! {
+            fn LegacyName() {}
+        };
+        let new: Item = parse_quote! {
+            fn FreshName() {}
+        };
+
+        let mut baseline = Baseline::new();
+        baseline.insert("naming-convention", "LegacyName");
+
+        let mut linter = ModuleLinter::new().with_baseline(baseline);
+        assert!(linter.analyze_item(&known).is_empty());
+
+        let diagnostics = linter.analyze_item(&new);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message().contains("FreshName"));
+
+        assert_eq!(linter.lint_count(), 1);
+        assert_eq!(linter.suppressed_count(), 1);
+    }
+
+    #[test]
+    fn test_module_linter_update_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lint-baseline.txt");
+
+        let item: Item = parse_quote! {
+            fn FreshName() {}
+        };
+
+        let mut linter = ModuleLinter::new();
+        linter.analyze_item(&item);
+        linter.update_baseline(&path).unwrap();
+
+        let mut rerun = ModuleLinter::new().with_baseline(Baseline::load(&path).unwrap());
+        assert!(rerun.analyze_item(&item).is_empty());
+        assert_eq!(rerun.current_baseline(), linter.current_baseline());
+    }
+}
@@ -59,7 +59,9 @@ struct badStructName {  // Should trigger PascalCase warning
     field8: std::collections::HashMap<String, i32>,
     field9: Box<dyn std::fmt::Display>,
     field10: std::sync::Arc<std::sync::Mutex<i32>>,
-    field11: tokio::sync::RwLock<String>,  // Should trigger "too many fields" warning
+    field11: tokio::sync::RwLock<String>,
+    field12: u8,
+    field13: char,  // Should trigger "too many fields" warning
 }
 
 #[derive(LintableStruct)]
@@ -91,6 +93,8 @@ fn main() {
         field9: Box::new("display"),
         field10: std::sync::Arc::new(std::sync::Mutex::new(42)),
         field11: tokio::sync::RwLock::new("test".to_string()),
+        field12: 12,
+        field13: 'x',
     };
     
     let _another_bad = another_bad_struct {
@@ -0,0 +1,275 @@
+//! Offloading blocking work from our custom runtime.
+//!
+//! The executor is cooperative: while one task is running, no other task
+//! can make progress. A task that sleeps, does blocking I/O, or crunches
+//! numbers for a long time therefore stalls everything else.
+//!
+//! `spawn_blocking` moves such work to a small pool of dedicated OS
+//! threads. The caller gets a `JoinHandle` back; when the work finishes,
+//! the pool thread stores the result and calls the task's waker, so the
+//! executor polls the task again and picks up the value.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread;
+
+use crate::join::{self, JoinHandle};
+
+/// Maximum number of threads the blocking pool will start
+pub const MAX_BLOCKING_THREADS: usize = 8;
+
+/// A unit of blocking work queued for the pool
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Run `f` on the blocking thread pool and get a handle to its result.
+///
+/// The executor keeps polling other tasks while `f` runs. If `f` panics,
+/// the panic is resumed, with its original payload, in the task that
+/// awaits the handle.
+///
+/// # Examples
+///
+/// ```
+/// use custom_runtime::{spawn_blocking, Executor};
+///
+/// let mut executor = Executor::new();
+/// executor.spawn(async {
+///     let sum = spawn_blocking(|| (1..=10).sum::<u32>()).await;
+///     assert_eq!(sum, 55);
+/// });
+/// executor.run();
+/// ```
+pub fn spawn_blocking<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (job, handle) = join::join_closure(f);
+    pool().submit(Box::new(job));
+    handle
+}
+
+/// Get the number of blocking jobs that are queued or running on the
+/// shared pool.
+///
+/// The executor uses this to tell a task that is waiting on blocking
+/// work apart from one that will never be woken.
+pub fn in_flight() -> usize {
+    pool().in_flight()
+}
+
+/// A pool of blocking threads.
+///
+/// Threads are started lazily, one per job until `max_threads` are
+/// running, and then wait on the queue for more work. `spawn_blocking`
+/// uses a process-wide pool of `MAX_BLOCKING_THREADS`; an executor can
+/// own a pool of its own instead.
+pub(crate) struct BlockingPool {
+    max_threads: usize,
+    queue: Mutex<PoolQueue>,
+    job_available: Condvar,
+
+    /// Jobs queued or running
+    in_flight: AtomicUsize,
+}
+
+/// Queued jobs and thread bookkeeping, guarded by one lock
+struct PoolQueue {
+    jobs: VecDeque<Job>,
+    threads: usize,
+    idle: usize,
+
+    /// Set when the pool's owner is gone; threads exit once the queue is empty
+    shut_down: bool,
+}
+
+/// The shared pool used by `spawn_blocking`
+pub(crate) fn pool() -> &'static Arc<BlockingPool> {
+    static POOL: OnceLock<Arc<BlockingPool>> = OnceLock::new();
+    POOL.get_or_init(|| BlockingPool::new(MAX_BLOCKING_THREADS))
+}
+
+impl BlockingPool {
+    /// Create a pool that runs at most `max_threads` jobs at a time.
+    pub(crate) fn new(max_threads: usize) -> Arc<Self> {
+        assert!(max_threads > 0, "a blocking pool needs at least one thread");
+
+        Arc::new(BlockingPool {
+            max_threads,
+            queue: Mutex::new(PoolQueue {
+                jobs: VecDeque::new(),
+                threads: 0,
+                idle: 0,
+                shut_down: false,
+            }),
+            job_available: Condvar::new(),
+            in_flight: AtomicUsize::new(0),
+        })
+    }
+
+    /// Get the number of jobs that are queued or running.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Queue a job, starting a new thread if none is idle and there's room.
+    ///
+    /// The job must catch its own panics.
+    pub(crate) fn submit(self: &Arc<Self>, job: Job) {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+
+        let mut queue = lock(&self.queue);
+        queue.jobs.push_back(job);
+
+        if queue.idle == 0 && queue.threads < self.max_threads {
+            let id = queue.threads;
+            queue.threads += 1;
+            drop(queue);
+
+            let pool = Arc::clone(self);
+            thread::Builder::new()
+                .name(format!("blocking-{}", id))
+                .spawn(move || pool.worker_loop())
+                .expect("failed to spawn blocking thread");
+        } else {
+            drop(queue);
+            self.job_available.notify_one();
+        }
+    }
+
+    /// Let the threads exit once they've run the jobs already queued.
+    pub(crate) fn shut_down(&self) {
+        lock(&self.queue).shut_down = true;
+        self.job_available.notify_all();
+    }
+
+    /// Run jobs until the pool is shut down, waiting for new ones when the
+    /// queue is empty.
+    fn worker_loop(&self) {
+        loop {
+            let job = {
+                let mut queue = lock(&self.queue);
+                loop {
+                    if let Some(job) = queue.jobs.pop_front() {
+                        break job;
+                    }
+                    if queue.shut_down {
+                        queue.threads -= 1;
+                        return;
+                    }
+                    queue.idle += 1;
+                    queue = self.job_available.wait(queue).unwrap_or_else(|e| e.into_inner());
+                    queue.idle -= 1;
+                }
+            };
+
+            // Jobs catch their own panics, so the thread survives them
+            job();
+
+            self.in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Lock a mutex, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::Executor;
+    use crate::executor::block_on;
+    use crate::timer::YieldTimer;
+    use std::panic;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_spawn_blocking_returns_value() {
+        let mut executor = Executor::new();
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = Arc::clone(&result);
+
+        executor.spawn(async move {
+            let value = spawn_blocking(|| {
+                thread::sleep(Duration::from_millis(20));
+                "done"
+            })
+            .await;
+            *result_clone.lock().unwrap() = Some(value);
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(*result.lock().unwrap(), Some("done"));
+    }
+
+    #[test]
+    fn test_cooperative_tasks_progress_during_blocking_sleep() {
+        let mut executor = Executor::new();
+        let yields_during_sleep = Arc::new(AtomicUsize::new(0));
+        let sleeping = Arc::new(std::sync::atomic::AtomicBool::new(true));
+
+        let sleeping_clone = Arc::clone(&sleeping);
+        executor.spawn(async move {
+            spawn_blocking(|| thread::sleep(Duration::from_millis(100))).await;
+            sleeping_clone.store(false, Ordering::SeqCst);
+        });
+
+        // A cooperative task that keeps yielding while the sleep runs
+        let yields_clone = Arc::clone(&yields_during_sleep);
+        let sleeping_clone = Arc::clone(&sleeping);
+        executor.spawn(async move {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(50) {
+                if sleeping_clone.load(Ordering::SeqCst) {
+                    yields_clone.fetch_add(1, Ordering::SeqCst);
+                }
+                YieldTimer::new(1).await;
+            }
+        });
+
+        let start = Instant::now();
+        assert_eq!(executor.run(), 2);
+
+        assert!(!sleeping.load(Ordering::SeqCst));
+        assert!(yields_during_sleep.load(Ordering::SeqCst) > 1);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_blocking_jobs_run_in_parallel() {
+        let mut executor = Executor::new();
+        let start = Instant::now();
+
+        for _ in 0..4 {
+            executor.spawn(async {
+                spawn_blocking(|| thread::sleep(Duration::from_millis(100))).await;
+            });
+        }
+
+        assert_eq!(executor.run(), 4);
+        assert!(start.elapsed() < Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_panic_is_resumed_in_awaiting_task() {
+        let mut executor = Executor::new();
+        executor.spawn(async {
+            spawn_blocking(|| -> u32 { panic!("blocking job failed") }).await;
+        });
+
+        // The executor isolates the resumed panic to the awaiting task
+        assert_eq!(executor.run(), 0);
+        assert_eq!(executor.panicked_tasks(), 1);
+    }
+
+    #[test]
+    fn test_panic_keeps_its_payload() {
+        let result = panic::catch_unwind(|| block_on(spawn_blocking(|| -> u32 { panic!("disk on fire") })));
+
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"disk on fire"));
+    }
+}
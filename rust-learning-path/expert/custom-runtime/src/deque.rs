@@ -0,0 +1,254 @@
+//! Work-stealing ready queue for our custom runtime.
+//!
+//! Each worker owns a deque of runnable tasks. The owner takes work from
+//! the front, while an idle worker steals from the back of someone else's
+//! deque. Instead of taking one task per steal, a thief takes half of the
+//! victim's tasks at once, so under bursty load it has to steal far less
+//! often.
+//!
+//! Our executor is single-threaded, but the deque is already safe to share
+//! between threads, so the same structure works for a multi-threaded runtime.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A deque of tasks that other workers can steal from in batches.
+///
+/// Cloning the deque gives another handle to the same tasks, which is how
+/// a thief on another thread gets access to its victim.
+pub struct WorkStealingDeque<T> {
+    /// The queued items, oldest at the front
+    items: Arc<Mutex<VecDeque<T>>>,
+
+    /// Counters for steals performed by this deque
+    counters: Arc<StealCounters>,
+}
+
+/// Atomic counters behind `StealStats`
+#[derive(Default)]
+struct StealCounters {
+    attempts: AtomicUsize,
+    successes: AtomicUsize,
+    stolen: AtomicUsize,
+}
+
+/// A snapshot of the steals a deque has performed as the thief.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StealStats {
+    /// How many times this deque tried to steal
+    pub steal_attempts: usize,
+    /// Steals that took at least one task
+    pub successful_steals: usize,
+    /// Total number of tasks taken across all steals
+    pub tasks_stolen: usize,
+}
+
+impl StealStats {
+    /// Average number of tasks taken per successful steal.
+    pub fn average_batch(&self) -> f64 {
+        if self.successful_steals == 0 {
+            0.0
+        } else {
+            self.tasks_stolen as f64 / self.successful_steals as f64
+        }
+    }
+}
+
+impl<T> WorkStealingDeque<T> {
+    /// Create an empty deque.
+    pub fn new() -> Self {
+        Self {
+            items: Arc::new(Mutex::new(VecDeque::new())),
+            counters: Arc::new(StealCounters::default()),
+        }
+    }
+
+    /// Add an item to the back of the deque.
+    pub fn push(&self, item: T) {
+        self.lock().push_back(item);
+    }
+
+    /// Add several items to the back of the deque, keeping their order.
+    pub fn extend<I: IntoIterator<Item = T>>(&self, items: I) {
+        self.lock().extend(items);
+    }
+
+    /// Take the oldest item from the front of the deque.
+    ///
+    /// This is the owner's end; thieves take from the back.
+    pub fn pop(&self) -> Option<T> {
+        self.lock().pop_front()
+    }
+
+    /// Steal half of `victim`'s items (rounded up) into this deque.
+    ///
+    /// The newest items are taken from the back of the victim and keep
+    /// their relative order here. Returns the number of items stolen.
+    pub fn steal_batch_from(&self, victim: &WorkStealingDeque<T>) -> usize {
+        self.counters.attempts.fetch_add(1, Ordering::Relaxed);
+
+        // Stealing from ourselves would only shuffle our own items
+        if Arc::ptr_eq(&self.items, &victim.items) {
+            return 0;
+        }
+
+        // Release the victim before locking ourselves so two workers
+        // stealing from each other can't deadlock
+        let batch: Vec<T> = {
+            let mut victim_items = victim.lock();
+            let count = victim_items.len().div_ceil(2);
+            let split_at = victim_items.len() - count;
+            victim_items.drain(split_at..).collect()
+        };
+
+        let stolen = batch.len();
+        if stolen > 0 {
+            self.counters.successes.fetch_add(1, Ordering::Relaxed);
+            self.counters.stolen.fetch_add(stolen, Ordering::Relaxed);
+            self.extend(batch);
+        }
+        stolen
+    }
+
+    /// Get the statistics of steals performed by this deque.
+    pub fn steal_stats(&self) -> StealStats {
+        StealStats {
+            steal_attempts: self.counters.attempts.load(Ordering::Relaxed),
+            successful_steals: self.counters.successes.load(Ordering::Relaxed),
+            tasks_stolen: self.counters.stolen.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Get the number of queued items.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if the deque has no items.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Count the queued items that match a predicate.
+    pub fn count_matching<F: Fn(&T) -> bool>(&self, predicate: F) -> usize {
+        self.lock().iter().filter(|item| predicate(item)).count()
+    }
+
+    /// Lock the items, recovering them if another thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        self.items.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T> Clone for WorkStealingDeque<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: Arc::clone(&self.items),
+            counters: Arc::clone(&self.counters),
+        }
+    }
+}
+
+impl<T> Default for WorkStealingDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn deque_of(count: usize) -> WorkStealingDeque<usize> {
+        let deque = WorkStealingDeque::new();
+        deque.extend(0..count);
+        deque
+    }
+
+    #[test]
+    fn test_owner_pops_in_fifo_order() {
+        let deque = deque_of(3);
+
+        assert_eq!(deque.pop(), Some(0));
+        assert_eq!(deque.pop(), Some(1));
+        assert_eq!(deque.pop(), Some(2));
+        assert_eq!(deque.pop(), None);
+    }
+
+    #[test]
+    fn test_steal_takes_half() {
+        let victim = deque_of(10);
+        let thief = WorkStealingDeque::new();
+
+        assert_eq!(thief.steal_batch_from(&victim), 5);
+        assert_eq!(victim.len(), 5);
+
+        // The thief gets the newest tasks, still in order
+        assert_eq!(thief.pop(), Some(5));
+        assert_eq!(victim.pop(), Some(0));
+
+        // Odd counts round up so a single task can still be stolen
+        let victim = deque_of(1);
+        assert_eq!(thief.steal_batch_from(&victim), 1);
+        assert!(victim.is_empty());
+    }
+
+    #[test]
+    fn test_steal_stats() {
+        let victim = deque_of(8);
+        let thief = WorkStealingDeque::new();
+
+        thief.steal_batch_from(&victim); // takes 4
+        thief.steal_batch_from(&victim); // takes 2
+        thief.steal_batch_from(&WorkStealingDeque::new()); // nothing to take
+
+        let stats = thief.steal_stats();
+        assert_eq!(stats, StealStats {
+            steal_attempts: 3,
+            successful_steals: 2,
+            tasks_stolen: 6,
+        });
+        assert_eq!(stats.average_batch(), 3.0);
+
+        // Only the thief records the steal
+        assert_eq!(victim.steal_stats(), StealStats::default());
+    }
+
+    #[test]
+    fn test_steal_from_self_is_a_no_op() {
+        let deque = deque_of(4);
+
+        assert_eq!(deque.steal_batch_from(&deque.clone()), 0);
+        assert_eq!(deque.len(), 4);
+    }
+
+    #[test]
+    fn test_concurrent_stealing_loses_nothing() {
+        let victim = deque_of(1000);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let victim = victim.clone();
+                thread::spawn(move || {
+                    let local = WorkStealingDeque::new();
+                    let mut taken = Vec::new();
+                    while local.steal_batch_from(&victim) > 0 {
+                        while let Some(item) = local.pop() {
+                            taken.push(item);
+                        }
+                    }
+                    taken
+                })
+            })
+            .collect();
+
+        let mut all: Vec<usize> = handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        all.sort_unstable();
+
+        assert_eq!(all, (0..1000).collect::<Vec<_>>());
+    }
+}
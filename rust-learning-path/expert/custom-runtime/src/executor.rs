@@ -6,94 +6,197 @@
 //! - Managing task lifecycle
 //! - Running the main event loop
 
-use crate::task::Task;
-use crate::waker::TaskWaker;
-use std::collections::VecDeque;
+use crate::blocking::{self, BlockingPool};
+use crate::deque::{StealStats, WorkStealingDeque};
+use crate::join::{self, JoinHandle};
+use crate::reactor;
+use crate::timer;
+use crate::task::{Task, TaskId};
+use crate::waker::{ReadyQueue, TaskWaker};
+use std::collections::HashMap;
 use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// How long `park` waits at a time while only blocking work can make progress
+const BLOCKING_WAIT: Duration = Duration::from_millis(10);
 
 /// A simple single-threaded executor for async tasks.
 /// 
-/// This executor uses a basic round-robin scheduling approach:
-/// 1. Tasks are stored in a queue
-/// 2. Ready tasks are polled in order
-/// 3. Completed tasks are removed
-/// 4. The process repeats until all tasks are done
+/// This executor schedules tasks through their wakers:
+/// 1. Ready tasks are stored in a queue and polled in order
+/// 2. A task that returns `Pending` is set aside until it's woken
+/// 3. Waking a task pushes its id onto the executor's ready queue,
+///    from which it moves back into the task queue
+/// 4. Completed tasks are removed
+///
+/// Tasks that aren't woken are never polled, and when nothing is ready
+/// the executor blocks until something is, rather than spinning.
+///
+/// The queue is a work-stealing deque, so an idle executor can take a
+/// batch of ready tasks from a busy one with `steal_from`.
+///
+/// Tasks spawned with `spawn_with_deadline` are polled earliest deadline
+/// first (EDF) within each pass over the queue, ahead of tasks without a
+/// deadline, which keep their FIFO order. This is best-effort: a pass
+/// never preempts a running task, a task woken mid-pass waits for the
+/// next pass, and missing a deadline has no consequence beyond ordering.
+///
+/// A task that panics while being polled is dropped and counted in
+/// `panicked_tasks`; the other tasks keep running. The panic is caught with
+/// `AssertUnwindSafe`, so a panicking task can leave data it shares with
+/// other tasks (say, behind a `RefCell` or a half-updated struct) in an
+/// inconsistent state. Poisoned `Mutex`es still report it.
+///
+/// Closures passed to `spawn_blocking` run on a pool of OS threads, shared
+/// with the free `spawn_blocking` function unless the executor was created
+/// with a pool of its own by `with_blocking_threads`.
 pub struct Executor {
-    /// Queue of tasks waiting to be executed
-    /// We use VecDeque for efficient push/pop operations
-    task_queue: VecDeque<Task>,
+    /// Queue of tasks ready to be polled
+    /// The owner pops from the front; thieves steal from the back
+    task_queue: WorkStealingDeque<Task>,
+
+    /// Tasks that returned `Pending` and haven't been woken since
+    waiting: HashMap<TaskId, WaitingTask>,
+
+    /// Ids of waiting tasks that have been woken, pushed by their wakers
+    ready_queue: Arc<ReadyQueue>,
+
+    /// Number of tasks dropped because they panicked
+    panicked_tasks: usize,
+
+    /// The executor's own blocking pool; `None` uses the shared one
+    blocking_pool: Option<Arc<BlockingPool>>,
+}
+
+/// A task waiting to be woken, along with the waker it was last polled with
+struct WaitingTask {
+    task: Task,
+
+    /// Every `Waker` handed out for the task shares this, so its reference
+    /// count tells whether anything can still wake the task
+    waker: Arc<TaskWaker>,
 }
 
 impl Executor {
     /// Create a new executor with an empty task queue.
     pub fn new() -> Self {
         Self {
-            task_queue: VecDeque::new(),
+            task_queue: WorkStealingDeque::new(),
+            waiting: HashMap::new(),
+            ready_queue: Arc::new(ReadyQueue::new()),
+            panicked_tasks: 0,
+            blocking_pool: None,
         }
     }
 
+    /// Create an executor with a blocking pool of its own, running at most
+    /// `threads` closures from `spawn_blocking` at a time.
+    ///
+    /// The pool's threads are started as needed and exit once the executor
+    /// is dropped and the work already submitted is done.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is 0.
+    pub fn with_blocking_threads(threads: usize) -> Self {
+        let mut executor = Self::new();
+        executor.blocking_pool = Some(BlockingPool::new(threads));
+        executor
+    }
+
     /// Spawn a new task on this executor.
     /// 
     /// The task will be added to the queue and executed when the
-    /// executor runs. The future must return () for simplicity.
-    pub fn spawn<F>(&mut self, future: F)
+    /// executor runs. The returned handle yields the future's output:
+    /// await it from another task, or call `try_join` after `run`.
+    /// Dropping the handle detaches the task, which still runs to completion.
+    pub fn spawn<F>(&mut self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (future, handle) = join::join_pair(future);
+        self.task_queue.push(Task::new(future));
+        handle
+    }
+
+    /// Spawn a new task that should finish by `deadline`.
+    ///
+    /// Whenever several tasks are ready, the one with the nearest deadline
+    /// is polled first. See the type-level docs for the limits of this.
+    pub fn spawn_with_deadline<F>(&mut self, future: F, deadline: Instant) -> JoinHandle<F::Output>
     where
-        F: Future<Output = ()> + Send + 'static,
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
     {
-        let task = Task::new(future);
-        self.task_queue.push_back(task);
+        let (future, handle) = join::join_pair(future);
+        self.task_queue.push(Task::with_deadline(future, deadline));
+        handle
+    }
+
+    /// Run a blocking closure on the executor's blocking pool.
+    ///
+    /// The closure runs on a pool thread, so it can sleep or compute for
+    /// as long as it needs to without holding up the executor's tasks.
+    /// Await the returned handle from a task to get the result, waking that
+    /// task when the closure is done; if the closure panics, awaiting the
+    /// handle panics too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_runtime::Executor;
+    ///
+    /// let mut executor = Executor::with_blocking_threads(2);
+    /// let sum = executor.spawn_blocking(|| (1..=10).sum::<u32>());
+    /// let handle = executor.spawn(async move { sum.await * 2 });
+    /// executor.run();
+    /// assert_eq!(handle.try_join(), Some(110));
+    /// ```
+    pub fn spawn_blocking<F, T>(&self, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (job, handle) = join::join_closure(f);
+        self.blocking_pool().submit(Box::new(job));
+        handle
+    }
+
+    /// Get the pool `spawn_blocking` submits to.
+    fn blocking_pool(&self) -> &Arc<BlockingPool> {
+        self.blocking_pool.as_ref().unwrap_or_else(|| blocking::pool())
     }
 
     /// Run the executor until all tasks complete.
     /// 
     /// This is the main event loop that:
-    /// 1. Checks each task to see if it's ready
-    /// 2. Polls ready tasks
-    /// 3. Removes completed tasks
-    /// 4. Repeats until no tasks remain
+    /// 1. Moves woken tasks into the task queue
+    /// 2. Polls the ready tasks
+    /// 3. Removes completed tasks and sets pending ones aside
+    /// 4. Blocks until a task is woken if none are ready
+    /// 5. Repeats until no tasks remain
     /// 
     /// Returns the number of tasks that were executed.
     pub fn run(&mut self) -> usize {
         let mut completed_tasks = 0;
 
         // Keep running while we have tasks
-        while !self.task_queue.is_empty() {
-            let mut tasks_to_retry = VecDeque::new();
-            
-            // Process all current tasks
-            while let Some(mut task) = self.task_queue.pop_front() {
-                if task.is_ready() {
-                    // Create a waker for this task
-                    let ready_handle = task.ready_handle();
-                    let task_waker = TaskWaker::new(ready_handle);
-                    let waker = task_waker.into_waker();
-                    let mut cx = Context::from_waker(&waker);
-
-                    // Poll the task
-                    match task.poll(&mut cx) {
-                        Poll::Ready(()) => {
-                            // Task completed, don't add it back to the queue
-                            completed_tasks += 1;
-                        }
-                        Poll::Pending => {
-                            // Task is not ready, mark it as such and re-queue
-                            task.set_not_ready();
-                            tasks_to_retry.push_back(task);
-                        }
-                    }
-                } else {
-                    // Task is not ready, just re-queue it
-                    tasks_to_retry.push_back(task);
-                }
-            }
+        while self.has_tasks() {
+            completed_tasks += self.run_once();
 
-            // Add all pending tasks back to the queue
-            self.task_queue = tasks_to_retry;
-
-            // If no tasks are ready and none completed this iteration,
-            // we might be in a deadlock situation
-            if self.task_queue.iter().all(|task| !task.is_ready()) && !self.task_queue.is_empty() {
+            // If no tasks are ready, sleep until one is woken
+            if self.task_queue.is_empty() && !self.waiting.is_empty() {
+                // Unless nothing can wake them anymore
+                if self.park() {
+                    self.requeue_woken();
+                    continue;
+                }
                 eprintln!("Warning: All tasks are pending and none are ready. Possible deadlock.");
                 break;
             }
@@ -110,53 +213,261 @@ impl Executor {
     /// Useful for more fine-grained control over execution.
     pub fn run_once(&mut self) -> usize {
         let mut completed_tasks = 0;
-        let mut tasks_to_retry = VecDeque::new();
-        
-        // Process all current tasks once
-        while let Some(mut task) = self.task_queue.pop_front() {
-            if task.is_ready() {
-                // Create a waker for this task
-                let ready_handle = task.ready_handle();
-                let task_waker = TaskWaker::new(ready_handle);
-                let waker = task_waker.into_waker();
-                let mut cx = Context::from_waker(&waker);
-
-                // Poll the task
-                match task.poll(&mut cx) {
-                    Poll::Ready(()) => {
-                        // Task completed
-                        completed_tasks += 1;
-                    }
-                    Poll::Pending => {
-                        // Task is not ready, mark it as such and re-queue
-                        task.set_not_ready();
-                        tasks_to_retry.push_back(task);
-                    }
-                }
-            } else {
-                // Task is not ready, just re-queue it
-                tasks_to_retry.push_back(task);
+
+        // Pick up tasks woken since the last iteration
+        self.requeue_woken();
+
+        // Process all ready tasks once
+        for task in self.take_pass() {
+            if self.poll_task(task) {
+                completed_tasks += 1;
             }
         }
 
-        // Add all pending tasks back to the queue
-        self.task_queue = tasks_to_retry;
+        // Pick up any I/O that became ready and any timers that are due,
+        // without waiting
+        poll_events();
+
+        // Tasks woken during the pass are ready for the next one
+        self.requeue_woken();
         completed_tasks
     }
 
+    /// Poll a task once. Returns true if it completed.
+    ///
+    /// A pending task is set aside in `waiting` until its waker is called.
+    fn poll_task(&mut self, mut task: Task) -> bool {
+        let task_waker = Arc::new(TaskWaker::with_queue(
+            task.id(),
+            task.ready_handle(),
+            Arc::clone(&self.ready_queue),
+        ));
+        let waker = TaskWaker::waker(&task_waker);
+        let mut cx = Context::from_waker(&waker);
+
+        // Clear the flag before polling, so a wake that happens during
+        // the poll (like a future waking itself) isn't lost
+        task.set_not_ready();
+
+        // Poll the task, containing any panic to this task
+        match panic::catch_unwind(AssertUnwindSafe(|| task.poll(&mut cx))) {
+            Ok(Poll::Ready(())) => true,
+            Ok(Poll::Pending) => {
+                // If it was woken during the poll, its id is already queued
+                // and `requeue_woken` moves it back
+                self.waiting.insert(task.id(), WaitingTask { task, waker: task_waker });
+                false
+            }
+            Err(_) => {
+                self.task_panicked();
+                false
+            }
+        }
+    }
+
+    /// Move every woken task from `waiting` to the task queue.
+    fn requeue_woken(&mut self) {
+        for id in self.ready_queue.drain() {
+            // Unknown ids belong to tasks that completed or were stolen
+            if let Some(waiting) = self.waiting.remove(&id) {
+                self.task_queue.push(waiting.task);
+            }
+        }
+    }
+
+    /// Check if anything besides this executor holds a waker for a waiting task.
+    fn has_outstanding_wakers(&self) -> bool {
+        self.waiting.values().any(|waiting| Arc::strong_count(&waiting.waker) > 1)
+    }
+
+    /// Get the number of tasks that panicked and were removed.
+    pub fn panicked_tasks(&self) -> usize {
+        self.panicked_tasks
+    }
+
+    /// Get the earliest time a pending timer might be due.
+    ///
+    /// When every task is waiting, `run` sleeps until this instant rather
+    /// than polling timers over and over. The timer wheel is shared by all
+    /// executors, so this covers their timers too.
+    pub fn next_timer_deadline(&self) -> Option<Instant> {
+        timer::next_deadline()
+    }
+
+    /// Block until something that could wake a waiting task happens.
+    ///
+    /// Returns false, without waiting, if nothing can. See `park`.
+    fn park(&self) -> bool {
+        let blocking_busy = blocking::in_flight() > 0 || self.blocking_pool().in_flight() > 0;
+        park(&self.ready_queue, self.has_outstanding_wakers(), blocking_busy)
+    }
+
+    /// Record a task that panicked while being polled.
+    ///
+    /// The task itself has already been dropped; the panic message was
+    /// printed by the panic hook when it happened.
+    fn task_panicked(&mut self) {
+        self.panicked_tasks += 1;
+        eprintln!("Warning: A task panicked and was removed from the executor.");
+    }
+
+    /// Take every ready task, in the order this pass should poll them.
+    ///
+    /// Tasks with a deadline come first, earliest deadline first. The sort
+    /// is stable, so tasks without one (and ties) keep their queue order.
+    fn take_pass(&self) -> Vec<Task> {
+        let mut tasks = Vec::with_capacity(self.task_queue.len());
+        while let Some(task) = self.task_queue.pop() {
+            tasks.push(task);
+        }
+        tasks.sort_by_key(|task| (task.deadline().is_none(), task.deadline()));
+        tasks
+    }
+
     /// Check if the executor has any tasks remaining.
     pub fn has_tasks(&self) -> bool {
-        !self.task_queue.is_empty()
+        !self.task_queue.is_empty() || !self.waiting.is_empty()
     }
 
-    /// Get the number of tasks currently in the queue.
+    /// Get the number of tasks, ready or waiting.
     pub fn task_count(&self) -> usize {
-        self.task_queue.len()
+        self.task_queue.len() + self.waiting.len()
     }
 
     /// Get the number of ready tasks.
+    ///
+    /// These are the queued tasks plus the waiting tasks that have been
+    /// woken; exactly the tasks the next iteration will poll.
     pub fn ready_task_count(&self) -> usize {
-        self.task_queue.iter().filter(|task| task.is_ready()).count()
+        let woken = self.ready_queue.snapshot().iter()
+            .filter(|id| self.waiting.contains_key(id))
+            .count();
+        self.task_queue.len() + woken
+    }
+
+    /// Steal half of another executor's ready tasks into this one.
+    ///
+    /// Returns the number of tasks stolen. An idle executor calls this on
+    /// a busy one; taking a batch means it has to steal less often.
+    /// Waiting tasks stay with their executor, which their wakers point to.
+    pub fn steal_from(&self, victim: &Executor) -> usize {
+        self.task_queue.steal_batch_from(&victim.task_queue)
+    }
+
+    /// Get the statistics of steals this executor has performed.
+    pub fn steal_stats(&self) -> StealStats {
+        self.task_queue.steal_stats()
+    }
+}
+
+/// Run a future to completion on the current thread and return its output.
+///
+/// This is the simplest way to use the runtime: no executor to set up, and
+/// the future doesn't need to be `Send` or `'static`. Timers, I/O sources,
+/// and `spawn_blocking` work inside it as they do in spawned tasks. Each
+/// call has its own ready queue, so calls can follow one another freely.
+///
+/// The future runs alone: use `select2` to race two futures, or an
+/// `Executor` to run several tasks.
+///
+/// # Panics
+///
+/// Panics if the future is pending and nothing can ever wake it.
+///
+/// # Examples
+///
+/// ```
+/// use custom_runtime::{block_on, Timer};
+///
+/// let value = block_on(async {
+///     Timer::after_millis(10).await;
+///     42
+/// });
+/// assert_eq!(value, 42);
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+
+    let ready_queue = Arc::new(ReadyQueue::new());
+    let ready = Arc::new(AtomicBool::new(true));
+    let task_waker = Arc::new(TaskWaker::with_queue(TaskId::next(), Arc::clone(&ready), Arc::clone(&ready_queue)));
+    let waker = TaskWaker::waker(&task_waker);
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        // Same as the executor: clear the flag first so no wake is lost
+        ready.store(false, Ordering::Release);
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        poll_events();
+        while !ready.load(Ordering::Acquire) {
+            // `task_waker` and `waker` are ours; anything more is held elsewhere
+            if !park(&ready_queue, Arc::strong_count(&task_waker) > 2, blocking::in_flight() > 0) {
+                panic!("block_on: the future is pending and nothing can wake it");
+            }
+        }
+        ready_queue.drain();
+    }
+}
+
+/// Process I/O events and due timers without waiting, waking their tasks.
+fn poll_events() {
+    if reactor::has_waiters() {
+        turn_reactor(Some(Duration::ZERO));
+    }
+    timer::fire_due();
+}
+
+/// Block until something that could wake a task happens: a waker being
+/// called, an I/O event, or a timer coming due.
+///
+/// Returns false, without waiting, if none of those can happen: no timer
+/// is pending, no blocking work is running (`blocking_busy`), and nothing
+/// holds a waker for any waiting task (`has_outstanding_wakers`).
+fn park(ready_queue: &ReadyQueue, has_outstanding_wakers: bool, blocking_busy: bool) -> bool {
+    let until_timer = timer::next_deadline()
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+    if until_timer.is_none() && !blocking_busy && !has_outstanding_wakers {
+        return false;
+    }
+
+    // Blocking work whose handle was dropped wakes nobody, so keep
+    // checking whether it's still running
+    let timeout = match (until_timer, blocking_busy) {
+        (Some(wait), true) => Some(wait.min(BLOCKING_WAIT)),
+        (None, true) => Some(BLOCKING_WAIT),
+        (wait, false) => wait,
+    };
+
+    if reactor::has_waiters() {
+        // Wakers interrupt the reactor while the thread blocks in it
+        ready_queue.block_in_reactor(|| turn_reactor(timeout));
+    } else {
+        ready_queue.wait(timeout);
+    }
+
+    timer::fire_due();
+    true
+}
+
+/// Process I/O events, waking the tasks waiting on them.
+///
+/// A failing poller is reported and otherwise ignored; the tasks
+/// waiting on it stay pending.
+fn turn_reactor(timeout: Option<Duration>) {
+    if let Err(e) = reactor::turn(timeout) {
+        eprintln!("Warning: I/O reactor failed: {}", e);
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.blocking_pool {
+            pool.shut_down();
+        }
     }
 }
 
@@ -255,4 +566,314 @@ mod tests {
         assert_eq!(completed, 1);
         assert_eq!(counter.load(Ordering::SeqCst), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_steal_from_busy_executor() {
+        let mut busy = Executor::new();
+        let idle = Executor::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..10 {
+            let counter_clone = Arc::clone(&counter);
+            busy.spawn(async move {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        assert_eq!(idle.steal_from(&busy), 5);
+        assert_eq!((busy.task_count(), idle.task_count()), (5, 5));
+        assert_eq!(idle.steal_stats().tasks_stolen, 5);
+
+        // Stealing moves tasks around without losing any
+        let mut idle = idle;
+        assert_eq!(busy.run() + idle.run(), 10);
+        assert_eq!(counter.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn test_earliest_deadline_polled_first() {
+        let mut executor = Executor::new();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let now = Instant::now();
+
+        // Spawned in a different order than their deadlines
+        let tasks = [
+            ("none-1", None),
+            ("late", Some(now + Duration::from_millis(300))),
+            ("early", Some(now + Duration::from_millis(100))),
+            ("none-2", None),
+            ("middle", Some(now + Duration::from_millis(200))),
+        ];
+        for (name, deadline) in tasks {
+            let order_clone = Arc::clone(&order);
+            let future = async move {
+                order_clone.lock().unwrap().push(name);
+            };
+            match deadline {
+                Some(deadline) => executor.spawn_with_deadline(future, deadline),
+                None => executor.spawn(future),
+            };
+        }
+
+        assert_eq!(executor.run(), 5);
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["early", "middle", "late", "none-1", "none-2"]
+        );
+    }
+
+    #[test]
+    fn test_deadline_order_applies_on_every_pass() {
+        use crate::timer::YieldTimer;
+
+        let mut executor = Executor::new();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let now = Instant::now();
+
+        // Both tasks yield, so they're ready together again on the second pass
+        for (name, offset_ms) in [("later", 50), ("sooner", 10)] {
+            let order_clone = Arc::clone(&order);
+            executor.spawn_with_deadline(async move {
+                order_clone.lock().unwrap().push(format!("{} start", name));
+                YieldTimer::new(1).await;
+                order_clone.lock().unwrap().push(format!("{} end", name));
+            }, now + Duration::from_millis(offset_ms));
+        }
+
+        assert_eq!(executor.run(), 2);
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["sooner start", "later start", "sooner end", "later end"]
+        );
+    }
+
+    #[test]
+    fn test_panicking_task_is_isolated() {
+        use crate::timer::YieldTimer;
+
+        let mut executor = Executor::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        for i in 0..4 {
+            let counter_clone = Arc::clone(&counter);
+            executor.spawn(async move {
+                YieldTimer::new(1).await;
+                if i == 1 {
+                    panic!("task {} failed", i);
+                }
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        // The panic happens on the second pass, after every task has started
+        assert_eq!(executor.run(), 3);
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(executor.panicked_tasks(), 1);
+        assert!(!executor.has_tasks());
+    }
+
+    #[test]
+    fn test_run_once_counts_panics() {
+        let mut executor = Executor::new();
+        executor.spawn(async { panic!("first poll fails") });
+        executor.spawn(async {});
+
+        assert_eq!(executor.run_once(), 1);
+        assert_eq!(executor.panicked_tasks(), 1);
+        assert!(!executor.has_tasks());
+    }
+
+    /// Returns `Pending` until `done` is set, counting its polls and
+    /// handing its waker to whoever is in `waker_slot`
+    struct ExternallyWoken {
+        done: Arc<std::sync::atomic::AtomicBool>,
+        polls: Arc<AtomicUsize>,
+        waker_slot: Arc<std::sync::Mutex<Option<std::task::Waker>>>,
+    }
+
+    impl Future for ExternallyWoken {
+        type Output = ();
+
+        fn poll(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.polls.fetch_add(1, Ordering::SeqCst);
+            if self.done.load(Ordering::SeqCst) {
+                return Poll::Ready(());
+            }
+            *self.waker_slot.lock().unwrap() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_task_woken_from_another_thread() {
+        let mut executor = Executor::new();
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let polls = Arc::new(AtomicUsize::new(0));
+        let waker_slot = Arc::new(std::sync::Mutex::new(None));
+
+        executor.spawn(ExternallyWoken {
+            done: Arc::clone(&done),
+            polls: Arc::clone(&polls),
+            waker_slot: Arc::clone(&waker_slot),
+        });
+
+        let waker_slot_clone = Arc::clone(&waker_slot);
+        let waker_thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            done.store(true, Ordering::SeqCst);
+            let waker: Option<std::task::Waker> = waker_slot_clone.lock().unwrap().take();
+            waker.expect("task should have been polled").wake();
+        });
+
+        let start = Instant::now();
+        assert_eq!(executor.run(), 1);
+        waker_thread.join().unwrap();
+
+        // Polled once to start and once after the wake: the executor
+        // blocked in between instead of re-polling the task
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_ready_task_count_follows_wakes() {
+        let mut executor = Executor::new();
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let polls = Arc::new(AtomicUsize::new(0));
+        let waker_slot = Arc::new(std::sync::Mutex::new(None));
+
+        executor.spawn(ExternallyWoken {
+            done: Arc::clone(&done),
+            polls: Arc::clone(&polls),
+            waker_slot: Arc::clone(&waker_slot),
+        });
+        assert_eq!(executor.ready_task_count(), 1);
+
+        // Pending and not woken: still a task, but not a ready one,
+        // and further iterations leave it alone
+        assert_eq!(executor.run_once(), 0);
+        assert_eq!(executor.run_once(), 0);
+        assert_eq!((executor.task_count(), executor.ready_task_count()), (1, 0));
+        assert_eq!(polls.load(Ordering::SeqCst), 1);
+
+        done.store(true, Ordering::SeqCst);
+        let waker: Option<std::task::Waker> = waker_slot.lock().unwrap().take();
+        waker.unwrap().wake();
+        assert_eq!(executor.ready_task_count(), 1);
+
+        assert_eq!(executor.run_once(), 1);
+        assert!(!executor.has_tasks());
+    }
+
+    #[test]
+    fn test_unwakeable_task_is_reported_not_waited_on() {
+        let mut executor = Executor::new();
+
+        // Nothing keeps this task's waker, so it can never be woken
+        executor.spawn(std::future::pending::<()>());
+
+        assert_eq!(executor.run(), 0);
+        assert_eq!(executor.task_count(), 1);
+    }
+
+    #[test]
+    fn test_block_on_timer_returns_value() {
+        use crate::timer::Timer;
+
+        let start = Instant::now();
+        let value = block_on(async {
+            Timer::after_millis(30).await;
+            "done"
+        });
+
+        assert_eq!(value, "done");
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_block_on_runs_sequentially_and_borrows() {
+        use crate::timer::{Timer, YieldTimer};
+
+        // Neither Send nor 'static: block_on doesn't need either
+        let shared = std::rc::Rc::new(std::cell::Cell::new(0));
+        let local = &shared;
+
+        for i in 1..=3 {
+            let result = block_on(async {
+                YieldTimer::new(2).await;
+                Timer::after_millis(5).await;
+                local.set(local.get() + i);
+                local.get()
+            });
+            assert_eq!(result, shared.get());
+        }
+        assert_eq!(shared.get(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing can wake it")]
+    fn test_block_on_unwakeable_future_panics() {
+        block_on(std::future::pending::<()>());
+    }
+
+    #[test]
+    fn test_spawn_blocking_runs_alongside_timer() {
+        use crate::timer::Timer;
+
+        let mut executor = Executor::with_blocking_threads(2);
+        let start = Instant::now();
+
+        let computation = executor.spawn_blocking(|| {
+            std::thread::sleep(Duration::from_millis(100));
+            (1..=100u64).sum::<u64>()
+        });
+        let blocking_task = executor.spawn(async move {
+            let sum = computation.await;
+            (sum, start.elapsed())
+        });
+        let timer_task = executor.spawn(async move {
+            Timer::after_millis(20).await;
+            start.elapsed()
+        });
+
+        assert_eq!(executor.run(), 2);
+
+        let (sum, blocking_done) = blocking_task.try_join().unwrap();
+        let timer_done = timer_task.try_join().unwrap();
+        assert_eq!(sum, 5050);
+        // The timer fired while the computation was still sleeping
+        assert!(timer_done < blocking_done, "timer {:?}, blocking {:?}", timer_done, blocking_done);
+        assert!(blocking_done >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_blocking_threads_limit_parallelism() {
+        let mut executor = Executor::with_blocking_threads(1);
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            let sleep = executor.spawn_blocking(|| std::thread::sleep(Duration::from_millis(50)));
+            executor.spawn(sleep);
+        }
+
+        // One thread runs the two sleeps one after the other
+        assert_eq!(executor.run(), 2);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_spawn_blocking_panic_reaches_awaiting_task() {
+        let mut executor = Executor::with_blocking_threads(1);
+        let failing = executor.spawn_blocking(|| -> u32 { panic!("blocking job failed") });
+        executor.spawn(async move {
+            failing.await;
+        });
+        // The pool thread survives the panic and runs the next closure
+        let next = executor.spawn_blocking(|| 7);
+        let next = executor.spawn(next);
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(executor.panicked_tasks(), 1);
+        assert_eq!(next.try_join(), Some(7));
+    }
+}
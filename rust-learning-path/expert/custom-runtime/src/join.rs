@@ -0,0 +1,231 @@
+//! Join handles: getting a spawned task's output back.
+//!
+//! The executor only runs futures that return `()`, so `spawn` wraps the
+//! user's future in one that stores its output in state shared with a
+//! `JoinHandle`. The handle is itself a future: awaiting it from another
+//! task waits for the output, and completion wakes the waiting task just
+//! like any other event. Blocking work delivers its output to a
+//! `JoinHandle` the same way, from a pool thread.
+
+use std::any::Any;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+/// A handle to a spawned task's output.
+///
+/// Await it from another task to get the output, or call `try_join` once
+/// the executor has run. The output is kept until it's taken.
+///
+/// Dropping the handle detaches the task: it still runs to completion,
+/// and its output is simply dropped.
+pub struct JoinHandle<T> {
+    state: Arc<Mutex<JoinState<T>>>,
+}
+
+/// State shared between a `JoinHandle` and its task
+struct JoinState<T> {
+    /// The task's output, until the handle takes it
+    output: Option<T>,
+
+    /// Set when the task is done, whether it returned or panicked
+    finished: bool,
+
+    /// Payload of a panic caught in a closure, until the handle resumes it
+    panic: Option<Box<dyn Any + Send>>,
+
+    /// Waker of the task awaiting the handle
+    waker: Option<Waker>,
+}
+
+/// Wrap `future` so its output is delivered to the returned handle.
+pub(crate) fn join_pair<F>(future: F) -> (impl Future<Output = ()> + Send + 'static, JoinHandle<F::Output>)
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (guard, handle) = guarded_handle();
+
+    let task = async move {
+        // If the future panics, the executor drops this task and the guard
+        // still marks it finished, so a waiting handle isn't left hanging
+        let output = future.await;
+        lock(&guard.state).output = Some(output);
+    };
+
+    (task, handle)
+}
+
+/// Wrap the closure `f` so its result is delivered to the returned handle.
+///
+/// The wrapper catches a panic in `f`, so it can run on a thread that has
+/// to survive it; awaiting the handle then resumes the panic with its
+/// original payload.
+pub(crate) fn join_closure<F, T>(f: F) -> (impl FnOnce() + Send + 'static, JoinHandle<T>)
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (guard, handle) = guarded_handle();
+
+    let job = move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let mut state = lock(&guard.state);
+        match result {
+            Ok(output) => state.output = Some(output),
+            Err(payload) => state.panic = Some(payload),
+        }
+    };
+
+    (job, handle)
+}
+
+/// Create a handle along with the guard that finishes it
+fn guarded_handle<T>() -> (FinishGuard<T>, JoinHandle<T>) {
+    let state = Arc::new(Mutex::new(JoinState {
+        output: None,
+        finished: false,
+        panic: None,
+        waker: None,
+    }));
+    let handle = JoinHandle {
+        state: Arc::clone(&state),
+    };
+
+    (FinishGuard { state }, handle)
+}
+
+impl<T> JoinHandle<T> {
+    /// Check if the task has finished running.
+    pub fn is_finished(&self) -> bool {
+        lock(&self.state).finished
+    }
+
+    /// Take the task's output, if it has finished and the output is still there.
+    pub fn try_join(&self) -> Option<T> {
+        lock(&self.state).output.take()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// Panics if the task panicked, or if its output was already taken
+    /// with `try_join`. A panic in blocking work is resumed as it was
+    /// raised, so it keeps its message.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.state);
+
+        if let Some(output) = state.output.take() {
+            return Poll::Ready(output);
+        }
+        if let Some(payload) = state.panic.take() {
+            drop(state);
+            panic::resume_unwind(payload);
+        }
+        if state.finished {
+            drop(state);
+            panic!("joined task panicked or its output was already taken");
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Marks the task finished and wakes its handle when dropped
+struct FinishGuard<T> {
+    state: Arc<Mutex<JoinState<T>>>,
+}
+
+impl<T> Drop for FinishGuard<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = lock(&self.state);
+            state.finished = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Lock a mutex, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::Executor;
+    use crate::timer::{Timer, YieldTimer};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_retrieve_output_after_run() {
+        let mut executor = Executor::new();
+        let handle = executor.spawn(async { 42 });
+
+        assert!(!handle.is_finished());
+        assert_eq!(executor.run(), 1);
+
+        assert!(handle.is_finished());
+        assert_eq!(handle.try_join(), Some(42));
+        assert_eq!(handle.try_join(), None);
+    }
+
+    #[test]
+    fn test_await_handle_from_another_task() {
+        let mut executor = Executor::new();
+        let producer = executor.spawn(async {
+            Timer::after_millis(10).await;
+            String::from("done")
+        });
+
+        let consumer = executor.spawn(async move {
+            let output = producer.await;
+            format!("{}!", output)
+        });
+
+        assert_eq!(executor.run(), 2);
+        assert_eq!(consumer.try_join().as_deref(), Some("done!"));
+    }
+
+    #[test]
+    fn test_dropped_handle_detaches_task() {
+        let mut executor = Executor::new();
+        let ran = Arc::new(AtomicBool::new(false));
+        let ran_clone = Arc::clone(&ran);
+
+        drop(executor.spawn(async move {
+            YieldTimer::new(1).await;
+            ran_clone.store(true, Ordering::SeqCst);
+        }));
+
+        assert_eq!(executor.run(), 1);
+        assert!(ran.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_awaiting_panicked_task_panics() {
+        let mut executor = Executor::new();
+        let failing = executor.spawn(async {
+            panic!("task failed");
+        });
+        let waiting = executor.spawn(async move {
+            failing.await;
+        });
+
+        // Both tasks panic: the first directly, the second through the handle
+        assert_eq!(executor.run(), 0);
+        assert_eq!(executor.panicked_tasks(), 2);
+        assert!(waiting.is_finished());
+        assert_eq!(waiting.try_join(), None);
+    }
+}
@@ -3,18 +3,30 @@
 //! This crate demonstrates how async runtimes work by implementing
 //! a basic executor, task system, and waker mechanism from scratch.
 
+pub mod deque;
 pub mod executor;
 pub mod task;
 pub mod waker;
 pub mod timer;
+pub mod stream;
+pub mod blocking;
+pub mod reactor;
+pub mod join;
+pub mod select;
 
-pub use executor::Executor;
+pub use deque::{StealStats, WorkStealingDeque};
+pub use executor::{block_on, Executor};
 pub use task::Task;
 pub use timer::{Timer, YieldTimer};
+pub use stream::{Stream, StreamExt};
+pub use blocking::spawn_blocking;
+pub use reactor::IoSource;
+pub use join::JoinHandle;
+pub use select::{select2, Either};
 
 /// Re-export commonly used types for convenience
 pub mod prelude {
-    pub use crate::{Executor, Task, Timer, YieldTimer};
+    pub use crate::{block_on, select2, spawn_blocking, Either, Executor, IoSource, JoinHandle, Stream, StreamExt, Task, Timer, YieldTimer};
     pub use std::future::Future;
     pub use std::pin::Pin;
     pub use std::task::{Context, Poll};
@@ -33,6 +33,12 @@ fn main() {
     println!("4. Running complex async operations:");
     run_complex_tasks();
 
+    println!("\n{}\n", "=".repeat(40));
+
+    // Example 5: Running a single future with block_on
+    println!("5. Running a single future with block_on:");
+    run_block_on();
+
     println!("\n=== Demo Complete ===");
 }
 
@@ -181,4 +187,15 @@ fn run_complex_tasks() {
     let completed = executor.run();
     println!("  Completed {} tasks", completed);
     println!("  Final shared data value: {}", shared_data.load(Ordering::SeqCst));
-}
\ No newline at end of file
+}
+
+/// Demonstrate running one future to completion without an executor
+fn run_block_on() {
+    let message = block_on(async {
+        println!("  Waiting on a timer inside block_on");
+        Timer::after_millis(20).await;
+        "timer fired"
+    });
+
+    println!("  block_on returned: {}", message);
+}
@@ -0,0 +1,483 @@
+//! The I/O reactor for our custom runtime.
+//!
+//! Timers can get away with waking themselves, but sockets can't: a read
+//! only makes progress once the OS says data has arrived. The reactor asks
+//! the OS (epoll on Linux, kqueue on macOS, via `mio`) which registered
+//! sources are ready, and wakes the tasks that were waiting on them.
+//!
+//! The executor drives the reactor: it checks for I/O events after every
+//! pass over its queue, and when every task is waiting on I/O it blocks in
+//! the reactor until the OS reports something.
+//!
+//! `mio` notifications are edge-triggered, so readiness is tracked here:
+//! a source is assumed ready until an operation on it fails with
+//! `WouldBlock`, then waits for the next event.
+
+use mio::event::Source;
+use mio::{Events, Interest, Poll as MioPoll, Registry, Token};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// How many events one turn of the reactor handles at most
+const EVENTS_CAPACITY: usize = 256;
+
+/// Token of the reactor's own waker; source tokens count up from zero
+const NOTIFY_TOKEN: Token = Token(usize::MAX);
+
+/// The direction of an I/O operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Read = 0,
+    Write = 1,
+}
+
+/// Process one round of I/O events, waking the tasks they concern.
+///
+/// Blocks for up to `timeout` waiting for an event, or indefinitely with
+/// `None`. Returns the number of tasks woken. If another thread is already
+/// turning the reactor, returns 0 straight away.
+pub fn turn(timeout: Option<Duration>) -> io::Result<usize> {
+    reactor().turn(timeout)
+}
+
+/// Check whether any task is waiting on an I/O event.
+pub fn has_waiters() -> bool {
+    reactor().has_waiters()
+}
+
+/// Interrupt a thread blocked in `turn`, or make the next `turn` return
+/// straight away.
+///
+/// Tasks can be woken by something other than I/O, such as another
+/// thread, while the executor is blocked here.
+pub fn notify() {
+    if let Err(e) = reactor().notifier.wake() {
+        eprintln!("Warning: failed to interrupt the I/O reactor: {}", e);
+    }
+}
+
+/// The process-wide reactor
+struct Reactor {
+    /// The OS poller and its event buffer, used by one thread at a time
+    poller: Mutex<(MioPoll, Events)>,
+
+    /// Handle for registering sources without locking the poller
+    registry: Registry,
+
+    /// Readiness state of every registered source
+    sources: Mutex<HashMap<Token, Arc<ScheduledIo>>>,
+
+    /// Token for the next registered source
+    next_token: AtomicUsize,
+
+    /// Wakes the poller up from other threads
+    notifier: mio::Waker,
+}
+
+fn reactor() -> &'static Reactor {
+    static REACTOR: OnceLock<Reactor> = OnceLock::new();
+    REACTOR.get_or_init(|| Reactor::new().expect("failed to create the I/O reactor"))
+}
+
+impl Reactor {
+    fn new() -> io::Result<Self> {
+        let poll = MioPoll::new()?;
+        let registry = poll.registry().try_clone()?;
+        let notifier = mio::Waker::new(&registry, NOTIFY_TOKEN)?;
+
+        Ok(Self {
+            poller: Mutex::new((poll, Events::with_capacity(EVENTS_CAPACITY))),
+            registry,
+            sources: Mutex::new(HashMap::new()),
+            next_token: AtomicUsize::new(0),
+            notifier,
+        })
+    }
+
+    /// Register a source for both readable and writable events.
+    fn register(&self, source: &mut impl Source) -> io::Result<(Token, Arc<ScheduledIo>)> {
+        let token = Token(self.next_token.fetch_add(1, Ordering::Relaxed));
+        self.registry
+            .register(source, token, Interest::READABLE | Interest::WRITABLE)?;
+
+        let io = Arc::new(ScheduledIo::default());
+        lock(&self.sources).insert(token, Arc::clone(&io));
+        Ok((token, io))
+    }
+
+    /// Stop watching a source.
+    fn deregister(&self, token: Token, source: &mut impl Source) -> io::Result<()> {
+        lock(&self.sources).remove(&token);
+        self.registry.deregister(source)
+    }
+
+    fn turn(&self, timeout: Option<Duration>) -> io::Result<usize> {
+        let mut poller = match self.poller.try_lock() {
+            Ok(poller) => poller,
+            Err(std::sync::TryLockError::WouldBlock) => return Ok(0),
+            Err(std::sync::TryLockError::Poisoned(e)) => e.into_inner(),
+        };
+        let (poll, events) = &mut *poller;
+
+        match poll.poll(events, timeout) {
+            Ok(()) => {}
+            // A signal interrupted the wait; that's just a turn with no events
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(0),
+            Err(e) => return Err(e),
+        }
+
+        // Collect the wakers first and wake them without holding any lock
+        let mut wakers = Vec::new();
+        {
+            let sources = lock(&self.sources);
+            for event in events.iter() {
+                // Skips `notify` events, which only interrupt the wait
+                let Some(io) = sources.get(&event.token()) else {
+                    continue;
+                };
+
+                // Errors and hang-ups wake both directions, so the next
+                // operation reports them
+                let failed = event.is_error();
+                if failed || event.is_readable() || event.is_read_closed() {
+                    wakers.extend(io.set_ready(Direction::Read));
+                }
+                if failed || event.is_writable() || event.is_write_closed() {
+                    wakers.extend(io.set_ready(Direction::Write));
+                }
+            }
+        }
+
+        let woken = wakers.len();
+        for waker in wakers {
+            waker.wake();
+        }
+        Ok(woken)
+    }
+
+    fn has_waiters(&self) -> bool {
+        lock(&self.sources).values().any(|io| io.has_waiter())
+    }
+}
+
+/// Readiness of one registered source, per direction
+#[derive(Default)]
+struct ScheduledIo {
+    state: Mutex<IoState>,
+}
+
+struct IoState {
+    /// Whether an operation in each direction might succeed
+    ready: [bool; 2],
+
+    /// Bumped on every event, so clearing readiness can't lose one that
+    /// arrived after the operation that hit `WouldBlock`
+    tick: u64,
+
+    /// Tasks waiting for each direction to become ready
+    wakers: [Option<Waker>; 2],
+}
+
+impl Default for IoState {
+    fn default() -> Self {
+        Self {
+            // Nothing is known about a new source, so let the first
+            // operation find out
+            ready: [true, true],
+            tick: 0,
+            wakers: [None, None],
+        }
+    }
+}
+
+impl ScheduledIo {
+    /// Mark a direction ready and take the waker waiting on it.
+    fn set_ready(&self, direction: Direction) -> Option<Waker> {
+        let mut state = lock(&self.state);
+        state.ready[direction as usize] = true;
+        state.tick += 1;
+        state.wakers[direction as usize].take()
+    }
+
+    /// Mark a direction not ready, unless an event arrived since `tick`.
+    fn clear_ready(&self, direction: Direction, tick: u64) {
+        let mut state = lock(&self.state);
+        if state.tick == tick {
+            state.ready[direction as usize] = false;
+        }
+    }
+
+    /// Return the current tick if the direction is ready, or register the waker.
+    fn poll_ready(&self, direction: Direction, cx: &mut Context<'_>) -> Poll<u64> {
+        let mut state = lock(&self.state);
+        if state.ready[direction as usize] {
+            Poll::Ready(state.tick)
+        } else {
+            state.wakers[direction as usize] = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn has_waiter(&self) -> bool {
+        lock(&self.state).wakers.iter().any(Option::is_some)
+    }
+}
+
+/// An I/O source registered with the reactor.
+///
+/// Wraps a non-blocking `mio` type (such as `mio::net::TcpStream` or
+/// `mio::net::UnixStream`) and turns its `WouldBlock` errors into waits
+/// on the reactor. The source is deregistered when this is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use custom_runtime::{Executor, IoSource};
+/// use mio::net::TcpStream;
+///
+/// let mut executor = Executor::new();
+/// executor.spawn(async {
+///     let stream = TcpStream::connect("127.0.0.1:7878".parse().unwrap()).unwrap();
+///     let stream = IoSource::new(stream).unwrap();
+///
+///     stream.write_all(b"ping").await.unwrap();
+///     let mut buf = [0; 4];
+///     let n = stream.read(&mut buf).await.unwrap();
+///     println!("echoed: {:?}", &buf[..n]);
+/// });
+/// executor.run();
+/// ```
+pub struct IoSource<S: Source> {
+    source: S,
+    token: Token,
+    io: Arc<ScheduledIo>,
+}
+
+impl<S: Source> IoSource<S> {
+    /// Register `source` with the reactor.
+    pub fn new(mut source: S) -> io::Result<Self> {
+        let (token, io) = reactor().register(&mut source)?;
+        Ok(Self { source, token, io })
+    }
+
+    /// Get a reference to the wrapped source.
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Wait until the source might be readable.
+    pub fn readable(&self) -> Readiness<'_> {
+        Readiness {
+            io: &self.io,
+            direction: Direction::Read,
+        }
+    }
+
+    /// Wait until the source might be writable.
+    pub fn writable(&self) -> Readiness<'_> {
+        Readiness {
+            io: &self.io,
+            direction: Direction::Write,
+        }
+    }
+
+    /// Run a non-blocking read operation, waiting and retrying on `WouldBlock`.
+    pub async fn read_with<R>(&self, op: impl FnMut(&S) -> io::Result<R>) -> io::Result<R> {
+        self.io_with(Direction::Read, op).await
+    }
+
+    /// Run a non-blocking write operation, waiting and retrying on `WouldBlock`.
+    pub async fn write_with<R>(&self, op: impl FnMut(&S) -> io::Result<R>) -> io::Result<R> {
+        self.io_with(Direction::Write, op).await
+    }
+
+    async fn io_with<R>(
+        &self,
+        direction: Direction,
+        mut op: impl FnMut(&S) -> io::Result<R>,
+    ) -> io::Result<R> {
+        loop {
+            let tick = Readiness { io: &self.io, direction }.await;
+            match op(&self.source) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    self.io.clear_ready(direction, tick);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<S: Source> IoSource<S>
+where
+    for<'a> &'a S: Read + Write,
+{
+    /// Read some bytes into `buf`, returning how many were read.
+    ///
+    /// Returns 0 once the other end has closed the connection.
+    pub async fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_with(|mut source| source.read(buf)).await
+    }
+
+    /// Write some bytes from `buf`, returning how many were written.
+    pub async fn write(&self, buf: &[u8]) -> io::Result<usize> {
+        self.write_with(|mut source| source.write(buf)).await
+    }
+
+    /// Write all of `buf`.
+    pub async fn write_all(&self, mut buf: &[u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            let written = self.write(buf).await?;
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            buf = &buf[written..];
+        }
+        Ok(())
+    }
+}
+
+impl<S: Source> Drop for IoSource<S> {
+    fn drop(&mut self) {
+        // The source is about to be closed anyway, so a failure here is harmless
+        let _ = reactor().deregister(self.token, &mut self.source);
+    }
+}
+
+/// Future returned by `IoSource::readable` and `IoSource::writable`.
+///
+/// Resolves to the readiness tick, which `io_with` uses to clear readiness
+/// without losing a newer event.
+pub struct Readiness<'a> {
+    io: &'a ScheduledIo,
+    direction: Direction,
+}
+
+impl Future for Readiness<'_> {
+    type Output = u64;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.io.poll_ready(self.direction, cx)
+    }
+}
+
+/// Lock a mutex, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::executor::Executor;
+    use mio::net::{TcpListener, TcpStream, UnixStream};
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn test_read_waits_for_data_on_socketpair() {
+        let (reader, writer) = UnixStream::pair().unwrap();
+        let mut executor = Executor::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = Arc::clone(&received);
+        let start = Instant::now();
+
+        executor.spawn(async move {
+            let reader = IoSource::new(reader).unwrap();
+            let mut buf = [0; 16];
+            let n = reader.read(&mut buf).await.unwrap();
+            received_clone.lock().unwrap().extend_from_slice(&buf[..n]);
+        });
+
+        // Write from another thread once the reader is parked in the reactor
+        let writer_thread = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            (&writer).write_all(b"hello").unwrap();
+            writer
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(*received.lock().unwrap(), b"hello");
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        drop(writer_thread.join().unwrap());
+    }
+
+    #[test]
+    fn test_readable_resolves_after_write() {
+        let (reader, writer) = UnixStream::pair().unwrap();
+        let mut executor = Executor::new();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let order_clone = Arc::clone(&order);
+        executor.spawn(async move {
+            let reader = IoSource::new(reader).unwrap();
+            let mut buf = [0; 16];
+            // Nothing written yet, so this parks the task in the reactor
+            let n = reader.read(&mut buf).await.unwrap();
+            order_clone.lock().unwrap().push(format!("read {}", n));
+        });
+
+        let order_clone = Arc::clone(&order);
+        executor.spawn(async move {
+            let writer = IoSource::new(writer).unwrap();
+            order_clone.lock().unwrap().push("write".to_string());
+            writer.write_all(b"abc").await.unwrap();
+        });
+
+        assert_eq!(executor.run(), 2);
+        assert_eq!(*order.lock().unwrap(), vec!["write", "read 3"]);
+    }
+
+    #[test]
+    fn test_tcp_echo() {
+        let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut executor = Executor::new();
+        let echoed = Arc::new(Mutex::new(Vec::new()));
+
+        // Echo server: accept one connection and send back what it reads
+        executor.spawn(async move {
+            let listener = IoSource::new(listener).unwrap();
+            let (stream, _) = listener.read_with(|listener| listener.accept()).await.unwrap();
+            let stream = IoSource::new(stream).unwrap();
+
+            let mut buf = [0; 64];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                stream.write_all(&buf[..n]).await.unwrap();
+            }
+        });
+
+        let echoed_clone = Arc::clone(&echoed);
+        executor.spawn(async move {
+            // A blocking connect to loopback finishes at once, so the stream
+            // is connected before it's handed to the reactor
+            let stream = std::net::TcpStream::connect(addr).unwrap();
+            stream.set_nonblocking(true).unwrap();
+            let stream = IoSource::new(TcpStream::from_std(stream)).unwrap();
+            stream.write_all(b"ping").await.unwrap();
+
+            let mut buf = [0; 64];
+            let mut reply = Vec::new();
+            while reply.len() < 4 {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "server closed the connection early");
+                reply.extend_from_slice(&buf[..n]);
+            }
+            *echoed_clone.lock().unwrap() = reply;
+            // Dropping the stream closes it, which ends the server loop
+        });
+
+        assert_eq!(executor.run(), 2);
+        assert_eq!(*echoed.lock().unwrap(), b"ping");
+    }
+}
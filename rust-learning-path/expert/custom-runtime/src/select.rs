@@ -0,0 +1,156 @@
+//! Awaiting the first of several futures.
+//!
+//! `select2` polls two futures together and resolves with whichever
+//! finishes first. Both are polled with the same waker, so whichever one
+//! makes progress wakes the task that's awaiting the selection.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// One of two values, as returned by `select2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Wait for the first of two futures to complete.
+///
+/// Resolves to `Either::Left` with `a`'s output or `Either::Right` with
+/// `b`'s, and drops the other future right away, cancelling whatever it
+/// was doing. If both are ready on the same poll, `a` wins.
+///
+/// # Examples
+///
+/// ```
+/// use custom_runtime::{select2, Either, Executor, Timer};
+///
+/// let mut executor = Executor::new();
+/// let handle = executor.spawn(async {
+///     match select2(Timer::after_millis(10), Timer::after_millis(500)).await {
+///         Either::Left(()) => "short",
+///         Either::Right(()) => "long",
+///     }
+/// });
+/// executor.run();
+/// assert_eq!(handle.try_join(), Some("short"));
+/// ```
+pub fn select2<A, B>(a: A, b: B) -> impl Future<Output = Either<A::Output, B::Output>>
+where
+    A: Future,
+    B: Future,
+{
+    Select2 {
+        a: Some(Box::pin(a)),
+        b: Some(Box::pin(b)),
+    }
+}
+
+/// Future returned by `select2`
+///
+/// The futures are boxed so they can be polled without being `Unpin`,
+/// and both are `None` once one of them has completed.
+struct Select2<A, B> {
+    a: Option<Pin<Box<A>>>,
+    b: Option<Pin<Box<B>>>,
+}
+
+impl<A: Future, B: Future> Future for Select2<A, B> {
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let (Some(a), Some(b)) = (this.a.as_mut(), this.b.as_mut()) else {
+            panic!("select2 polled after completion");
+        };
+
+        // While neither is done, both get polled with the current waker, so
+        // either one can wake the task
+        let output = if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            Either::Left(output)
+        } else if let Poll::Ready(output) = b.as_mut().poll(cx) {
+            Either::Right(output)
+        } else {
+            return Poll::Pending;
+        };
+
+        // Drop the loser now rather than whenever the selection is dropped
+        this.a = None;
+        this.b = None;
+        Poll::Ready(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::Executor;
+    use crate::timer::{Timer, YieldTimer};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_short_timer_wins() {
+        let mut executor = Executor::new();
+        let start = Instant::now();
+
+        let handle = executor.spawn(async {
+            select2(Timer::after_millis(20), Timer::after_millis(2000)).await
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(handle.try_join(), Some(Either::Left(())));
+        // The long timer was dropped rather than waited for
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_second_future_can_win() {
+        let mut executor = Executor::new();
+
+        let handle = executor.spawn(async {
+            select2(
+                async {
+                    Timer::after_millis(2000).await;
+                    "slow"
+                },
+                async {
+                    YieldTimer::new(3).await;
+                    42
+                },
+            )
+            .await
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(handle.try_join(), Some(Either::Right(42)));
+    }
+
+    #[test]
+    fn test_loser_is_dropped_on_completion() {
+        /// Sets its flag when dropped
+        struct DropFlag(Arc<AtomicBool>);
+
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(Arc::clone(&dropped));
+        let loser = async move {
+            let _flag = flag;
+            std::future::pending::<()>().await;
+        };
+
+        let mut selection = Box::pin(select2(std::future::ready(7), loser));
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        assert_eq!(selection.as_mut().poll(&mut cx), Poll::Ready(Either::Left(7)));
+        // Dropped as soon as the winner finished, while the selection is still alive
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
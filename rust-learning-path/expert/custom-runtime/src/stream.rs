@@ -0,0 +1,242 @@
+//! Streams: async iteration for our custom runtime.
+//!
+//! A future produces one value; a stream produces many, one per
+//! `poll_next`. Returning `Poll::Ready(None)` ends the stream, and
+//! `Poll::Pending` means "no value yet, I'll wake you", exactly like a future.
+//!
+//! `StreamExt` adds the methods that make streams pleasant to use from
+//! async code: `next().await` and `for_each`.
+
+use crate::timer::Timer;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// An asynchronous sequence of values.
+///
+/// This is the async counterpart of `Iterator`: instead of `next()`
+/// returning `Option<Item>`, `poll_next` returns `Poll<Option<Item>>`.
+pub trait Stream {
+    /// The type of value the stream yields
+    type Item;
+
+    /// Attempt to get the next value.
+    ///
+    /// Returns:
+    /// - `Poll::Ready(Some(item))` with the next value
+    /// - `Poll::Ready(None)` once the stream is finished
+    /// - `Poll::Pending` if no value is available yet; the waker in `cx`
+    ///   will be called when it's worth polling again
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+/// Convenience methods for every `Stream`.
+pub trait StreamExt: Stream {
+    /// Get a future that resolves to the next value, or `None` at the end.
+    ///
+    /// The stream must be `Unpin` so it can be polled through a plain
+    /// `&mut` reference.
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: self }
+    }
+
+    /// Get a future that calls `f` on every value and resolves once the
+    /// stream is finished.
+    fn for_each<F>(self, f: F) -> ForEach<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item),
+    {
+        ForEach { stream: self, f }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Future returned by `StreamExt::next`.
+pub struct Next<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<S: Stream + Unpin + ?Sized> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// Future returned by `StreamExt::for_each`.
+pub struct ForEach<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Future for ForEach<S, F>
+where
+    S: Stream + Unpin,
+    F: FnMut(S::Item) + Unpin,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Keep pulling values until the stream is finished or has to wait
+        loop {
+            match Pin::new(&mut self.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => (self.f)(item),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A stream that yields the items of an iterator, always immediately.
+pub struct Iter<I> {
+    iter: I,
+}
+
+/// Turn any iterator into a stream.
+pub fn iter<I: IntoIterator>(items: I) -> Iter<I::IntoIter> {
+    Iter {
+        iter: items.into_iter(),
+    }
+}
+
+impl<I: Iterator + Unpin> Stream for Iter<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next())
+    }
+}
+
+/// A stream that ticks every `period`, yielding the tick number starting at 0.
+///
+/// Ticks are scheduled from the start time rather than from when the
+/// previous tick was consumed, so a slow consumer doesn't make the
+/// interval drift. With a limit set, the stream ends after that many ticks.
+pub struct Interval {
+    period: Duration,
+    start: Instant,
+    ticks: u64,
+    limit: Option<u64>,
+    timer: Timer,
+}
+
+impl Interval {
+    /// Create an interval whose first tick fires one `period` from now.
+    pub fn new(period: Duration) -> Self {
+        let start = Instant::now();
+        Self {
+            period,
+            start,
+            ticks: 0,
+            limit: None,
+            timer: Timer::new(period),
+        }
+    }
+
+    /// End the stream after `count` ticks.
+    pub fn take(mut self, count: u64) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    /// Get the number of ticks yielded so far.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+}
+
+/// Create an interval ticker that fires every `period`.
+pub fn interval(period: Duration) -> Interval {
+    Interval::new(period)
+}
+
+impl Stream for Interval {
+    type Item = u64;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.limit.is_some_and(|limit| self.ticks >= limit) {
+            return Poll::Ready(None);
+        }
+
+        match Pin::new(&mut self.timer).poll(cx) {
+            Poll::Ready(()) => {
+                let tick = self.ticks;
+                self.ticks += 1;
+
+                // Schedule the next tick relative to the start time
+                let next_deadline = self.start + self.period * (self.ticks as u32 + 1);
+                self.timer = Timer::new(next_deadline.saturating_duration_since(Instant::now()));
+
+                Poll::Ready(Some(tick))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::Executor;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_next_receives_values_in_order() {
+        let mut executor = Executor::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = Arc::clone(&received);
+
+        executor.spawn(async move {
+            let mut numbers = iter(1..=5);
+            while let Some(n) = numbers.next().await {
+                received_clone.lock().unwrap().push(n);
+            }
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(*received.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_for_each_consumes_whole_stream() {
+        let mut executor = Executor::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = Arc::clone(&received);
+
+        executor.spawn(async move {
+            iter(vec!["a", "b", "c"])
+                .for_each(|item| received_clone.lock().unwrap().push(item))
+                .await;
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(*received.lock().unwrap(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_interval_ticks_in_order() {
+        let mut executor = Executor::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = Arc::clone(&received);
+        let start = Instant::now();
+
+        executor.spawn(async move {
+            interval(Duration::from_millis(5))
+                .take(4)
+                .for_each(|tick| received_clone.lock().unwrap().push(tick))
+                .await;
+        });
+
+        assert_eq!(executor.run(), 1);
+        assert_eq!(*received.lock().unwrap(), vec![0, 1, 2, 3]);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -5,9 +5,25 @@
 
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// Identifies a task, unique across all executors.
+///
+/// Wakers carry the id rather than the task itself, so waking only has
+/// to queue an id for the executor that owns the task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    /// Allocate a fresh id.
+    pub(crate) fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        TaskId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 /// A task represents a unit of async work that can be executed by our runtime.
 /// 
@@ -16,12 +32,19 @@ use std::task::{Context, Poll};
 /// - Track their completion status
 /// - Handle waking and scheduling
 pub struct Task {
+    /// This task's id, which its wakers use to re-queue it
+    id: TaskId,
+
     /// The future being executed, pinned and boxed for type erasure
     future: Pin<Box<dyn Future<Output = ()> + Send>>,
     
     /// Whether this task is ready to be polled
     /// This is set by the waker when the task should be scheduled
     ready: Arc<AtomicBool>,
+
+    /// When this task would like to be done by, if it has a deadline
+    /// The executor polls ready tasks with nearer deadlines first
+    deadline: Option<Instant>,
 }
 
 impl Task {
@@ -36,11 +59,37 @@ impl Task {
         F: Future<Output = ()> + Send + 'static,
     {
         Self {
+            id: TaskId::next(),
             future: Box::pin(future),
             ready: Arc::new(AtomicBool::new(true)), // Start ready for first poll
+            deadline: None,
+        }
+    }
+
+    /// Create a new task from a future with a deadline.
+    ///
+    /// The deadline only affects scheduling order; a task that misses
+    /// it keeps running as normal.
+    pub fn with_deadline<F>(future: F, deadline: Instant) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            deadline: Some(deadline),
+            ..Self::new(future)
         }
     }
 
+    /// Get this task's id.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Get this task's deadline, if it has one.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
     /// Check if this task is ready to be polled.
     /// 
     /// Tasks become ready when:
@@ -124,6 +173,13 @@ mod tests {
         assert!(task.is_ready());
     }
 
+    #[test]
+    fn test_task_ids_are_unique() {
+        let first = Task::new(async {});
+        let second = Task::new(async {});
+        assert_ne!(first.id(), second.id());
+    }
+
     #[test]
     fn test_task_polling() {
         let mut task = Task::new(async {
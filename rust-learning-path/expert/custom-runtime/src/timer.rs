@@ -2,17 +2,25 @@
 //! 
 //! This provides a basic async timer that completes after a specified duration.
 //! It demonstrates how to implement custom futures and integrate with our waker system.
+//!
+//! Pending timers are kept in a hierarchical timer wheel. Instead of every
+//! timer waking itself on each pass to check the clock, a timer registers
+//! its waker in the wheel once, and the executor fires only the timers that
+//! are actually due. Between passes the executor can sleep until
+//! `next_deadline()` instead of spinning.
 
 use std::future::Future;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
 use std::time::{Duration, Instant};
 
 /// A simple timer future that completes after a specified duration.
 /// 
-/// This timer uses busy-waiting (checking the current time on each poll)
-/// rather than integrating with a proper timer system. In a real runtime,
-/// you'd typically use epoll/kqueue or similar for efficient waiting.
+/// The first time the timer is polled before its deadline, it registers
+/// its waker with the timer wheel. The executor wakes it once the deadline
+/// has passed, so it's only polled again when it's ready to complete.
 pub struct Timer {
     /// When this timer should complete
     deadline: Instant,
@@ -20,6 +28,9 @@ pub struct Timer {
     /// Whether this timer has been polled before
     /// Used to print debug information on first poll
     first_poll: bool,
+
+    /// This timer's entry in the timer wheel, once it has been registered
+    registration: Option<Arc<Registration>>,
 }
 
 impl Timer {
@@ -31,6 +42,7 @@ impl Timer {
         Self {
             deadline: Instant::now() + duration,
             first_poll: true,
+            registration: None,
         }
     }
 
@@ -76,27 +88,288 @@ impl Future for Timer {
         // Check if the timer has expired
         if self.is_expired() {
             println!("Timer: Completed!");
-            Poll::Ready(())
+            if let Some(registration) = self.registration.take() {
+                cancel(&registration);
+            }
+            return Poll::Ready(());
+        }
+
+        match &self.registration {
+            // Already in the wheel: just make sure it wakes the current task
+            Some(registration) => {
+                *lock(&registration.waker) = Some(cx.waker().clone());
+            }
+            None => {
+                let registration = Arc::new(Registration {
+                    waker: Mutex::new(Some(cx.waker().clone())),
+                    done: AtomicBool::new(false),
+                });
+                let registered = lock(wheel()).insert(self.deadline, Arc::clone(&registration));
+                if !registered {
+                    // The wheel has already moved past the deadline
+                    cx.waker().wake_by_ref();
+                }
+                self.registration = Some(registration);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        // Take a timer dropped before firing out of the wheel's pending count
+        if let Some(registration) = self.registration.take() {
+            cancel(&registration);
+        }
+    }
+}
+
+/// Get the earliest time a registered timer might be due.
+///
+/// Timers far in the future sit in coarse buckets, so this can be earlier
+/// than any actual deadline; it is never later. Returns `None` when no
+/// timer is pending.
+pub fn next_deadline() -> Option<Instant> {
+    lock(wheel()).next_deadline()
+}
+
+/// Wake every timer whose deadline has passed.
+///
+/// Returns the number of timers woken.
+pub fn fire_due() -> usize {
+    let fired = lock(wheel()).advance(Instant::now());
+
+    // Wake outside the wheel lock, in deadline order
+    let mut woken = 0;
+    for entry in fired {
+        if let Some(waker) = lock(&entry.registration.waker).take() {
+            waker.wake();
+            woken += 1;
+        }
+    }
+    woken
+}
+
+/// Get the number of registered timers that haven't fired yet.
+pub fn pending_timers() -> usize {
+    lock(wheel()).pending
+}
+
+/// Number of slots per wheel level
+const SLOTS: usize = 64;
+
+/// log2(SLOTS): each level's slots are 64 times wider than the level below
+const SLOT_BITS: u32 = 6;
+
+/// Number of levels. With 1ms ticks, level 0 covers 64ms, level 1 about
+/// 4s, level 2 about 4.5 minutes, and so on; 6 levels reach about 2 years.
+const LEVELS: usize = 6;
+
+/// Number of ticks the whole wheel spans
+const MAX_TICKS: u64 = 1 << (SLOT_BITS * LEVELS as u32);
+
+/// Wheel state shared between a `Timer` and the wheel
+struct Registration {
+    /// Waker of the task waiting on the timer
+    waker: Mutex<Option<Waker>>,
+
+    /// Set once the timer fired or was cancelled.
+    /// Only changed while holding the wheel lock
+    done: AtomicBool,
+}
+
+/// A timer waiting in the wheel
+struct Entry {
+    /// Deadline in wheel ticks (milliseconds since the wheel's origin)
+    tick: u64,
+    registration: Arc<Registration>,
+}
+
+/// One level of the wheel: 64 buckets and a bitmap of the non-empty ones
+struct Level {
+    slots: [Vec<Entry>; SLOTS],
+    occupied: u64,
+}
+
+/// A hierarchical timer wheel with 1ms ticks.
+///
+/// Level 0 has one slot per tick, level 1 one slot per 64 ticks, and so
+/// on. A timer goes into the lowest level whose slot range covers the
+/// distance to its deadline. When a higher-level slot comes due, its timers
+/// cascade down into finer slots, until they reach level 0 and fire.
+/// Inserting is O(1), and finding the next due slot scans at most one
+/// bitmap per level, regardless of how many timers are pending.
+struct TimerWheel {
+    /// Instant of tick 0
+    origin: Instant,
+
+    /// Ticks processed so far; every entry is due after this
+    elapsed: u64,
+
+    levels: Vec<Level>,
+
+    /// Timers that have neither fired nor been cancelled
+    pending: usize,
+}
+
+fn wheel() -> &'static Mutex<TimerWheel> {
+    static WHEEL: OnceLock<Mutex<TimerWheel>> = OnceLock::new();
+    WHEEL.get_or_init(|| Mutex::new(TimerWheel::new(Instant::now())))
+}
+
+/// Mark a registration done, if it hasn't fired yet.
+fn cancel(registration: &Registration) {
+    let mut wheel = lock(wheel());
+    if !registration.done.swap(true, Ordering::Relaxed) {
+        wheel.pending -= 1;
+    }
+    // The entry itself is dropped lazily, when its slot comes due
+}
+
+impl TimerWheel {
+    fn new(origin: Instant) -> Self {
+        Self {
+            origin,
+            elapsed: 0,
+            levels: (0..LEVELS)
+                .map(|_| Level {
+                    slots: std::array::from_fn(|_| Vec::new()),
+                    occupied: 0,
+                })
+                .collect(),
+            pending: 0,
+        }
+    }
+
+    /// Convert a deadline to a tick, rounding up so timers never fire early.
+    fn tick_for(&self, deadline: Instant) -> u64 {
+        let since_origin = deadline.saturating_duration_since(self.origin);
+        let millis = since_origin.as_millis() as u64;
+        if since_origin > Duration::from_millis(millis) {
+            millis + 1
         } else {
-            // Timer hasn't expired yet
-            if let Some(remaining) = self.remaining() {
-                println!("Timer: Still waiting, {:?} remaining", remaining);
+            millis
+        }
+    }
+
+    /// Convert the current time to a tick, rounding down.
+    fn now_tick(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.origin).as_millis() as u64
+    }
+
+    /// Add a timer. Returns false, without adding it, if it's already due.
+    fn insert(&mut self, deadline: Instant, registration: Arc<Registration>) -> bool {
+        let tick = self.tick_for(deadline);
+        if tick <= self.elapsed {
+            return false;
+        }
+        self.pending += 1;
+        self.place(Entry { tick, registration });
+        true
+    }
+
+    /// Put an entry into the slot for its tick, relative to `elapsed`.
+    fn place(&mut self, entry: Entry) {
+        let (level, slot) = self.slot_for(entry.tick);
+        let level = &mut self.levels[level];
+        level.slots[slot].push(entry);
+        level.occupied |= 1 << slot;
+    }
+
+    /// The level is picked by the highest 6-bit group in which the tick
+    /// differs from `elapsed`, so an entry's slot is never the current one.
+    /// Timers further out than the wheel spans go in the top level, and
+    /// get placed again each time their slot comes around.
+    fn slot_for(&self, tick: u64) -> (usize, usize) {
+        let tick = tick.min(self.elapsed + MAX_TICKS - 1);
+        let masked = (self.elapsed ^ tick) | (SLOTS as u64 - 1);
+        let significant = 63 - masked.leading_zeros();
+        let level = ((significant / SLOT_BITS) as usize).min(LEVELS - 1);
+        let slot = ((tick >> (level as u32 * SLOT_BITS)) as usize) % SLOTS;
+        (level, slot)
+    }
+
+    /// Find the next slot to process: its level, index, and start tick.
+    fn next_expiration(&self) -> Option<(usize, usize, u64)> {
+        for (index, level) in self.levels.iter().enumerate() {
+            if level.occupied == 0 {
+                continue;
             }
-            
-            // In a real implementation, we would register this timer with
-            // a timer wheel or similar data structure, and the waker would
-            // be called when the timer expires.
-            // 
-            // For this simple implementation, we immediately wake ourselves
-            // so we'll be polled again soon. This creates a busy-wait loop,
-            // which is inefficient but demonstrates the polling mechanism.
-            cx.waker().wake_by_ref();
-            
-            Poll::Pending
+
+            let slot_range = 1u64 << (index as u32 * SLOT_BITS);
+            let level_range = slot_range * SLOTS as u64;
+
+            // The first occupied slot at or after the current position
+            let now_slot = ((self.elapsed / slot_range) % SLOTS as u64) as u32;
+            let distance = level.occupied.rotate_right(now_slot).trailing_zeros();
+            let slot = (now_slot + distance) as usize % SLOTS;
+
+            let level_start = self.elapsed & !(level_range - 1);
+            let mut start = level_start + slot as u64 * slot_range;
+            if start <= self.elapsed {
+                // Only possible on the top level, for a timer clamped by
+                // `slot_for`: its slot is in the level's next rotation
+                start += level_range;
+            }
+            return Some((index, slot, start));
         }
+        None
+    }
+
+    /// Earliest instant a pending timer might be due.
+    fn next_deadline(&self) -> Option<Instant> {
+        if self.pending == 0 {
+            return None;
+        }
+        self.next_expiration()
+            .map(|(_, _, tick)| self.origin + Duration::from_millis(tick))
+    }
+
+    /// Process every slot due by `now`, returning the timers that fired.
+    ///
+    /// Entries in a due slot of a higher level that aren't due yet cascade
+    /// into lower levels. Cancelled entries are dropped.
+    fn advance(&mut self, now: Instant) -> Vec<Entry> {
+        let now_tick = self.now_tick(now);
+        let mut fired = Vec::new();
+
+        while let Some((level, slot, start)) = self.next_expiration() {
+            if start > now_tick {
+                break;
+            }
+            self.elapsed = self.elapsed.max(start);
+
+            let level = &mut self.levels[level];
+            level.occupied &= !(1 << slot);
+            let entries = std::mem::take(&mut level.slots[slot]);
+
+            for entry in entries {
+                if entry.registration.done.load(Ordering::Relaxed) {
+                    continue;
+                }
+                if entry.tick <= now_tick {
+                    entry.registration.done.store(true, Ordering::Relaxed);
+                    self.pending -= 1;
+                    fired.push(entry);
+                } else {
+                    self.place(entry);
+                }
+            }
+        }
+
+        self.elapsed = self.elapsed.max(now_tick);
+        fired.sort_by_key(|entry| entry.tick);
+        fired
     }
 }
 
+/// Lock a mutex, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 /// A timer that yields control back to the executor a specified number of times.
 /// 
 /// This is useful for testing cooperative multitasking - the timer will return
@@ -225,4 +498,100 @@ mod tests {
             Poll::Pending => panic!("Zero-yield timer should complete immediately"),
         }
     }
-}
\ No newline at end of file
+
+    fn registration() -> Arc<Registration> {
+        Arc::new(Registration {
+            waker: Mutex::new(None),
+            done: AtomicBool::new(false),
+        })
+    }
+
+    #[test]
+    fn test_wheel_fires_in_deadline_order_across_levels() {
+        let origin = Instant::now();
+        let mut wheel = TimerWheel::new(origin);
+
+        // Deadlines spanning levels 0, 1 and 2, inserted out of order
+        let ticks = [5000, 3, 70, 64, 1, 300_000, 63];
+        for tick in ticks {
+            assert!(wheel.insert(origin + Duration::from_millis(tick), registration()));
+        }
+        assert_eq!(wheel.pending, ticks.len());
+
+        let mut fired = Vec::new();
+        let mut now = 0;
+        while wheel.pending > 0 {
+            // Jump straight to the next slot instead of ticking every millisecond
+            let next = wheel.next_deadline().unwrap();
+            let next_tick = next.duration_since(origin).as_millis() as u64;
+            assert!(next_tick >= now, "the wheel went backwards");
+            now = next_tick;
+
+            for entry in wheel.advance(next) {
+                assert!(entry.tick <= now, "fired {} early at {}", entry.tick, now);
+                fired.push(entry.tick);
+            }
+        }
+
+        let mut sorted = ticks.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(fired, sorted);
+        assert_eq!(wheel.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_wheel_skips_cancelled_and_rejects_past_deadlines() {
+        let origin = Instant::now();
+        let mut wheel = TimerWheel::new(origin);
+
+        let cancelled = registration();
+        wheel.insert(origin + Duration::from_millis(10), Arc::clone(&cancelled));
+        wheel.insert(origin + Duration::from_millis(20), registration());
+
+        // What `cancel` does, without going through the global wheel
+        cancelled.done.store(true, Ordering::Relaxed);
+        wheel.pending -= 1;
+
+        let fired = wheel.advance(origin + Duration::from_millis(30));
+        assert_eq!(fired.iter().map(|entry| entry.tick).collect::<Vec<_>>(), vec![20]);
+
+        // The wheel has moved on, so an earlier deadline is already due
+        assert!(!wheel.insert(origin + Duration::from_millis(25), registration()));
+        assert_eq!(wheel.pending, 0);
+    }
+
+    #[test]
+    fn test_thousand_timers_fire_in_order() {
+        use crate::executor::Executor;
+
+        let mut executor = Executor::new();
+        let fired = Arc::new(Mutex::new(Vec::with_capacity(1000)));
+        let start = Instant::now();
+
+        // Spread deadlines over 0-249ms, spawned in a scrambled order
+        for i in 0..1000u64 {
+            let delay = (i * 613) % 250;
+            let fired_clone = Arc::clone(&fired);
+            executor.spawn(async move {
+                Timer::after_millis(delay).await;
+                fired_clone.lock().unwrap().push((delay, start.elapsed()));
+            });
+        }
+
+        assert_eq!(executor.run(), 1000);
+
+        let fired = fired.lock().unwrap();
+        assert_eq!(fired.len(), 1000);
+        for (delay, elapsed) in fired.iter() {
+            assert!(*elapsed >= Duration::from_millis(*delay), "timer for {}ms fired early", delay);
+        }
+
+        // Timers due within the same pass run in queue order, so allow a
+        // little slack, but never a timer firing well before an earlier one
+        let mut latest_delay = 0;
+        for (delay, _) in fired.iter() {
+            assert!(*delay + 5 >= latest_delay, "{}ms fired after {}ms", delay, latest_delay);
+            latest_delay = latest_delay.max(*delay);
+        }
+    }
+}
@@ -2,20 +2,120 @@
 //! 
 //! Wakers are the mechanism by which async operations signal that they're
 //! ready to make progress. Our custom waker integrates with our task
-//! scheduling system.
+//! scheduling system: waking a task pushes its id onto the owning
+//! executor's `ReadyQueue`, so the executor only polls tasks that were
+//! actually woken instead of re-checking every task on every pass.
 
+use crate::reactor;
+use crate::task::TaskId;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use std::task::{RawWaker, RawWakerVTable, Waker};
+use std::time::Duration;
 
-/// A custom waker that signals task readiness through an atomic boolean.
+/// Ids of woken tasks, waiting for their executor to poll them.
+///
+/// Wakers may be called from any thread, so the queue is shared behind
+/// an `Arc` and the executor can block on it until something is woken.
+pub struct ReadyQueue {
+    /// Woken task ids, in the order they were woken
+    woken: Mutex<Vec<TaskId>>,
+
+    /// Signalled whenever an id is pushed
+    condvar: Condvar,
+
+    /// Set while the executor is blocked in the I/O reactor instead of on
+    /// `condvar`, so a wake has to interrupt the reactor
+    in_reactor: AtomicBool,
+}
+
+impl ReadyQueue {
+    /// Create an empty ready queue.
+    pub fn new() -> Self {
+        Self {
+            woken: Mutex::new(Vec::new()),
+            condvar: Condvar::new(),
+            in_reactor: AtomicBool::new(false),
+        }
+    }
+
+    /// Queue a woken task's id and wake up the executor if it's blocked.
+    pub fn push(&self, id: TaskId) {
+        lock(&self.woken).push(id);
+        self.condvar.notify_one();
+
+        if self.in_reactor.load(Ordering::SeqCst) {
+            reactor::notify();
+        }
+    }
+
+    /// Take all queued ids, oldest first.
+    pub fn drain(&self) -> Vec<TaskId> {
+        std::mem::take(&mut *lock(&self.woken))
+    }
+
+    /// Get the queued ids without taking them.
+    pub fn snapshot(&self) -> Vec<TaskId> {
+        lock(&self.woken).clone()
+    }
+
+    /// Check if no ids are queued.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.woken).is_empty()
+    }
+
+    /// Block until an id is queued or `timeout` passes (forever with `None`).
+    pub fn wait(&self, timeout: Option<Duration>) {
+        let woken = lock(&self.woken);
+        if !woken.is_empty() {
+            return;
+        }
+
+        match timeout {
+            Some(timeout) => {
+                let _ = self.condvar.wait_timeout(woken, timeout);
+            }
+            None => {
+                drop(self.condvar.wait(woken));
+            }
+        }
+    }
+
+    /// Run `block`, which blocks in the I/O reactor, unless an id is
+    /// already queued.
+    ///
+    /// While `block` runs, pushing an id interrupts the reactor.
+    pub fn block_in_reactor<F: FnOnce()>(&self, block: F) {
+        self.in_reactor.store(true, Ordering::SeqCst);
+        // Checked after setting the flag: a push either lands before this
+        // check, or sees the flag and interrupts the reactor
+        if self.is_empty() {
+            block();
+        }
+        self.in_reactor.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Default for ReadyQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A custom waker that signals task readiness.
 /// 
 /// When an async operation (like a timer or I/O) completes, it calls
-/// wake() on the waker, which sets the ready flag to true. The executor
-/// can then check this flag to know when to poll the task again.
+/// wake() on the waker, which sets the ready flag to true and, the first
+/// time, pushes the task's id onto its executor's ready queue.
+///
+/// The `Waker` built from it is backed by an `Arc<TaskWaker>`, so cloning
+/// a waker is just a reference count increment.
 pub struct TaskWaker {
     /// Shared flag indicating whether the associated task is ready to poll
     ready: Arc<AtomicBool>,
+
+    /// The task's id and the queue to push it onto, if it has an executor
+    queue: Option<(TaskId, Arc<ReadyQueue>)>,
 }
 
 impl TaskWaker {
@@ -24,34 +124,45 @@ impl TaskWaker {
     /// The ready flag is shared between the task and its waker,
     /// allowing the waker to signal when the task should be polled.
     pub fn new(ready: Arc<AtomicBool>) -> Self {
-        Self { ready }
+        Self { ready, queue: None }
+    }
+
+    /// Create a TaskWaker that also re-queues task `id` on `queue` when woken.
+    pub fn with_queue(id: TaskId, ready: Arc<AtomicBool>, queue: Arc<ReadyQueue>) -> Self {
+        Self {
+            ready,
+            queue: Some((id, queue)),
+        }
     }
 
     /// Convert this TaskWaker into a standard library Waker.
-    /// 
-    /// This creates the low-level RawWaker with the appropriate vtable
-    /// and wraps it in the safe Waker interface.
     pub fn into_waker(self) -> Waker {
-        let raw_waker = self.into_raw_waker();
-        unsafe { Waker::from_raw(raw_waker) }
+        Self::waker(&Arc::new(self))
     }
 
-    /// Convert this TaskWaker into a RawWaker.
+    /// Create a standard library Waker sharing this TaskWaker.
     /// 
-    /// RawWaker is the low-level interface that the Waker uses internally.
-    /// We need to provide a vtable with function pointers for the waker operations.
-    fn into_raw_waker(self) -> RawWaker {
-        // Box the TaskWaker and convert to a raw pointer
-        let ptr = Box::into_raw(Box::new(self)) as *const ();
-        RawWaker::new(ptr, &VTABLE)
+    /// This creates the low-level RawWaker with the appropriate vtable
+    /// and wraps it in the safe Waker interface. Holding on to the `Arc`
+    /// lets the caller see, through its reference count, whether anything
+    /// else still holds a waker for the task.
+    pub fn waker(this: &Arc<Self>) -> Waker {
+        // The new reference is owned by the Waker and released by drop_waker
+        let ptr = Arc::into_raw(Arc::clone(this)) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(ptr, &VTABLE)) }
     }
 
     /// Wake the associated task by marking it as ready.
     /// 
     /// This is called when an async operation completes and the task
-    /// should be polled again.
+    /// should be polled again. Only the wake that flips the flag queues
+    /// the id, so a task is queued at most once until it's polled.
     fn wake(&self) {
-        self.ready.store(true, Ordering::Release);
+        if !self.ready.swap(true, Ordering::AcqRel) {
+            if let Some((id, queue)) = &self.queue {
+                queue.push(*id);
+            }
+        }
     }
 }
 
@@ -71,14 +182,9 @@ static VTABLE: RawWakerVTable = RawWakerVTable::new(
 /// This creates a new RawWaker that shares the same TaskWaker data.
 /// Both wakers will signal the same task when woken.
 unsafe fn clone_waker(ptr: *const ()) -> RawWaker {
-    // Convert back to TaskWaker reference
-    let waker = &*(ptr as *const TaskWaker);
-    
-    // Create a new TaskWaker with the same ready flag
-    let cloned = TaskWaker::new(Arc::clone(&waker.ready));
-    
-    // Convert to RawWaker
-    cloned.into_raw_waker()
+    // One more owner of the same Arc<TaskWaker>
+    Arc::increment_strong_count(ptr as *const TaskWaker);
+    RawWaker::new(ptr, &VTABLE)
 }
 
 /// Wake a task by consuming the waker.
@@ -86,13 +192,13 @@ unsafe fn clone_waker(ptr: *const ()) -> RawWaker {
 /// This is called when wake() is called on the Waker.
 /// The waker is consumed in the process.
 unsafe fn wake_waker(ptr: *const ()) {
-    // Convert back to owned TaskWaker
-    let waker = Box::from_raw(ptr as *mut TaskWaker);
+    // Take back the Waker's reference
+    let waker = Arc::from_raw(ptr as *const TaskWaker);
     
     // Wake the task
     waker.wake();
     
-    // TaskWaker is dropped automatically
+    // The reference is released automatically
 }
 
 /// Wake a task by reference without consuming the waker.
@@ -111,9 +217,13 @@ unsafe fn wake_by_ref_waker(ptr: *const ()) {
 /// 
 /// This is called when the Waker is dropped.
 unsafe fn drop_waker(ptr: *const ()) {
-    // Convert back to owned TaskWaker and drop it
-    let _waker = Box::from_raw(ptr as *mut TaskWaker);
-    // Automatic drop happens here
+    // Release the Waker's reference; the TaskWaker is freed with the last one
+    drop(Arc::from_raw(ptr as *const TaskWaker));
+}
+
+/// Lock a mutex, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 #[cfg(test)]
@@ -177,4 +287,35 @@ mod tests {
         waker2.wake();
         assert!(ready.load(Ordering::Acquire));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_wake_queues_task_once() {
+        let queue = Arc::new(ReadyQueue::new());
+        let ready = Arc::new(AtomicBool::new(false));
+        let id = crate::task::Task::new(async {}).id();
+        let waker = TaskWaker::with_queue(id, Arc::clone(&ready), Arc::clone(&queue)).into_waker();
+
+        // Repeated wakes before the task is polled queue it only once
+        waker.wake_by_ref();
+        waker.clone().wake();
+        assert_eq!(queue.drain(), vec![id]);
+
+        // Once the executor clears the flag, the next wake queues it again
+        ready.store(false, Ordering::Release);
+        waker.wake();
+        assert_eq!(queue.drain(), vec![id]);
+    }
+
+    #[test]
+    fn test_waker_reference_count() {
+        let task_waker = Arc::new(TaskWaker::new(Arc::new(AtomicBool::new(false))));
+
+        let waker = TaskWaker::waker(&task_waker);
+        let clone = waker.clone();
+        assert_eq!(Arc::strong_count(&task_waker), 3);
+
+        drop(waker);
+        clone.wake();
+        assert_eq!(Arc::strong_count(&task_waker), 1);
+    }
+}
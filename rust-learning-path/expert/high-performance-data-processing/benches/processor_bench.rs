@@ -195,28 +195,32 @@ fn bench_complete_pipeline(c: &mut Criterion) {
             use_parallel: false,
             use_memory_map: false,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         }),
         ("simd_only", ProcessingConfig {
             use_simd: true,
             use_parallel: false,
             use_memory_map: false,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         }),
         ("parallel_only", ProcessingConfig {
             use_simd: false,
             use_parallel: true,
             use_memory_map: false,
             thread_count: Some(2),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         }),
         ("memory_map_only", ProcessingConfig {
             use_simd: false,
             use_parallel: false,
             use_memory_map: true,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         }),
         ("all_optimizations", ProcessingConfig::default()),
     ];
@@ -0,0 +1,370 @@
+//! Benchmark suites comparing the optimized and plain processing paths
+//!
+//! Each suite times groups of equivalent operations with `bench`, one row
+//! per variant. The first row of a group is its baseline, and the other
+//! rows report their speedup over it. `format_table` prints the rows with
+//! fixed-width columns, so the output of two runs can be diffed.
+
+use crate::{DataProcessor, MemoryMappedProcessor, ParallelProcessor, ProcessingConfig};
+use crate::{SimdBackend, SimdOperations};
+use anyhow::Result;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Timed runs per benchmark in the suites
+pub const SUITE_ITERATIONS: usize = 10;
+
+/// Timings of one benchmark
+#[derive(Debug, Clone)]
+pub struct BenchStats {
+    pub name: String,
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl BenchStats {
+    /// Items processed per second, based on the median run
+    pub fn throughput(&self, items: usize) -> f64 {
+        items as f64 / self.median.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+
+    /// How many times faster the median run is than `baseline`'s
+    pub fn speedup_over(&self, baseline: &BenchStats) -> f64 {
+        baseline.median.as_secs_f64() / self.median.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Time `f` over `iterations` runs, after one untimed warm-up run
+///
+/// At least one run is timed even if `iterations` is zero.
+pub fn bench<F: FnMut()>(name: &str, iterations: usize, mut f: F) -> BenchStats {
+    f();
+
+    let mut samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+
+    // The middle sample, or the mean of the middle two
+    let len = samples.len();
+    let median = (samples[(len - 1) / 2] + samples[len / 2]) / 2;
+
+    BenchStats {
+        name: name.to_string(),
+        iterations: samples.len(),
+        min: samples[0],
+        median,
+        mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+    }
+}
+
+/// One line of a benchmark table
+#[derive(Debug, Clone)]
+pub struct BenchRow {
+    pub stats: BenchStats,
+    /// Items each run processes, for the throughput column
+    pub items: usize,
+    /// Speedup over the first row of the row's group
+    pub speedup: f64,
+}
+
+/// Turn a baseline and its alternatives into table rows
+fn group(items: usize, baseline: BenchStats, others: Vec<BenchStats>) -> Vec<BenchRow> {
+    let mut rows = vec![BenchRow {
+        stats: baseline.clone(),
+        items,
+        speedup: 1.0,
+    }];
+    rows.extend(others.into_iter().map(|stats| BenchRow {
+        speedup: stats.speedup_over(&baseline),
+        stats,
+        items,
+    }));
+    rows
+}
+
+/// Format rows as a fixed-width table, one line per row
+pub fn format_table(rows: &[BenchRow]) -> String {
+    let mut table = format!(
+        "{:<36} {:>12} {:>12} {:>12} {:>18} {:>9}\n",
+        "Benchmark", "Min (ms)", "Median (ms)", "Mean (ms)", "Items/second", "Speedup"
+    );
+    let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    for row in rows {
+        table.push_str(&format!(
+            "{:<36} {:>12.4} {:>12.4} {:>12.4} {:>18.0} {:>8.2}x\n",
+            row.stats.name,
+            millis(row.stats.min),
+            millis(row.stats.median),
+            millis(row.stats.mean),
+            row.stats.throughput(row.items),
+            row.speedup
+        ));
+    }
+    table
+}
+
+/// Deterministic test vectors, so runs are comparable
+fn vector(size: usize, scale: f64) -> Vec<f64> {
+    (0..size).map(|i| (i as f64 * scale).sin() * 100.0).collect()
+}
+
+/// Time `operation` on `size` elements with the scalar backend and the
+/// detected one
+fn simd_group<R, F>(name: &str, size: usize, operation: F) -> Vec<BenchRow>
+where
+    F: Fn(&SimdOperations, &[f64], &[f64]) -> R,
+{
+    let a = vector(size, 0.37);
+    let b = vector(size, 0.91);
+    let scalar = SimdOperations::with_backend(SimdBackend::Scalar);
+    let simd = SimdOperations::new();
+    let backend = format!("{:?}", simd.backend()).to_lowercase();
+
+    let baseline = bench(&format!("{} (scalar)", name), SUITE_ITERATIONS, || {
+        black_box(operation(&scalar, &a, &b));
+    });
+    let optimized = bench(&format!("{} ({})", name, backend), SUITE_ITERATIONS, || {
+        black_box(operation(&simd, &a, &b));
+    });
+    group(size, baseline, vec![optimized])
+}
+
+/// Scalar and SIMD versions of each vectorized operation
+pub fn simd_benchmarks(size: usize) -> Result<Vec<BenchRow>> {
+    let mut rows = simd_group("add", size, |ops, a, b| ops.add_arrays(a, b));
+    rows.extend(simd_group("dot product", size, |ops, a, b| ops.dot_product(a, b)));
+    rows.extend(simd_group("sum", size, |ops, a, _| ops.sum(a)));
+    rows.extend(simd_group("max", size, |ops, a, _| ops.max(a)));
+    Ok(rows)
+}
+
+/// Sequential and parallel versions of chunked work and sorting, with one
+/// thread per core
+pub fn parallel_benchmarks(size: usize) -> Result<Vec<BenchRow>> {
+    let data = vector(size, 0.37);
+    let processor = ParallelProcessor::new(None);
+    let chunk_size = size.div_ceil(processor.thread_count() * 4).max(1);
+    let sum_of_squares = |chunk: &[f64]| chunk.iter().map(|x| x * x).sum::<f64>();
+
+    let mut rows = group(
+        size,
+        bench("sum of squares (sequential)", SUITE_ITERATIONS, || {
+            black_box(data.chunks(chunk_size).map(sum_of_squares).sum::<f64>());
+        }),
+        vec![bench("sum of squares (parallel)", SUITE_ITERATIONS, || {
+            black_box(processor.process_chunks(&data, chunk_size, sum_of_squares));
+        })],
+    );
+    rows.extend(group(
+        size,
+        bench("sort (sequential)", SUITE_ITERATIONS, || {
+            let mut sorted = data.clone();
+            sorted.sort_by(f64::total_cmp);
+            black_box(sorted);
+        }),
+        vec![bench("sort (parallel)", SUITE_ITERATIONS, || {
+            let mut sorted = data.clone();
+            processor.parallel_sort(&mut sorted, f64::total_cmp);
+            black_box(sorted);
+        })],
+    ));
+    Ok(rows)
+}
+
+/// A generated dataset in the temp directory, removed when dropped
+struct TempDataset {
+    csv: PathBuf,
+    binary: PathBuf,
+}
+
+impl TempDataset {
+    fn create(records: usize) -> Result<Self> {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        let stem = format!(
+            "hp-data-bench-{}-{}",
+            std::process::id(),
+            CREATED.fetch_add(1, Ordering::Relaxed)
+        );
+        let dir = std::env::temp_dir();
+        let dataset = Self {
+            csv: dir.join(format!("{}.csv", stem)),
+            binary: dir.join(format!("{}.bin", stem)),
+        };
+
+        let processor = MemoryMappedProcessor::new();
+        processor.create_sample_dataset(&dataset.csv, records)?;
+        processor.create_binary_dataset(&dataset.binary, records)?;
+        Ok(dataset)
+    }
+}
+
+impl Drop for TempDataset {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.csv);
+        let _ = std::fs::remove_file(&self.binary);
+    }
+}
+
+/// Time processing `path` with `config`
+fn bench_processing(name: &str, path: &Path, config: ProcessingConfig) -> Result<BenchStats> {
+    let processor = DataProcessor::new(config);
+
+    // Keep the first error and skip the remaining runs, so it can be
+    // returned once timing is over
+    let mut failure = None;
+    let stats = bench(name, SUITE_ITERATIONS, || {
+        if failure.is_none() {
+            match processor.process_csv_file(path) {
+                Ok(result) => {
+                    black_box(result);
+                }
+                Err(e) => failure = Some(e),
+            }
+        }
+    });
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(stats),
+    }
+}
+
+/// Configuration with every optimization off
+fn plain_config() -> ProcessingConfig {
+    ProcessingConfig {
+        use_simd: false,
+        use_parallel: false,
+        use_memory_map: false,
+        ..ProcessingConfig::default()
+    }
+}
+
+/// Buffered reads and memory-mapped reads of a CSV file
+///
+/// Uses `input` if given, otherwise a generated dataset of `size` records,
+/// which is also read back in the binary format.
+pub fn memory_map_benchmarks(input: Option<&Path>, size: usize) -> Result<Vec<BenchRow>> {
+    match input {
+        Some(input) => read_benchmarks(input, None),
+        None => {
+            let dataset = TempDataset::create(size)?;
+            read_benchmarks(&dataset.csv, Some(&dataset.binary))
+        }
+    }
+}
+
+fn read_benchmarks(csv: &Path, binary: Option<&Path>) -> Result<Vec<BenchRow>> {
+    let records = DataProcessor::new(plain_config()).process_csv_file(csv)?.records_processed;
+
+    let baseline = bench_processing("CSV (buffered reads)", csv, plain_config())?;
+    let mut others = vec![bench_processing("CSV (memory-mapped)", csv, ProcessingConfig {
+        use_memory_map: true,
+        ..plain_config()
+    })?];
+    if let Some(binary) = binary {
+        let processor = MemoryMappedProcessor::new();
+        processor.read_binary_dataset(binary)?;
+        others.push(bench("binary dataset (memory-mapped)", SUITE_ITERATIONS, || {
+            black_box(processor.read_binary_dataset(binary).unwrap());
+        }));
+    }
+    Ok(group(records, baseline, others))
+}
+
+/// CSV processing with no optimizations against all of them together
+pub fn combined_benchmarks(size: usize) -> Result<Vec<BenchRow>> {
+    let dataset = TempDataset::create(size)?;
+
+    let baseline = bench_processing("CSV (no optimizations)", &dataset.csv, plain_config())?;
+    let optimized = bench_processing("CSV (all optimizations)", &dataset.csv, ProcessingConfig::default())?;
+    Ok(group(size, baseline, vec![optimized]))
+}
+
+/// A quick selection: SIMD dot product and combined CSV processing
+pub fn basic_benchmarks(size: usize) -> Result<Vec<BenchRow>> {
+    let mut rows = simd_group("dot product", size, |ops, a, b| ops.dot_product(a, b));
+    rows.extend(combined_benchmarks(size)?);
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_statistics() {
+        let mut runs = 0;
+        let stats = bench("sleep", 4, || {
+            runs += 1;
+            std::thread::sleep(Duration::from_millis(if runs == 3 { 20 } else { 1 }));
+        });
+
+        // One warm-up run, then the timed ones
+        assert_eq!(runs, 5);
+        assert_eq!(stats.iterations, 4);
+        assert!(stats.min >= Duration::from_millis(1));
+        assert!(stats.min <= stats.median);
+        // The one slow run pulls the mean up, but not the median
+        assert!(stats.median < Duration::from_millis(20));
+        assert!(stats.mean > stats.median);
+
+        assert_eq!(bench("once", 0, || {}).iterations, 1);
+    }
+
+    #[test]
+    fn test_format_table_is_aligned() {
+        let stats = |name: &str, millis| BenchStats {
+            name: name.to_string(),
+            iterations: 1,
+            min: Duration::from_millis(millis),
+            median: Duration::from_millis(millis),
+            mean: Duration::from_millis(millis),
+        };
+        let rows = group(1000, stats("slow", 4), vec![stats("fast", 1)]);
+        assert_eq!(rows[1].speedup, 4.0);
+
+        let table = format_table(&rows);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        assert!(lines[1].ends_with(" 250000     1.00x"), "{}", lines[1]);
+        assert!(lines[2].ends_with("1000000     4.00x"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn test_processing_errors_are_returned() {
+        let missing = Path::new("does/not/exist.csv");
+        assert!(bench_processing("missing", missing, plain_config()).is_err());
+        assert!(memory_map_benchmarks(Some(missing), 16).is_err());
+    }
+
+    #[test]
+    fn test_suites_run_on_tiny_inputs() -> Result<()> {
+        let suites = [
+            ("simd", simd_benchmarks(16)?),
+            ("parallel", parallel_benchmarks(16)?),
+            ("memory map", memory_map_benchmarks(None, 16)?),
+            ("combined", combined_benchmarks(16)?),
+            ("basic", basic_benchmarks(16)?),
+        ];
+
+        for (suite, rows) in suites {
+            assert!(!rows.is_empty(), "{}", suite);
+            for row in rows {
+                assert!(row.stats.min > Duration::ZERO, "{}: {}", suite, row.stats.name);
+                assert!(row.stats.mean > Duration::ZERO, "{}: {}", suite, row.stats.name);
+                assert!(row.speedup > 0.0, "{}: {}", suite, row.stats.name);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+//! Compact binary encoding of `DataRow` datasets
+//!
+//! A binary dataset is a fixed-size header followed by the records, all
+//! little-endian:
+//!
+//! | Offset | Size | Field                                    |
+//! |--------|------|------------------------------------------|
+//! | 0      | 4    | Magic bytes `HPDB`                       |
+//! | 4      | 4    | Format version (`u32`, currently 1)      |
+//! | 8      | 8    | Record count (`u64`)                     |
+//! | 16     | 4    | CRC32 of the record bytes (`u32`)        |
+//! | 20     | 4    | Reserved, zero                           |
+//! | 24     | 40n  | Records: `id` as `u64`, then `value1` to `value4` as `f64` |
+//!
+//! The checksum lets a reader detect a dataset that was corrupted on disk
+//! or cut short, instead of silently processing wrong numbers.
+
+use crate::memory_map::DataRow;
+use std::io::{self, Seek, SeekFrom, Write};
+use thiserror::Error;
+
+/// Magic bytes at the start of every binary dataset
+pub const MAGIC: [u8; 4] = *b"HPDB";
+
+/// The format version written by `encode`
+pub const VERSION: u32 = 1;
+
+/// Size of the header in bytes
+pub const HEADER_SIZE: usize = 24;
+
+/// Size of one encoded record in bytes
+pub const RECORD_SIZE: usize = 40;
+
+/// Why a binary dataset couldn't be decoded
+#[derive(Debug, Error, PartialEq)]
+pub enum DataError {
+    #[error("Not a binary dataset (bad magic bytes)")]
+    BadMagic,
+
+    #[error("Unsupported binary dataset version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Binary dataset is truncated: expected {expected} bytes, found {actual}")]
+    Truncated { expected: usize, actual: usize },
+
+    #[error("Binary dataset checksum mismatch: header says {expected:#010x}, data is {actual:#010x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+/// Encode rows into the binary dataset format
+pub fn encode(rows: &[DataRow]) -> Vec<u8> {
+    let mut records = Vec::with_capacity(rows.len() * RECORD_SIZE);
+    for row in rows {
+        records.extend_from_slice(&encode_record(row));
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + records.len());
+    bytes.extend_from_slice(&encode_header(rows.len() as u64, crc32fast::hash(&records)));
+    bytes.extend_from_slice(&records);
+    bytes
+}
+
+fn encode_header(count: u64, checksum: u32) -> [u8; HEADER_SIZE] {
+    let mut header = [0; HEADER_SIZE];
+    header[0..4].copy_from_slice(&MAGIC);
+    header[4..8].copy_from_slice(&VERSION.to_le_bytes());
+    header[8..16].copy_from_slice(&count.to_le_bytes());
+    header[16..20].copy_from_slice(&checksum.to_le_bytes());
+    header
+}
+
+fn encode_record(row: &DataRow) -> [u8; RECORD_SIZE] {
+    let mut record = [0; RECORD_SIZE];
+    record[0..8].copy_from_slice(&row.id.to_le_bytes());
+    for (i, value) in [row.value1, row.value2, row.value3, row.value4].iter().enumerate() {
+        record[8 + i * 8..16 + i * 8].copy_from_slice(&value.to_le_bytes());
+    }
+    record
+}
+
+/// Writes a binary dataset one row at a time
+///
+/// Unlike `encode`, the dataset never has to fit in memory. The record
+/// count and checksum aren't known until the last row, so a placeholder
+/// header is written first and filled in by `finish`. A dataset that is
+/// never finished fails to decode instead of passing for a shorter one.
+pub struct DatasetWriter<W: Write + Seek> {
+    inner: W,
+    count: u64,
+    hasher: crc32fast::Hasher,
+}
+
+impl<W: Write + Seek> DatasetWriter<W> {
+    /// Start a dataset at the current position of `inner`
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(&[0; HEADER_SIZE])?;
+        Ok(Self {
+            inner,
+            count: 0,
+            hasher: crc32fast::Hasher::new(),
+        })
+    }
+
+    /// Append one row
+    pub fn write_row(&mut self, row: &DataRow) -> io::Result<()> {
+        let record = encode_record(row);
+        self.inner.write_all(&record)?;
+        self.hasher.update(&record);
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write the real header and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        let header = encode_header(self.count, self.hasher.finalize());
+        let end = self.inner.stream_position()?;
+        let start = end - HEADER_SIZE as u64 - self.count * RECORD_SIZE as u64;
+        self.inner.seek(SeekFrom::Start(start))?;
+        self.inner.write_all(&header)?;
+        self.inner.seek(SeekFrom::Start(end))?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+/// Decode a binary dataset, verifying its checksum
+pub fn decode(bytes: &[u8]) -> Result<Vec<DataRow>, DataError> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(DataError::Truncated {
+            expected: HEADER_SIZE,
+            actual: bytes.len(),
+        });
+    }
+    let (header, records) = bytes.split_at(HEADER_SIZE);
+
+    if header[0..4] != MAGIC {
+        return Err(DataError::BadMagic);
+    }
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version != VERSION {
+        return Err(DataError::UnsupportedVersion(version));
+    }
+    let count = u64::from_le_bytes(header[8..16].try_into().unwrap()) as usize;
+    let expected_checksum = u32::from_le_bytes(header[16..20].try_into().unwrap());
+
+    let expected_len = count.saturating_mul(RECORD_SIZE).saturating_add(HEADER_SIZE);
+    if bytes.len() != expected_len {
+        return Err(DataError::Truncated {
+            expected: expected_len,
+            actual: bytes.len(),
+        });
+    }
+
+    let actual_checksum = crc32fast::hash(records);
+    if actual_checksum != expected_checksum {
+        return Err(DataError::ChecksumMismatch {
+            expected: expected_checksum,
+            actual: actual_checksum,
+        });
+    }
+
+    Ok(records.chunks_exact(RECORD_SIZE).map(decode_record).collect())
+}
+
+fn decode_record(record: &[u8]) -> DataRow {
+    let field = |index: usize| -> [u8; 8] { record[index * 8..index * 8 + 8].try_into().unwrap() };
+    DataRow {
+        id: u64::from_le_bytes(field(0)),
+        value1: f64::from_le_bytes(field(1)),
+        value2: f64::from_le_bytes(field(2)),
+        value3: f64::from_le_bytes(field(3)),
+        value4: f64::from_le_bytes(field(4)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<DataRow> {
+        (0..5)
+            .map(|i| DataRow {
+                id: i,
+                value1: i as f64 * 1.5,
+                value2: -(i as f64),
+                value3: f64::MAX / (i + 1) as f64,
+                value4: 0.1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rows = rows();
+        let bytes = encode(&rows);
+        assert_eq!(bytes.len(), HEADER_SIZE + rows.len() * RECORD_SIZE);
+        assert_eq!(decode(&bytes).unwrap(), rows);
+
+        assert_eq!(decode(&encode(&[])).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_dataset_writer_matches_encode() {
+        let rows = rows();
+        let mut writer = DatasetWriter::new(io::Cursor::new(Vec::new())).unwrap();
+        for row in &rows {
+            writer.write_row(row).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap().into_inner(), encode(&rows));
+
+        // An unfinished dataset is rejected
+        let mut writer = DatasetWriter::new(io::Cursor::new(Vec::new())).unwrap();
+        writer.write_row(&rows[0]).unwrap();
+        assert_eq!(decode(writer.inner.get_ref()), Err(DataError::BadMagic));
+    }
+
+    #[test]
+    fn test_corrupted_record_fails_checksum() {
+        let mut bytes = encode(&rows());
+        bytes[HEADER_SIZE + 2 * RECORD_SIZE + 11] ^= 0x01;
+
+        assert!(matches!(decode(&bytes), Err(DataError::ChecksumMismatch { .. })));
+    }
+
+    #[test]
+    fn test_malformed_header() {
+        let bytes = encode(&rows());
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(decode(&bad_magic), Err(DataError::BadMagic));
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 9;
+        assert_eq!(decode(&bad_version), Err(DataError::UnsupportedVersion(9)));
+
+        assert!(matches!(decode(&bytes[..bytes.len() - 1]), Err(DataError::Truncated { .. })));
+        assert!(matches!(decode(&bytes[..10]), Err(DataError::Truncated { .. })));
+    }
+}
@@ -4,15 +4,20 @@
 //! performance optimization techniques including SIMD operations, memory-mapped
 //! files, and parallel processing.
 
+pub mod benchmark;
+pub mod binary_format;
 pub mod processor;
 pub mod simd_ops;
 pub mod memory_map;
 pub mod parallel;
+pub use progress;
 
-pub use processor::{DataProcessor, ProcessingConfig, ProcessingResult};
+pub use binary_format::DataError;
+pub use processor::{ColumnStats, DataProcessor, ProcessingConfig, ProcessingResult};
 pub use memory_map::MemoryMappedProcessor;
-pub use simd_ops::SimdOperations;
+pub use simd_ops::{SimdBackend, SimdOperations};
 pub use parallel::ParallelProcessor;
+pub use progress::{BarProgress, Progress, SilentProgress};
 
 /// Re-export commonly used types
 pub use anyhow::{Result, Error};
@@ -5,7 +5,7 @@
 
 use clap::{Parser, Subcommand};
 use high_performance_data_processing::{
-    DataProcessor, ProcessingConfig, MemoryMappedProcessor
+    benchmark, progress, DataProcessor, ProcessingConfig, MemoryMappedProcessor
 };
 use anyhow::Result;
 use std::path::PathBuf;
@@ -48,9 +48,13 @@ enum Commands {
         #[arg(short, long)]
         threads: Option<usize>,
         
-        /// Chunk size for processing
+        /// Rows per chunk when summarizing
         #[arg(short, long, default_value_t = 8192)]
-        chunk_size: usize,
+        chunk_rows: usize,
+        
+        /// Read buffer size in bytes when memory mapping is off
+        #[arg(long, default_value_t = 8 * 1024)]
+        read_buffer_bytes: usize,
     },
     
     /// Generate sample datasets for testing
@@ -117,9 +121,18 @@ fn main() -> Result<()> {
             parallel,
             memory_map,
             threads,
-            chunk_size,
+            chunk_rows,
+            read_buffer_bytes,
         } => {
-            process_csv_command(input, output, simd, parallel, memory_map, threads, chunk_size)?;
+            let config = ProcessingConfig {
+                use_simd: simd,
+                use_parallel: parallel,
+                use_memory_map: memory_map,
+                thread_count: threads,
+                chunk_rows,
+                read_buffer_bytes,
+            };
+            process_csv_command(input, output, config)?;
         }
         
         Commands::GenerateData { output, records, format } => {
@@ -143,47 +156,47 @@ fn main() -> Result<()> {
 }
 
 /// Process a CSV file with the specified optimizations
-fn process_csv_command(
-    input: PathBuf,
-    _output: Option<PathBuf>,
-    simd: bool,
-    parallel: bool,
-    memory_map: bool,
-    threads: Option<usize>,
-    chunk_size: usize,
-) -> Result<()> {
+fn process_csv_command(input: PathBuf, output: Option<PathBuf>, config: ProcessingConfig) -> Result<()> {
     println!("Processing CSV file: {}", input.display());
     println!("Optimizations enabled:");
-    println!("  SIMD: {}", simd);
-    println!("  Parallel: {}", parallel);
-    println!("  Memory-mapped I/O: {}", memory_map);
-    println!("  Threads: {:?}", threads);
-    println!("  Chunk size: {}", chunk_size);
+    println!("  SIMD: {}", config.use_simd);
+    println!("  Parallel: {}", config.use_parallel);
+    println!("  Memory-mapped I/O: {}", config.use_memory_map);
+    println!("  Threads: {:?}", config.thread_count);
+    println!("  Chunk rows: {}", config.chunk_rows);
+    println!("  Read buffer: {} bytes", config.read_buffer_bytes);
     println!();
     
-    let config = ProcessingConfig {
-        use_simd: simd,
-        use_parallel: parallel,
-        use_memory_map: memory_map,
-        thread_count: threads,
-        chunk_size,
-    };
-    
     let processor = DataProcessor::new(config);
     let start_time = Instant::now();
     
-    // TODO: Implement actual CSV processing
     let result = processor.process_csv_file(&input)?;
     
     let elapsed = start_time.elapsed();
     
     println!("Processing completed!");
     println!("Records processed: {}", result.records_processed);
+    println!("Malformed rows skipped: {}", result.malformed_rows);
     println!("Processing time: {:.2}ms", result.processing_time_ms);
     println!("Throughput: {:.2} records/second", result.throughput_rps);
     println!("Memory used: {} bytes", result.memory_used);
     println!("Total elapsed: {:.2}ms", elapsed.as_millis());
     
+    println!();
+    println!("{:<16} {:>16} {:>16} {:>16} {:>16}", "Column", "Sum", "Mean", "Min", "Max");
+    for column in &result.column_stats {
+        println!(
+            "{:<16} {:>16.4} {:>16.4} {:>16.4} {:>16.4}",
+            column.name, column.sum, column.mean, column.min, column.max
+        );
+    }
+    
+    if let Some(output) = output {
+        MemoryMappedProcessor::new().write_results(&output, &result)?;
+        println!();
+        println!("Results written to {}", output.display());
+    }
+    
     Ok(())
 }
 
@@ -192,7 +205,7 @@ fn generate_data_command(output: PathBuf, records: usize, format: String) -> Res
     println!("Generating {} records in {} format", records, format);
     println!("Output file: {}", output.display());
     
-    let processor = MemoryMappedProcessor::new();
+    let processor = MemoryMappedProcessor::new().with_progress(progress::for_terminal("records"));
     
     match format.as_str() {
         "csv" => {
@@ -200,8 +213,8 @@ fn generate_data_command(output: PathBuf, records: usize, format: String) -> Res
             println!("CSV dataset generated successfully!");
         }
         "binary" => {
-            // TODO: Implement binary dataset generation
-            println!("Binary format not yet implemented");
+            processor.create_binary_dataset(&output, records)?;
+            println!("Binary dataset generated successfully!");
         }
         _ => {
             return Err(anyhow::anyhow!("Unsupported format: {}", format));
@@ -279,6 +292,20 @@ fn simd_demo_command(size: usize, iterations: usize) -> Result<()> {
     println!("Throughput: {:.2} operations/second", 
              iterations as f64 / elapsed.as_secs_f64());
     
+    // Benchmark reductions
+    println!("\nBenchmarking sum/min/max...");
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let _sum = simd_ops.sum(&b);
+        let _min = simd_ops.min(&b);
+        let _max = simd_ops.max(&b);
+    }
+    let elapsed = start.elapsed();
+    println!("Time: {:.2}ms", elapsed.as_millis());
+    println!("Throughput: {:.2} operations/second", 
+             iterations as f64 / elapsed.as_secs_f64());
+    println!("Sum: {}, min: {:?}, max: {:?}", simd_ops.sum(&b), simd_ops.min(&b), simd_ops.max(&b));
+    
     Ok(())
 }
 
@@ -320,28 +347,28 @@ fn parallel_demo_command(size: usize, threads: Option<usize>) -> Result<()> {
     Ok(())
 }
 
-// TODO: Implement benchmark functions
-fn run_simd_benchmarks(_size: usize) -> Result<()> {
-    println!("SIMD benchmarks not yet implemented");
+fn run_simd_benchmarks(size: usize) -> Result<()> {
+    print!("{}", benchmark::format_table(&benchmark::simd_benchmarks(size)?));
     Ok(())
 }
 
-fn run_parallel_benchmarks(_size: usize) -> Result<()> {
-    println!("Parallel benchmarks not yet implemented");
+fn run_parallel_benchmarks(size: usize) -> Result<()> {
+    print!("{}", benchmark::format_table(&benchmark::parallel_benchmarks(size)?));
     Ok(())
 }
 
-fn run_memory_map_benchmarks(_input: Option<PathBuf>, _size: usize) -> Result<()> {
-    println!("Memory-map benchmarks not yet implemented");
+fn run_memory_map_benchmarks(input: Option<PathBuf>, size: usize) -> Result<()> {
+    let rows = benchmark::memory_map_benchmarks(input.as_deref(), size)?;
+    print!("{}", benchmark::format_table(&rows));
     Ok(())
 }
 
-fn run_combined_benchmarks(_size: usize) -> Result<()> {
-    println!("Combined benchmarks not yet implemented");
+fn run_combined_benchmarks(size: usize) -> Result<()> {
+    print!("{}", benchmark::format_table(&benchmark::combined_benchmarks(size)?));
     Ok(())
 }
 
-fn run_basic_benchmarks(_size: usize) -> Result<()> {
-    println!("Basic benchmarks not yet implemented");
+fn run_basic_benchmarks(size: usize) -> Result<()> {
+    print!("{}", benchmark::format_table(&benchmark::basic_benchmarks(size)?));
     Ok(())
 }
\ No newline at end of file
@@ -5,20 +5,27 @@
 //! performance and memory efficiency.
 
 use memmap2::{Mmap, MmapOptions};
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use std::io::BufWriter;
 use std::path::Path;
-use anyhow::Result;
-use csv::ReaderBuilder;
+use std::sync::Arc;
+use anyhow::{Context, Result};
+use csv::{ReaderBuilder, WriterBuilder};
 use serde::Deserialize;
+use crate::binary_format::{self, DatasetWriter};
+use crate::processor::ProcessingResult;
+use crate::progress::{Progress, SilentProgress};
 
 /// A processor that uses memory-mapped files for efficient data access
 pub struct MemoryMappedProcessor {
     /// Buffer size for processing chunks
     chunk_size: usize,
+    /// Where long-running operations report their progress
+    progress: Arc<dyn Progress>,
 }
 
 /// Represents a row of numerical data from a CSV file
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DataRow {
     pub id: u64,
     pub value1: f64,
@@ -30,14 +37,21 @@ pub struct DataRow {
 impl MemoryMappedProcessor {
     /// Create a new memory-mapped processor
     pub fn new() -> Self {
-        Self {
-            chunk_size: 64 * 1024, // 64KB chunks
-        }
+        Self::with_chunk_size(64 * 1024) // 64KB chunks
     }
 
     /// Create a processor with custom chunk size
     pub fn with_chunk_size(chunk_size: usize) -> Self {
-        Self { chunk_size }
+        Self {
+            chunk_size,
+            progress: Arc::new(SilentProgress::new()),
+        }
+    }
+
+    /// Report the progress of streaming and dataset generation to `progress`
+    pub fn with_progress(mut self, progress: Arc<dyn Progress>) -> Self {
+        self.progress = progress;
+        self
     }
 
     /// Process a CSV file using memory-mapped I/O
@@ -76,20 +90,62 @@ impl MemoryMappedProcessor {
 
     /// Process a binary file using memory-mapped I/O
     /// 
-    /// TODO: Implement memory-mapped binary processing that demonstrates:
-    /// 1. Direct access to binary data structures
-    /// 2. Zero-copy data access patterns
-    /// 3. Efficient processing of structured binary data
-    pub fn process_binary_file<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<f64>> {
+    /// The binary counterpart of `process_csv_file`, for datasets written by
+    /// `create_binary_dataset` or `write_binary_dataset`.
+    pub fn process_binary_file<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<DataRow>> {
+        self.read_binary_dataset(file_path)
+    }
+
+    /// Write rows to a binary dataset file
+    /// 
+    /// See `binary_format` for the layout. The header carries a checksum of
+    /// the records, which `read_binary_dataset` verifies.
+    pub fn write_binary_dataset<P: AsRef<Path>>(&self, output_path: P, rows: &[DataRow]) -> Result<()> {
+        std::fs::write(output_path, binary_format::encode(rows))?;
+        Ok(())
+    }
+
+    /// Read a binary dataset file using memory-mapped I/O
+    /// 
+    /// Fails with a `DataError` if the file isn't a valid binary dataset,
+    /// including `DataError::ChecksumMismatch` if the records don't match
+    /// the checksum in the header.
+    pub fn read_binary_dataset<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<DataRow>> {
         let file = File::open(&file_path)?;
         let mmap = unsafe { MmapOptions::new().map(&file)? };
         
-        // TODO: Implement binary data processing
-        // This should demonstrate safe transmutation of binary data
-        // Use bytemuck for safe casting of byte slices to typed data
+        Ok(binary_format::decode(&mmap)?)
+    }
+
+    /// Write a CSV summary of `result` using memory-mapped I/O
+    /// 
+    /// The summary has two tables separated by a blank line: the overall
+    /// figures as `metric,value` rows, then one `column,count,sum,mean,min,max`
+    /// row per column. The file is created at its final size and filled
+    /// through a writable memory map, replacing any existing file.
+    pub fn write_results<P: AsRef<Path>>(&self, output_path: P, result: &ProcessingResult) -> Result<()> {
+        let output_path = output_path.as_ref();
+        if let Some(dir) = output_path.parent() {
+            if !dir.as_os_str().is_empty() && !dir.is_dir() {
+                return Err(anyhow::anyhow!("Output directory {} does not exist", dir.display()));
+            }
+        }
+        
+        let summary = render_results(result)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(output_path)
+            .with_context(|| format!("Failed to create {}", output_path.display()))?;
+        file.set_len(summary.len() as u64)?;
+        
+        let mut mmap = unsafe { MmapOptions::new().map_mut(&file)? };
+        mmap.copy_from_slice(&summary);
+        mmap.flush()?;
         
-        // Placeholder implementation
-        Ok(Vec::new())
+        Ok(())
     }
 
     /// Stream process a large file in chunks
@@ -112,10 +168,13 @@ impl MemoryMappedProcessor {
         // Process the file in chunks of self.chunk_size
         // Handle partial records at chunk boundaries
         
+        self.progress.set_total(mmap.len() as u64);
         for chunk in mmap.chunks(self.chunk_size) {
             processor(chunk)?;
             processed_bytes += chunk.len();
+            self.progress.inc(chunk.len() as u64);
         }
+        self.progress.finish();
         
         Ok(processed_bytes)
     }
@@ -185,28 +244,109 @@ impl MemoryMappedProcessor {
         
         // TODO: Generate sample data with various patterns
         // Include different data distributions for realistic testing
+        self.progress.set_total(num_records as u64);
         for i in 0..num_records {
+            let row = sample_row(i);
             writeln!(
                 file,
                 "{},{},{},{},{}",
-                i,
-                (i as f64).sin(),
-                (i as f64).cos(),
-                (i as f64).sqrt(),
-                (i as f64).ln().abs()
+                row.id, row.value1, row.value2, row.value3, row.value4
             )?;
+            self.progress.inc(1);
+        }
+        self.progress.finish();
+        
+        Ok(())
+    }
+
+    /// Create a sample dataset in the binary format
+    /// 
+    /// Holds the same rows as `create_sample_dataset`. The file is laid out
+    /// as described in `binary_format`, all little-endian:
+    /// 
+    /// - a 24-byte header: magic bytes `HPDB`, format version (`u32`),
+    ///   record count (`u64`), CRC32 of the records (`u32`), 4 reserved bytes
+    /// - one 40-byte record per row: `id` as `u64`, then `value1` to
+    ///   `value4` as `f64`
+    /// 
+    /// Rows are streamed to disk, so the dataset needn't fit in memory, and
+    /// the file is synced before returning. If writing fails partway, the
+    /// incomplete file is removed.
+    pub fn create_binary_dataset<P: AsRef<Path>>(&self, output_path: P, num_records: usize) -> Result<()> {
+        let output_path = output_path.as_ref();
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create {}", output_path.display()))?;
+        
+        let written = self.write_sample_rows(file, num_records);
+        if written.is_err() {
+            let _ = std::fs::remove_file(output_path);
+        }
+        written
+    }
+
+    fn write_sample_rows(&self, file: File, num_records: usize) -> Result<()> {
+        let mut writer = DatasetWriter::new(BufWriter::new(file))?;
+        
+        self.progress.set_total(num_records as u64);
+        for i in 0..num_records {
+            writer.write_row(&sample_row(i))?;
+            self.progress.inc(1);
         }
         
+        let file = writer.finish()?.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+        self.progress.finish();
+        
         Ok(())
     }
 }
 
+/// Row `i` of the generated sample datasets
+fn sample_row(i: usize) -> DataRow {
+    let x = i as f64;
+    DataRow {
+        id: i as u64,
+        value1: x.sin(),
+        value2: x.cos(),
+        value3: x.sqrt(),
+        value4: x.ln().abs(),
+    }
+}
+
 impl Default for MemoryMappedProcessor {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Render the CSV summary written by `write_results`
+fn render_results(result: &ProcessingResult) -> Result<Vec<u8>> {
+    let mut writer = WriterBuilder::new().from_writer(Vec::new());
+    writer.write_record(["metric", "value"])?;
+    writer.write_record(["records_processed", &result.records_processed.to_string()])?;
+    writer.write_record(["malformed_rows", &result.malformed_rows.to_string()])?;
+    writer.write_record(["processing_time_ms", &result.processing_time_ms.to_string()])?;
+    writer.write_record(["throughput_rps", &result.throughput_rps.to_string()])?;
+    writer.write_record(["memory_used", &result.memory_used.to_string()])?;
+    let mut summary = writer.into_inner()?;
+    
+    summary.push(b'\n');
+    
+    let mut writer = WriterBuilder::new().from_writer(summary);
+    writer.write_record(["column", "count", "sum", "mean", "min", "max"])?;
+    for column in &result.column_stats {
+        writer.write_record([
+            column.name.clone(),
+            column.count.to_string(),
+            column.sum.to_string(),
+            column.mean.to_string(),
+            column.min.to_string(),
+            column.max.to_string(),
+        ])?;
+    }
+    Ok(writer.into_inner()?)
+}
+
 /// Statistics about a processed file
 #[derive(Debug, Default)]
 pub struct FileStats {
@@ -219,6 +359,7 @@ pub struct FileStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::binary_format::DataError;
     use tempfile::NamedTempFile;
     use std::io::Write;
 
@@ -267,6 +408,141 @@ mod tests {
         Ok(())
     }
 
+    fn sample_rows(count: u64) -> Vec<DataRow> {
+        (0..count)
+            .map(|i| DataRow {
+                id: i,
+                value1: (i as f64).sin(),
+                value2: (i as f64).cos(),
+                value3: -(i as f64),
+                value4: i as f64 * 0.25,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_binary_dataset_round_trip() -> Result<()> {
+        let processor = MemoryMappedProcessor::new();
+        let temp_file = NamedTempFile::new()?;
+        let rows = sample_rows(100);
+        
+        processor.write_binary_dataset(temp_file.path(), &rows)?;
+        assert_eq!(processor.read_binary_dataset(temp_file.path())?, rows);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_binary_dataset_round_trip() -> Result<()> {
+        let progress = Arc::new(SilentProgress::new());
+        let processor = MemoryMappedProcessor::new().with_progress(progress.clone());
+        let temp_file = NamedTempFile::new()?;
+        
+        processor.create_binary_dataset(temp_file.path(), 1000)?;
+        let file_size = std::fs::metadata(temp_file.path())?.len() as usize;
+        assert_eq!(file_size, binary_format::HEADER_SIZE + 1000 * binary_format::RECORD_SIZE);
+        assert_eq!(progress.position(), 1000);
+        assert!(progress.is_finished());
+        
+        let rows = processor.process_binary_file(temp_file.path())?;
+        assert_eq!(rows.len(), 1000);
+        assert_eq!(rows[0].value4, f64::INFINITY);
+        assert_eq!(rows[999].id, 999);
+        assert_eq!(rows[999].value3, 999f64.sqrt());
+        
+        // Same rows as the CSV dataset, which prints them with full precision
+        let csv_file = NamedTempFile::new()?;
+        processor.create_sample_dataset(csv_file.path(), 1000)?;
+        assert_eq!(processor.process_csv_file(csv_file.path())?, rows);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_binary_dataset_missing_directory() {
+        let processor = MemoryMappedProcessor::new();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("data.bin");
+        
+        assert!(processor.create_binary_dataset(&path, 10).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_corrupted_binary_dataset_fails_checksum() -> Result<()> {
+        let processor = MemoryMappedProcessor::new();
+        let temp_file = NamedTempFile::new()?;
+        processor.write_binary_dataset(temp_file.path(), &sample_rows(100))?;
+        
+        // Flip one bit in the middle of the records
+        let mut bytes = std::fs::read(temp_file.path())?;
+        let middle = bytes.len() / 2;
+        bytes[middle] ^= 0x10;
+        std::fs::write(temp_file.path(), &bytes)?;
+        
+        let error = processor.read_binary_dataset(temp_file.path()).unwrap_err();
+        assert!(
+            matches!(error.downcast_ref::<DataError>(), Some(DataError::ChecksumMismatch { .. })),
+            "{}",
+            error
+        );
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_results_round_trip() -> Result<()> {
+        let processor = MemoryMappedProcessor::new();
+        let dir = tempfile::tempdir()?;
+        let input = dir.path().join("input.csv");
+        let output = dir.path().join("summary.csv");
+        processor.create_sample_dataset(&input, 10)?;
+        
+        let result = crate::DataProcessor::new(Default::default()).process_csv_file(&input)?;
+        processor.write_results(&output, &result)?;
+        
+        let written = std::fs::read_to_string(&output)?;
+        let (totals, columns) = written.split_once("\n\n").expect("two tables");
+        let totals: Vec<&str> = totals.lines().collect();
+        assert_eq!(&totals[..3], ["metric,value", "records_processed,10", "malformed_rows,0"]);
+        
+        let columns: Vec<&str> = columns.lines().collect();
+        assert_eq!(columns.len(), 6);
+        assert_eq!(columns[0], "column,count,sum,mean,min,max");
+        assert_eq!(columns[1], "id,10,45,4.5,0,9");
+        assert!(columns[2].starts_with("value1,10,"));
+        
+        // Writing again replaces the file instead of leaving stale bytes
+        let empty = ProcessingResult {
+            column_stats: Vec::new(),
+            ..result
+        };
+        processor.write_results(&output, &empty)?;
+        assert!(std::fs::read_to_string(&output)?.ends_with("\n\ncolumn,count,sum,mean,min,max\n"));
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_results_missing_directory() -> Result<()> {
+        let processor = MemoryMappedProcessor::new();
+        let dir = tempfile::tempdir()?;
+        let result = ProcessingResult {
+            records_processed: 0,
+            processing_time_ms: 0,
+            throughput_rps: 0.0,
+            memory_used: 0,
+            malformed_rows: 0,
+            column_stats: Vec::new(),
+        };
+        
+        let missing = dir.path().join("missing");
+        let error = processor.write_results(missing.join("summary.csv"), &result).unwrap_err();
+        assert_eq!(error.to_string(), format!("Output directory {} does not exist", missing.display()));
+        
+        Ok(())
+    }
+
     #[test]
     fn test_search_pattern() -> Result<()> {
         let processor = MemoryMappedProcessor::new();
@@ -305,4 +581,26 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_progress_is_reported() -> Result<()> {
+        let progress = Arc::new(SilentProgress::new());
+        let processor = MemoryMappedProcessor::with_chunk_size(100).with_progress(progress.clone());
+        let temp_file = NamedTempFile::new()?;
+        
+        processor.create_sample_dataset(temp_file.path(), 250)?;
+        assert_eq!(progress.total(), 250);
+        assert_eq!(progress.position(), 250);
+        assert!(progress.is_finished());
+        
+        // Streaming counts bytes, and the chunks add up to the whole file
+        let file_size = std::fs::metadata(temp_file.path())?.len();
+        let bytes_processed = processor.stream_process_file(temp_file.path(), |_| Ok(()))?;
+        assert_eq!(bytes_processed as u64, file_size);
+        assert_eq!(progress.total(), file_size);
+        assert_eq!(progress.position(), file_size);
+        assert!(progress.is_finished());
+        
+        Ok(())
+    }
+}
@@ -7,15 +7,25 @@ use rayon::prelude::*;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use anyhow::Result;
+use crate::progress::{Progress, SilentProgress};
 
 /// Parallel processor that coordinates multi-threaded operations
 pub struct ParallelProcessor {
     /// Number of threads in the thread pool
     thread_count: usize,
+    /// Pool of `thread_count` threads that chunked processing runs on, or
+    /// `None` to use rayon's global pool, which already has that many
+    pool: Option<rayon::ThreadPool>,
+    /// Where chunked processing reports its progress
+    progress: Arc<dyn Progress>,
 }
 
 impl ParallelProcessor {
     /// Create a new parallel processor with default thread count
+    /// 
+    /// A pool of its own is only started when `thread_count` differs from
+    /// the size of rayon's global pool; the other operations always run on
+    /// the global pool.
     pub fn new(thread_count: Option<usize>) -> Self {
         let thread_count = thread_count.unwrap_or_else(|| {
             std::thread::available_parallelism()
@@ -23,19 +33,32 @@ impl ParallelProcessor {
                 .unwrap_or(4)
         });
         
-        // Configure Rayon thread pool if needed
-        if let Some(count) = thread_count.checked_sub(1) {
+        let pool = (thread_count != rayon::current_num_threads()).then(|| {
             rayon::ThreadPoolBuilder::new()
-                .num_threads(count.max(1))
-                .build_global()
-                .ok(); // Ignore errors if already initialized
-        }
+                .num_threads(thread_count)
+                .build()
+                .expect("failed to start thread pool")
+        });
         
-        Self { thread_count }
+        Self {
+            thread_count,
+            pool,
+            progress: Arc::new(SilentProgress::new()),
+        }
+    }
+
+    /// Report the progress of chunked processing to `progress`, one unit per chunk
+    pub fn with_progress(mut self, progress: Arc<dyn Progress>) -> Self {
+        self.progress = progress;
+        self
     }
 
     /// Process data in parallel chunks
     /// 
+    /// Chunks run on a pool of `thread_count` threads, and the results come
+    /// back in chunk order. The call is one progress run: it sets the total
+    /// to the number of chunks and finishes the run at the end.
+    /// 
     /// TODO: Implement parallel chunk processing that:
     /// 1. Divides data into optimal chunk sizes for each thread
     /// 2. Balances load across available CPU cores
@@ -51,9 +74,38 @@ impl ParallelProcessor {
         // Consider work-stealing vs fixed partitioning
         // Handle remainder chunks appropriately
         
-        data.par_chunks(chunk_size)
-            .map(|chunk| processor(chunk))
-            .collect()
+        self.progress.set_total(data.len().div_ceil(chunk_size) as u64);
+        let results = self.process_batch(data, chunk_size, processor);
+        self.progress.finish();
+        
+        results
+    }
+
+    /// Process one batch of a longer run in parallel chunks
+    /// 
+    /// Like `process_chunks`, but it only counts the batch's chunks as done,
+    /// so a caller that streams its data in batches gets one progress run
+    /// for all of them instead of a new run per batch.
+    pub fn process_batch<T, R, F>(&self, data: &[T], chunk_size: usize, processor: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&[T]) -> R + Sync,
+    {
+        let run = || {
+            data.par_chunks(chunk_size)
+                .map(|chunk| {
+                    let result = processor(chunk);
+                    self.progress.inc(1);
+                    result
+                })
+                .collect()
+        };
+        
+        match &self.pool {
+            Some(pool) => pool.install(run),
+            None => run(),
+        }
     }
 
     /// Parallel map operation with load balancing
@@ -311,6 +363,43 @@ mod tests {
         assert_eq!(result[2], 15); // 7+8
     }
 
+    #[test]
+    fn test_process_chunks_reports_progress() {
+        let progress = Arc::new(SilentProgress::new());
+        let processor = ParallelProcessor::new(Some(2)).with_progress(progress.clone());
+        let data: Vec<u64> = (1..=1000).collect();
+        
+        let sums = processor.process_chunks(&data, 64, |chunk| chunk.iter().sum::<u64>());
+        
+        assert_eq!(sums.iter().sum::<u64>(), 500500);
+        // One unit per chunk, the last one partial
+        assert_eq!(progress.total(), 16);
+        assert_eq!(progress.position(), progress.total());
+        assert!(progress.is_finished());
+    }
+
+    #[test]
+    fn test_batches_share_one_progress_run() {
+        let progress = Arc::new(SilentProgress::new());
+        let processor = ParallelProcessor::new(Some(2)).with_progress(progress.clone());
+        progress.set_total(5);
+        
+        let data: Vec<u64> = (1..=10).collect();
+        for batch in data.chunks(6) {
+            processor.process_batch(batch, 2, |chunk| chunk.iter().sum::<u64>());
+        }
+        
+        assert_eq!(progress.total(), 5);
+        assert_eq!(progress.position(), 5);
+        assert!(!progress.is_finished());
+    }
+
+    #[test]
+    fn test_pool_matching_the_global_one_is_not_started() {
+        assert!(ParallelProcessor::new(Some(rayon::current_num_threads())).pool.is_none());
+        assert!(ParallelProcessor::new(Some(rayon::current_num_threads() + 1)).pool.is_some());
+    }
+
     #[test]
     fn test_parallel_search() {
         let processor = ParallelProcessor::new(Some(2));
@@ -3,8 +3,12 @@
 //! This module contains the core data processing logic that coordinates
 //! various optimization techniques for maximum performance.
 
-use crate::{MemoryMappedProcessor, SimdOperations, ParallelProcessor};
-use anyhow::Result;
+use crate::ParallelProcessor;
+use anyhow::{Context, Result};
+use csv::{ByteRecord, Reader, ReaderBuilder};
+use memmap2::MmapOptions;
+use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 use std::time::Instant;
 
@@ -19,8 +23,10 @@ pub struct ProcessingConfig {
     pub use_memory_map: bool,
     /// Number of threads for parallel processing
     pub thread_count: Option<usize>,
-    /// Chunk size for processing
-    pub chunk_size: usize,
+    /// Rows per chunk when summarizing CSV files
+    pub chunk_rows: usize,
+    /// Size in bytes of the read buffer when memory mapping is off
+    pub read_buffer_bytes: usize,
 }
 
 impl Default for ProcessingConfig {
@@ -30,7 +36,8 @@ impl Default for ProcessingConfig {
             use_parallel: true,
             use_memory_map: true,
             thread_count: None, // Use default thread pool size
-            chunk_size: 8192,   // 8KB chunks
+            chunk_rows: 8192,
+            read_buffer_bytes: 8 * 1024, // 8KB read buffer
         }
     }
 }
@@ -46,13 +53,68 @@ pub struct ProcessingResult {
     pub throughput_rps: f64,
     /// Memory usage in bytes
     pub memory_used: usize,
+    /// Rows that were skipped because they couldn't be parsed
+    pub malformed_rows: usize,
+    /// Statistics for each column, in file order
+    pub column_stats: Vec<ColumnStats>,
+}
+
+/// Summary statistics of one numeric column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// Column name from the header row
+    pub name: String,
+    /// Number of values in the column
+    pub count: usize,
+    pub sum: f64,
+    /// Mean of the values, or 0.0 for an empty column
+    pub mean: f64,
+    /// Smallest value, or 0.0 for an empty column
+    pub min: f64,
+    /// Largest value, or 0.0 for an empty column
+    pub max: f64,
+}
+
+impl ColumnStats {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            count: 0,
+            sum: 0.0,
+            mean: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Fold in the statistics of a later run of rows
+    fn merge(&mut self, other: &ColumnStats) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    fn finish(&mut self) {
+        if self.count == 0 {
+            self.min = 0.0;
+            self.max = 0.0;
+        } else {
+            self.mean = self.sum / self.count as f64;
+        }
+    }
 }
 
 /// Main data processor that coordinates all optimization techniques
 pub struct DataProcessor {
     config: ProcessingConfig,
-    memory_processor: MemoryMappedProcessor,
-    simd_ops: SimdOperations,
     parallel_processor: ParallelProcessor,
 }
 
@@ -60,8 +122,6 @@ impl DataProcessor {
     /// Create a new data processor with the given configuration
     pub fn new(config: ProcessingConfig) -> Self {
         Self {
-            memory_processor: MemoryMappedProcessor::new(),
-            simd_ops: SimdOperations::new(),
             parallel_processor: ParallelProcessor::new(config.thread_count),
             config,
         }
@@ -69,19 +129,44 @@ impl DataProcessor {
 
     /// Process a CSV file with numerical data
     /// 
-    /// TODO: Implement the main processing logic that:
-    /// 1. Uses memory-mapped files for efficient I/O
-    /// 2. Applies SIMD operations for mathematical computations
-    /// 3. Utilizes parallel processing for multi-core performance
-    /// 4. Measures and reports performance metrics
+    /// The first row names the columns and every column must be numeric.
+    /// Rows with a value that isn't a number, or with the wrong number of
+    /// fields, are counted in `malformed_rows` and skipped.
+    /// 
+    /// With `use_memory_map` the file is mapped into memory and parsed in
+    /// place; otherwise it's read through a buffer of `read_buffer_bytes`.
+    /// `memory_used` is the size of the mapping or of that buffer.
+    /// 
+    /// Rows are summarized in chunks of `chunk_rows` rows, which run on
+    /// `thread_count` threads with `use_parallel` and one after another
+    /// without it. The chunks' statistics are merged in file order, so the
+    /// result is exactly the same either way.
     pub fn process_csv_file<P: AsRef<Path>>(&self, file_path: P) -> Result<ProcessingResult> {
         let start_time = Instant::now();
+        let file_path = file_path.as_ref();
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open {}", file_path.display()))?;
         
-        // TODO: Implement file processing logic
-        // This should demonstrate the integration of all optimization techniques
+        let (stats, malformed_rows, memory_used) = if self.config.use_memory_map {
+            // SAFETY: the mapping is only read, and only while `file` is
+            // open. If another process truncates or rewrites the file
+            // meanwhile, the data read is undefined; like any memory-mapped
+            // reader, this assumes the input isn't modified while it runs.
+            let mmap = unsafe { MmapOptions::new().map(&file)? };
+            let reader = ReaderBuilder::new().flexible(true).from_reader(&mmap[..]);
+            let (stats, malformed_rows) = self.compute_column_stats(reader)?;
+            (stats, malformed_rows, mmap.len())
+        } else {
+            let buffer_bytes = self.config.read_buffer_bytes.max(1);
+            let reader = ReaderBuilder::new()
+                .flexible(true)
+                .buffer_capacity(buffer_bytes)
+                .from_reader(file);
+            let (stats, malformed_rows) = self.compute_column_stats(reader)?;
+            (stats, malformed_rows, buffer_bytes)
+        };
         
-        // Placeholder implementation
-        let records_processed = 0;
+        let records_processed = stats.first().map_or(0, |column| column.count);
         let processing_time = start_time.elapsed();
         
         Ok(ProcessingResult {
@@ -92,7 +177,9 @@ impl DataProcessor {
             } else {
                 0.0
             },
-            memory_used: 0, // TODO: Implement memory usage tracking
+            memory_used: memory_used + stats.len() * std::mem::size_of::<ColumnStats>(),
+            malformed_rows,
+            column_stats: stats,
         })
     }
 
@@ -107,27 +194,109 @@ impl DataProcessor {
         Ok(data.to_vec())
     }
 
-    /// Benchmark different optimization strategies
+    /// Read every row of a CSV file, returning per-column statistics and
+    /// the number of malformed rows
     /// 
-    /// TODO: Implement benchmarking that compares:
-    /// - Scalar vs SIMD operations
-    /// - Sequential vs parallel processing
-    /// - Regular I/O vs memory-mapped files
-    pub fn benchmark_optimizations(&self, data_size: usize) -> Result<BenchmarkResults> {
-        // TODO: Implement comprehensive benchmarking
-        Ok(BenchmarkResults::default())
+    /// Rows are read in batches of one chunk per thread, so only a batch
+    /// is held in memory at a time.
+    fn compute_column_stats<R: Read>(&self, mut reader: Reader<R>) -> Result<(Vec<ColumnStats>, usize)> {
+        let headers = reader.headers().context("Failed to read CSV header")?;
+        let mut stats: Vec<ColumnStats> = headers
+            .iter()
+            .map(|name| ColumnStats::new(name.to_string()))
+            .collect();
+        
+        let chunk_size = self.config.chunk_rows.max(1);
+        let batch_size = if self.config.use_parallel {
+            chunk_size * self.parallel_processor.thread_count()
+        } else {
+            chunk_size
+        };
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut record = ByteRecord::new();
+        let mut malformed_rows = 0;
+        
+        loop {
+            let more = match reader.read_byte_record(&mut record) {
+                Ok(more) => more,
+                Err(e) if e.is_io_error() => return Err(e.into()),
+                Err(_) => {
+                    malformed_rows += 1;
+                    continue;
+                }
+            };
+            if more {
+                batch.push(record.clone());
+            }
+            
+            if batch.len() == batch_size || (!more && !batch.is_empty()) {
+                malformed_rows += self.add_batch(&mut stats, &batch, chunk_size);
+                batch.clear();
+            }
+            if !more {
+                break;
+            }
+        }
+        
+        for column in &mut stats {
+            column.finish();
+        }
+        Ok((stats, malformed_rows))
+    }
+
+    /// Add a batch of rows to `stats` chunk by chunk, returning the number
+    /// of malformed rows in it
+    fn add_batch(&self, stats: &mut [ColumnStats], batch: &[ByteRecord], chunk_size: usize) -> usize {
+        let summarize = |chunk: &[ByteRecord]| chunk_stats(stats, chunk);
+        let partials: Vec<_> = if self.config.use_parallel {
+            self.parallel_processor.process_batch(batch, chunk_size, summarize)
+        } else {
+            batch.chunks(chunk_size).map(summarize).collect()
+        };
+        
+        let mut malformed_rows = 0;
+        for (partial, malformed) in partials {
+            for (column, chunk_column) in stats.iter_mut().zip(&partial) {
+                column.merge(chunk_column);
+            }
+            malformed_rows += malformed;
+        }
+        malformed_rows
     }
 }
 
-/// Results from benchmarking different optimization strategies
-#[derive(Debug, Default)]
-pub struct BenchmarkResults {
-    pub scalar_time_ms: u128,
-    pub simd_time_ms: u128,
-    pub sequential_time_ms: u128,
-    pub parallel_time_ms: u128,
-    pub regular_io_time_ms: u128,
-    pub memory_mapped_time_ms: u128,
+/// Statistics of one chunk of rows for the given columns, and the number
+/// of malformed rows in it
+fn chunk_stats(columns: &[ColumnStats], chunk: &[ByteRecord]) -> (Vec<ColumnStats>, usize) {
+    let mut stats: Vec<ColumnStats> = columns
+        .iter()
+        .map(|column| ColumnStats::new(column.name.clone()))
+        .collect();
+    let mut values = Vec::with_capacity(stats.len());
+    let mut malformed_rows = 0;
+    
+    for record in chunk {
+        // Parse the whole row before adding any of it, so a bad value
+        // late in a row doesn't leave the columns with different counts
+        values.clear();
+        let parsed = record.iter().try_for_each(|field| {
+            values.push(parse_number(field)?);
+            Some(())
+        });
+        if parsed.is_none() || values.len() != stats.len() {
+            malformed_rows += 1;
+            continue;
+        }
+        
+        for (column, &value) in stats.iter_mut().zip(&values) {
+            column.add(value);
+        }
+    }
+    (stats, malformed_rows)
+}
+
+fn parse_number(field: &[u8]) -> Option<f64> {
+    std::str::from_utf8(field).ok()?.trim().parse().ok()
 }
 
 #[cfg(test)]
@@ -142,12 +311,130 @@ mod tests {
         assert!(true);
     }
 
+    fn write_csv(contents: &str) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+    
+    const SAMPLE_CSV: &str = "\
+id,price,quantity
+1,2.5,10
+2,-1.0,4
+3,4.5,7
+4,oops,1
+5,1.0
+6,3.0,9,extra
+7,6.0,0
+";
+    
+    #[test]
+    fn test_process_csv_computes_column_stats() {
+        let file = write_csv(SAMPLE_CSV);
+        
+        for use_memory_map in [true, false] {
+            let config = ProcessingConfig {
+                use_memory_map,
+                chunk_rows: 16,
+                read_buffer_bytes: 16,
+                ..ProcessingConfig::default()
+            };
+            let result = DataProcessor::new(config).process_csv_file(file.path()).unwrap();
+            
+            assert_eq!(result.records_processed, 4);
+            assert_eq!(result.malformed_rows, 3);
+            assert!(result.memory_used > 0);
+            
+            let names: Vec<&str> = result.column_stats.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, ["id", "price", "quantity"]);
+            
+            let price = &result.column_stats[1];
+            assert_eq!(price.count, 4);
+            assert_eq!(price.sum, 12.0);
+            assert_eq!(price.mean, 3.0);
+            assert_eq!(price.min, -1.0);
+            assert_eq!(price.max, 6.0);
+            
+            let quantity = &result.column_stats[2];
+            assert_eq!(quantity.sum, 21.0);
+            assert_eq!(quantity.mean, 5.25);
+            assert_eq!(quantity.min, 0.0);
+            assert_eq!(quantity.max, 10.0);
+        }
+    }
+    
+    #[test]
+    fn test_parallel_and_sequential_stats_match() {
+        let mut csv = String::from("id,wave,ramp\n");
+        for i in 0..5000 {
+            let x = i as f64;
+            if i % 97 == 0 {
+                csv.push_str(&format!("{},bad,{}\n", i, x));
+            } else {
+                csv.push_str(&format!("{},{},{}\n", i, (x * 0.37).sin() * 1e3, x * 0.1 - 250.0));
+            }
+        }
+        let file = write_csv(&csv);
+        
+        let process = |use_parallel, use_memory_map, thread_count| {
+            let config = ProcessingConfig {
+                use_parallel,
+                use_memory_map,
+                thread_count,
+                chunk_rows: 64,
+                ..ProcessingConfig::default()
+            };
+            DataProcessor::new(config).process_csv_file(file.path()).unwrap()
+        };
+        
+        let sequential = process(false, true, None);
+        assert_eq!(sequential.malformed_rows, 52);
+        assert_eq!(sequential.records_processed, 5000 - 52);
+        assert_eq!(sequential.column_stats[2].min, -250.0 + 0.1);
+        
+        for (use_memory_map, threads) in [(true, 4), (false, 4), (true, 1), (true, 3)] {
+            let parallel = process(true, use_memory_map, Some(threads));
+            assert_eq!(parallel.records_processed, sequential.records_processed);
+            assert_eq!(parallel.malformed_rows, sequential.malformed_rows);
+            assert_eq!(parallel.column_stats, sequential.column_stats, "{} threads", threads);
+        }
+    }
+    
+    #[test]
+    fn test_process_csv_with_only_a_header() {
+        let file = write_csv("a,b\n");
+        let result = DataProcessor::new(ProcessingConfig::default())
+            .process_csv_file(file.path())
+            .unwrap();
+        
+        assert_eq!(result.records_processed, 0);
+        assert_eq!(result.malformed_rows, 0);
+        assert_eq!(result.column_stats[0], ColumnStats {
+            name: "a".to_string(),
+            count: 0,
+            sum: 0.0,
+            mean: 0.0,
+            min: 0.0,
+            max: 0.0,
+        });
+    }
+    
+    #[test]
+    fn test_process_csv_missing_file() {
+        let processor = DataProcessor::new(ProcessingConfig::default());
+        assert!(processor.process_csv_file("/nonexistent/data.csv").is_err());
+    }
+    
     #[test]
     fn test_default_config() {
         let config = ProcessingConfig::default();
         assert!(config.use_simd);
         assert!(config.use_parallel);
         assert!(config.use_memory_map);
-        assert_eq!(config.chunk_size, 8192);
+        assert_eq!(config.chunk_rows, 8192);
+        assert_eq!(config.read_buffer_bytes, 8 * 1024);
     }
 }
\ No newline at end of file
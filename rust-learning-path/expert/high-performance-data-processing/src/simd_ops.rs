@@ -3,53 +3,77 @@
 //! This module provides vectorized operations that can process multiple data elements
 //! simultaneously, significantly improving performance for mathematical computations.
 
-use wide::f64x4;
 use anyhow::Result;
 
+/// Number of f64 values processed per SIMD instruction
+pub const LANES: usize = 4;
+
+/// Implementation used for the vectorized operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdBackend {
+    /// 256-bit AVX2 instructions, processing `LANES` values at a time
+    Avx2,
+    /// Plain scalar code, available everywhere
+    Scalar,
+}
+
 /// SIMD operations handler
 pub struct SimdOperations {
-    /// Whether SIMD is available on the current platform
-    simd_available: bool,
+    /// Backend chosen for this CPU
+    backend: SimdBackend,
 }
 
 impl SimdOperations {
-    /// Create a new SIMD operations handler
+    /// Create a SIMD operations handler using the best backend this CPU supports
     pub fn new() -> Self {
         Self {
-            // TODO: Implement runtime SIMD capability detection
-            simd_available: Self::detect_simd_support(),
+            backend: Self::detect_backend(),
+        }
+    }
+
+    /// Create a handler that uses `backend`, for comparing backends
+    /// 
+    /// Asking for AVX2 on a CPU without it gives the scalar backend, since
+    /// running AVX2 instructions there would crash.
+    pub fn with_backend(backend: SimdBackend) -> Self {
+        let backend = match backend {
+            SimdBackend::Avx2 => Self::detect_backend(),
+            SimdBackend::Scalar => SimdBackend::Scalar,
+        };
+        Self { backend }
+    }
+
+    /// Check at runtime which instruction sets the CPU supports
+    fn detect_backend() -> SimdBackend {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return SimdBackend::Avx2;
+            }
         }
+        SimdBackend::Scalar
     }
 
-    /// Detect if SIMD operations are supported on the current platform
-    fn detect_simd_support() -> bool {
-        // TODO: Implement proper SIMD capability detection
-        // This should check for specific instruction sets (SSE, AVX, etc.)
-        cfg!(target_feature = "sse2") || cfg!(target_arch = "x86_64")
+    /// Get the backend the operations run on
+    pub fn backend(&self) -> SimdBackend {
+        self.backend
     }
 
     /// Add two arrays of f64 values using SIMD operations
     /// 
-    /// TODO: Implement SIMD-optimized addition that:
-    /// 1. Processes 4 f64 values simultaneously using f64x4
-    /// 2. Handles arrays that aren't multiples of 4
-    /// 3. Falls back to scalar operations when SIMD isn't available
-    /// 4. Demonstrates proper alignment and memory access patterns
+    /// With AVX2, `LANES` pairs are added per instruction and the elements
+    /// left over at the end are added one at a time.
     pub fn add_arrays(&self, a: &[f64], b: &[f64]) -> Result<Vec<f64>> {
         if a.len() != b.len() {
             return Err(anyhow::anyhow!("Arrays must have the same length"));
         }
 
-        if !self.simd_available {
-            return Ok(self.add_arrays_scalar(a, b));
+        match self.backend {
+            // SAFETY: the AVX2 backend is only chosen when the CPU supports it
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => Ok(unsafe { avx2::add_arrays(a, b) }),
+            _ => Ok(self.add_arrays_scalar(a, b)),
         }
-
-        // TODO: Implement SIMD addition
-        // Hint: Use f64x4::new() to create SIMD vectors
-        // Process 4 elements at a time, handle remainder with scalar operations
-        
-        // Placeholder: fallback to scalar for now
-        Ok(self.add_arrays_scalar(a, b))
     }
 
     /// Scalar fallback for array addition
@@ -65,7 +89,7 @@ impl SimdOperations {
             return Err(anyhow::anyhow!("Arrays must have the same length"));
         }
 
-        if !self.simd_available {
+        if !self.is_simd_available() {
             return Ok(self.multiply_arrays_scalar(a, b));
         }
 
@@ -80,17 +104,48 @@ impl SimdOperations {
 
     /// Compute the sum of an array using SIMD operations
     /// 
-    /// TODO: Implement SIMD-optimized sum that:
-    /// 1. Uses horizontal addition within SIMD vectors
-    /// 2. Accumulates partial sums efficiently
-    /// 3. Demonstrates reduction operations with SIMD
-    pub fn sum_array(&self, data: &[f64]) -> f64 {
-        if !self.simd_available {
-            return data.iter().sum();
+    /// Like `dot_product`, values are summed in `LANES` running totals, so
+    /// both backends return exactly the same result.
+    pub fn sum(&self, data: &[f64]) -> f64 {
+        match self.backend {
+            // SAFETY: the AVX2 backend is only chosen when the CPU supports it
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => unsafe { avx2::sum(data) },
+            _ => sum_scalar(data),
         }
+    }
+
+    /// Compute the sum of an array; same as `sum`
+    pub fn sum_array(&self, data: &[f64]) -> f64 {
+        self.sum(data)
+    }
+
+    /// Find the smallest value in an array using SIMD operations
+    /// 
+    /// NaN values are ignored. Returns `None` if the array is empty or
+    /// holds nothing but NaN.
+    pub fn min(&self, data: &[f64]) -> Option<f64> {
+        let min = match self.backend {
+            // SAFETY: the AVX2 backend is only chosen when the CPU supports it
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => unsafe { avx2::min(data) },
+            _ => data.iter().fold(f64::INFINITY, |acc, &x| acc.min(x)),
+        };
+        found_extreme(min, data)
+    }
 
-        // TODO: Implement SIMD sum with horizontal addition
-        data.iter().sum()
+    /// Find the largest value in an array using SIMD operations
+    /// 
+    /// NaN values are ignored. Returns `None` if the array is empty or
+    /// holds nothing but NaN.
+    pub fn max(&self, data: &[f64]) -> Option<f64> {
+        let max = match self.backend {
+            // SAFETY: the AVX2 backend is only chosen when the CPU supports it
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => unsafe { avx2::max(data) },
+            _ => data.iter().fold(f64::NEG_INFINITY, |acc, &x| acc.max(x)),
+        };
+        found_extreme(max, data)
     }
 
     /// Apply a mathematical function to an array using SIMD operations
@@ -101,7 +156,7 @@ impl SimdOperations {
     where
         F: Fn(f64) -> f64 + Sync,
     {
-        if !self.simd_available {
+        if !self.is_simd_available() {
             return data.iter().map(|&x| func(x)).collect();
         }
 
@@ -113,21 +168,20 @@ impl SimdOperations {
 
     /// Compute dot product of two vectors using SIMD operations
     /// 
-    /// TODO: Implement SIMD-optimized dot product that demonstrates:
-    /// 1. Element-wise multiplication using SIMD
-    /// 2. Horizontal addition for final sum
-    /// 3. Proper handling of vector lengths
+    /// Products are summed in `LANES` running totals that are combined at
+    /// the end. Both backends add in the same order, so they return exactly
+    /// the same result.
     pub fn dot_product(&self, a: &[f64], b: &[f64]) -> Result<f64> {
         if a.len() != b.len() {
             return Err(anyhow::anyhow!("Vectors must have the same length"));
         }
 
-        if !self.simd_available {
-            return Ok(a.iter().zip(b.iter()).map(|(x, y)| x * y).sum());
+        match self.backend {
+            // SAFETY: the AVX2 backend is only chosen when the CPU supports it
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => Ok(unsafe { avx2::dot_product(a, b) }),
+            _ => Ok(dot_product_scalar(a, b)),
         }
-
-        // TODO: Implement SIMD dot product
-        Ok(a.iter().zip(b.iter()).map(|(x, y)| x * y).sum())
     }
 
     /// Normalize a vector using SIMD operations
@@ -140,7 +194,7 @@ impl SimdOperations {
             return Err(anyhow::anyhow!("Cannot normalize zero vector"));
         }
 
-        if !self.simd_available {
+        if !self.is_simd_available() {
             return Ok(data.iter().map(|&x| x / magnitude).collect());
         }
 
@@ -148,9 +202,9 @@ impl SimdOperations {
         Ok(data.iter().map(|&x| x / magnitude).collect())
     }
 
-    /// Check if SIMD operations are available
+    /// Check if the operations run on a SIMD backend
     pub fn is_simd_available(&self) -> bool {
-        self.simd_available
+        self.backend != SimdBackend::Scalar
     }
 }
 
@@ -160,6 +214,140 @@ impl Default for SimdOperations {
     }
 }
 
+/// Scalar dot product that sums in the same order as the SIMD version
+fn dot_product_scalar(a: &[f64], b: &[f64]) -> f64 {
+    let mut lanes = [0.0; LANES];
+    for (x, y) in a.chunks_exact(LANES).zip(b.chunks_exact(LANES)) {
+        for lane in 0..LANES {
+            lanes[lane] += x[lane] * y[lane];
+        }
+    }
+    finish_dot_product(lanes, a, b)
+}
+
+/// Scalar sum that adds in the same order as the SIMD version
+fn sum_scalar(data: &[f64]) -> f64 {
+    let mut lanes = [0.0; LANES];
+    for chunk in data.chunks_exact(LANES) {
+        for lane in 0..LANES {
+            lanes[lane] += chunk[lane];
+        }
+    }
+    finish_sum(lanes, data)
+}
+
+/// Elements after the last full group of `LANES`
+fn remainder(data: &[f64]) -> &[f64] {
+    &data[data.len() - data.len() % LANES..]
+}
+
+/// Add up the running totals, pairing neighbouring lanes
+fn horizontal_sum(lanes: [f64; LANES]) -> f64 {
+    (lanes[0] + lanes[1]) + (lanes[2] + lanes[3])
+}
+
+/// Combine the running totals and add the products of the remainder
+fn finish_dot_product(lanes: [f64; LANES], a: &[f64], b: &[f64]) -> f64 {
+    let mut sum = horizontal_sum(lanes);
+    for (x, y) in remainder(a).iter().zip(remainder(b)) {
+        sum += x * y;
+    }
+    sum
+}
+
+/// Combine the running totals and add the remainder
+fn finish_sum(lanes: [f64; LANES], data: &[f64]) -> f64 {
+    remainder(data).iter().fold(horizontal_sum(lanes), |sum, x| sum + x)
+}
+
+/// Turn the starting value of a min or max, which is what an empty or
+/// all-NaN array leaves behind, into `None`
+fn found_extreme(extreme: f64, data: &[f64]) -> Option<f64> {
+    if extreme.is_infinite() && !data.contains(&extreme) {
+        None
+    } else {
+        Some(extreme)
+    }
+}
+
+/// AVX2 implementations; callers must check the CPU supports AVX2
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::{finish_dot_product, finish_sum, remainder, LANES};
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_arrays(a: &[f64], b: &[f64]) -> Vec<f64> {
+        let mut result = vec![0.0; a.len()];
+        for ((x, y), out) in a
+            .chunks_exact(LANES)
+            .zip(b.chunks_exact(LANES))
+            .zip(result.chunks_exact_mut(LANES))
+        {
+            let sum = _mm256_add_pd(_mm256_loadu_pd(x.as_ptr()), _mm256_loadu_pd(y.as_ptr()));
+            _mm256_storeu_pd(out.as_mut_ptr(), sum);
+        }
+
+        let tail = a.len() - a.len() % LANES;
+        for i in tail..a.len() {
+            result[i] = a[i] + b[i];
+        }
+        result
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn dot_product(a: &[f64], b: &[f64]) -> f64 {
+        let mut totals = _mm256_setzero_pd();
+        for (x, y) in a.chunks_exact(LANES).zip(b.chunks_exact(LANES)) {
+            let product = _mm256_mul_pd(_mm256_loadu_pd(x.as_ptr()), _mm256_loadu_pd(y.as_ptr()));
+            totals = _mm256_add_pd(totals, product);
+        }
+
+        let mut lanes = [0.0; LANES];
+        _mm256_storeu_pd(lanes.as_mut_ptr(), totals);
+        finish_dot_product(lanes, a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sum(data: &[f64]) -> f64 {
+        let mut totals = _mm256_setzero_pd();
+        for chunk in data.chunks_exact(LANES) {
+            totals = _mm256_add_pd(totals, _mm256_loadu_pd(chunk.as_ptr()));
+        }
+
+        let mut lanes = [0.0; LANES];
+        _mm256_storeu_pd(lanes.as_mut_ptr(), totals);
+        finish_sum(lanes, data)
+    }
+
+    // minpd and maxpd return their second operand when either is NaN, so
+    // passing the running result second skips NaNs in the data
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn min(data: &[f64]) -> f64 {
+        let mut mins = _mm256_set1_pd(f64::INFINITY);
+        for chunk in data.chunks_exact(LANES) {
+            mins = _mm256_min_pd(_mm256_loadu_pd(chunk.as_ptr()), mins);
+        }
+
+        let mut lanes = [0.0; LANES];
+        _mm256_storeu_pd(lanes.as_mut_ptr(), mins);
+        lanes.iter().chain(remainder(data)).fold(f64::INFINITY, |acc, &x| acc.min(x))
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn max(data: &[f64]) -> f64 {
+        let mut maxes = _mm256_set1_pd(f64::NEG_INFINITY);
+        for chunk in data.chunks_exact(LANES) {
+            maxes = _mm256_max_pd(_mm256_loadu_pd(chunk.as_ptr()), maxes);
+        }
+
+        let mut lanes = [0.0; LANES];
+        _mm256_storeu_pd(lanes.as_mut_ptr(), maxes);
+        lanes.iter().chain(remainder(data)).fold(f64::NEG_INFINITY, |acc, &x| acc.max(x))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,8 +355,12 @@ mod tests {
     #[test]
     fn test_simd_operations_creation() {
         let simd_ops = SimdOperations::new();
-        // Basic smoke test
-        assert!(true);
+        assert_eq!(simd_ops.is_simd_available(), simd_ops.backend() == SimdBackend::Avx2);
+        #[cfg(target_arch = "x86_64")]
+        assert_eq!(simd_ops.is_simd_available(), is_x86_feature_detected!("avx2"));
+
+        // Asking for AVX2 never picks it on a CPU without it
+        assert_eq!(SimdOperations::with_backend(SimdBackend::Avx2).backend(), simd_ops.backend());
     }
 
     #[test]
@@ -227,6 +419,103 @@ mod tests {
         }
     }
 
+    /// Values with plenty of significant bits, so summation order matters
+    fn awkward_data(len: usize, seed: f64) -> Vec<f64> {
+        (0..len).map(|i| ((i as f64 + seed) * 1.618).sin() * 1e3).collect()
+    }
+
+    #[test]
+    fn test_backends_agree_on_awkward_lengths() {
+        let simd = SimdOperations::new();
+        let scalar = SimdOperations::with_backend(SimdBackend::Scalar);
+        assert!(!scalar.is_simd_available());
+
+        for len in [0, 1, 3, 4, 5, 7, 13, 1000] {
+            let a = awkward_data(len, 0.5);
+            let b = awkward_data(len, 2.0);
+
+            assert_eq!(simd.add_arrays(&a, &b).unwrap(), scalar.add_arrays(&a, &b).unwrap(), "len {}", len);
+            assert_eq!(simd.dot_product(&a, &b).unwrap(), scalar.dot_product(&a, &b).unwrap(), "len {}", len);
+        }
+    }
+
+    /// Pseudo-random values in [-1000, 1000) from an xorshift generator
+    fn random_data(len: usize, mut seed: u64) -> Vec<f64> {
+        (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                (seed >> 11) as f64 / (1u64 << 53) as f64 * 2000.0 - 1000.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reductions_match_naive_folds() {
+        let scalar = SimdOperations::with_backend(SimdBackend::Scalar);
+
+        for ops in [SimdOperations::new(), scalar] {
+            for (len, seed) in [(1, 1), (7, 2), (13, 3), (1000, 4), (1003, 5)] {
+                let mut data = random_data(len, seed);
+                if len > 1 {
+                    data[len / 2] = f64::NAN;
+                }
+                let values = || data.iter().copied().filter(|x| !x.is_nan());
+
+                assert_eq!(ops.min(&data), values().reduce(f64::min), "len {}", len);
+                assert_eq!(ops.max(&data), values().reduce(f64::max), "len {}", len);
+
+                data.retain(|x| !x.is_nan());
+                let expected: f64 = data.iter().sum();
+                assert!((ops.sum(&data) - expected).abs() < 1e-9, "len {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reductions_agree_across_backends() {
+        let simd = SimdOperations::new();
+        let scalar = SimdOperations::with_backend(SimdBackend::Scalar);
+
+        for len in [0, 1, 3, 4, 5, 7, 13, 1000] {
+            let data = random_data(len, len as u64 + 1);
+            assert_eq!(simd.sum(&data), scalar.sum(&data), "len {}", len);
+            assert_eq!(simd.min(&data), scalar.min(&data), "len {}", len);
+            assert_eq!(simd.max(&data), scalar.max(&data), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_min_max_edge_cases() {
+        let simd_ops = SimdOperations::new();
+
+        assert_eq!(simd_ops.min(&[]), None);
+        assert_eq!(simd_ops.max(&[]), None);
+        assert_eq!(simd_ops.sum(&[]), 0.0);
+
+        let all_nan = [f64::NAN; 6];
+        assert_eq!(simd_ops.min(&all_nan), None);
+        assert_eq!(simd_ops.max(&all_nan), None);
+
+        // Infinities are real values, not "nothing found"
+        let data = [f64::NAN, f64::INFINITY, 1.0, f64::NEG_INFINITY, f64::NAN];
+        assert_eq!(simd_ops.min(&data), Some(f64::NEG_INFINITY));
+        assert_eq!(simd_ops.max(&data), Some(f64::INFINITY));
+        assert_eq!(simd_ops.max(&[f64::NAN, f64::INFINITY]), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_add_arrays_remainder() {
+        let simd_ops = SimdOperations::new();
+        let a: Vec<f64> = (0..7).map(f64::from).collect();
+        let b = vec![10.0; 7];
+
+        let result = simd_ops.add_arrays(&a, &b).unwrap();
+        assert_eq!(result, vec![10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0]);
+        assert_eq!(simd_ops.dot_product(&a, &b).unwrap(), 210.0);
+    }
+
     #[test]
     fn test_mismatched_array_lengths() {
         let simd_ops = SimdOperations::new();
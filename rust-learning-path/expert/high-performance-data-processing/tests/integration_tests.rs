@@ -27,28 +27,32 @@ fn test_end_to_end_csv_processing() -> Result<()> {
             use_parallel: false,
             use_memory_map: false,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         },
         ProcessingConfig {
             use_simd: true,
             use_parallel: false,
             use_memory_map: false,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         },
         ProcessingConfig {
             use_simd: false,
             use_parallel: true,
             use_memory_map: false,
             thread_count: Some(2),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         },
         ProcessingConfig {
             use_simd: false,
             use_parallel: false,
             use_memory_map: true,
             thread_count: Some(1),
-            chunk_size: 1024,
+            chunk_rows: 1024,
+            read_buffer_bytes: 8 * 1024,
         },
         ProcessingConfig::default(),
     ];
@@ -61,7 +65,10 @@ fn test_end_to_end_csv_processing() -> Result<()> {
         
         // Basic validation
         assert!(result.processing_time_ms >= 0);
-        // Note: records_processed might be 0 in the TODO implementation
+        assert_eq!(result.records_processed, 1000);
+        assert_eq!(result.malformed_rows, 0);
+        assert_eq!(result.column_stats.len(), 5);
+        assert_eq!(result.column_stats[0].sum, 499500.0);
         
         println!("  Processing time: {}ms", result.processing_time_ms);
         println!("  Records processed: {}", result.records_processed);
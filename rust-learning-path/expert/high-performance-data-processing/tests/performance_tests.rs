@@ -394,28 +394,32 @@ mod benchmark_tests {
                 use_parallel: false,
                 use_memory_map: false,
                 thread_count: Some(1),
-                chunk_size: 1024,
+                chunk_rows: 1024,
+                read_buffer_bytes: 8 * 1024,
             }),
             ("SIMD only", ProcessingConfig {
                 use_simd: true,
                 use_parallel: false,
                 use_memory_map: false,
                 thread_count: Some(1),
-                chunk_size: 1024,
+                chunk_rows: 1024,
+                read_buffer_bytes: 8 * 1024,
             }),
             ("Parallel only", ProcessingConfig {
                 use_simd: false,
                 use_parallel: true,
                 use_memory_map: false,
                 thread_count: None,
-                chunk_size: 1024,
+                chunk_rows: 1024,
+                read_buffer_bytes: 8 * 1024,
             }),
             ("Memory-map only", ProcessingConfig {
                 use_simd: false,
                 use_parallel: false,
                 use_memory_map: true,
                 thread_count: Some(1),
-                chunk_size: 1024,
+                chunk_rows: 1024,
+                read_buffer_bytes: 8 * 1024,
             }),
             ("All optimizations", ProcessingConfig::default()),
         ];
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
+use observability::LogFormat;
 use std::path::PathBuf;
 use tokio::signal;
 use tracing::{info, error};
@@ -41,16 +42,23 @@ struct Cli {
     /// Enable Monitoring
     #[arg(long, default_value = "true")]
     monitoring: bool,
+
+    /// Log level or filter directives; RUST_LOG overrides it
+    #[arg(long, default_value = "info")]
+    log_level: String,
+
+    /// Log line format: plain or json
+    #[arg(long, default_value = "plain")]
+    log_format: LogFormat,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
     let cli = Cli::parse();
+
+    // Initialize tracing
+    observability::init(&cli.log_level, cli.log_format)?;
+    observability::install_panic_hook();
     
     // Load configuration
     let config = if let Some(config_path) = cli.config {
@@ -0,0 +1,232 @@
+//! Logging setup shared by the servers, so they all log the same way
+//!
+//! The distributed analysis coordinator, the async network server and the
+//! module 1 async server all start by calling [`init`] and
+//! [`install_panic_hook`].
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fmt;
+use std::panic::PanicHookInfo;
+use std::str::FromStr;
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::EnvFilter;
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Plain,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" => Ok(LogFormat::Plain),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("Unknown log format: {} (expected plain or json)", other)),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Plain => write!(f, "plain"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Install the global tracing subscriber, writing to stdout
+///
+/// `level` is a level such as `info`, or filter directives such as
+/// `coordinator=debug`. The `RUST_LOG` environment variable overrides it
+/// when set, so the level can be changed without changing how the binary
+/// is started. Fails if a global subscriber is already installed.
+pub fn init(level: &str, format: LogFormat) -> Result<()> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => EnvFilter::try_new(level).with_context(|| format!("Invalid log level: {}", level))?,
+    };
+
+    tracing::subscriber::set_global_default(subscriber(filter, format, std::io::stdout))
+        .context("A global tracing subscriber is already installed")
+}
+
+/// Build a subscriber that writes events passing `filter` to `writer`
+pub fn subscriber<W>(filter: EnvFilter, format: LogFormat, writer: W) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Plain => Box::new(registry.with(tracing_subscriber::fmt::layer().with_writer(writer))),
+        LogFormat::Json => Box::new(registry.with(tracing_subscriber::fmt::layer().json().with_writer(writer))),
+    }
+}
+
+/// Log every panic through tracing instead of printing it to stderr
+///
+/// The event has the panic's message, location, thread and backtrace as
+/// fields, so a panic in a spawned task shows up in the logs with
+/// everything needed to find it, in whatever format the logs are in. The
+/// backtrace is captured whatever `RUST_BACKTRACE` is set to. This replaces
+/// any hook installed before.
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(log_panic));
+}
+
+fn log_panic(info: &PanicHookInfo<'_>) {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    let location = info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let thread = std::thread::current();
+
+    tracing::error!(
+        panic.message = message,
+        panic.location = %location,
+        panic.thread = thread.name().unwrap_or("<unnamed>"),
+        panic.backtrace = %Backtrace::force_capture(),
+        "Thread panicked"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// A writer that keeps everything written to it
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Capture {
+        fn lines(&self) -> Vec<String> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for Capture {
+        type Writer = Capture;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// Log one event at each level through a subscriber, returning the lines written
+    fn capture(level: &str, format: LogFormat) -> Vec<String> {
+        let output = Capture::default();
+        let subscriber = subscriber(EnvFilter::new(level), format, output.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::trace!("trace event");
+            tracing::debug!("debug event");
+            tracing::info!(job_id = 7, "info event");
+            tracing::warn!("warn event");
+            tracing::error!("error event");
+        });
+
+        output.lines()
+    }
+
+    #[test]
+    fn test_json_lines_are_parseable() {
+        let lines = capture("info", LogFormat::Json);
+        assert_eq!(lines.len(), 3);
+
+        for line in &lines {
+            let event: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(event["timestamp"].is_string(), "{}", line);
+            assert!(event["fields"]["message"].is_string(), "{}", line);
+        }
+
+        let info: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(info["level"], "INFO");
+        assert_eq!(info["fields"]["message"], "info event");
+        assert_eq!(info["fields"]["job_id"], 7);
+    }
+
+    #[test]
+    fn test_level_filter_suppresses_lower_levels() {
+        let lines = capture("warn", LogFormat::Plain);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("warn event"), "{}", lines[0]);
+        assert!(lines[1].contains("error event"), "{}", lines[1]);
+        assert!(lines.iter().all(|line| !line.contains("info event")));
+    }
+
+    #[test]
+    fn test_panic_hook_logs_panic() {
+        let output = Capture::default();
+        let writer = output.clone();
+
+        let previous = std::panic::take_hook();
+        install_panic_hook();
+        let result = std::thread::Builder::new()
+            .name("connection-7".to_string())
+            .spawn(move || {
+                // The hook runs on the panicking thread and logs to its subscriber
+                let subscriber = subscriber(EnvFilter::new("error"), LogFormat::Json, writer);
+                tracing::subscriber::with_default(subscriber, || panic!("worker exploded: {}", 42));
+            })
+            .unwrap()
+            .join();
+        std::panic::set_hook(previous);
+        assert!(result.is_err());
+
+        let lines = output.lines();
+        assert_eq!(lines.len(), 1, "{:?}", lines);
+        let event: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        let fields = &event["fields"];
+        assert_eq!(event["level"], "ERROR");
+        assert_eq!(fields["message"], "Thread panicked");
+        assert_eq!(fields["panic.message"], "worker exploded: 42");
+        assert!(fields["panic.location"].as_str().unwrap().contains("src/lib.rs"), "{}", fields);
+        assert_eq!(fields["panic.thread"], "connection-7");
+        assert!(fields["panic.backtrace"].as_str().unwrap().contains("test_panic_hook_logs_panic"), "{}", fields);
+    }
+
+    #[test]
+    fn test_log_format_names() {
+        for format in [LogFormat::Plain, LogFormat::Json] {
+            assert_eq!(format.to_string().parse::<LogFormat>(), Ok(format));
+        }
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
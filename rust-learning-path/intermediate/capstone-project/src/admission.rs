@@ -0,0 +1,270 @@
+//! Admission control for submitted tasks
+//!
+//! `TaskQueueSystem` asks its `AdmissionPolicy` about every task before the
+//! task is queued. A policy sees the task's metadata and the queue it would
+//! join, so it can shed load (`PriorityFloor`), refuse certain kinds of work
+//! (`TagBlocklist`) or keep one user from flooding the queue (`KeyQuota`).
+//! Policies combine with `AllOf`; a rejected task fails to submit with
+//! `TaskError::Rejected`.
+
+use std::collections::HashSet;
+
+use crate::error::{Result, TaskError};
+use crate::task::{TaskMetadata, TaskQueue};
+
+/// Decides whether a task may join the queue
+///
+/// Policies are consulted while the system holds the queue lock, so the
+/// queue they see doesn't change until the task is queued.
+pub trait AdmissionPolicy: Send + Sync {
+    /// Accept the task, or reject it with `TaskError::Rejected`
+    fn admit(&self, task: &TaskMetadata, queue: &TaskQueue) -> Result<()>;
+}
+
+/// Accepts every task; the default policy
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+impl AdmissionPolicy for AllowAll {
+    fn admit(&self, _task: &TaskMetadata, _queue: &TaskQueue) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Rejects tasks below a minimum priority while the queue is busy
+///
+/// Below `max_pending` pending tasks every task is accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct PriorityFloor {
+    min_priority: i32,
+    max_pending: usize,
+}
+
+impl PriorityFloor {
+    /// Once `max_pending` tasks are pending, only accept tasks with at
+    /// least `min_priority`
+    pub fn new(min_priority: i32, max_pending: usize) -> Self {
+        Self { min_priority, max_pending }
+    }
+}
+
+impl AdmissionPolicy for PriorityFloor {
+    fn admit(&self, task: &TaskMetadata, queue: &TaskQueue) -> Result<()> {
+        if task.priority >= self.min_priority {
+            return Ok(());
+        }
+
+        let pending = queue.pending_count()?;
+        if pending >= self.max_pending {
+            return Err(TaskError::rejected(&task.name, &format!(
+                "priority {} is below {} while {} tasks are pending",
+                task.priority, self.min_priority, pending
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects tasks carrying any of the blocked tags
+#[derive(Debug, Clone, Default)]
+pub struct TagBlocklist {
+    blocked: HashSet<String>,
+}
+
+impl TagBlocklist {
+    /// Block every tag in `tags`
+    pub fn new<I, S>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            blocked: tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl AdmissionPolicy for TagBlocklist {
+    fn admit(&self, task: &TaskMetadata, _queue: &TaskQueue) -> Result<()> {
+        match task.tags.iter().find(|tag| self.blocked.contains(*tag)) {
+            Some(tag) => Err(TaskError::rejected(&task.name, &format!("tag '{}' is blocked", tag))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Function picking the key a task counts against, or `None` for no quota
+type KeyFn = dyn Fn(&TaskMetadata) -> Option<String> + Send + Sync;
+
+/// Limits how many tasks with the same key may be outstanding at once
+///
+/// Pending and running tasks count against the quota, so a key gets room
+/// again as its tasks finish.
+pub struct KeyQuota {
+    max_outstanding: usize,
+    key: Box<KeyFn>,
+}
+
+impl KeyQuota {
+    /// Allow `max_outstanding` tasks per key, with keys picked by `key`
+    pub fn new<F>(max_outstanding: usize, key: F) -> Self
+    where
+        F: Fn(&TaskMetadata) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            max_outstanding,
+            key: Box::new(key),
+        }
+    }
+
+    /// Allow `max_outstanding` tasks per task name
+    pub fn by_name(max_outstanding: usize) -> Self {
+        Self::new(max_outstanding, |task| Some(task.name.clone()))
+    }
+
+    /// Allow `max_outstanding` tasks per value of the custom data entry
+    /// `field` (a user id, say); tasks without the entry are unlimited
+    pub fn by_custom_data(field: &str, max_outstanding: usize) -> Self {
+        let field = field.to_string();
+        Self::new(max_outstanding, move |task| task.custom_data.get(&field).cloned())
+    }
+}
+
+impl AdmissionPolicy for KeyQuota {
+    fn admit(&self, task: &TaskMetadata, queue: &TaskQueue) -> Result<()> {
+        let Some(key) = (self.key)(task) else {
+            return Ok(());
+        };
+
+        let outstanding = queue.count_outstanding(|other| (self.key)(other).as_ref() == Some(&key))?;
+        if outstanding >= self.max_outstanding {
+            return Err(TaskError::rejected(&task.name, &format!(
+                "quota for '{}' is used up ({} of {} tasks outstanding)",
+                key, outstanding, self.max_outstanding
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Accepts a task only if every policy in it does
+///
+/// Policies are asked in the order they were added; the first rejection wins.
+#[derive(Default)]
+pub struct AllOf {
+    policies: Vec<Box<dyn AdmissionPolicy>>,
+}
+
+impl AllOf {
+    /// Create a policy that accepts everything until policies are added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also require `policy` to accept each task
+    pub fn with(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.policies.push(Box::new(policy));
+        self
+    }
+}
+
+impl AdmissionPolicy for AllOf {
+    fn admit(&self, task: &TaskMetadata, queue: &TaskQueue) -> Result<()> {
+        self.policies.iter().try_for_each(|policy| policy.admit(task, queue))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TaskBox;
+
+    #[derive(Debug)]
+    struct NoopTask;
+
+    impl crate::task::Task for NoopTask {
+        type Output = ();
+        type Error = std::io::Error;
+
+        fn execute(&self) -> std::result::Result<Self::Output, Self::Error> {
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "noop"
+        }
+    }
+
+    fn queue_with(tasks: &[TaskMetadata]) -> TaskQueue {
+        let queue = TaskQueue::new();
+        for metadata in tasks {
+            let mut task = TaskBox::new(NoopTask);
+            *task.metadata_mut() = metadata.clone();
+            queue.submit(task).unwrap();
+        }
+        queue
+    }
+
+    fn is_rejected(result: Result<()>) -> bool {
+        matches!(result, Err(TaskError::Rejected { .. }))
+    }
+
+    #[test]
+    fn test_priority_floor_only_applies_under_load() {
+        let policy = PriorityFloor::new(5, 2);
+        let low = TaskMetadata::new("low").with_priority(1);
+        let high = TaskMetadata::new("high").with_priority(5);
+
+        let shallow = queue_with(&[TaskMetadata::new("a")]);
+        assert!(policy.admit(&low, &shallow).is_ok());
+
+        let deep = queue_with(&[TaskMetadata::new("a"), TaskMetadata::new("b")]);
+        assert!(is_rejected(policy.admit(&low, &deep)));
+        assert!(policy.admit(&high, &deep).is_ok());
+    }
+
+    #[test]
+    fn test_tag_blocklist() {
+        let policy = TagBlocklist::new(["batch", "experimental"]);
+        let queue = TaskQueue::new();
+
+        let blocked = TaskMetadata::new("report").with_tag("nightly").with_tag("batch");
+        let err = policy.admit(&blocked, &queue).unwrap_err();
+        assert_eq!(err.to_string(), "Task 'report' rejected: tag 'batch' is blocked");
+
+        assert!(policy.admit(&TaskMetadata::new("report").with_tag("nightly"), &queue).is_ok());
+    }
+
+    #[test]
+    fn test_key_quota_counts_outstanding_tasks_per_key() {
+        let policy = KeyQuota::by_custom_data("user", 2);
+        let task = |user: &str| TaskMetadata::new("upload").with_custom_data("user", user);
+        let queue = queue_with(&[task("alice"), task("alice"), task("bob")]);
+
+        assert!(is_rejected(policy.admit(&task("alice"), &queue)));
+        assert!(policy.admit(&task("bob"), &queue).is_ok());
+        // Tasks without the key aren't limited
+        assert!(policy.admit(&TaskMetadata::new("upload"), &queue).is_ok());
+
+        // Finishing a task frees up quota
+        let queue = queue_with(&[task("alice"), task("alice")]);
+        assert!(is_rejected(policy.admit(&task("alice"), &queue)));
+        let (task_id, _) = queue.next_task().unwrap().unwrap();
+        queue.complete_task(task_id, String::new()).unwrap();
+        assert!(policy.admit(&task("alice"), &queue).is_ok());
+    }
+
+    #[test]
+    fn test_all_of_rejects_if_any_policy_does() {
+        let policy = AllOf::new()
+            .with(AllowAll)
+            .with(TagBlocklist::new(["batch"]))
+            .with(KeyQuota::by_name(1));
+        let queue = queue_with(&[TaskMetadata::new("busy")]);
+
+        assert!(policy.admit(&TaskMetadata::new("idle"), &queue).is_ok());
+        assert!(is_rejected(policy.admit(&TaskMetadata::new("busy"), &queue)));
+        assert!(is_rejected(policy.admit(&TaskMetadata::new("idle").with_tag("batch"), &queue)));
+        assert!(AllOf::new().admit(&TaskMetadata::new("busy"), &queue).is_ok());
+    }
+}
@@ -24,6 +24,14 @@ pub struct Config {
     /// Maximum number of retries for failed tasks
     pub max_retries: u32,
     
+    /// Delay before a failed task is put back in the queue for another attempt
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    
+    /// Maximum number of pending tasks, or `None` for an unbounded queue
+    #[serde(default)]
+    pub max_queue_size: Option<usize>,
+    
     /// Worker thread configuration
     pub worker_config: WorkerConfig,
     
@@ -68,12 +76,18 @@ impl Default for Config {
             storage_path: PathBuf::from("./task_storage"),
             default_timeout: Duration::from_secs(300), // 5 minutes
             max_retries: 3,
+            retry_backoff_ms: default_retry_backoff_ms(),
+            max_queue_size: None,
             worker_config: WorkerConfig::default(),
             monitoring: MonitoringConfig::default(),
         }
     }
 }
 
+fn default_retry_backoff_ms() -> u64 {
+    100
+}
+
 impl Default for WorkerConfig {
     fn default() -> Self {
         Self {
@@ -119,6 +133,12 @@ impl Config {
             ));
         }
         
+        if self.max_queue_size == Some(0) {
+            return Err(crate::error::TaskError::ConfigurationError(
+                "Maximum queue size must be greater than 0".to_string()
+            ));
+        }
+        
         if !matches!(self.storage_type.as_str(), "json" | "memory") {
             return Err(crate::error::TaskError::ConfigurationError(
                 format!("Invalid storage type: {}", self.storage_type)
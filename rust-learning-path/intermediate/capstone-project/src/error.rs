@@ -67,6 +67,23 @@ pub enum TaskError {
     /// Worker pool error
     WorkerPoolError(String),
     
+    /// The bounded task queue has no room for another task
+    QueueFull {
+        capacity: usize,
+    },
+    
+    /// The task queue was closed and accepts no new tasks
+    QueueClosed,
+    
+    /// Storage can't persist tasks right now, so new ones are rejected
+    StorageUnavailable(String),
+    
+    /// The system's admission policy refused the task
+    Rejected {
+        task_name: String,
+        reason: String,
+    },
+    
     /// Storage backend error
     StorageError {
         operation: String,
@@ -130,6 +147,22 @@ impl fmt::Display for TaskError {
                 write!(f, "Worker pool error: {}", msg)
             }
             
+            TaskError::QueueFull { capacity } => {
+                write!(f, "Task queue is full (capacity {})", capacity)
+            }
+            
+            TaskError::QueueClosed => {
+                write!(f, "Task queue is closed to new tasks")
+            }
+            
+            TaskError::StorageUnavailable(reason) => {
+                write!(f, "Storage unavailable: {}", reason)
+            }
+            
+            TaskError::Rejected { task_name, reason } => {
+                write!(f, "Task '{}' rejected: {}", task_name, reason)
+            }
+            
             TaskError::StorageError { operation, details } => {
                 write!(f, "Storage error during {}: {}", operation, details)
             }
@@ -236,6 +269,14 @@ impl TaskError {
         }
     }
     
+    /// Create an admission rejection
+    pub fn rejected(task_name: &str, reason: &str) -> Self {
+        TaskError::Rejected {
+            task_name: task_name.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+    
     /// Create a storage error
     pub fn storage_error(operation: &str, details: &str) -> Self {
         TaskError::StorageError {
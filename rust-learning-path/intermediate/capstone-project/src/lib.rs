@@ -42,6 +42,7 @@
 //! ```
 
 pub mod task;
+pub mod admission;
 pub mod worker;
 pub mod monitor;
 pub mod storage;
@@ -49,10 +50,11 @@ pub mod error;
 pub mod config;
 
 // Re-export main types for convenience
-pub use task::{Task, TaskId, TaskQueue, TaskResult, TaskStatus};
+pub use task::{Task, TaskId, TaskQueue, TaskResult, TaskResultSummary, TaskStatus};
+pub use admission::AdmissionPolicy;
 pub use worker::{WorkerPool, WorkerConfig};
-pub use monitor::{Monitor, TaskMonitor, ProgressReport};
-pub use storage::{Storage, JsonStorage, MemoryStorage};
+pub use monitor::{Monitor, TaskMonitor, ProgressReport, TaskTypeStats};
+pub use storage::{Storage, StorageHealth, JsonStorage, MemoryStorage};
 pub use error::{TaskError, Result};
 pub use config::Config;
 
@@ -62,11 +64,22 @@ pub struct TaskQueueSystem {
     worker_pool: WorkerPool,
     monitor: TaskMonitor,
     storage: Box<dyn Storage>,
+    admission: Box<dyn AdmissionPolicy>,
+    /// Set by `stop` and `drain` so dropping doesn't shut down a second time
+    stopped: std::sync::atomic::AtomicBool,
 }
 
 impl TaskQueueSystem {
     /// Create a new task queue system with the given configuration
     pub fn new(config: Config) -> Result<Self> {
+        Self::with_id_generator(config, task::RandomIdGenerator)
+    }
+    
+    /// Create a task queue system whose task ids come from `generator`
+    /// 
+    /// Tests can pass a `task::SequentialIdGenerator` to know every task's
+    /// id in advance.
+    pub fn with_id_generator(config: Config, generator: impl task::IdGenerator + 'static) -> Result<Self> {
         let storage: Box<dyn Storage> = match config.storage_type.as_str() {
             "json" => Box::new(JsonStorage::new(&config.storage_path)?),
             "memory" => Box::new(MemoryStorage::new()),
@@ -75,10 +88,32 @@ impl TaskQueueSystem {
             )),
         };
         
-        let queue = std::sync::Arc::new(std::sync::Mutex::new(TaskQueue::new()));
+        Self::build(config, storage, generator)
+    }
+    
+    /// Create a task queue system that persists tasks to `storage`
+    /// 
+    /// `config.storage_type` and `config.storage_path` are ignored.
+    pub fn with_storage(config: Config, storage: Box<dyn Storage>) -> Result<Self> {
+        Self::build(config, storage, task::RandomIdGenerator)
+    }
+    
+    fn build(config: Config, storage: Box<dyn Storage>, generator: impl task::IdGenerator + 'static) -> Result<Self> {
+        let queue = match config.max_queue_size {
+            Some(max) => TaskQueue::with_capacity(max),
+            None => TaskQueue::new(),
+        }
+        .with_id_generator(generator);
+        let queue = std::sync::Arc::new(std::sync::Mutex::new(queue));
         let monitor = TaskMonitor::new();
-        let worker_pool = WorkerPool::new(
-            config.worker_count,
+        let worker_config = WorkerConfig {
+            thread_count: config.worker_count,
+            poll_interval: config.worker_config.poll_interval,
+            retry_backoff: std::time::Duration::from_millis(config.retry_backoff_ms),
+            default_timeout: Some(config.default_timeout),
+        };
+        let worker_pool = WorkerPool::with_config(
+            worker_config,
             std::sync::Arc::clone(&queue),
             monitor.clone(),
         )?;
@@ -88,15 +123,97 @@ impl TaskQueueSystem {
             worker_pool,
             monitor,
             storage,
+            admission: Box::new(admission::AllowAll),
+            stopped: std::sync::atomic::AtomicBool::new(false),
         })
     }
     
+    /// Consult `policy` before queueing each submitted task
+    /// 
+    /// Replaces the default policy, which accepts every task. Combine
+    /// several policies with `admission::AllOf`.
+    pub fn with_admission_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.admission = Box::new(policy);
+        self
+    }
+    
+    /// Rebuild a task queue system from the tasks saved in JSON storage
+    /// 
+    /// Finished tasks get their stored results back. Pending tasks, and tasks
+    /// that were running when the previous process stopped, are queued again
+    /// as pending. Tasks whose payload is missing or of an unregistered type
+    /// (see `task::traits::register_task_type`) are skipped with a warning.
+    pub fn recover(config: Config) -> Result<Self> {
+        Self::recover_with_id_generator(config, task::RandomIdGenerator)
+    }
+    
+    /// Like `recover`, taking the ids of new tasks from `generator`
+    /// 
+    /// `generator` learns every recovered id, so a `task::SequentialIdGenerator`
+    /// continues after the highest one instead of reusing it.
+    pub fn recover_with_id_generator(config: Config, generator: impl task::IdGenerator + 'static) -> Result<Self> {
+        if config.storage_type != "json" {
+            return Err(TaskError::ConfigurationError(
+                format!("Recovery requires json storage, not {}", config.storage_type)
+            ));
+        }
+        
+        let store = JsonStorage::new(&config.storage_path)?;
+        let system = Self::with_id_generator(config, generator)?;
+        {
+            let queue = system.queue.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?;
+            
+            for (task_id, result) in store.load_all()? {
+                if result.is_terminal() {
+                    queue.restore_result(result)?;
+                    continue;
+                }
+                
+                let Some(metadata) = store.load_metadata(task_id)? else {
+                    log::warn!("Skipping task {}: no stored metadata", task_id);
+                    continue;
+                };
+                let Some(payload) = metadata.payload.as_ref() else {
+                    log::warn!("Skipping task {}: it was not submitted with a serialized payload", task_id);
+                    continue;
+                };
+                
+                match task::traits::decode_task(payload) {
+                    Some(Ok(mut task)) => {
+                        *task.metadata_mut() = metadata;
+                        queue.restore_pending(task_id, task, result)?;
+                        system.monitor.task_submitted(task_id);
+                    }
+                    Some(Err(e)) => {
+                        log::warn!("Skipping task {}: could not deserialize {}: {}", task_id, payload.task_type, e);
+                    }
+                    None => {
+                        log::warn!("Skipping task {}: unknown task type {}", task_id, payload.task_type);
+                    }
+                }
+            }
+        }
+        
+        Ok(system)
+    }
+    
     /// Submit a task for execution
-    pub fn submit<T: Task + 'static>(&self, task: T) -> Result<TaskId> {
+    /// 
+    /// Like every submit method, fails with `TaskError::StorageUnavailable`
+    /// instead of queueing the task while storage reports itself unhealthy,
+    /// and with `TaskError::Rejected` if the admission policy refuses it.
+    pub fn submit<T: Task + 'static>(&self, task: T) -> Result<TaskId>
+    where
+        T::Output: serde::Serialize,
+    {
+        self.check_storage()?;
+        let task = task::TaskBox::new(task);
         let task_id = {
-            let mut queue = self.queue.lock()
+            let queue = self.queue.lock()
                 .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?;
-            queue.submit(Box::new(task))?
+            self.admission.admit(task.metadata(), &queue)?;
+            queue.submit(task)?
         };
         
         // Persist the task
@@ -108,6 +225,88 @@ impl TaskQueueSystem {
         Ok(task_id)
     }
     
+    /// Submit a task together with a serialized copy of it
+    /// 
+    /// With JSON storage the copy lets `recover` queue the task again after a
+    /// restart. Tasks submitted with `submit` keep their results but can't be
+    /// rebuilt.
+    pub fn submit_persistent<T>(&self, task: T) -> Result<TaskId>
+    where
+        T: task::traits::SerializableTask + 'static,
+        T::Output: serde::Serialize,
+    {
+        self.check_storage()?;
+        let task = task::TaskBox::serializable(task)?;
+        let queue = self.queue.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?;
+        self.admission.admit(task.metadata(), &queue)?;
+        let task_id = queue.submit(task)?;
+        
+        self.storage.save_task(task_id, &queue)?;
+        self.monitor.task_submitted(task_id);
+        
+        Ok(task_id)
+    }
+    
+    /// Submit a task, waiting for space if the queue is bounded and full
+    /// 
+    /// With a timeout, fails with `TaskError::QueueFull` if no slot opens up in time.
+    /// The admission policy is consulted once, before waiting.
+    pub fn submit_blocking<T: Task + 'static>(&self, task: T, timeout: Option<std::time::Duration>) -> Result<TaskId>
+    where
+        T::Output: serde::Serialize,
+    {
+        self.check_storage()?;
+        
+        // Wait on a shared handle so the system lock isn't held while blocked
+        let queue = self.queue.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?
+            .clone();
+        let task = task::TaskBox::new(task);
+        self.admission.admit(task.metadata(), &queue)?;
+        let task_id = queue.submit_blocking(task, timeout)?;
+        
+        self.storage.save_task(task_id, &queue)?;
+        self.monitor.task_submitted(task_id);
+        
+        Ok(task_id)
+    }
+    
+    /// Submit a task that only runs once every task in `deps` has completed
+    /// 
+    /// If a dependency fails, times out, or is cancelled, the task is marked
+    /// `Failed` without running. Fails with `TaskError::TaskNotFound` if a
+    /// dependency was never submitted.
+    pub fn submit_with_deps<T: Task + 'static>(&self, task: T, deps: &[TaskId]) -> Result<TaskId>
+    where
+        T::Output: serde::Serialize,
+    {
+        self.check_storage()?;
+        let mut task = task::TaskBox::new(task);
+        task.metadata_mut().depends_on.extend_from_slice(deps);
+        
+        let queue = self.queue.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?;
+        self.admission.admit(task.metadata(), &queue)?;
+        let task_id = queue.submit(task)?;
+        
+        self.storage.save_task(task_id, &queue)?;
+        self.monitor.task_submitted(task_id);
+        
+        Ok(task_id)
+    }
+    
+    /// Refuse new tasks while storage can't persist them
+    /// 
+    /// Health is checked on every submission, so submissions are accepted
+    /// again as soon as storage recovers.
+    fn check_storage(&self) -> Result<()> {
+        match self.storage.health() {
+            StorageHealth::Healthy => Ok(()),
+            StorageHealth::Unhealthy(reason) => Err(TaskError::StorageUnavailable(reason)),
+        }
+    }
+    
     /// Get the result of a completed task
     pub fn get_result(&self, task_id: TaskId) -> Result<Option<TaskResult>> {
         let queue = self.queue.lock()
@@ -115,9 +314,25 @@ impl TaskQueueSystem {
         Ok(queue.get_result(task_id))
     }
     
-    /// Wait for a task to complete and return its result
+    /// Wait for a task to reach a terminal state (completed, failed,
+    /// cancelled, or timed out) and return its result
     pub fn wait_for_result(&self, task_id: TaskId) -> Result<TaskResult> {
-        self.monitor.wait_for_completion(task_id)
+        let queue = self.queue.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?
+            .clone();
+        let result = queue.wait_for_result(task_id, None)?;
+        self.storage.save_task(task_id, &queue)?;
+        Ok(result)
+    }
+    
+    /// Save the current state of every task to storage
+    pub fn checkpoint(&self) -> Result<()> {
+        let queue = self.queue.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire queue lock".to_string()))?;
+        for task_id in queue.task_ids()? {
+            self.storage.save_task(task_id, &queue)?;
+        }
+        Ok(())
     }
     
     /// Get current system status
@@ -125,20 +340,60 @@ impl TaskQueueSystem {
         self.monitor.get_progress_report()
     }
     
+    /// Receive a new progress report whenever task counts change
+    pub fn subscribe(&self) -> std::sync::mpsc::Receiver<ProgressReport> {
+        self.monitor.subscribe()
+    }
+    
+    /// Execution statistics per task name, covering every attempt so far
+    pub fn stats_by_type(&self) -> std::collections::HashMap<String, TaskTypeStats> {
+        self.monitor.stats_by_type()
+    }
+    
     /// Start the worker pool
     pub fn start(&self) -> Result<()> {
+        self.stopped.store(false, std::sync::atomic::Ordering::SeqCst);
         self.worker_pool.start()
     }
     
-    /// Stop the worker pool gracefully
+    /// Stop the worker pool after the current tasks and save every task
+    /// 
+    /// Tasks still in the queue stay pending; see `drain` to finish them first.
     pub fn stop(&self) -> Result<()> {
-        self.worker_pool.stop()
+        self.stopped.store(true, std::sync::atomic::Ordering::SeqCst);
+        self.worker_pool.stop()?;
+        self.checkpoint()
+    }
+    
+    /// Stop accepting tasks, finish everything already queued, then stop
+    /// the worker pool and save every task
+    /// 
+    /// Returns the number of tasks drained. See `WorkerPool::drain`.
+    pub fn drain(&self, timeout: std::time::Duration) -> Result<usize> {
+        self.stopped.store(true, std::sync::atomic::Ordering::SeqCst);
+        let drained = self.worker_pool.drain(timeout);
+        self.checkpoint()?;
+        drained
     }
 }
 
+/// How long dropping a `TaskQueueSystem` waits for queued tasks to finish
+const DROP_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 impl Drop for TaskQueueSystem {
     fn drop(&mut self) {
-        let _ = self.worker_pool.stop();
+        // An explicit stop or drain already saved everything
+        if self.stopped.load(std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+        
+        // drain stops the workers even when it fails, so only saving is left to retry
+        if let Err(e) = self.drain(DROP_DRAIN_TIMEOUT) {
+            log::warn!("Task queue system dropped without draining: {}", e);
+            if let Err(e) = self.checkpoint() {
+                log::error!("Failed to shut down task queue system cleanly: {}", e);
+            }
+        }
     }
 }
 
@@ -164,6 +419,8 @@ mod tests {
         }
     }
     
+    impl task::traits::SerializableTask for TestTask {}
+    
     #[test]
     fn test_system_creation() {
         let config = Config::default();
@@ -180,4 +437,303 @@ mod tests {
         let task_id = system.submit(task);
         assert!(task_id.is_ok());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_bounded_system_queue() {
+        let config = Config {
+            max_queue_size: Some(1),
+            ..Config::default()
+        };
+        let system = TaskQueueSystem::new(config).unwrap();
+        
+        // Workers aren't started, so nothing drains the queue
+        system.submit(TestTask { value: 1 }).unwrap();
+        assert!(matches!(system.submit(TestTask { value: 2 }), Err(TaskError::QueueFull { capacity: 1 })));
+        
+        let blocked = system.submit_blocking(TestTask { value: 3 }, Some(std::time::Duration::from_millis(20)));
+        assert!(matches!(blocked, Err(TaskError::QueueFull { .. })));
+    }
+    
+    /// Storage that saves nothing and reports whatever health it's told to
+    struct StubStorage {
+        healthy: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+    
+    impl Storage for StubStorage {
+        fn save_task(&self, _task_id: TaskId, _queue: &TaskQueue) -> Result<()> {
+            Ok(())
+        }
+        
+        fn load_tasks(&self) -> Result<Vec<TaskId>> {
+            Ok(Vec::new())
+        }
+        
+        fn delete_task(&self, _task_id: TaskId) -> Result<()> {
+            Ok(())
+        }
+        
+        fn list_results(&self) -> Result<Vec<TaskResultSummary>> {
+            Ok(Vec::new())
+        }
+        
+        fn load_full_result(&self, _task_id: TaskId) -> Result<Option<TaskResult>> {
+            Ok(None)
+        }
+        
+        fn health(&self) -> StorageHealth {
+            if self.healthy.load(std::sync::atomic::Ordering::SeqCst) {
+                StorageHealth::Healthy
+            } else {
+                StorageHealth::Unhealthy("disk full".to_string())
+            }
+        }
+    }
+    
+    #[test]
+    fn test_unhealthy_storage_rejects_submissions() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        
+        let healthy = std::sync::Arc::new(AtomicBool::new(false));
+        let storage = StubStorage { healthy: std::sync::Arc::clone(&healthy) };
+        let system = TaskQueueSystem::with_storage(Config::default(), Box::new(storage)).unwrap();
+        
+        match system.submit(TestTask { value: 1 }) {
+            Err(TaskError::StorageUnavailable(reason)) => assert_eq!(reason, "disk full"),
+            other => panic!("expected StorageUnavailable, got {:?}", other),
+        }
+        assert!(matches!(system.submit_persistent(TestTask { value: 2 }), Err(TaskError::StorageUnavailable(_))));
+        assert!(matches!(system.submit_blocking(TestTask { value: 3 }, None), Err(TaskError::StorageUnavailable(_))));
+        assert!(matches!(system.submit_with_deps(TestTask { value: 4 }, &[]), Err(TaskError::StorageUnavailable(_))));
+        assert_eq!(system.queue.lock().unwrap().pending_count().unwrap(), 0);
+        
+        // Submissions go through again as soon as storage recovers
+        healthy.store(true, Ordering::SeqCst);
+        let task_id = system.submit(TestTask { value: 5 }).unwrap();
+        system.submit_persistent(TestTask { value: 6 }).unwrap();
+        assert_eq!(system.queue.lock().unwrap().pending_count().unwrap(), 2);
+        
+        system.start().unwrap();
+        assert_eq!(system.wait_for_result(task_id).unwrap().output.as_deref(), Some("10"));
+    }
+    
+    #[derive(Debug)]
+    struct UrgentTask;
+    
+    impl Task for UrgentTask {
+        type Output = i32;
+        type Error = String;
+    
+        fn execute(&self) -> std::result::Result<Self::Output, Self::Error> {
+            Ok(0)
+        }
+    
+        fn name(&self) -> &str {
+            "urgent_task"
+        }
+    
+        fn priority(&self) -> i32 {
+            10
+        }
+    }
+    
+    #[test]
+    fn test_priority_floor_under_load() {
+        let storage = StubStorage { healthy: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true)) };
+        let system = TaskQueueSystem::with_storage(Config::default(), Box::new(storage))
+            .unwrap()
+            .with_admission_policy(admission::PriorityFloor::new(5, 2));
+    
+        // Workers aren't started, so the queue only gets deeper
+        system.submit(TestTask { value: 1 }).unwrap();
+        system.submit_persistent(TestTask { value: 2 }).unwrap();
+    
+        match system.submit(TestTask { value: 3 }) {
+            Err(TaskError::Rejected { task_name, reason }) => {
+                assert_eq!(task_name, "test_task");
+                assert_eq!(reason, "priority 0 is below 5 while 2 tasks are pending");
+            }
+            other => panic!("expected Rejected, got {:?}", other),
+        }
+        assert!(matches!(system.submit_with_deps(TestTask { value: 4 }, &[]), Err(TaskError::Rejected { .. })));
+        assert!(matches!(system.submit_blocking(TestTask { value: 5 }, None), Err(TaskError::Rejected { .. })));
+    
+        // High-priority work still gets in
+        system.submit(UrgentTask).unwrap();
+        assert_eq!(system.queue.lock().unwrap().pending_count().unwrap(), 3);
+    
+        // Once the queue is shallow again, low-priority tasks are accepted
+        let queue = system.queue.lock().unwrap().clone();
+        while let Some((task_id, _)) = queue.next_task().unwrap() {
+            queue.complete_task(task_id, String::new()).unwrap();
+        }
+        system.submit(TestTask { value: 6 }).unwrap();
+    }
+    
+    #[test]
+    fn test_recover_after_restart() {
+        task::traits::register_task_type::<TestTask>();
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            storage_type: "json".to_string(),
+            storage_path: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        
+        let (finished, interrupted, waiting, opaque) = {
+            let system = TaskQueueSystem::new(config.clone()).unwrap();
+            let queue = system.queue.lock().unwrap().clone();
+            
+            // Act as a worker: finish one task and leave another mid-run
+            let finished = system.submit_persistent(TestTask { value: 1 }).unwrap();
+            queue.next_task().unwrap().unwrap();
+            queue.complete_task(finished, "2".to_string()).unwrap();
+            
+            let interrupted = system.submit_persistent(TestTask { value: 2 }).unwrap();
+            queue.next_task().unwrap().unwrap();
+            
+            let waiting = system.submit_persistent(TestTask { value: 3 }).unwrap();
+            let opaque = system.submit(TestTask { value: 4 }).unwrap();
+            
+            (finished, interrupted, waiting, opaque)
+        };
+        
+        let system = TaskQueueSystem::recover(config).unwrap();
+        
+        let restored = system.get_result(finished).unwrap().unwrap();
+        assert_eq!(restored.status, TaskStatus::Completed);
+        assert_eq!(restored.output.as_deref(), Some("2"));
+        
+        assert_eq!(system.get_result(interrupted).unwrap().unwrap().status, TaskStatus::Pending);
+        assert_eq!(system.get_result(waiting).unwrap().unwrap().status, TaskStatus::Pending);
+        // Submitted without a payload, so there is nothing to rebuild
+        assert!(system.get_result(opaque).unwrap().is_none());
+        
+        // The recovered tasks run to completion
+        system.start().unwrap();
+        assert_eq!(system.wait_for_result(interrupted).unwrap().output.as_deref(), Some("4"));
+        assert_eq!(system.wait_for_result(waiting).unwrap().output.as_deref(), Some("6"));
+    }
+    
+    #[test]
+    fn test_recovered_ids_are_not_reused() {
+        use task::SequentialIdGenerator;
+        
+        task::traits::register_task_type::<TestTask>();
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            storage_type: "json".to_string(),
+            storage_path: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        
+        {
+            let system = TaskQueueSystem::with_id_generator(config.clone(), SequentialIdGenerator::new()).unwrap();
+            assert_eq!(system.submit_persistent(TestTask { value: 1 }).unwrap(), TaskId::from_u128(1));
+            assert_eq!(system.submit_persistent(TestTask { value: 2 }).unwrap(), TaskId::from_u128(2));
+        }
+        
+        let system = TaskQueueSystem::recover_with_id_generator(config, SequentialIdGenerator::new()).unwrap();
+        let next = system.submit_persistent(TestTask { value: 3 }).unwrap();
+        assert_eq!(next, TaskId::from_u128(3));
+        assert_eq!(system.get_result(TaskId::from_u128(1)).unwrap().unwrap().status, TaskStatus::Pending);
+    }
+    
+    #[test]
+    fn test_drop_after_stop_does_not_drain() {
+        let system = TaskQueueSystem::new(Config::default()).unwrap();
+        let queue = system.queue.lock().unwrap().clone();
+        system.submit(TestTask { value: 1 }).unwrap();
+        
+        system.stop().unwrap();
+        drop(system);
+        
+        // Draining would have closed the queue; the pending task stays put
+        assert!(!queue.is_closed());
+        assert_eq!(queue.pending_count().unwrap(), 1);
+    }
+    
+    #[test]
+    fn test_progress_subscription() {
+        let system = TaskQueueSystem::new(Config::default()).unwrap();
+        let reports = system.subscribe();
+        
+        system.submit(TestTask { value: 1 }).unwrap();
+        system.submit(TestTask { value: 2 }).unwrap();
+        system.start().unwrap();
+        
+        // Collect reports until both tasks are reported complete
+        let mut completed = Vec::new();
+        while completed.last() != Some(&2) {
+            let report = reports.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+            completed.push(report.completed_tasks);
+        }
+        
+        assert!(completed.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(completed.first(), Some(&0));
+        assert_eq!(completed.last(), Some(&2));
+        assert!(completed.contains(&1));
+    }
+    
+    #[test]
+    fn test_submit_with_deps() {
+        use task::traits::{MathTask, SleepTask};
+        
+        let system = TaskQueueSystem::new(Config::default()).unwrap();
+        let a = system.submit(SleepTask { duration_ms: 50, name: "a".to_string() }).unwrap();
+        let b = system.submit_with_deps(MathTask {
+            operation: "add".to_string(),
+            operands: vec![1.0, 2.0],
+        }, &[a]).unwrap();
+        
+        // Division by zero fails on every attempt, so its dependent never runs
+        let bad = system.submit(MathTask { operation: "divide".to_string(), operands: vec![1.0, 0.0] }).unwrap();
+        let after_bad = system.submit_with_deps(TestTask { value: 1 }, &[bad]).unwrap();
+        system.start().unwrap();
+        
+        let a_result = system.wait_for_result(a).unwrap();
+        let b_result = system.wait_for_result(b).unwrap();
+        assert_eq!(b_result.status, TaskStatus::Completed);
+        assert!(b_result.started_at.unwrap() >= a_result.completed_at.unwrap());
+        
+        let skipped = system.wait_for_result(after_bad).unwrap();
+        assert_eq!(system.get_result(bad).unwrap().unwrap().status, TaskStatus::Failed);
+        assert_eq!(skipped.status, TaskStatus::Failed);
+        assert!(skipped.error.unwrap().contains(&bad.to_string()));
+        assert!(skipped.started_at.is_none());
+    }
+    
+    #[test]
+    fn test_stats_by_type() {
+        use task::traits::{MathTask, SleepTask};
+        
+        let system = TaskQueueSystem::new(Config::default()).unwrap();
+        let mut task_ids = Vec::new();
+        for i in 0..3 {
+            task_ids.push(system.submit(MathTask {
+                operation: "add".to_string(),
+                operands: vec![i as f64, 1.0],
+            }).unwrap());
+            task_ids.push(system.submit(SleepTask {
+                duration_ms: 30,
+                name: "nap".to_string(),
+            }).unwrap());
+        }
+        system.start().unwrap();
+        
+        for task_id in task_ids {
+            system.wait_for_result(task_id).unwrap();
+        }
+        
+        // Stats are recorded just before the result, so every attempt is counted by now
+        let stats = system.stats_by_type();
+        let math = &stats["add"];
+        let sleep = &stats["nap"];
+        
+        assert_eq!(math.count, 3);
+        assert_eq!(sleep.count, 3);
+        assert_eq!(math.failure_count + sleep.failure_count, 0);
+        assert!(sleep.avg_duration_ms >= 30.0);
+        assert!(sleep.avg_duration_ms > math.avg_duration_ms * 10.0);
+        assert!(sleep.max_duration_ms >= sleep.avg_duration_ms);
+    }
+}
@@ -8,10 +8,15 @@ pub mod reporter;
 pub use status::TaskMonitor;
 pub use reporter::ProgressReport;
 
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use crate::task::TaskId;
 use crate::error::Result;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProgressReport {
     pub pending_tasks: usize,
     pub running_tasks: usize,
@@ -20,25 +25,156 @@ pub struct ProgressReport {
     pub total_processed: usize,
 }
 
+/// Execution statistics for every task sharing a `Task::name()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskTypeStats {
+    /// Number of execution attempts, including retried and timed-out ones
+    pub count: usize,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: f64,
+    /// Attempts that returned an error or timed out
+    pub failure_count: usize,
+}
+
 pub trait Monitor: Send + Sync {
     fn task_submitted(&self, task_id: TaskId);
     fn task_started(&self, task_id: TaskId);
     fn task_completed(&self, task_id: TaskId);
     fn task_failed(&self, task_id: TaskId);
+    
+    /// A failed attempt was put back in the queue to be retried
+    fn task_retried(&self, _task_id: TaskId) {}
+    
+    /// One attempt of a task finished after `duration`
+    fn task_executed(&self, _task_name: &str, _duration: Duration, _succeeded: bool) {}
+}
+
+/// Task counts shared by every clone of a `TaskMonitor`
+#[derive(Debug, Default)]
+struct Counts {
+    pending: usize,
+    running: usize,
+    completed: usize,
+    failed: usize,
+}
+
+impl Counts {
+    fn report(&self) -> ProgressReport {
+        ProgressReport {
+            pending_tasks: self.pending,
+            running_tasks: self.running,
+            completed_tasks: self.completed,
+            failed_tasks: self.failed,
+            total_processed: self.completed + self.failed,
+        }
+    }
+}
+
+/// Running totals behind a `TaskTypeStats`
+#[derive(Debug, Default)]
+struct TypeTotals {
+    count: usize,
+    total: Duration,
+    max: Duration,
+    failures: usize,
 }
 
-#[derive(Debug, Clone)]
+impl TypeTotals {
+    fn stats(&self) -> TaskTypeStats {
+        let avg = if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.count as u32
+        };
+        TaskTypeStats {
+            count: self.count,
+            avg_duration_ms: avg.as_secs_f64() * 1000.0,
+            max_duration_ms: self.max.as_secs_f64() * 1000.0,
+            failure_count: self.failures,
+        }
+    }
+}
+
+/// Tracks task counts and pushes a `ProgressReport` to subscribers on every change
+/// 
+/// Clones share the same counts and subscribers, so the copy handed to the
+/// worker pool and the one kept by the system see the same progress.
+#[derive(Debug, Clone, Default)]
 pub struct TaskMonitor {
-    // Placeholder implementation
+    counts: Arc<Mutex<Counts>>,
+    subscribers: Arc<Mutex<Vec<Sender<ProgressReport>>>>,
+    by_type: Arc<Mutex<HashMap<String, TypeTotals>>>,
 }
 
 impl TaskMonitor {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+    
+    /// Receive a new report every time a task is submitted, started,
+    /// completed, failed, or retried
+    /// 
+    /// Each call returns an independent receiver. Dropping it is fine; the
+    /// monitor forgets it the next time it has a report to send.
+    pub fn subscribe(&self) -> Receiver<ProgressReport> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(sender);
+        receiver
     }
     
     pub fn task_submitted(&self, _task_id: TaskId) {
-        // Implementation would track task submission
+        self.update(|counts| counts.pending += 1);
+    }
+    
+    pub fn task_started(&self, _task_id: TaskId) {
+        self.update(|counts| {
+            counts.pending = counts.pending.saturating_sub(1);
+            counts.running += 1;
+        });
+    }
+    
+    pub fn task_completed(&self, _task_id: TaskId) {
+        self.update(|counts| {
+            counts.running = counts.running.saturating_sub(1);
+            counts.completed += 1;
+        });
+    }
+    
+    pub fn task_failed(&self, _task_id: TaskId) {
+        self.update(|counts| {
+            counts.running = counts.running.saturating_sub(1);
+            counts.failed += 1;
+        });
+    }
+    
+    pub fn task_retried(&self, _task_id: TaskId) {
+        self.update(|counts| {
+            counts.running = counts.running.saturating_sub(1);
+            counts.pending += 1;
+        });
+    }
+    
+    /// Record how long one attempt of a task took, keyed by the task's name
+    pub fn task_executed(&self, task_name: &str, duration: Duration, succeeded: bool) {
+        let mut by_type = self.by_type.lock().unwrap_or_else(|e| e.into_inner());
+        let totals = by_type.entry(task_name.to_string()).or_default();
+        totals.count += 1;
+        totals.total += duration;
+        totals.max = totals.max.max(duration);
+        if !succeeded {
+            totals.failures += 1;
+        }
+    }
+    
+    /// Execution statistics per `Task::name()`
+    pub fn stats_by_type(&self) -> HashMap<String, TaskTypeStats> {
+        self.by_type.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, totals)| (name.clone(), totals.stats()))
+            .collect()
     }
     
     pub fn wait_for_completion(&self, _task_id: TaskId) -> Result<crate::TaskResult> {
@@ -47,30 +183,103 @@ impl TaskMonitor {
     }
     
     pub fn get_progress_report(&self) -> ProgressReport {
-        ProgressReport {
-            pending_tasks: 0,
-            running_tasks: 0,
-            completed_tasks: 0,
-            failed_tasks: 0,
-            total_processed: 0,
-        }
+        self.counts.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .report()
+    }
+    
+    /// Apply a change to the counts and send the new report to every live subscriber
+    fn update(&self, change: impl FnOnce(&mut Counts)) {
+        // Hold the counts lock while sending so subscribers see reports in order
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        change(&mut counts);
+        let report = counts.report();
+        
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|sender| sender.send(report.clone()).is_ok());
     }
 }
 
 impl Monitor for TaskMonitor {
     fn task_submitted(&self, task_id: TaskId) {
-        self.task_submitted(task_id);
+        TaskMonitor::task_submitted(self, task_id);
+    }
+    
+    fn task_started(&self, task_id: TaskId) {
+        TaskMonitor::task_started(self, task_id);
     }
     
-    fn task_started(&self, _task_id: TaskId) {
-        // Implementation would track task start
+    fn task_completed(&self, task_id: TaskId) {
+        TaskMonitor::task_completed(self, task_id);
     }
     
-    fn task_completed(&self, _task_id: TaskId) {
-        // Implementation would track task completion
+    fn task_failed(&self, task_id: TaskId) {
+        TaskMonitor::task_failed(self, task_id);
     }
     
-    fn task_failed(&self, _task_id: TaskId) {
-        // Implementation would track task failure
+    fn task_retried(&self, task_id: TaskId) {
+        TaskMonitor::task_retried(self, task_id);
     }
-}
\ No newline at end of file
+    
+    fn task_executed(&self, task_name: &str, duration: Duration, succeeded: bool) {
+        TaskMonitor::task_executed(self, task_name, duration, succeeded);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_subscribers_get_their_own_reports() {
+        let monitor = TaskMonitor::new();
+        let first = monitor.subscribe();
+        let second = monitor.subscribe();
+        let task_id = TaskId::new_v4();
+        
+        monitor.task_submitted(task_id);
+        monitor.task_started(task_id);
+        monitor.task_completed(task_id);
+        
+        for receiver in [first, second] {
+            let reports: Vec<ProgressReport> = receiver.try_iter().collect();
+            assert_eq!(reports.len(), 3);
+            assert_eq!(reports[0].pending_tasks, 1);
+            assert_eq!(reports[1].running_tasks, 1);
+            assert_eq!(reports[2].completed_tasks, 1);
+            assert_eq!(reports[2].total_processed, 1);
+        }
+    }
+    
+    #[test]
+    fn test_dropped_subscriber_is_forgotten() {
+        let monitor = TaskMonitor::new();
+        let kept = monitor.subscribe();
+        drop(monitor.subscribe());
+        
+        monitor.task_submitted(TaskId::new_v4());
+        
+        assert_eq!(monitor.subscribers.lock().unwrap().len(), 1);
+        assert_eq!(kept.try_iter().count(), 1);
+        assert_eq!(monitor.get_progress_report().pending_tasks, 1);
+    }
+    
+    #[test]
+    fn test_stats_by_type() {
+        let monitor = TaskMonitor::new();
+        monitor.task_executed("resize", Duration::from_millis(10), true);
+        monitor.task_executed("resize", Duration::from_millis(30), false);
+        monitor.task_executed("upload", Duration::from_millis(5), true);
+        
+        let stats = monitor.stats_by_type();
+        assert_eq!(stats.len(), 2);
+        
+        let resize = &stats["resize"];
+        assert_eq!(resize.count, 2);
+        assert_eq!(resize.failure_count, 1);
+        assert!((resize.avg_duration_ms - 20.0).abs() < 1e-6);
+        assert!((resize.max_duration_ms - 30.0).abs() < 1e-6);
+        
+        assert_eq!(stats["upload"].failure_count, 0);
+    }
+}
@@ -0,0 +1,339 @@
+//! JSON file storage backend
+//! 
+//! Each task is written to its own `<task id>.json` file in the storage
+//! directory, so saving one task never rewrites the others. A summary of
+//! the result, without its output, is written next to it in the
+//! `summaries` subdirectory, so listing results never reads the outputs.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Storage, StorageHealth};
+use crate::error::{Result, TaskError};
+use crate::task::{TaskId, TaskMetadata, TaskQueue, TaskResult, TaskResultSummary};
+
+/// What is written to disk for a single task
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredTask {
+    result: TaskResult,
+    /// Only known while the task is pending or running; kept from the
+    /// previous save once the task has finished
+    #[serde(default)]
+    metadata: Option<TaskMetadata>,
+}
+
+/// Subdirectory of the storage directory that holds result summaries
+const SUMMARY_DIR: &str = "summaries";
+
+/// Storage backend that keeps tasks as JSON files in a directory
+pub struct JsonStorage {
+    path: PathBuf,
+}
+
+impl JsonStorage {
+    /// Open a storage directory, creating it if needed
+    pub fn new(path: &Path) -> Result<Self> {
+        fs::create_dir_all(path)
+            .map_err(|e| TaskError::io_error("create storage directory", Some(path), e))?;
+        
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+    
+    /// Load the stored result of every task, oldest submission first
+    /// 
+    /// Files that can't be read or parsed are skipped with a warning, so one
+    /// damaged file doesn't prevent the rest from being recovered.
+    pub fn load_all(&self) -> Result<Vec<(TaskId, TaskResult)>> {
+        Ok(self.load_stored()?
+            .into_iter()
+            .map(|stored| (stored.result.task_id, stored.result))
+            .collect())
+    }
+    
+    /// Read every stored task file, oldest submission first, skipping damaged ones
+    fn load_stored(&self) -> Result<Vec<StoredTask>> {
+        let mut stored_tasks = Vec::new();
+        for path in self.task_files()? {
+            match Self::read_file(&path) {
+                Ok(stored) => stored_tasks.push(stored),
+                Err(e) => log::warn!("Skipping stored task {}: {}", path.display(), e),
+            }
+        }
+        
+        stored_tasks.sort_by_key(|stored| stored.result.submitted_at);
+        Ok(stored_tasks)
+    }
+    
+    /// List the `.json` task files in the storage directory
+    fn task_files(&self) -> Result<Vec<PathBuf>> {
+        let entries = fs::read_dir(&self.path)
+            .map_err(|e| TaskError::io_error("read storage directory", Some(&self.path), e))?;
+        
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| TaskError::io_error("read storage directory", Some(&self.path), e))?
+                .path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+    
+    /// Read the summary stored for a task file
+    /// 
+    /// Task files saved before summaries were stored have none; they are
+    /// read in full and summarized instead.
+    fn read_summary(&self, task_path: &Path) -> Result<TaskResultSummary> {
+        let summary_path = self.path.join(SUMMARY_DIR).join(task_path.file_name().unwrap_or_default());
+        
+        match fs::read_to_string(&summary_path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| TaskError::serialization_error(&format!("result summary {}", summary_path.display()), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let stored = Self::read_file(task_path)?;
+                Ok(stored.result.summary(stored.metadata.map(|metadata| metadata.name)))
+            }
+            Err(e) => Err(TaskError::io_error("read result summary", Some(&summary_path), e)),
+        }
+    }
+    
+    /// Load the metadata saved for a task, including its serialized payload
+    pub fn load_metadata(&self, task_id: TaskId) -> Result<Option<TaskMetadata>> {
+        Ok(self.load(task_id)?.and_then(|stored| stored.metadata))
+    }
+    
+    fn task_path(&self, task_id: TaskId) -> PathBuf {
+        self.path.join(format!("{}.json", task_id))
+    }
+    
+    fn summary_path(&self, task_id: TaskId) -> PathBuf {
+        self.path.join(SUMMARY_DIR).join(format!("{}.json", task_id))
+    }
+    
+    /// Write to a temporary file first so a crash mid-write never leaves a
+    /// truncated file behind
+    fn write_atomically(path: &Path, contents: String) -> Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, contents)
+            .map_err(|e| TaskError::io_error("write stored task", Some(&tmp_path), e))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|e| TaskError::io_error("write stored task", Some(path), e))
+    }
+    
+    fn load(&self, task_id: TaskId) -> Result<Option<StoredTask>> {
+        match Self::read_file(&self.task_path(task_id)) {
+            Ok(stored) => Ok(Some(stored)),
+            Err(TaskError::IoError { source, .. }) if source.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    
+    fn read_file(path: &Path) -> Result<StoredTask> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| TaskError::io_error("read stored task", Some(path), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| TaskError::serialization_error(&format!("stored task {}", path.display()), e))
+    }
+}
+
+impl Storage for JsonStorage {
+    fn save_task(&self, task_id: TaskId, queue: &TaskQueue) -> Result<()> {
+        let result = queue.get_result(task_id)
+            .ok_or(TaskError::TaskNotFound(task_id))?;
+        let metadata = match queue.task_metadata(task_id) {
+            Some(metadata) => Some(metadata),
+            None => self.load_metadata(task_id)?,
+        };
+        
+        let summary = result.summary(metadata.as_ref().map(|metadata| metadata.name.clone()));
+        let contents = serde_json::to_string_pretty(&StoredTask { result, metadata })
+            .map_err(|e| TaskError::serialization_error("stored task", e))?;
+        let summary = serde_json::to_string_pretty(&summary)
+            .map_err(|e| TaskError::serialization_error("result summary", e))?;
+        
+        let summary_dir = self.path.join(SUMMARY_DIR);
+        fs::create_dir_all(&summary_dir)
+            .map_err(|e| TaskError::io_error("create storage directory", Some(&summary_dir), e))?;
+        
+        Self::write_atomically(&self.task_path(task_id), contents)?;
+        Self::write_atomically(&self.summary_path(task_id), summary)
+    }
+    
+    fn load_tasks(&self) -> Result<Vec<TaskId>> {
+        Ok(self.load_all()?.into_iter().map(|(task_id, _)| task_id).collect())
+    }
+    
+    fn delete_task(&self, task_id: TaskId) -> Result<()> {
+        // The task file goes first, so a summary is never left without one
+        for path in [self.task_path(task_id), self.summary_path(task_id)] {
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(TaskError::io_error("delete stored task", Some(&path), e)),
+            }
+        }
+        Ok(())
+    }
+    
+    /// Reads only the stored summaries, never the task files with outputs
+    fn list_results(&self) -> Result<Vec<TaskResultSummary>> {
+        let mut summaries = Vec::new();
+        for path in self.task_files()? {
+            match self.read_summary(&path) {
+                Ok(summary) => summaries.push(summary),
+                Err(e) => log::warn!("Skipping stored task {}: {}", path.display(), e),
+            }
+        }
+        
+        summaries.sort_by_key(|summary| summary.submitted_at);
+        Ok(summaries)
+    }
+    
+    fn load_full_result(&self, task_id: TaskId) -> Result<Option<TaskResult>> {
+        Ok(self.load(task_id)?.map(|stored| stored.result))
+    }
+    
+    /// Unhealthy if the storage directory is gone, not a directory, or read-only
+    fn health(&self) -> StorageHealth {
+        match fs::metadata(&self.path) {
+            Ok(metadata) if !metadata.is_dir() => StorageHealth::Unhealthy(
+                format!("{} is not a directory", self.path.display())
+            ),
+            Ok(metadata) if metadata.permissions().readonly() => StorageHealth::Unhealthy(
+                format!("{} is read-only", self.path.display())
+            ),
+            Ok(_) => StorageHealth::Healthy,
+            Err(e) => StorageHealth::Unhealthy(
+                format!("cannot access {}: {}", self.path.display(), e)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::traits::{MathTask, TaskBox};
+    use crate::task::TaskStatus;
+    
+    #[test]
+    fn test_save_and_load_all() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = JsonStorage::new(dir.path()).unwrap();
+        let queue = TaskQueue::new();
+        
+        let task = TaskBox::serializable(MathTask {
+            operation: "add".to_string(),
+            operands: vec![1.0, 2.0],
+        }).unwrap();
+        let task_id = queue.submit(task).unwrap();
+        storage.save_task(task_id, &queue).unwrap();
+        
+        // Finishing the task keeps the payload saved while it was pending
+        queue.next_task().unwrap().unwrap();
+        queue.complete_task(task_id, "3.0".to_string()).unwrap();
+        storage.save_task(task_id, &queue).unwrap();
+        
+        let loaded = storage.load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, task_id);
+        assert_eq!(loaded[0].1.status, TaskStatus::Completed);
+        assert!(storage.load_metadata(task_id).unwrap().unwrap().payload.is_some());
+        
+        storage.delete_task(task_id).unwrap();
+        assert!(storage.load_all().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_list_results_leaves_out_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = JsonStorage::new(dir.path()).unwrap();
+        
+        // One queue per task, so each one's fate doesn't depend on queue order
+        let store_task = |operation: &str, finish: &dyn Fn(&TaskQueue, TaskId)| {
+            let queue = TaskQueue::new();
+            let task_id = queue.submit(TaskBox::new(MathTask {
+                operation: operation.to_string(),
+                operands: vec![1.0, 2.0],
+            })).unwrap();
+            // Saved on submission like the system does, which keeps the name
+            storage.save_task(task_id, &queue).unwrap();
+            finish(&queue, task_id);
+            storage.save_task(task_id, &queue).unwrap();
+            task_id
+        };
+        
+        let big_output = "x".repeat(10_000);
+        let completed = store_task("add", &|queue, task_id| {
+            queue.next_task().unwrap().unwrap();
+            queue.complete_task(task_id, big_output.clone()).unwrap();
+        });
+        let failed = store_task("divide", &|queue, task_id| {
+            queue.next_task().unwrap().unwrap();
+            queue.fail_task(task_id, "Division by zero".to_string()).unwrap();
+        });
+        let pending = store_task("multiply", &|_, _| {});
+        
+        let summaries = storage.list_results().unwrap();
+        assert_eq!(summaries.len(), 3);
+        let summary = |task_id| summaries.iter().find(|summary| summary.task_id == task_id).unwrap();
+        
+        assert_eq!(summary(completed).status, TaskStatus::Completed);
+        assert_eq!(summary(completed).name.as_deref(), Some("add"));
+        assert_eq!(summary(completed).output_len, Some(big_output.len()));
+        assert!(summary(completed).duration.is_some());
+        assert_eq!(summary(failed).status, TaskStatus::Failed);
+        assert_eq!(summary(failed).output_len, None);
+        assert_eq!(summary(pending).status, TaskStatus::Pending);
+        assert!(summary(pending).started_at.is_none());
+        assert!(!serde_json::to_string(&summaries).unwrap().contains(&big_output));
+
+        
+        let full = storage.load_full_result(completed).unwrap().unwrap();
+        assert_eq!(full.output.as_deref(), Some(big_output.as_str()));
+        assert_eq!(full.status, summary(completed).status);
+        assert!(storage.load_full_result(TaskId::new_v4()).unwrap().is_none());
+        
+        // Listing reads the stored summaries, not the task files with the outputs
+        fs::write(storage.task_path(completed), "not json").unwrap();
+        assert_eq!(storage.list_results().unwrap(), summaries);
+        
+        // Without its summary, a task file is read in full instead
+        fs::remove_file(storage.summary_path(failed)).unwrap();
+        assert_eq!(storage.list_results().unwrap(), summaries);
+        
+        storage.delete_task(pending).unwrap();
+        assert!(!storage.summary_path(pending).exists());
+        assert_eq!(storage.list_results().unwrap().len(), 2);
+    }
+    
+    #[test]
+    fn test_health_follows_storage_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tasks");
+        let storage = JsonStorage::new(&path).unwrap();
+        assert_eq!(storage.health(), StorageHealth::Healthy);
+        
+        fs::remove_dir(&path).unwrap();
+        assert!(!storage.health().is_healthy());
+        
+        fs::create_dir(&path).unwrap();
+        assert_eq!(storage.health(), StorageHealth::Healthy);
+    }
+    
+    #[test]
+    fn test_corrupt_file_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = JsonStorage::new(dir.path()).unwrap();
+        fs::write(dir.path().join("broken.json"), "not json").unwrap();
+        
+        assert!(storage.load_all().unwrap().is_empty());
+    }
+}
@@ -8,42 +8,46 @@ pub mod memory_store;
 pub use json_store::JsonStorage;
 pub use memory_store::MemoryStorage;
 
-use crate::task::{TaskId, TaskQueue};
+use crate::task::{TaskId, TaskQueue, TaskResult, TaskResultSummary};
 use crate::error::Result;
 
-/// Trait for storage backends
-pub trait Storage: Send + Sync {
-    fn save_task(&self, task_id: TaskId, queue: &TaskQueue) -> Result<()>;
-    fn load_tasks(&self) -> Result<Vec<TaskId>>;
-    fn delete_task(&self, task_id: TaskId) -> Result<()>;
-}
-
-pub struct JsonStorage {
-    path: std::path::PathBuf,
+/// Whether a storage backend can currently persist tasks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageHealth {
+    /// Tasks can be saved
+    Healthy,
+    /// Tasks can't be saved right now; the message says why
+    Unhealthy(String),
 }
 
-impl JsonStorage {
-    pub fn new(path: &std::path::Path) -> Result<Self> {
-        Ok(Self {
-            path: path.to_path_buf(),
-        })
+impl StorageHealth {
+    /// Whether tasks can be saved
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, StorageHealth::Healthy)
     }
 }
 
-impl Storage for JsonStorage {
-    fn save_task(&self, _task_id: TaskId, _queue: &TaskQueue) -> Result<()> {
-        // Placeholder implementation
-        Ok(())
-    }
+/// Trait for storage backends
+pub trait Storage: Send + Sync {
+    fn save_task(&self, task_id: TaskId, queue: &TaskQueue) -> Result<()>;
+    fn load_tasks(&self) -> Result<Vec<TaskId>>;
+    fn delete_task(&self, task_id: TaskId) -> Result<()>;
     
-    fn load_tasks(&self) -> Result<Vec<TaskId>> {
-        // Placeholder implementation
-        Ok(Vec::new())
-    }
+    /// Summarize every stored result, oldest submission first
+    /// 
+    /// Summaries leave out task outputs, so listing stays cheap however large
+    /// they are; use `load_full_result` for the complete record.
+    fn list_results(&self) -> Result<Vec<TaskResultSummary>>;
     
-    fn delete_task(&self, _task_id: TaskId) -> Result<()> {
-        // Placeholder implementation
-        Ok(())
+    /// Load the complete stored result of a task, including its output
+    fn load_full_result(&self, task_id: TaskId) -> Result<Option<TaskResult>>;
+    
+    /// Check whether the backend can currently persist tasks
+    /// 
+    /// Called before every submission, so it should be cheap. Backends that
+    /// can't fail report `Healthy`, which is the default.
+    fn health(&self) -> StorageHealth {
+        StorageHealth::Healthy
     }
 }
 
@@ -72,4 +76,14 @@ impl Storage for MemoryStorage {
         // Placeholder implementation
         Ok(())
     }
+    
+    fn list_results(&self) -> Result<Vec<TaskResultSummary>> {
+        // Placeholder implementation
+        Ok(Vec::new())
+    }
+    
+    fn load_full_result(&self, _task_id: TaskId) -> Result<Option<TaskResult>> {
+        // Placeholder implementation
+        Ok(None)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+//! Task id generation
+//!
+//! The queue asks an `IdGenerator` for the id of every submitted task.
+//! Real queues use random UUIDs; tests can plug in `SequentialIdGenerator`
+//! to get ids they can predict and assert on.
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::TaskId;
+
+/// Source of ids for newly submitted tasks
+///
+/// Generators are shared by every thread submitting to a queue, so they
+/// take `&self` and must be thread-safe. Every id they return must be unique.
+pub trait IdGenerator: Debug + Send + Sync {
+    /// Get the id for the next submitted task
+    fn next_id(&self) -> TaskId;
+
+    /// Record that `id` is already taken, e.g. by a task recovered from storage
+    ///
+    /// Generators that could hand out `id` later must skip it. Random
+    /// generators can rely on collisions being vanishingly unlikely, so the
+    /// default does nothing.
+    fn mark_used(&self, _id: TaskId) {}
+}
+
+/// Generates random (version 4) UUIDs; the default for every queue
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    fn next_id(&self) -> TaskId {
+        TaskId::new_v4()
+    }
+}
+
+/// Generates ids counting up from 1, for reproducible tests
+///
+/// The n-th id handed out is `TaskId::from_u128(n)`, so the first task
+/// submitted to a fresh queue is `00000000-0000-0000-0000-000000000001`.
+/// Ids marked as used move the counter past them, so a queue recovered
+/// from storage continues after the highest recovered id.
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first id is 1
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Create a generator whose first id is `first`
+    pub fn starting_at(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first),
+        }
+    }
+}
+
+impl Default for SequentialIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> TaskId {
+        TaskId::from_u128(self.next.fetch_add(1, Ordering::Relaxed) as u128)
+    }
+
+    fn mark_used(&self, id: TaskId) {
+        // Ids past the counter's range (such as random ones) can never be reached
+        if let Ok(n) = u64::try_from(id.as_u128()) {
+            self.next.fetch_max(n.saturating_add(1), Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_sequential_ids() {
+        let generator = SequentialIdGenerator::new();
+        assert_eq!(generator.next_id().to_string(), "00000000-0000-0000-0000-000000000001");
+        assert_eq!(generator.next_id(), TaskId::from_u128(2));
+
+        let generator = SequentialIdGenerator::starting_at(100);
+        assert_eq!(generator.next_id(), TaskId::from_u128(100));
+    }
+
+    #[test]
+    fn test_sequential_ids_skip_used_ids() {
+        let generator = SequentialIdGenerator::new();
+        generator.mark_used(TaskId::from_u128(5));
+        generator.mark_used(TaskId::from_u128(3));
+        generator.mark_used(TaskId::new_v4());
+        assert_eq!(generator.next_id(), TaskId::from_u128(6));
+    }
+
+    #[test]
+    fn test_sequential_ids_unique_across_threads() {
+        let generator = Arc::new(SequentialIdGenerator::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..250).map(|_| generator.next_id()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let ids: HashSet<TaskId> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+        assert_eq!(ids.len(), 1000);
+        assert!(ids.contains(&TaskId::from_u128(1)));
+        assert!(ids.contains(&TaskId::from_u128(1000)));
+    }
+}
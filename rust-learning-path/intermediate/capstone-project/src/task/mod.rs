@@ -5,11 +5,14 @@
 //! - Task queue for managing pending and completed tasks
 //! - Task registry for persistent storage
 //! - Task result handling and status tracking
+//! - Task id generation
 
+pub mod id;
 pub mod queue;
 pub mod registry;
 pub mod traits;
 
+pub use id::{IdGenerator, RandomIdGenerator, SequentialIdGenerator};
 pub use queue::TaskQueue;
 pub use registry::TaskRegistry;
 pub use traits::{Task, TaskResult, TaskStatus, TaskId};
@@ -135,6 +138,41 @@ impl TaskResult {
     pub fn is_successful(&self) -> bool {
         self.status == TaskStatus::Completed
     }
+    
+    /// Summarize the result without its output
+    pub fn summary(&self, name: Option<String>) -> TaskResultSummary {
+        TaskResultSummary {
+            task_id: self.task_id,
+            name,
+            status: self.status.clone(),
+            submitted_at: self.submitted_at,
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+            duration: self.duration,
+            output_len: self.output.as_ref().map(String::len),
+        }
+    }
+}
+
+/// A task result without its output, for listing many tasks cheaply
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskResultSummary {
+    /// Unique identifier for the task
+    pub task_id: TaskId,
+    /// Name of the task, if its metadata is known
+    pub name: Option<String>,
+    /// Current status of the task
+    pub status: TaskStatus,
+    /// When the task was submitted
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+    /// When the task started executing
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the task completed (successfully or with error)
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// How long the task took to execute
+    pub duration: Option<Duration>,
+    /// Length of the output in bytes, if the task produced one
+    pub output_len: Option<usize>,
 }
 
 /// Metadata associated with a task
@@ -154,6 +192,24 @@ pub struct TaskMetadata {
     pub tags: Vec<String>,
     /// Custom metadata
     pub custom_data: HashMap<String, String>,
+    /// Serialized form of the task, kept so it can be rebuilt after a restart
+    #[serde(default)]
+    pub payload: Option<TaskPayload>,
+    /// Tasks that must complete successfully before this one may run
+    #[serde(default)]
+    pub depends_on: Vec<TaskId>,
+}
+
+/// A task serialized together with the name of its type
+/// 
+/// The type name is used to find the right decoder when the task is
+/// loaded back from storage (see `traits::register_task_type`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskPayload {
+    /// Type the task was serialized from
+    pub task_type: String,
+    /// The task itself, as JSON
+    pub data: String,
 }
 
 impl Default for TaskMetadata {
@@ -166,6 +222,8 @@ impl Default for TaskMetadata {
             retry_count: 0,
             tags: Vec::new(),
             custom_data: HashMap::new(),
+            payload: None,
+            depends_on: Vec::new(),
         }
     }
 }
@@ -186,6 +244,9 @@ impl TaskMetadata {
     }
     
     /// Set the timeout
+    /// 
+    /// Once it passes the task is marked `TimedOut` and its worker moves on,
+    /// but the task itself runs until `execute` returns.
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
@@ -203,6 +264,12 @@ impl TaskMetadata {
         self
     }
     
+    /// Add a task that must complete before this one runs
+    pub fn with_dependency(mut self, task_id: TaskId) -> Self {
+        self.depends_on.push(task_id);
+        self
+    }
+    
     /// Add custom data
     pub fn with_custom_data(mut self, key: &str, value: &str) -> Self {
         self.custom_data.insert(key.to_string(), value.to_string());
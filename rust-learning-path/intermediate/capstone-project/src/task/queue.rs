@@ -7,11 +7,12 @@
 //! - Interior mutability patterns
 
 use std::collections::{BinaryHeap, HashMap, VecDeque};
-use std::sync::{Arc, Mutex, Condvar};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, MutexGuard, Condvar};
 use std::cmp::Ordering;
 use std::time::{Duration, Instant};
 
-use super::{TaskId, TaskResult, TaskStatus, TaskBox};
+use super::{IdGenerator, RandomIdGenerator, TaskId, TaskMetadata, TaskResult, TaskStatus, TaskBox};
 use crate::error::{TaskError, Result};
 
 /// A thread-safe task queue that manages pending and completed tasks
@@ -26,8 +27,8 @@ pub struct TaskQueue {
     /// Pending tasks ordered by priority
     pending: Arc<Mutex<BinaryHeap<PriorityTask>>>,
     
-    /// Currently running tasks
-    running: Arc<Mutex<HashMap<TaskId, TaskBox>>>,
+    /// Metadata of currently running tasks (the task itself is owned by the worker)
+    running: Arc<Mutex<HashMap<TaskId, TaskMetadata>>>,
     
     /// Completed task results
     completed: Arc<Mutex<HashMap<TaskId, TaskResult>>>,
@@ -38,8 +39,28 @@ pub struct TaskQueue {
     /// Condition variable for notifying waiters of completed tasks
     task_completed: Arc<Condvar>,
     
+    /// Condition variable for notifying blocked submitters that a slot opened up
+    space_available: Arc<Condvar>,
+    
+    /// Maximum number of pending tasks, or `None` for unbounded
+    capacity: Option<usize>,
+    
+    /// Set once the queue stops accepting new submissions
+    closed: Arc<AtomicBool>,
+    
     /// Queue statistics
     stats: Arc<Mutex<QueueStats>>,
+    
+    /// Source of ids for submitted tasks
+    id_generator: Arc<dyn IdGenerator>,
+}
+
+/// Whether a pending task's dependencies allow it to run
+enum DependencyState {
+    Ready,
+    Waiting,
+    /// A dependency can never complete; the message explains which one
+    Failed(String),
 }
 
 /// Wrapper for tasks with priority ordering
@@ -79,6 +100,8 @@ pub struct QueueStats {
     pub total_completed: u64,
     pub total_failed: u64,
     pub total_cancelled: u64,
+    pub total_retried: u64,
+    pub total_timed_out: u64,
     pub current_pending: usize,
     pub current_running: usize,
 }
@@ -92,25 +115,129 @@ impl TaskQueue {
             completed: Arc::new(Mutex::new(HashMap::new())),
             task_available: Arc::new(Condvar::new()),
             task_completed: Arc::new(Condvar::new()),
+            space_available: Arc::new(Condvar::new()),
+            capacity: None,
+            closed: Arc::new(AtomicBool::new(false)),
             stats: Arc::new(Mutex::new(QueueStats::default())),
+            id_generator: Arc::new(RandomIdGenerator),
         }
     }
     
+    /// Create a queue that holds at most `max` pending tasks
+    /// 
+    /// Once full, `submit` fails with `TaskError::QueueFull` and
+    /// `submit_blocking` waits for a worker to take a task.
+    pub fn with_capacity(max: usize) -> Self {
+        Self {
+            capacity: Some(max),
+            ..Self::new()
+        }
+    }
+    
+    /// Use `generator` for the ids of tasks submitted from now on
+    /// 
+    /// Queues use random ids by default; tests can pass a
+    /// `SequentialIdGenerator` to know every task's id in advance.
+    pub fn with_id_generator(mut self, generator: impl IdGenerator + 'static) -> Self {
+        self.id_generator = Arc::new(generator);
+        self
+    }
+    
+    /// Maximum number of pending tasks, if the queue is bounded
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+    
+    /// Stop accepting new tasks
+    /// 
+    /// Tasks already in the queue can still be taken, retried, and finished.
+    /// Blocked `submit_blocking` callers wake up and fail with `TaskError::QueueClosed`.
+    pub fn close(&self) {
+        // Take the pending lock so a blocked submitter can't miss the wakeup
+        let _pending = self.pending.lock();
+        self.closed.store(true, AtomicOrdering::SeqCst);
+        self.space_available.notify_all();
+    }
+    
+    /// Whether `close` has been called
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(AtomicOrdering::SeqCst)
+    }
+    
     /// Submit a new task to the queue
+    /// 
+    /// Fails with `TaskError::QueueFull` if the queue is at capacity and
+    /// with `TaskError::QueueClosed` once the queue has been closed.
     pub fn submit(&self, task: TaskBox) -> Result<TaskId> {
-        let task_id = TaskId::new_v4();
-        let priority_task = PriorityTask {
-            task,
-            task_id,
-            submitted_at: Instant::now(),
+        let pending = self.pending.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+        
+        if self.is_closed() {
+            return Err(TaskError::QueueClosed);
+        }
+        if let Some(capacity) = self.capacity {
+            if pending.len() >= capacity {
+                return Err(TaskError::QueueFull { capacity });
+            }
+        }
+        
+        self.enqueue(pending, task)
+    }
+    
+    /// Submit a new task, waiting for space if the queue is at capacity
+    /// 
+    /// With a timeout, fails with `TaskError::QueueFull` if no slot opens up
+    /// in time. Without one, waits indefinitely.
+    pub fn submit_blocking(&self, task: TaskBox, timeout: Option<Duration>) -> Result<TaskId> {
+        let pending = self.pending.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+        
+        if self.is_closed() {
+            return Err(TaskError::QueueClosed);
+        }
+        
+        let Some(capacity) = self.capacity else {
+            return self.enqueue(pending, task);
         };
         
-        // Add to pending queue
-        {
-            let mut pending = self.pending.lock()
-                .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
-            pending.push(priority_task);
+        let is_full = |pending: &mut BinaryHeap<PriorityTask>| {
+            pending.len() >= capacity && !self.is_closed()
+        };
+        let pending = if let Some(timeout) = timeout {
+            let (pending, wait_result) = self.space_available.wait_timeout_while(pending, timeout, is_full)
+                .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?;
+            if wait_result.timed_out() && pending.len() >= capacity {
+                return Err(TaskError::QueueFull { capacity });
+            }
+            pending
+        } else {
+            self.space_available.wait_while(pending, is_full)
+                .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?
+        };
+        
+        if self.is_closed() {
+            return Err(TaskError::QueueClosed);
         }
+        self.enqueue(pending, task)
+    }
+    
+    /// Add a task to the pending queue while the caller holds its lock
+    /// 
+    /// The result and statistics are recorded before the task becomes
+    /// visible, so a worker can never pick up a task that has no result yet.
+    fn enqueue(&self, mut pending: MutexGuard<'_, BinaryHeap<PriorityTask>>, task: TaskBox) -> Result<TaskId> {
+        // Create initial result, refusing dependencies the queue has never seen
+        // before an id is handed out for the task
+        let task_id = {
+            let mut completed = self.completed.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+            if let Some(unknown) = task.metadata().depends_on.iter().find(|id| !completed.contains_key(id)) {
+                return Err(TaskError::TaskNotFound(*unknown));
+            }
+            let task_id = self.id_generator.next_id();
+            completed.insert(task_id, TaskResult::new(task_id));
+            task_id
+        };
         
         // Update statistics
         {
@@ -120,13 +247,13 @@ impl TaskQueue {
             stats.current_pending += 1;
         }
         
-        // Create initial result
-        let result = TaskResult::new(task_id);
-        {
-            let mut completed = self.completed.lock()
-                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
-            completed.insert(task_id, result);
-        }
+        // Add to pending queue
+        pending.push(PriorityTask {
+            task,
+            task_id,
+            submitted_at: Instant::now(),
+        });
+        drop(pending);
         
         // Notify waiting workers
         self.task_available.notify_one();
@@ -134,69 +261,155 @@ impl TaskQueue {
         Ok(task_id)
     }
     
-    /// Get the next task to execute (blocks if no tasks available)
+    /// Get the next task whose dependencies have all completed, if any
+    /// 
+    /// Ownership of the task moves to the caller; only its metadata stays
+    /// in the running set until it is completed, failed, or requeued.
     pub fn next_task(&self) -> Result<Option<(TaskId, TaskBox)>> {
+        let pending = self.pending.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+        self.take_ready(pending)
+    }
+    
+    /// Wait for a task to become ready and take it
+    /// 
+    /// Returns `None` if nothing became ready before the timeout.
+    pub fn wait_for_task(&self, timeout: Option<Duration>) -> Result<Option<(TaskId, TaskBox)>> {
         let mut pending = self.pending.lock()
             .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
         
-        if let Some(priority_task) = pending.pop() {
-            // Move task to running state
-            let task_id = priority_task.task_id;
-            let task = priority_task.task;
-            
-            {
-                let mut running = self.running.lock()
-                    .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
-                running.insert(task_id, task);
-            }
-            
-            // Update statistics
-            {
-                let mut stats = self.stats.lock()
-                    .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
-                stats.current_pending -= 1;
-                stats.current_running += 1;
-            }
-            
-            // Update task result status
-            self.update_task_status(task_id, TaskStatus::Running)?;
-            
-            // Get the task back from running queue to return it
-            let running = self.running.lock()
-                .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
-            if let Some(task) = running.get(&task_id) {
-                // We need to clone the task for execution while keeping it in running state
-                // This is a limitation of our current design - in a real system you might
-                // handle this differently
-                Ok(Some((task_id, TaskBox::new(task.downcast_ref::<crate::task::traits::MathTask>().unwrap().clone()))))
-            } else {
-                Err(TaskError::TaskNotFound(task_id))
-            }
+        if let Some(ready) = self.take_ready_locked(&mut pending)? {
+            drop(pending);
+            self.space_available.notify_one();
+            return Ok(Some(ready));
+        }
+        
+        // Pending tasks may all be waiting on dependencies, so sleep until
+        // something is submitted or finishes instead of spinning
+        let pending = if let Some(timeout) = timeout {
+            self.task_available.wait_timeout(pending, timeout)
+                .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?
+                .0
         } else {
-            Ok(None)
+            self.task_available.wait(pending)
+                .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?
+        };
+        
+        self.take_ready(pending)
+    }
+    
+    /// Take the highest priority ready task and release the pending lock
+    fn take_ready(&self, mut pending: MutexGuard<'_, BinaryHeap<PriorityTask>>) -> Result<Option<(TaskId, TaskBox)>> {
+        let ready = self.take_ready_locked(&mut pending)?;
+        drop(pending);
+        
+        if ready.is_some() {
+            // A slot opened up for blocked submitters
+            self.space_available.notify_one();
         }
+        Ok(ready)
     }
     
-    /// Wait for the next task to become available
-    pub fn wait_for_task(&self, timeout: Option<Duration>) -> Result<Option<(TaskId, TaskBox)>> {
-        let pending = self.pending.lock()
-            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+    /// Pop tasks in priority order until one has all of its dependencies completed
+    /// 
+    /// Tasks still waiting on a dependency go back into the queue unchanged.
+    /// Tasks with a dependency that failed, timed out, was cancelled, or is
+    /// unknown are removed and marked `Failed`.
+    fn take_ready_locked(&self, pending: &mut BinaryHeap<PriorityTask>) -> Result<Option<(TaskId, TaskBox)>> {
+        let mut blocked = Vec::new();
+        let mut dependents_failed = false;
+        let mut ready = None;
         
-        let pending = if pending.is_empty() {
-            if let Some(timeout) = timeout {
-                self.task_available.wait_timeout(pending, timeout)
-                    .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?
-                    .0
-            } else {
-                self.task_available.wait(pending)
-                    .map_err(|_| TaskError::LockError("Condition variable wait failed".to_string()))?
+        while let Some(priority_task) = pending.pop() {
+            match self.dependency_state(priority_task.task.metadata())? {
+                DependencyState::Ready => {
+                    ready = Some(priority_task);
+                    break;
+                }
+                DependencyState::Waiting => blocked.push(priority_task),
+                DependencyState::Failed(error) => {
+                    self.fail_pending(priority_task.task_id, error)?;
+                    dependents_failed = true;
+                }
             }
-        } else {
-            pending
+        }
+        pending.extend(blocked);
+        
+        if dependents_failed {
+            self.task_completed.notify_all();
+            self.space_available.notify_all();
+        }
+        
+        let Some(priority_task) = ready else {
+            return Ok(None);
         };
         
-        drop(pending);
-        self.next_task()
+        // Move task to running state
+        let task_id = priority_task.task_id;
+        let task = priority_task.task;
+        
+        {
+            let mut running = self.running.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
+            running.insert(task_id, task.metadata().clone());
+        }
+        
+        // Update statistics
+        {
+            let mut stats = self.stats.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+            stats.current_pending -= 1;
+            stats.current_running += 1;
+        }
+        
+        // Update task result status
+        self.update_task_status(task_id, TaskStatus::Running)?;
+        
+        Ok(Some((task_id, task)))
+    }
+    
+    /// Check the results of a task's dependencies
+    fn dependency_state(&self, metadata: &TaskMetadata) -> Result<DependencyState> {
+        if metadata.depends_on.is_empty() {
+            return Ok(DependencyState::Ready);
+        }
+        
+        let completed = self.completed.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+        
+        let mut state = DependencyState::Ready;
+        for dependency in &metadata.depends_on {
+            match completed.get(dependency).map(|result| &result.status) {
+                Some(TaskStatus::Completed) => {}
+                Some(TaskStatus::Pending) | Some(TaskStatus::Running) => state = DependencyState::Waiting,
+                Some(status) => {
+                    return Ok(DependencyState::Failed(format!(
+                        "Dependency {} did not complete ({:?})", dependency, status
+                    )));
+                }
+                None => {
+                    return Ok(DependencyState::Failed(format!("Dependency {} not found", dependency)));
+                }
+            }
+        }
+        Ok(state)
+    }
+    
+    /// Mark a task that was removed from the pending queue as failed
+    fn fail_pending(&self, task_id: TaskId, error: String) -> Result<()> {
+        {
+            let mut completed = self.completed.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+            if let Some(result) = completed.get_mut(&task_id) {
+                result.mark_failed(error);
+            }
+        }
+        
+        let mut stats = self.stats.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+        stats.current_pending -= 1;
+        stats.total_failed += 1;
+        Ok(())
     }
     
     /// Mark a task as completed with a result
@@ -227,12 +440,147 @@ impl TaskQueue {
             stats.total_completed += 1;
         }
         
-        // Notify waiters
+        // Notify waiters, and workers whose pending tasks may depend on this one
         self.task_completed.notify_all();
+        self.task_available.notify_all();
         
         Ok(())
     }
     
+    /// Put a running task back into the pending queue so it can be attempted again
+    /// 
+    /// The task keeps its original id, so waiters see a single result that
+    /// only becomes terminal once the final attempt finishes.
+    pub fn requeue_task(&self, task_id: TaskId, task: TaskBox) -> Result<()> {
+        {
+            let mut running = self.running.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
+            if running.remove(&task_id).is_none() {
+                return Err(TaskError::invalid_state(task_id, "not running", "requeue"));
+            }
+        }
+        
+        // Reset the result back to pending
+        {
+            let mut completed = self.completed.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+            if let Some(result) = completed.get_mut(&task_id) {
+                result.status = TaskStatus::Pending;
+            }
+        }
+        
+        {
+            let mut pending = self.pending.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+            pending.push(PriorityTask {
+                task,
+                task_id,
+                submitted_at: Instant::now(),
+            });
+        }
+        
+        // Update statistics
+        {
+            let mut stats = self.stats.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+            stats.current_running -= 1;
+            stats.current_pending += 1;
+            stats.total_retried += 1;
+        }
+        
+        self.task_available.notify_one();
+        
+        Ok(())
+    }
+    
+    /// Put a task loaded from storage back into the pending queue
+    /// 
+    /// The task keeps its stored id and submission time, and the id
+    /// generator will not hand that id out again. A task that was
+    /// running when the previous process stopped starts over as pending.
+    /// Restoring ignores the queue's capacity so no recovered work is lost.
+    pub fn restore_pending(&self, task_id: TaskId, task: TaskBox, mut result: TaskResult) -> Result<()> {
+        self.id_generator.mark_used(task_id);
+        result.status = TaskStatus::Pending;
+        result.started_at = None;
+        
+        let mut pending = self.pending.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+        
+        {
+            let mut completed = self.completed.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+            completed.insert(task_id, result);
+        }
+        
+        {
+            let mut stats = self.stats.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+            stats.total_submitted += 1;
+            stats.current_pending += 1;
+        }
+        
+        pending.push(PriorityTask {
+            task,
+            task_id,
+            submitted_at: Instant::now(),
+        });
+        drop(pending);
+        
+        self.task_available.notify_one();
+        
+        Ok(())
+    }
+    
+    /// Record the result of a task that finished before a restart
+    /// 
+    /// Like `restore_pending`, this keeps the id generator from reusing its id.
+    pub fn restore_result(&self, result: TaskResult) -> Result<()> {
+        if !result.is_terminal() {
+            return Err(TaskError::invalid_state(result.task_id, &format!("{:?}", result.status), "restore as finished"));
+        }
+        self.id_generator.mark_used(result.task_id);
+        
+        {
+            let mut stats = self.stats.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+            stats.total_submitted += 1;
+            match result.status {
+                TaskStatus::Completed => stats.total_completed += 1,
+                TaskStatus::Failed => stats.total_failed += 1,
+                TaskStatus::Cancelled => stats.total_cancelled += 1,
+                TaskStatus::TimedOut => stats.total_timed_out += 1,
+                TaskStatus::Pending | TaskStatus::Running => {}
+            }
+        }
+        
+        let mut completed = self.completed.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+        completed.insert(result.task_id, result);
+        
+        Ok(())
+    }
+    
+    /// Get the metadata of a task that is still pending or running
+    pub fn task_metadata(&self, task_id: TaskId) -> Option<TaskMetadata> {
+        {
+            let pending = self.pending.lock().ok()?;
+            if let Some(priority_task) = pending.iter().find(|t| t.task_id == task_id) {
+                return Some(priority_task.task.metadata().clone());
+            }
+        }
+        
+        let running = self.running.lock().ok()?;
+        running.get(&task_id).cloned()
+    }
+    
+    /// Get the ids of every task the queue has a result for
+    pub fn task_ids(&self) -> Result<Vec<TaskId>> {
+        let completed = self.completed.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+        Ok(completed.keys().copied().collect())
+    }
+    
     /// Mark a task as failed with an error
     pub fn fail_task(&self, task_id: TaskId, error: String) -> Result<()> {
         // Remove from running queue
@@ -261,8 +609,44 @@ impl TaskQueue {
             stats.total_failed += 1;
         }
         
-        // Notify waiters
+        // Notify waiters, and workers whose pending tasks may depend on this one
         self.task_completed.notify_all();
+        self.task_available.notify_all();
+        
+        Ok(())
+    }
+    
+    /// Mark a running task as timed out
+    pub fn timeout_task(&self, task_id: TaskId) -> Result<()> {
+        // Remove from running queue
+        {
+            let mut running = self.running.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
+            running.remove(&task_id);
+        }
+        
+        // Update result
+        {
+            let mut completed = self.completed.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire completed queue lock".to_string()))?;
+            if let Some(result) = completed.get_mut(&task_id) {
+                result.mark_timed_out();
+            } else {
+                return Err(TaskError::TaskNotFound(task_id));
+            }
+        }
+        
+        // Update statistics
+        {
+            let mut stats = self.stats.lock()
+                .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+            stats.current_running -= 1;
+            stats.total_timed_out += 1;
+        }
+        
+        // Notify waiters, and workers whose pending tasks may depend on this one
+        self.task_completed.notify_all();
+        self.task_available.notify_all();
         
         Ok(())
     }
@@ -355,6 +739,7 @@ impl TaskQueue {
                 }
                 
                 self.task_completed.notify_all();
+                self.space_available.notify_one();
                 return Ok(true);
             }
         }
@@ -377,6 +762,31 @@ impl TaskQueue {
         Ok(pending.len())
     }
     
+    /// Get the number of tasks that are pending or running
+    /// 
+    /// Read from the statistics, which move a task between pending and
+    /// running in one step, so a task being retried is never missed.
+    pub fn outstanding_count(&self) -> Result<usize> {
+        let stats = self.stats.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire stats lock".to_string()))?;
+        Ok(stats.current_pending + stats.current_running)
+    }
+    
+    /// Count the pending and running tasks whose metadata matches `predicate`
+    pub fn count_outstanding<F>(&self, predicate: F) -> Result<usize>
+    where
+        F: Fn(&TaskMetadata) -> bool,
+    {
+        let pending = self.pending.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire pending queue lock".to_string()))?;
+        let running = self.running.lock()
+            .map_err(|_| TaskError::LockError("Failed to acquire running queue lock".to_string()))?;
+        
+        let pending_matches = pending.iter().filter(|t| predicate(t.task.metadata())).count();
+        let running_matches = running.values().filter(|metadata| predicate(metadata)).count();
+        Ok(pending_matches + running_matches)
+    }
+    
     /// Get the number of running tasks
     pub fn running_count(&self) -> Result<usize> {
         let running = self.running.lock()
@@ -431,7 +841,11 @@ impl Clone for TaskQueue {
             completed: Arc::clone(&self.completed),
             task_available: Arc::clone(&self.task_available),
             task_completed: Arc::clone(&self.task_completed),
+            space_available: Arc::clone(&self.space_available),
+            capacity: self.capacity,
+            closed: Arc::clone(&self.closed),
             stats: Arc::clone(&self.stats),
+            id_generator: Arc::clone(&self.id_generator),
         }
     }
 }
@@ -440,6 +854,7 @@ impl Clone for TaskQueue {
 mod tests {
     use super::*;
     use crate::task::traits::{MathTask, Task};
+    use crate::task::SequentialIdGenerator;
     use std::thread;
     use std::time::Duration;
     
@@ -510,6 +925,163 @@ mod tests {
         assert_eq!(retrieved_id, high_id);
     }
     
+    #[test]
+    fn test_requeue_task() {
+        let queue = TaskQueue::new();
+        let task = MathTask {
+            operation: "add".to_string(),
+            operands: vec![1.0, 2.0],
+        };
+        
+        let task_id = queue.submit(TaskBox::new(task)).unwrap();
+        let (id, task) = queue.next_task().unwrap().unwrap();
+        assert_eq!(queue.running_count().unwrap(), 1);
+        
+        queue.requeue_task(id, task).unwrap();
+        assert_eq!(queue.running_count().unwrap(), 0);
+        assert_eq!(queue.pending_count().unwrap(), 1);
+        assert_eq!(queue.get_result(task_id).unwrap().status, TaskStatus::Pending);
+        assert_eq!(queue.stats().unwrap().total_retried, 1);
+        
+        // The same id comes back out
+        let (retried_id, _) = queue.next_task().unwrap().unwrap();
+        assert_eq!(retried_id, task_id);
+    }
+    
+    fn add_task() -> TaskBox {
+        TaskBox::new(MathTask {
+            operation: "add".to_string(),
+            operands: vec![1.0, 2.0],
+        })
+    }
+    
+    #[test]
+    fn test_bounded_queue_rejects_when_full() {
+        let queue = TaskQueue::with_capacity(2);
+        queue.submit(add_task()).unwrap();
+        queue.submit(add_task()).unwrap();
+        
+        match queue.submit(add_task()) {
+            Err(TaskError::QueueFull { capacity }) => assert_eq!(capacity, 2),
+            other => panic!("Expected QueueFull, got {:?}", other),
+        }
+        assert_eq!(queue.pending_count().unwrap(), 2);
+        assert_eq!(queue.stats().unwrap().total_submitted, 2);
+        
+        // Taking a task frees a slot
+        queue.next_task().unwrap().unwrap();
+        assert!(queue.submit(add_task()).is_ok());
+    }
+    
+    #[test]
+    fn test_submit_blocking_waits_for_space() {
+        let queue = TaskQueue::with_capacity(2);
+        queue.submit(add_task()).unwrap();
+        queue.submit(add_task()).unwrap();
+        
+        // Nothing consumes the queue, so a short wait gives up
+        let timed_out = queue.submit_blocking(add_task(), Some(Duration::from_millis(20)));
+        assert!(matches!(timed_out, Err(TaskError::QueueFull { .. })));
+        
+        // A consumer takes a task shortly after the submitter starts waiting
+        let consumer = {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                queue.next_task().unwrap().unwrap();
+            })
+        };
+        
+        let task_id = queue.submit_blocking(add_task(), Some(Duration::from_secs(5))).unwrap();
+        consumer.join().unwrap();
+        
+        assert_eq!(queue.pending_count().unwrap(), 2);
+        assert_eq!(queue.get_result(task_id).unwrap().status, TaskStatus::Pending);
+    }
+    
+    fn dependent_task(dependency: TaskId) -> TaskBox {
+        let mut task = add_task();
+        task.metadata_mut().depends_on.push(dependency);
+        task
+    }
+    
+    #[test]
+    fn test_dependent_waits_for_dependency() {
+        let queue = TaskQueue::new();
+        let a = queue.submit(add_task()).unwrap();
+        let b = queue.submit(dependent_task(a)).unwrap();
+        
+        assert_eq!(queue.next_task().unwrap().unwrap().0, a);
+        
+        // B stays queued while A is running
+        assert!(queue.next_task().unwrap().is_none());
+        assert_eq!(queue.pending_count().unwrap(), 1);
+        
+        queue.complete_task(a, "3".to_string()).unwrap();
+        assert_eq!(queue.next_task().unwrap().unwrap().0, b);
+    }
+    
+    #[test]
+    fn test_dependent_fails_with_dependency() {
+        let queue = TaskQueue::new();
+        let a = queue.submit(add_task()).unwrap();
+        let b = queue.submit(dependent_task(a)).unwrap();
+        
+        queue.next_task().unwrap().unwrap();
+        queue.fail_task(a, "boom".to_string()).unwrap();
+        
+        assert!(queue.next_task().unwrap().is_none());
+        let result = queue.get_result(b).unwrap();
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert!(result.error.unwrap().contains(&a.to_string()));
+        assert_eq!(queue.outstanding_count().unwrap(), 0);
+        assert_eq!(queue.stats().unwrap().total_failed, 2);
+    }
+    
+    #[test]
+    fn test_unknown_dependency_rejected() {
+        let queue = TaskQueue::new();
+        let unknown = TaskId::new_v4();
+        
+        assert!(matches!(queue.submit(dependent_task(unknown)), Err(TaskError::TaskNotFound(id)) if id == unknown));
+        assert_eq!(queue.pending_count().unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_sequential_task_ids() {
+        let queue = TaskQueue::new().with_id_generator(SequentialIdGenerator::new());
+        
+        let ids: Vec<TaskId> = (0..3).map(|_| queue.submit(add_task()).unwrap()).collect();
+        assert_eq!(ids, vec![TaskId::from_u128(1), TaskId::from_u128(2), TaskId::from_u128(3)]);
+        assert_eq!(queue.get_result(TaskId::from_u128(2)).unwrap().task_id, ids[1]);
+        
+        // Ids are known before submission, so dependencies can name them directly
+        let dependent_id = queue.submit(dependent_task(TaskId::from_u128(3))).unwrap();
+        assert_eq!(dependent_id, TaskId::from_u128(4));
+        
+        // Rejected submissions don't use up an id
+        assert!(queue.submit(dependent_task(TaskId::from_u128(99))).is_err());
+        assert_eq!(queue.submit(add_task()).unwrap(), TaskId::from_u128(5));
+    }
+    
+    #[test]
+    fn test_id_generator_shared_by_blocking_submitters() {
+        let queue = Arc::new(
+            TaskQueue::with_capacity(1).with_id_generator(SequentialIdGenerator::starting_at(10)),
+        );
+        assert_eq!(queue.submit(add_task()).unwrap(), TaskId::from_u128(10));
+        
+        let submitter = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.submit_blocking(add_task(), None).unwrap())
+        };
+        thread::sleep(Duration::from_millis(20));
+        let (first, _) = queue.next_task().unwrap().unwrap();
+        assert_eq!(first, TaskId::from_u128(10));
+        
+        assert_eq!(submitter.join().unwrap(), TaskId::from_u128(11));
+    }
+
     #[test]
     fn test_task_cancellation() {
         let queue = TaskQueue::new();
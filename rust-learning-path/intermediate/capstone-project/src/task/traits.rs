@@ -7,11 +7,14 @@
 //! - Trait bounds and where clauses
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::{OnceLock, RwLock};
 use std::time::Duration;
 use serde::{Serialize, Deserialize};
 
-pub use super::{TaskId, TaskResult, TaskStatus, TaskMetadata};
+pub use super::{TaskId, TaskResult, TaskStatus, TaskMetadata, TaskPayload};
+use crate::error::TaskError;
 
 /// Core trait that all tasks must implement
 /// 
@@ -86,6 +89,17 @@ pub trait Task: Send + Sync + Debug {
 /// 
 /// This enables task persistence and distribution across processes
 pub trait SerializableTask: Task + Serialize + for<'de> Deserialize<'de> {
+    /// Name stored alongside the serialized task to identify its type
+    /// 
+    /// Override this to keep stored tasks loadable after the type is renamed
+    /// or moved to another module.
+    fn task_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
+    
     /// Serialize the task to JSON
     fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -100,6 +114,51 @@ pub trait SerializableTask: Task + Serialize + for<'de> Deserialize<'de> {
     }
 }
 
+/// Rebuilds a `TaskBox` from a serialized task of one known type
+type TaskDecoder = fn(&str) -> Result<TaskBox, serde_json::Error>;
+
+fn decode<T>(data: &str) -> Result<TaskBox, serde_json::Error>
+where
+    T: SerializableTask + 'static,
+    T::Output: Serialize,
+{
+    T::from_json(data).map(TaskBox::new)
+}
+
+/// Process-wide table of task types that can be loaded back from storage
+fn task_decoders() -> &'static RwLock<HashMap<String, TaskDecoder>> {
+    static DECODERS: OnceLock<RwLock<HashMap<String, TaskDecoder>>> = OnceLock::new();
+    DECODERS.get_or_init(|| {
+        let mut decoders: HashMap<String, TaskDecoder> = HashMap::new();
+        decoders.insert(MathTask::task_type().to_string(), decode::<MathTask>);
+        decoders.insert(SleepTask::task_type().to_string(), decode::<SleepTask>);
+        RwLock::new(decoders)
+    })
+}
+
+/// Register a task type so persisted tasks of that type can be recovered
+/// 
+/// The built-in `MathTask` and `SleepTask` are always registered.
+pub fn register_task_type<T>()
+where
+    T: SerializableTask + 'static,
+    T::Output: Serialize,
+{
+    let mut decoders = task_decoders().write().unwrap_or_else(|e| e.into_inner());
+    decoders.insert(T::task_type().to_string(), decode::<T>);
+}
+
+/// Rebuild a task from its payload
+/// 
+/// Returns `None` if the payload's type was never registered.
+pub fn decode_task(payload: &TaskPayload) -> Option<Result<TaskBox, serde_json::Error>> {
+    let decoder = {
+        let decoders = task_decoders().read().unwrap_or_else(|e| e.into_inner());
+        *decoders.get(&payload.task_type)?
+    };
+    Some(decoder(&payload.data))
+}
+
 /// Trait for tasks that can be cloned
 /// 
 /// This is useful for retry logic and task distribution
@@ -216,9 +275,15 @@ impl TaskBox {
         T: Task + 'static,
         T::Output: Serialize,
     {
-        let metadata = TaskMetadata::new(task.name())
+        let mut metadata = TaskMetadata::new(task.name())
             .with_priority(task.priority())
             .with_max_retries(task.max_retries());
+        if let Some(timeout) = task.timeout() {
+            metadata = metadata.with_timeout(timeout);
+        }
+        for tag in task.tags() {
+            metadata = metadata.with_tag(&tag);
+        }
         
         Self {
             inner: Box::new(task),
@@ -226,6 +291,26 @@ impl TaskBox {
         }
     }
     
+    /// Create a TaskBox that carries a serialized copy of the task
+    /// 
+    /// Storage backends persist the copy so the task can be recovered after
+    /// a restart, as long as its type is registered with `register_task_type`.
+    pub fn serializable<T>(task: T) -> Result<Self, TaskError>
+    where
+        T: SerializableTask + 'static,
+        T::Output: Serialize,
+    {
+        let data = task.to_json()
+            .map_err(|e| TaskError::serialization_error(&format!("task '{}'", task.name()), e))?;
+        
+        let mut task_box = Self::new(task);
+        task_box.metadata.payload = Some(TaskPayload {
+            task_type: T::task_type().to_string(),
+            data,
+        });
+        Ok(task_box)
+    }
+    
     /// Execute the wrapped task
     pub fn execute(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         self.inner.validate()?;
@@ -237,6 +322,11 @@ impl TaskBox {
         result
     }
     
+    /// Name reported by the wrapped task
+    pub fn name(&self) -> &str {
+        self.inner.name()
+    }
+    
     /// Get the task metadata
     pub fn metadata(&self) -> &TaskMetadata {
         &self.metadata
@@ -403,6 +493,28 @@ mod tests {
         assert_eq!(task.operands, deserialized.operands);
     }
     
+    #[test]
+    fn test_task_payload_round_trip() {
+        let task = MathTask {
+            operation: "add".to_string(),
+            operands: vec![1.0, 2.0],
+        };
+        
+        let task_box = TaskBox::serializable(task).unwrap();
+        let payload = task_box.metadata().payload.clone().unwrap();
+        assert_eq!(payload.task_type, MathTask::task_type());
+        
+        let decoded = decode_task(&payload).unwrap().unwrap();
+        assert_eq!(decoded.inner.name(), "add");
+        assert_eq!(decoded.execute().unwrap(), "3.0");
+        
+        let unknown = TaskPayload {
+            task_type: "no_such_task".to_string(),
+            data: "{}".to_string(),
+        };
+        assert!(decode_task(&unknown).is_none());
+    }
+    
     #[test]
     fn test_sleep_task() {
         let task = SleepTask {
@@ -8,56 +8,474 @@ pub mod executor;
 pub use pool::{WorkerPool, WorkerConfig};
 
 // Placeholder implementations for the capstone
-use crate::error::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::error::{Result, TaskError};
+use crate::task::{TaskBox, TaskId};
+use crate::{TaskMonitor, TaskQueue};
+
+/// How often `WorkerPool::drain` checks whether the queue is empty
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 #[derive(Debug, Clone)]
 pub struct WorkerConfig {
     pub thread_count: usize,
-    pub poll_interval: std::time::Duration,
+    pub poll_interval: Duration,
+    /// How long a worker waits before re-enqueueing a failed task
+    pub retry_backoff: Duration,
+    /// Timeout for tasks that don't set one in their metadata
+    /// 
+    /// A timed-out task is reported as `TimedOut` but not stopped: it keeps
+    /// its thread until `execute` returns (see `execute_with_timeout`).
+    pub default_timeout: Option<Duration>,
 }
 
 impl Default for WorkerConfig {
     fn default() -> Self {
         Self {
             thread_count: num_cpus::get(),
-            poll_interval: std::time::Duration::from_millis(100),
+            poll_interval: Duration::from_millis(100),
+            retry_backoff: Duration::from_millis(100),
+            default_timeout: None,
         }
     }
 }
 
 pub struct WorkerPool {
     config: WorkerConfig,
+    queue: Arc<Mutex<TaskQueue>>,
+    monitor: TaskMonitor,
+    shutdown: Arc<AtomicBool>,
+    handles: Mutex<Vec<JoinHandle<()>>>,
 }
 
 impl WorkerPool {
     pub fn new(
         thread_count: usize,
-        _queue: std::sync::Arc<std::sync::Mutex<crate::TaskQueue>>,
-        _monitor: crate::TaskMonitor,
+        queue: Arc<Mutex<TaskQueue>>,
+        monitor: TaskMonitor,
     ) -> Result<Self> {
-        Ok(Self {
-            config: WorkerConfig {
+        Self::with_config(
+            WorkerConfig {
                 thread_count,
                 ..Default::default()
             },
+            queue,
+            monitor,
+        )
+    }
+    
+    /// Create a worker pool with full control over polling and retry behavior
+    pub fn with_config(
+        config: WorkerConfig,
+        queue: Arc<Mutex<TaskQueue>>,
+        monitor: TaskMonitor,
+    ) -> Result<Self> {
+        if config.thread_count == 0 {
+            return Err(TaskError::WorkerPoolError(
+                "Worker pool needs at least one thread".to_string()
+            ));
+        }
+        
+        Ok(Self {
+            config,
+            queue,
+            monitor,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            handles: Mutex::new(Vec::new()),
         })
     }
     
     pub fn start(&self) -> Result<()> {
         println!("Starting worker pool with {} threads", self.config.thread_count);
+        
+        // TaskQueue clones share the same underlying state, so each worker
+        // gets its own handle and never holds the outer lock while waiting
+        let queue = self.queue.lock()?.clone();
+        let mut handles = self.handles.lock()?;
+        self.shutdown.store(false, Ordering::SeqCst);
+        
+        for worker_id in 0..self.config.thread_count {
+            let worker = Worker {
+                id: worker_id,
+                queue: queue.clone(),
+                monitor: self.monitor.clone(),
+                shutdown: Arc::clone(&self.shutdown),
+                config: self.config.clone(),
+            };
+            
+            let handle = thread::Builder::new()
+                .name(format!("task-worker-{}", worker_id))
+                .spawn(move || worker.run())
+                .map_err(|e| TaskError::WorkerPoolError(format!("Failed to spawn worker: {}", e)))?;
+            handles.push(handle);
+        }
+        
         Ok(())
     }
     
+    /// Close the queue, let the workers finish every task already in it,
+    /// then stop them
+    /// 
+    /// Returns how many tasks were pending or running when the drain began.
+    /// Fails without waiting if no workers are running, and fails once
+    /// `timeout` passes with tasks still outstanding. The workers are
+    /// stopped either way and the queue stays closed.
+    pub fn drain(&self, timeout: Duration) -> Result<usize> {
+        let queue = self.queue.lock()?.clone();
+        queue.close();
+        
+        let drained = queue.outstanding_count()?;
+        let deadline = Instant::now() + timeout;
+        let mut outcome = Ok(drained);
+        
+        if drained > 0 && self.handles.lock()?.is_empty() {
+            outcome = Err(TaskError::WorkerPoolError(format!(
+                "Cannot drain {} task(s): no workers are running", drained
+            )));
+        }
+        
+        while outcome.is_ok() {
+            let remaining = queue.outstanding_count()?;
+            if remaining == 0 {
+                break;
+            }
+            if Instant::now() >= deadline {
+                outcome = Err(TaskError::WorkerPoolError(format!(
+                    "{} task(s) still outstanding after draining for {:?}", remaining, timeout
+                )));
+                break;
+            }
+            thread::sleep(DRAIN_POLL_INTERVAL);
+        }
+        
+        self.stop()?;
+        outcome
+    }
+    
+    /// Stop the workers as soon as they finish their current task,
+    /// leaving anything still queued in place
     pub fn stop(&self) -> Result<()> {
         println!("Stopping worker pool");
+        self.shutdown.store(true, Ordering::SeqCst);
+        
+        // Workers notice the flag within one poll interval
+        let handles: Vec<_> = self.handles.lock()?.drain(..).collect();
+        for handle in handles {
+            handle.join()
+                .map_err(|_| TaskError::WorkerPoolError("Worker thread panicked".to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// State owned by a single worker thread
+struct Worker {
+    id: usize,
+    queue: TaskQueue,
+    monitor: TaskMonitor,
+    shutdown: Arc<AtomicBool>,
+    config: WorkerConfig,
+}
+
+impl Worker {
+    fn run(self) {
+        while !self.shutdown.load(Ordering::SeqCst) {
+            match self.queue.wait_for_task(Some(self.config.poll_interval)) {
+                Ok(Some((task_id, task))) => {
+                    if let Err(e) = self.process(task_id, task) {
+                        log::error!("Worker {} failed to record result for {}: {}", self.id, task_id, e);
+                    }
+                }
+                Ok(None) => continue,
+                Err(e) => {
+                    log::error!("Worker {} could not fetch a task: {}", self.id, e);
+                    thread::sleep(self.config.poll_interval);
+                }
+            }
+        }
+    }
+    
+    /// Execute one attempt of a task.
+    /// 
+    /// A failed task is re-enqueued while `metadata.can_retry()` allows it;
+    /// only once retries are exhausted is the result marked `Failed`.
+    /// A task that exceeds its timeout is marked `TimedOut` and not retried.
+    /// Every attempt's duration is reported to the monitor under the task's name.
+    fn process(&self, task_id: TaskId, task: TaskBox) -> Result<()> {
+        self.monitor.task_started(task_id);
+        
+        let task_name = task.name().to_string();
+        let started = Instant::now();
+        let timeout = task.metadata().timeout.or(self.config.default_timeout);
+        let (mut task, outcome) = match timeout {
+            Some(timeout) => match execute_with_timeout(task, timeout) {
+                Some(finished) => finished,
+                None => {
+                    log::warn!("Task {} timed out after {:?}", task_id, timeout);
+                    self.monitor.task_executed(&task_name, started.elapsed(), false);
+                    self.queue.timeout_task(task_id)?;
+                    self.monitor.task_failed(task_id);
+                    return Ok(());
+                }
+            },
+            None => {
+                let outcome = task.execute();
+                (task, outcome)
+            }
+        };
+        self.monitor.task_executed(&task_name, started.elapsed(), outcome.is_ok());
+        
+        match outcome {
+            Ok(output) => {
+                self.queue.complete_task(task_id, output)?;
+                self.monitor.task_completed(task_id);
+            }
+            Err(error) if task.metadata().can_retry() => {
+                task.metadata_mut().increment_retry();
+                log::warn!(
+                    "Task {} failed (attempt {} of {}), retrying: {}",
+                    task_id,
+                    task.metadata().retry_count,
+                    task.metadata().max_retries + 1,
+                    error
+                );
+                
+                if !self.config.retry_backoff.is_zero() {
+                    thread::sleep(self.config.retry_backoff);
+                }
+                self.queue.requeue_task(task_id, task)?;
+                self.monitor.task_retried(task_id);
+            }
+            Err(error) => {
+                let attempts = task.metadata().retry_count + 1;
+                self.queue.fail_task(
+                    task_id,
+                    format!("{} (failed after {} attempt(s))", error, attempts),
+                )?;
+                self.monitor.task_failed(task_id);
+            }
+        }
+        
         Ok(())
     }
 }
 
+type TaskOutcome = std::result::Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Runs a task on a scratch thread and waits at most `timeout` for it.
+/// 
+/// Returns `None` if the deadline passes. Rust threads can't be killed, so a
+/// timed-out task keeps running in the background until it returns on its
+/// own; its result is simply discarded. It only holds a shared reference to
+/// the task, so it can't touch queue state after the worker has moved on.
+/// 
+/// Leaving the thread detached is deliberate: the timeout frees the worker,
+/// not the task's resources. A task that never returns holds its thread
+/// until the process exits, so tasks that can hang should bound their own
+/// work (e.g. with I/O timeouts) rather than rely on this one.
+fn execute_with_timeout(task: TaskBox, timeout: Duration) -> Option<(TaskBox, TaskOutcome)> {
+    let task = Arc::new(task);
+    let (tx, rx) = mpsc::channel();
+    
+    let runner = {
+        let task = Arc::clone(&task);
+        thread::spawn(move || {
+            let _ = tx.send(task.execute());
+        })
+    };
+    
+    match rx.recv_timeout(timeout) {
+        Ok(outcome) => {
+            // Joining guarantees the scratch thread has dropped its reference
+            let _ = runner.join();
+            let task = Arc::try_unwrap(task).ok()?;
+            Some((task, outcome))
+        }
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => {
+            // The task panicked before producing a result
+            let _ = runner.join();
+            let task = Arc::try_unwrap(task).ok()?;
+            Some((task, Err("task panicked".into())))
+        }
+    }
+}
+
 mod num_cpus {
     pub fn get() -> usize {
         std::thread::available_parallelism()
             .map(|n| n.get())
             .unwrap_or(4)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Task, TaskStatus};
+    use crate::task::traits::SleepTask;
+    use std::sync::atomic::AtomicU32;
+    
+    /// Fails until its shared failure budget runs out
+    #[derive(Debug, Clone)]
+    struct FlakyTask {
+        failures_left: Arc<AtomicU32>,
+        attempts: Arc<AtomicU32>,
+        max_retries: u32,
+    }
+    
+    impl Task for FlakyTask {
+        type Output = String;
+        type Error = std::io::Error;
+        
+        fn execute(&self) -> std::result::Result<Self::Output, Self::Error> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let remaining = self.failures_left.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures_left.store(remaining - 1, Ordering::SeqCst);
+                return Err(std::io::Error::other("transient failure"));
+            }
+            Ok("done".to_string())
+        }
+        
+        fn name(&self) -> &str {
+            "flaky"
+        }
+        
+        fn can_retry(&self) -> bool {
+            true
+        }
+        
+        fn max_retries(&self) -> u32 {
+            self.max_retries
+        }
+    }
+    
+    fn flaky(failures: u32, max_retries: u32) -> (FlakyTask, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let task = FlakyTask {
+            failures_left: Arc::new(AtomicU32::new(failures)),
+            attempts: Arc::clone(&attempts),
+            max_retries,
+        };
+        (task, attempts)
+    }
+    
+    fn start_pool() -> (TaskQueue, WorkerPool) {
+        let queue = TaskQueue::new();
+        let config = WorkerConfig {
+            thread_count: 2,
+            poll_interval: Duration::from_millis(10),
+            retry_backoff: Duration::from_millis(5),
+            default_timeout: None,
+        };
+        let pool = WorkerPool::with_config(
+            config,
+            Arc::new(Mutex::new(queue.clone())),
+            TaskMonitor::new(),
+        ).unwrap();
+        pool.start().unwrap();
+        (queue, pool)
+    }
+    
+    #[test]
+    fn test_retry_until_success() {
+        let (queue, pool) = start_pool();
+        let (task, attempts) = flaky(2, 3);
+        
+        let task_id = queue.submit(TaskBox::new(task)).unwrap();
+        let result = queue.wait_for_result(task_id, Some(Duration::from_secs(5))).unwrap();
+        pool.stop().unwrap();
+        
+        assert_eq!(result.status, TaskStatus::Completed);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(queue.stats().unwrap().total_retried, 2);
+    }
+    
+    #[test]
+    fn test_fail_after_retries_exhausted() {
+        let (queue, pool) = start_pool();
+        let (task, attempts) = flaky(10, 2);
+        
+        let task_id = queue.submit(TaskBox::new(task)).unwrap();
+        let result = queue.wait_for_result(task_id, Some(Duration::from_secs(5))).unwrap();
+        pool.stop().unwrap();
+        
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(result.error.unwrap().contains("failed after 3 attempt(s)"));
+    }
+    
+    #[test]
+    fn test_task_exceeding_timeout() {
+        let (queue, pool) = start_pool();
+        let mut task = TaskBox::new(SleepTask {
+            duration_ms: 500,
+            name: "slow".to_string(),
+        });
+        task.metadata_mut().timeout = Some(Duration::from_millis(20));
+        
+        let task_id = queue.submit(task).unwrap();
+        let result = queue.wait_for_result(task_id, Some(Duration::from_secs(5))).unwrap();
+        pool.stop().unwrap();
+        
+        assert_eq!(result.status, TaskStatus::TimedOut);
+        assert_eq!(queue.stats().unwrap().total_timed_out, 1);
+        // The worker gave up well before the task would have finished
+        assert!(result.duration.unwrap() < Duration::from_millis(500));
+    }
+    
+    #[test]
+    fn test_task_within_timeout() {
+        let (queue, pool) = start_pool();
+        let task_id = queue.submit(TaskBox::new(SleepTask {
+            duration_ms: 10,
+            name: "quick".to_string(),
+        })).unwrap();
+        
+        let result = queue.wait_for_result(task_id, Some(Duration::from_secs(5))).unwrap();
+        pool.stop().unwrap();
+        
+        assert_eq!(result.status, TaskStatus::Completed);
+    }
+    
+    #[test]
+    fn test_drain_finishes_queued_tasks() {
+        let (queue, pool) = start_pool();
+        let task_ids: Vec<TaskId> = (0..10)
+            .map(|i| queue.submit(TaskBox::new(SleepTask {
+                duration_ms: 20,
+                name: format!("slow-{}", i),
+            })).unwrap())
+            .collect();
+        
+        assert_eq!(pool.drain(Duration::from_secs(5)).unwrap(), 10);
+        
+        for task_id in task_ids {
+            assert_eq!(queue.get_result(task_id).unwrap().status, TaskStatus::Completed);
+        }
+        assert!(matches!(
+            queue.submit(TaskBox::new(SleepTask { duration_ms: 1, name: "late".to_string() })),
+            Err(TaskError::QueueClosed)
+        ));
+    }
+    
+    #[test]
+    fn test_drain_times_out() {
+        let (queue, pool) = start_pool();
+        for i in 0..4 {
+            queue.submit(TaskBox::new(SleepTask {
+                duration_ms: 100,
+                name: format!("slow-{}", i),
+            })).unwrap();
+        }
+        
+        assert!(matches!(pool.drain(Duration::from_millis(20)), Err(TaskError::WorkerPoolError(_))));
+        assert!(queue.outstanding_count().unwrap() > 0);
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Policy settings for circulation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LibraryConfig {
+    /// How many days a book may be kept before it is due
+    pub loan_period_days: i64,
+    /// Send a reminder when a loan is due within this many days
+    pub reminder_window_days: i64,
+    /// Fine charged for each day a book is overdue, in cents
+    pub fine_per_day_cents: u64,
+    /// How many books one user may have on loan at once
+    pub max_loans_per_user: usize,
+    /// Users owing more than this, in cents, may not borrow
+    pub max_outstanding_fine_cents: u64,
+}
+
+impl Default for LibraryConfig {
+    fn default() -> Self {
+        LibraryConfig {
+            loan_period_days: 14,
+            reminder_window_days: 3,
+            fine_per_day_cents: 25,
+            max_loans_per_user: 5,
+            max_outstanding_fine_cents: 1000,
+        }
+    }
+}
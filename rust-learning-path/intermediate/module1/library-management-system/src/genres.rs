@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Normalizes a genre name for grouping: trimmed, lowercase, single-spaced.
+/// Returns `None` for a blank name.
+pub fn normalize_genre(genre: &str) -> Option<String> {
+    let normalized = genre
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
+/// Genres assigned to books, keyed by book ID.
+///
+/// A book may have any number of genres; books without one simply have no entry.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GenreCatalog {
+    books: BTreeMap<usize, BTreeSet<String>>,
+}
+
+impl GenreCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces a book's genres. Blank names are ignored and duplicates that
+    /// differ only in case collapse into one.
+    pub fn set_genres<S: AsRef<str>>(&mut self, book_id: usize, genres: &[S]) {
+        let genres: BTreeSet<String> = genres
+            .iter()
+            .filter_map(|genre| normalize_genre(genre.as_ref()))
+            .collect();
+
+        if genres.is_empty() {
+            self.books.remove(&book_id);
+        } else {
+            self.books.insert(book_id, genres);
+        }
+    }
+
+    /// Adds one genre to a book, keeping any it already has
+    pub fn add_genre(&mut self, book_id: usize, genre: &str) {
+        if let Some(genre) = normalize_genre(genre) {
+            self.books.entry(book_id).or_default().insert(genre);
+        }
+    }
+
+    /// Forgets every genre of a book, e.g. when it is removed from the library
+    pub fn remove_book(&mut self, book_id: usize) {
+        self.books.remove(&book_id);
+    }
+
+    /// A book's genres in alphabetical order
+    pub fn genres_for(&self, book_id: usize) -> Vec<&str> {
+        self.books
+            .get(&book_id)
+            .map(|genres| genres.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// IDs of books in a genre, matched case-insensitively
+    pub fn books_with_genre(&self, genre: &str) -> Vec<usize> {
+        let Some(genre) = normalize_genre(genre) else {
+            return Vec::new();
+        };
+
+        self.books
+            .iter()
+            .filter(|(_, genres)| genres.contains(&genre))
+            .map(|(&book_id, _)| book_id)
+            .collect()
+    }
+
+    /// Every genre with the number of books in it, most books first
+    pub fn genre_counts(&self) -> Vec<(&str, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for genres in self.books.values() {
+            for genre in genres {
+                *counts.entry(genre.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        // Stable sort keeps ties in alphabetical order
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+}
@@ -1,6 +1,16 @@
+mod config;
+mod genres;
+mod loans;
 mod models;
+mod reminders;
+mod state;
 mod utils;
 
+pub use config::LibraryConfig;
+pub use genres::{normalize_genre, GenreCatalog};
+pub use loans::{Clock, Loan, LoanLedger, LoanRecord, ManualClock, SystemClock};
 pub use models::{Book, User, Library, LibraryError};
+pub use reminders::{Reminder, ReminderKind, ReminderLog, ReminderScheduler};
+pub use state::{LibraryState, DEFAULT_STATE_FILE};
 
-// Re-export other items as needed
\ No newline at end of file
+// Re-export other items as needed
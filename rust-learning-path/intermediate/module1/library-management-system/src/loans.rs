@@ -0,0 +1,227 @@
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::config::LibraryConfig;
+use crate::models::LibraryError;
+
+/// Source of "today" for anything date-dependent.
+/// Injected so tests can move time forward without waiting.
+pub trait Clock: Send + Sync {
+    fn today(&self) -> NaiveDate;
+}
+
+/// Reads the local date from the system
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
+}
+
+/// A clock that only moves when told to
+pub struct ManualClock {
+    today: Mutex<NaiveDate>,
+}
+
+impl ManualClock {
+    pub fn new(today: NaiveDate) -> Self {
+        ManualClock {
+            today: Mutex::new(today),
+        }
+    }
+
+    pub fn set(&self, today: NaiveDate) {
+        *self.today.lock().unwrap() = today;
+    }
+
+    pub fn advance_days(&self, days: i64) {
+        let mut today = self.today.lock().unwrap();
+        *today += Duration::days(days);
+    }
+}
+
+impl Clock for ManualClock {
+    fn today(&self) -> NaiveDate {
+        *self.today.lock().unwrap()
+    }
+}
+
+/// A book currently lent to a user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Loan {
+    pub book_id: usize,
+    pub user_id: usize,
+    pub borrowed_on: NaiveDate,
+    pub due_on: NaiveDate,
+}
+
+impl Loan {
+    /// Days past the due date, or 0 if not overdue
+    pub fn days_overdue(&self, today: NaiveDate) -> i64 {
+        (today - self.due_on).num_days().max(0)
+    }
+
+    /// Fine accrued as of `today`. A book returned on its due date owes nothing.
+    pub fn fine(&self, today: NaiveDate, fine_per_day_cents: u64) -> u64 {
+        self.days_overdue(today) as u64 * fine_per_day_cents
+    }
+}
+
+/// One borrowing of a book, kept after the book comes back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoanRecord {
+    pub book_id: usize,
+    pub user_id: usize,
+    pub borrowed_on: NaiveDate,
+    /// `None` while the book is still out
+    pub returned_on: Option<NaiveDate>,
+}
+
+/// Records which books are out on loan and when they are due
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LoanLedger {
+    loans: Vec<Loan>,
+    /// Every checkout in order, including ones since returned
+    #[serde(default)]
+    history: Vec<LoanRecord>,
+}
+
+impl LoanLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lends a book to a user for `loan_period_days` starting `today`
+    pub fn checkout(
+        &mut self,
+        book_id: usize,
+        user_id: usize,
+        today: NaiveDate,
+        loan_period_days: i64,
+    ) -> Result<&Loan, LibraryError> {
+        if self.find_loan(book_id).is_some() {
+            return Err(LibraryError::BookNotAvailable);
+        }
+
+        self.history.push(LoanRecord {
+            book_id,
+            user_id,
+            borrowed_on: today,
+            returned_on: None,
+        });
+        self.loans.push(Loan {
+            book_id,
+            user_id,
+            borrowed_on: today,
+            due_on: today + Duration::days(loan_period_days),
+        });
+        Ok(self.loans.last().unwrap())
+    }
+
+    /// Checks library policy, then lends a book for the configured loan period.
+    pub fn borrow(
+        &mut self,
+        book_id: usize,
+        user_id: usize,
+        today: NaiveDate,
+        config: &LibraryConfig,
+    ) -> Result<&Loan, LibraryError> {
+        self.check_eligibility(user_id, today, config)?;
+        self.checkout(book_id, user_id, today, config.loan_period_days)
+    }
+
+    /// Fails if the user is at their loan limit or owes too much in fines
+    pub fn check_eligibility(
+        &self,
+        user_id: usize,
+        today: NaiveDate,
+        config: &LibraryConfig,
+    ) -> Result<(), LibraryError> {
+        if self.loans_for_user(user_id).len() >= config.max_loans_per_user {
+            return Err(LibraryError::BorrowLimitReached {
+                limit: config.max_loans_per_user,
+            });
+        }
+
+        let owed_cents = self.outstanding_fines(user_id, today, config.fine_per_day_cents);
+        if owed_cents > config.max_outstanding_fine_cents {
+            return Err(LibraryError::OutstandingFines {
+                owed_cents,
+                limit_cents: config.max_outstanding_fine_cents,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Ends the loan for a book on `today` and returns it.
+    /// Any fine is settled at return, so it stops counting towards the user's total.
+    pub fn checkin(&mut self, book_id: usize, today: NaiveDate) -> Result<Loan, LibraryError> {
+        let index = self.loans.iter()
+            .position(|loan| loan.book_id == book_id)
+            .ok_or_else(|| LibraryError::InvalidOperation(format!("book {} is not on loan", book_id)))?;
+
+        if let Some(record) = self.history.iter_mut()
+            .rev()
+            .find(|record| record.book_id == book_id && record.returned_on.is_none())
+        {
+            record.returned_on = Some(today);
+        }
+        Ok(self.loans.remove(index))
+    }
+
+    /// Finds the active loan for a book, if any
+    pub fn find_loan(&self, book_id: usize) -> Option<&Loan> {
+        self.loans.iter().find(|loan| loan.book_id == book_id)
+    }
+
+    /// All active loans, in checkout order
+    pub fn active_loans(&self) -> &[Loan] {
+        &self.loans
+    }
+
+    /// Active loans held by one user
+    pub fn loans_for_user(&self, user_id: usize) -> Vec<&Loan> {
+        self.loans.iter().filter(|loan| loan.user_id == user_id).collect()
+    }
+
+    /// Every checkout ever made, oldest first
+    pub fn history(&self) -> &[LoanRecord] {
+        &self.history
+    }
+
+    /// Past and current checkouts by one user, oldest first
+    pub fn history_for_user(&self, user_id: usize) -> Vec<&LoanRecord> {
+        self.history.iter().filter(|record| record.user_id == user_id).collect()
+    }
+
+    /// Past and current checkouts of one book, oldest first
+    pub fn history_for_book(&self, book_id: usize) -> Vec<&LoanRecord> {
+        self.history.iter().filter(|record| record.book_id == book_id).collect()
+    }
+
+    /// Books ranked by how many times they have been borrowed, most first.
+    /// Ties are broken by book id.
+    pub fn popular_books(&self) -> Vec<(usize, usize)> {
+        let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+        for record in &self.history {
+            *counts.entry(record.book_id).or_default() += 1;
+        }
+
+        let mut counts: Vec<(usize, usize)> = counts.into_iter().collect();
+        // Stable sort keeps ties in book id order
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+
+    /// Total fine a user owes on their overdue loans as of `today`, in cents
+    pub fn outstanding_fines(&self, user_id: usize, today: NaiveDate, fine_per_day_cents: u64) -> u64 {
+        self.loans_for_user(user_id)
+            .iter()
+            .map(|loan| loan.fine(today, fine_per_day_cents))
+            .sum()
+    }
+}
@@ -1,13 +1,24 @@
 use clap::{Parser, Subcommand};
 use colored::*;
-use library_management_system::{Library, LibraryError};
+use library_management_system::{
+    Clock, Library, LibraryError, LibraryState, ReminderScheduler, SystemClock, DEFAULT_STATE_FILE,
+};
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// A command-line library management system
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// File where loans and reminders are kept between runs
+    #[arg(long, global = true, default_value = DEFAULT_STATE_FILE)]
+    state_file: PathBuf,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -28,7 +39,25 @@ enum Commands {
     },
     
     /// List all books in the library
-    ListBooks,
+    ListBooks {
+        /// Only show books in this genre (case-insensitive)
+        #[arg(short, long)]
+        genre: Option<String>,
+    },
+    
+    /// Assign one or more genres to a book, replacing any it had
+    SetGenres {
+        /// ID of the book
+        #[arg(short, long)]
+        book_id: usize,
+        
+        /// Genre names, e.g. --genre fantasy --genre "young adult"
+        #[arg(short, long = "genre", required = true)]
+        genres: Vec<String>,
+    },
+    
+    /// List all genres with the number of books in each
+    Genres,
     
     /// Search for books by title
     SearchBooks {
@@ -64,6 +93,41 @@ enum Commands {
         #[arg(short, long)]
         book_id: usize,
     },
+    
+    /// Show past and current loans for a user or a book
+    History {
+        /// Only show loans by this user
+        #[arg(short, long, conflicts_with = "book", required_unless_present = "book")]
+        user: Option<usize>,
+        
+        /// Only show loans of this book
+        #[arg(short, long)]
+        book: Option<usize>,
+    },
+    
+    /// Rank books by how many times they have been borrowed
+    Popular {
+        /// Number of books to show
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    
+    /// Show the total outstanding fine for a user
+    Fines {
+        /// ID of the user
+        user_id: usize,
+    },
+    
+    /// Scan loans and show due-soon and overdue reminders
+    Reminders {
+        /// Remind about loans due within this many days (defaults to the configured window)
+        #[arg(short, long)]
+        days: Option<i64>,
+        
+        /// Keep scanning in the background every this many seconds until Enter is pressed
+        #[arg(short, long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+        watch: Option<u64>,
+    },
 }
 
 fn main() {
@@ -74,7 +138,136 @@ fn main() {
     // 4. Handles and displays errors
     
     println!("{}", "Library Management System".green().bold());
-    println!("Run with --help to see available commands");
+    
+    let cli = Cli::parse();
+    let Some(command) = cli.command else {
+        println!("Run with --help to see available commands");
+        return;
+    };
+    
+    // Circulation commands work on the saved loan state
+    let mut state = match LibraryState::load(&cli.state_file) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("{} {}", "Error loading state:".red(), e);
+            process::exit(1);
+        }
+    };
+    let today = SystemClock.today();
+    let mut exit_code = 0;
+    
+    match command {
+        Commands::BorrowBook { book_id, user_id } => {
+            match state.ledger.borrow(book_id, user_id, today, &state.config) {
+                Ok(loan) => println!("Book {} lent to user {}, due {}", book_id, user_id, loan.due_on),
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    exit_code = 1;
+                },
+            }
+        },
+        Commands::ReturnBook { book_id } => {
+            match state.ledger.checkin(book_id, today) {
+                Ok(loan) => {
+                    println!("Book {} returned", book_id);
+                    let fine = loan.fine(today, state.config.fine_per_day_cents);
+                    if fine > 0 {
+                        println!("Late by {} day(s), fine paid: {}", loan.days_overdue(today), format_cents(fine));
+                    }
+                },
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red(), e);
+                    exit_code = 1;
+                },
+            }
+        },
+        Commands::ListBooks { genre: Some(genre) } => {
+            let books = state.genres.books_with_genre(&genre);
+            if books.is_empty() {
+                println!("No books in genre '{}'", genre);
+            }
+            for book_id in books {
+                println!("Book {}: {}", book_id, state.genres.genres_for(book_id).join(", "));
+            }
+        },
+        Commands::SetGenres { book_id, genres } => {
+            state.genres.set_genres(book_id, &genres);
+            println!("Book {} genres: {}", book_id, state.genres.genres_for(book_id).join(", "));
+        },
+        Commands::Genres => {
+            let counts = state.genres.genre_counts();
+            if counts.is_empty() {
+                println!("No genres assigned yet");
+            }
+            for (genre, count) in counts {
+                println!("{:<20} {}", genre, count);
+            }
+        },
+        Commands::History { user, book } => {
+            let records = match (user, book) {
+                (Some(user_id), _) => state.ledger.history_for_user(user_id),
+                (None, Some(book_id)) => state.ledger.history_for_book(book_id),
+                (None, None) => unreachable!("clap requires --user or --book"),
+            };
+            if records.is_empty() {
+                println!("No loan history");
+            }
+            for record in records {
+                let returned = record.returned_on
+                    .map(|day| day.to_string())
+                    .unwrap_or_else(|| "still out".to_string());
+                println!(
+                    "Book {} borrowed by user {} on {}, returned: {}",
+                    record.book_id, record.user_id, record.borrowed_on, returned
+                );
+            }
+        },
+        Commands::Popular { limit } => {
+            let ranking = state.ledger.popular_books();
+            if ranking.is_empty() {
+                println!("No books have been borrowed yet");
+            }
+            for (rank, (book_id, count)) in ranking.into_iter().take(limit).enumerate() {
+                println!("{:>3}. Book {:<6} {} borrow(s)", rank + 1, book_id, count);
+            }
+        },
+        Commands::Fines { user_id } => {
+            let rate = state.config.fine_per_day_cents;
+            for loan in state.ledger.loans_for_user(user_id) {
+                if loan.days_overdue(today) > 0 {
+                    println!(
+                        "Book {}: {} day(s) overdue, {}",
+                        loan.book_id,
+                        loan.days_overdue(today),
+                        format_cents(loan.fine(today, rate))
+                    );
+                }
+            }
+            let total = state.ledger.outstanding_fines(user_id, today, rate);
+            println!("Outstanding fines for user {}: {}", user_id, format_cents(total));
+        },
+        Commands::Reminders { days, watch } => {
+            let window = days.unwrap_or(state.config.reminder_window_days);
+            let before = state.reminders.reminders().len();
+            state.reminders.scan(&state.ledger, today, window);
+            if let Some(seconds) = watch {
+                watch_reminders(&mut state, Duration::from_secs(seconds), window);
+            }
+            println!("{} new reminder(s)", state.reminders.reminders().len() - before);
+            for reminder in state.reminders.reminders() {
+                println!("{}", reminder);
+            }
+        },
+        _ => println!("This command is not implemented yet"),
+    }
+    
+    if let Err(e) = state.save(&cli.state_file) {
+        eprintln!("{} {}", "Error saving state:".red(), e);
+        exit_code = 1;
+    }
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
     
     // Example implementation:
     /*
@@ -91,4 +284,30 @@ fn main() {
         // Implement other commands...
     }
     */
-}
\ No newline at end of file
+}
+
+/// Runs a `ReminderScheduler` over the state's loans until Enter is pressed
+fn watch_reminders(state: &mut LibraryState, interval: Duration, window_days: i64) {
+    let ledger = Arc::new(Mutex::new(std::mem::take(&mut state.ledger)));
+    let log = Arc::new(Mutex::new(std::mem::take(&mut state.reminders)));
+    let scheduler = ReminderScheduler::start(
+        Arc::clone(&ledger),
+        Arc::clone(&log),
+        Arc::new(SystemClock),
+        interval,
+        window_days,
+    );
+    
+    println!("Scanning for reminders every {}s, press Enter to stop", interval.as_secs());
+    let _ = io::stdin().read_line(&mut String::new());
+    scheduler.stop();
+    
+    // The scheduler thread has exited, so these are the only handles left
+    state.ledger = std::mem::take(&mut *ledger.lock().unwrap());
+    state.reminders = std::mem::take(&mut *log.lock().unwrap());
+}
+
+/// Formats an amount in cents as dollars, e.g. `$1.25`
+fn format_cents(cents: u64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
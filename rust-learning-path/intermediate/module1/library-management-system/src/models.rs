@@ -15,6 +15,12 @@ pub enum LibraryError {
     #[error("User not found")]
     UserNotFound,
     
+    #[error("User already has {limit} books on loan, the maximum allowed")]
+    BorrowLimitReached { limit: usize },
+    
+    #[error("User owes {owed_cents} cents in fines, above the {limit_cents} cent limit")]
+    OutstandingFines { owed_cents: u64, limit_cents: u64 },
+    
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
 }
@@ -0,0 +1,156 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::loans::{Clock, Loan, LoanLedger};
+
+/// Why a reminder was generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReminderKind {
+    /// The loan is due within the reminder window
+    DueSoon,
+    /// The due date has passed
+    Overdue,
+}
+
+/// A notice generated for one loan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub book_id: usize,
+    pub user_id: usize,
+    pub borrowed_on: NaiveDate,
+    pub due_on: NaiveDate,
+    pub kind: ReminderKind,
+    pub created_on: NaiveDate,
+}
+
+impl Reminder {
+    fn is_for(&self, loan: &Loan, kind: ReminderKind) -> bool {
+        self.kind == kind
+            && self.book_id == loan.book_id
+            && self.user_id == loan.user_id
+            && self.borrowed_on == loan.borrowed_on
+    }
+}
+
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.kind {
+            ReminderKind::DueSoon => "Due soon",
+            ReminderKind::Overdue => "Overdue",
+        };
+        write!(
+            f,
+            "[{}] {}: book {} (user {}) due {}",
+            self.created_on, label, self.book_id, self.user_id, self.due_on
+        )
+    }
+}
+
+/// Every reminder generated so far.
+///
+/// Each loan gets at most one `DueSoon` and one `Overdue` reminder,
+/// no matter how many times it is scanned.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReminderLog {
+    reminders: Vec<Reminder>,
+}
+
+impl ReminderLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks every active loan against `today` and records new reminders.
+    /// Returns only the reminders created by this scan.
+    pub fn scan(&mut self, ledger: &LoanLedger, today: NaiveDate, window_days: i64) -> Vec<Reminder> {
+        let mut created = Vec::new();
+
+        for loan in ledger.active_loans() {
+            let kind = if today > loan.due_on {
+                ReminderKind::Overdue
+            } else if loan.due_on - today <= Duration::days(window_days) {
+                ReminderKind::DueSoon
+            } else {
+                continue;
+            };
+
+            if self.reminders.iter().any(|r| r.is_for(loan, kind)) {
+                continue;
+            }
+
+            let reminder = Reminder {
+                book_id: loan.book_id,
+                user_id: loan.user_id,
+                borrowed_on: loan.borrowed_on,
+                due_on: loan.due_on,
+                kind,
+                created_on: today,
+            };
+            self.reminders.push(reminder.clone());
+            created.push(reminder);
+        }
+
+        created
+    }
+
+    /// All reminders, oldest first
+    pub fn reminders(&self) -> &[Reminder] {
+        &self.reminders
+    }
+}
+
+/// Background thread that scans the ledger for reminders at a fixed interval
+pub struct ReminderScheduler {
+    stop_tx: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReminderScheduler {
+    /// Starts scanning every `interval`, using `clock` for today's date
+    pub fn start(
+        ledger: Arc<Mutex<LoanLedger>>,
+        log: Arc<Mutex<ReminderLog>>,
+        clock: Arc<dyn Clock>,
+        interval: std::time::Duration,
+        window_days: i64,
+    ) -> Self {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+        // A stop message or a dropped sender both end the loop
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                let ledger = ledger.lock().unwrap();
+                log.lock().unwrap().scan(&ledger, clock.today(), window_days);
+            }
+        });
+
+        ReminderScheduler {
+            stop_tx: Some(stop_tx),
+            handle: Some(handle),
+        }
+    }
+
+    /// Signals the thread to stop and waits for it to exit
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ReminderScheduler {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
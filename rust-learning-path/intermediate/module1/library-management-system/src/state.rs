@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::LibraryConfig;
+use crate::genres::GenreCatalog;
+use crate::loans::LoanLedger;
+use crate::reminders::ReminderLog;
+
+/// Default file the command-line tool keeps its state in
+pub const DEFAULT_STATE_FILE: &str = "library_state.json";
+
+/// Everything the command-line tool persists between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LibraryState {
+    pub config: LibraryConfig,
+    pub ledger: LoanLedger,
+    pub reminders: ReminderLog,
+    pub genres: GenreCatalog,
+}
+
+impl LibraryState {
+    /// Loads state from `path`. A missing file means a fresh library.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes state to `path` as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,126 @@
+//! End-to-end tests that run the library-management-system binary
+//!
+//! Each case is one run of the binary: its arguments and what it should
+//! print and exit with. The cases run in order against the same state
+//! file, so a case can rely on what the ones before it did. Add a case to
+//! `CASES` to cover a new scenario.
+
+use std::path::Path;
+use std::process::Command;
+
+struct CliCase {
+    name: &'static str,
+    args: &'static [&'static str],
+    exit_code: i32,
+    stdout: &'static [&'static str],
+    stderr: &'static [&'static str],
+}
+
+const CASES: &[CliCase] = &[
+    CliCase {
+        name: "borrow a book",
+        args: &["borrow-book", "--book-id", "1", "--user-id", "7"],
+        exit_code: 0,
+        stdout: &["Book 1 lent to user 7"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "borrow a book that is already out",
+        args: &["borrow-book", "--book-id", "1", "--user-id", "8"],
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["Error:"],
+    },
+    CliCase {
+        name: "history shows the open loan",
+        args: &["history", "--user", "7"],
+        exit_code: 0,
+        stdout: &["Book 1 borrowed by user 7", "returned: still out"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "return the book",
+        args: &["return-book", "--book-id", "1"],
+        exit_code: 0,
+        stdout: &["Book 1 returned"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "return a book that is not on loan",
+        args: &["return-book", "--book-id", "1"],
+        exit_code: 1,
+        stdout: &[],
+        stderr: &["book 1 is not on loan"],
+    },
+    CliCase {
+        name: "assign genres",
+        args: &["set-genres", "--book-id", "1", "--genre", "Fantasy"],
+        exit_code: 0,
+        stdout: &["Book 1 genres: fantasy"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "borrow a book due in two weeks",
+        args: &["borrow-book", "--book-id", "2", "--user-id", "7"],
+        exit_code: 0,
+        stdout: &["Book 2 lent to user 7"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "watch for reminders until stdin closes",
+        args: &["reminders", "--days", "30", "--watch", "1"],
+        exit_code: 0,
+        stdout: &["Scanning for reminders every 1s", "1 new reminder(s)"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "a later scan does not repeat the reminder",
+        args: &["reminders", "--days", "30"],
+        exit_code: 0,
+        stdout: &["0 new reminder(s)"],
+        stderr: &[],
+    },
+    CliCase {
+        name: "missing required argument",
+        args: &["history"],
+        exit_code: 2,
+        stdout: &[],
+        stderr: &["--user"],
+    },
+];
+
+fn run_case(case: &CliCase, state_file: &Path) {
+    let output = Command::new(env!("CARGO_BIN_EXE_library-management-system"))
+        .args(case.args)
+        .arg("--state-file")
+        .arg(state_file)
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(case.exit_code),
+        "{}: wrong exit code\nstdout:\n{}\nstderr:\n{}",
+        case.name, stdout, stderr
+    );
+    for expected in case.stdout {
+        assert!(stdout.contains(expected), "{}: stdout is missing {:?}:\n{}", case.name, expected, stdout);
+    }
+    for expected in case.stderr {
+        assert!(stderr.contains(expected), "{}: stderr is missing {:?}:\n{}", case.name, expected, stderr);
+    }
+}
+
+#[test]
+fn test_cli_cases() {
+    let state_file = std::env::temp_dir().join(format!("library_cli_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&state_file);
+
+    for case in CASES {
+        run_case(case, &state_file);
+    }
+
+    let _ = std::fs::remove_file(&state_file);
+}
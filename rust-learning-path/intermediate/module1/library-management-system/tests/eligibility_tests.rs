@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use library_management_system::{LibraryConfig, LibraryError, LoanLedger};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+}
+
+fn config() -> LibraryConfig {
+    LibraryConfig {
+        loan_period_days: 7,
+        fine_per_day_cents: 50,
+        max_loans_per_user: 2,
+        max_outstanding_fine_cents: 200,
+        ..LibraryConfig::default()
+    }
+}
+
+#[test]
+fn test_user_at_loan_limit_is_refused() {
+    let config = config();
+    let mut ledger = LoanLedger::new();
+    ledger.borrow(1, 7, date(1), &config).unwrap();
+    ledger.borrow(2, 7, date(1), &config).unwrap();
+
+    let err = ledger.borrow(3, 7, date(1), &config).unwrap_err();
+    assert!(matches!(err, LibraryError::BorrowLimitReached { limit: 2 }));
+    assert!(ledger.find_loan(3).is_none());
+
+    // Returning a book frees a slot
+    ledger.checkin(1, date(1)).unwrap();
+    assert!(ledger.borrow(3, 7, date(1), &config).is_ok());
+}
+
+#[test]
+fn test_user_with_excessive_fines_is_refused() {
+    let config = config();
+    let mut ledger = LoanLedger::new();
+    ledger.borrow(1, 7, date(1), &config).unwrap(); // due the 8th
+
+    // 4 days late: 200 cents, exactly at the limit, still allowed
+    assert!(ledger.check_eligibility(7, date(12), &config).is_ok());
+
+    // 5 days late: 250 cents, over the limit
+    let err = ledger.borrow(2, 7, date(13), &config).unwrap_err();
+    assert!(matches!(
+        err,
+        LibraryError::OutstandingFines { owed_cents: 250, limit_cents: 200 }
+    ));
+}
+
+#[test]
+fn test_eligible_user_can_borrow() {
+    let config = config();
+    let mut ledger = LoanLedger::new();
+    ledger.borrow(1, 8, date(1), &config).unwrap(); // another user at their limit
+    ledger.borrow(2, 8, date(1), &config).unwrap();
+
+    let loan = ledger.borrow(3, 7, date(1), &config).unwrap();
+    assert_eq!(loan.due_on, date(8));
+    assert_eq!(ledger.loans_for_user(7).len(), 1);
+}
@@ -0,0 +1,57 @@
+use chrono::NaiveDate;
+use library_management_system::{LibraryConfig, LoanLedger};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+}
+
+const RATE: u64 = 25;
+
+#[test]
+fn test_default_fine_rate() {
+    assert_eq!(LibraryConfig::default().fine_per_day_cents, RATE);
+}
+
+#[test]
+fn test_fines_across_overdue_loans() {
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 7, date(1), 10).unwrap(); // due the 11th
+    ledger.checkout(2, 7, date(1), 5).unwrap(); // due the 6th
+    ledger.checkout(3, 7, date(1), 30).unwrap(); // not due yet
+    ledger.checkout(4, 8, date(1), 5).unwrap(); // another user
+
+    let today = date(16);
+    assert_eq!(ledger.find_loan(1).unwrap().days_overdue(today), 5);
+    assert_eq!(ledger.find_loan(2).unwrap().days_overdue(today), 10);
+    assert_eq!(ledger.find_loan(3).unwrap().days_overdue(today), 0);
+
+    assert_eq!(ledger.outstanding_fines(7, today, RATE), (5 + 10) * RATE);
+    assert_eq!(ledger.outstanding_fines(8, today, RATE), 10 * RATE);
+    assert_eq!(ledger.outstanding_fines(99, today, RATE), 0);
+}
+
+#[test]
+fn test_return_on_due_date_has_no_fine() {
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 7, date(1), 14).unwrap();
+
+    let due_on = ledger.find_loan(1).unwrap().due_on;
+    assert_eq!(ledger.outstanding_fines(7, due_on, RATE), 0);
+
+    let loan = ledger.checkin(1, due_on).unwrap();
+    assert_eq!(loan.fine(due_on, RATE), 0);
+}
+
+#[test]
+fn test_return_clears_fine() {
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 7, date(1), 3).unwrap(); // due the 4th
+    ledger.checkout(2, 7, date(1), 3).unwrap();
+
+    let today = date(10);
+    assert_eq!(ledger.outstanding_fines(7, today, RATE), 2 * 6 * RATE);
+
+    let loan = ledger.checkin(1, today).unwrap();
+    assert_eq!(loan.fine(today, RATE), 6 * RATE);
+    assert_eq!(ledger.outstanding_fines(7, today, RATE), 6 * RATE);
+}
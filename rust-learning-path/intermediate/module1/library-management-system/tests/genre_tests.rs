@@ -0,0 +1,58 @@
+use library_management_system::{normalize_genre, GenreCatalog};
+
+fn catalog() -> GenreCatalog {
+    let mut catalog = GenreCatalog::new();
+    catalog.set_genres(1, &["Fantasy", "Young Adult"]);
+    catalog.set_genres(2, &["fantasy"]);
+    catalog.set_genres(3, &["  Science   Fiction ", "FANTASY"]);
+    catalog.set_genres(4, &["History"]);
+    catalog
+}
+
+#[test]
+fn test_normalize_genre() {
+    assert_eq!(normalize_genre("  Science   FICTION "), Some("science fiction".to_string()));
+    assert_eq!(normalize_genre("   "), None);
+}
+
+#[test]
+fn test_filter_by_genre() {
+    let catalog = catalog();
+
+    assert_eq!(catalog.books_with_genre("FANTASY"), vec![1, 2, 3]);
+    assert_eq!(catalog.books_with_genre("science fiction"), vec![3]);
+    assert!(catalog.books_with_genre("poetry").is_empty());
+    assert_eq!(catalog.genres_for(1), vec!["fantasy", "young adult"]);
+    assert!(catalog.genres_for(99).is_empty());
+}
+
+#[test]
+fn test_genre_counts() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.genre_counts(),
+        vec![
+            ("fantasy", 3),
+            ("history", 1),
+            ("science fiction", 1),
+            ("young adult", 1),
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_and_replaced_genres() {
+    let mut catalog = GenreCatalog::new();
+    catalog.set_genres(1, &["Mystery", "mystery", "MYSTERY"]);
+    assert_eq!(catalog.genres_for(1), vec!["mystery"]);
+
+    catalog.add_genre(1, "Thriller");
+    assert_eq!(catalog.genres_for(1), vec!["mystery", "thriller"]);
+
+    catalog.set_genres(1, &["Horror"]);
+    assert_eq!(catalog.genres_for(1), vec!["horror"]);
+
+    catalog.set_genres::<&str>(1, &[]);
+    assert!(catalog.genre_counts().is_empty());
+}
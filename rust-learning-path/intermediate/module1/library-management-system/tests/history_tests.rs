@@ -0,0 +1,77 @@
+use chrono::NaiveDate;
+use library_management_system::{LibraryState, LoanLedger, LoanRecord};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+}
+
+/// Book 1 is borrowed three times, book 2 twice, book 3 once and still out
+fn ledger_with_history() -> LoanLedger {
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 7, date(1), 14).unwrap();
+    ledger.checkout(2, 8, date(1), 14).unwrap();
+    ledger.checkin(1, date(3)).unwrap();
+    ledger.checkout(1, 8, date(4), 14).unwrap();
+    ledger.checkin(2, date(5)).unwrap();
+    ledger.checkin(1, date(6)).unwrap();
+    ledger.checkout(2, 7, date(7), 14).unwrap();
+    ledger.checkin(2, date(8)).unwrap();
+    ledger.checkout(1, 7, date(9), 14).unwrap();
+    ledger.checkin(1, date(10)).unwrap();
+    ledger.checkout(3, 8, date(11), 14).unwrap();
+    ledger
+}
+
+#[test]
+fn test_history_survives_returns() {
+    let ledger = ledger_with_history();
+
+    assert_eq!(ledger.history().len(), 6);
+    assert_eq!(ledger.active_loans().len(), 1);
+
+    let book_one: Vec<(usize, NaiveDate, Option<NaiveDate>)> = ledger.history_for_book(1)
+        .iter()
+        .map(|record| (record.user_id, record.borrowed_on, record.returned_on))
+        .collect();
+    assert_eq!(book_one, vec![
+        (7, date(1), Some(date(3))),
+        (8, date(4), Some(date(6))),
+        (7, date(9), Some(date(10))),
+    ]);
+
+    assert_eq!(ledger.history_for_user(8), vec![
+        &LoanRecord { book_id: 2, user_id: 8, borrowed_on: date(1), returned_on: Some(date(5)) },
+        &LoanRecord { book_id: 1, user_id: 8, borrowed_on: date(4), returned_on: Some(date(6)) },
+        &LoanRecord { book_id: 3, user_id: 8, borrowed_on: date(11), returned_on: None },
+    ]);
+    assert!(ledger.history_for_user(99).is_empty());
+}
+
+#[test]
+fn test_popular_books_ranking() {
+    let ledger = ledger_with_history();
+    assert_eq!(ledger.popular_books(), vec![(1, 3), (2, 2), (3, 1)]);
+
+    // Ties are ranked by book id
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(5, 7, date(1), 14).unwrap();
+    ledger.checkout(4, 7, date(1), 14).unwrap();
+    assert_eq!(ledger.popular_books(), vec![(4, 1), (5, 1)]);
+}
+
+#[test]
+fn test_history_is_persisted() {
+    let path = std::env::temp_dir().join(format!("library_history_{}.json", std::process::id()));
+
+    let state = LibraryState {
+        ledger: ledger_with_history(),
+        ..LibraryState::default()
+    };
+    state.save(&path).unwrap();
+
+    let loaded = LibraryState::load(&path).unwrap();
+    assert_eq!(loaded.ledger.history(), state.ledger.history());
+    assert_eq!(loaded.ledger.popular_books(), vec![(1, 3), (2, 2), (3, 1)]);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,90 @@
+use chrono::NaiveDate;
+use library_management_system::{
+    Clock, LoanLedger, ManualClock, ReminderKind, ReminderLog, ReminderScheduler,
+};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+#[test]
+fn test_due_soon_then_overdue_once_per_loan() {
+    let clock = ManualClock::new(date(1));
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 10, clock.today(), 7).unwrap(); // due on the 8th
+    ledger.checkout(2, 20, clock.today(), 14).unwrap(); // due on the 15th
+    let mut log = ReminderLog::new();
+
+    // Nothing is close to due yet
+    assert!(log.scan(&ledger, clock.today(), 3).is_empty());
+
+    // Book 1 enters the reminder window
+    clock.set(date(5));
+    let created = log.scan(&ledger, clock.today(), 3);
+    assert_eq!(created.len(), 1);
+    assert_eq!((created[0].book_id, created[0].kind), (1, ReminderKind::DueSoon));
+
+    // Scanning again the same day, or the next, doesn't repeat it
+    assert!(log.scan(&ledger, clock.today(), 3).is_empty());
+    clock.advance_days(1);
+    assert!(log.scan(&ledger, clock.today(), 3).is_empty());
+
+    // Book 1 is overdue, book 2 is due within the window
+    clock.set(date(12));
+    let created = log.scan(&ledger, clock.today(), 3);
+    let kinds: Vec<(usize, ReminderKind)> = created.iter().map(|r| (r.book_id, r.kind)).collect();
+    assert_eq!(kinds, vec![(1, ReminderKind::Overdue), (2, ReminderKind::DueSoon)]);
+
+    clock.advance_days(10);
+    let created = log.scan(&ledger, clock.today(), 3);
+    assert_eq!(created.len(), 1);
+    assert_eq!((created[0].book_id, created[0].kind), (2, ReminderKind::Overdue));
+
+    assert_eq!(log.reminders().len(), 4);
+}
+
+#[test]
+fn test_returned_loans_get_no_reminders() {
+    let mut ledger = LoanLedger::new();
+    ledger.checkout(1, 10, date(1), 7).unwrap();
+    ledger.checkin(1, date(2)).unwrap();
+
+    let mut log = ReminderLog::new();
+    assert!(log.scan(&ledger, date(20), 3).is_empty());
+}
+
+#[test]
+fn test_scheduler_scans_and_stops() {
+    let clock = Arc::new(ManualClock::new(date(1)));
+    let ledger = Arc::new(Mutex::new(LoanLedger::new()));
+    let log = Arc::new(Mutex::new(ReminderLog::new()));
+    ledger.lock().unwrap().checkout(1, 10, date(1), 2).unwrap();
+
+    let scheduler = ReminderScheduler::start(
+        Arc::clone(&ledger),
+        Arc::clone(&log),
+        clock.clone(),
+        Duration::from_millis(5),
+        3,
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while log.lock().unwrap().reminders().is_empty() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(5));
+    }
+    clock.advance_days(5);
+    while log.lock().unwrap().reminders().len() < 2 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    // Let several more scans run before stopping
+    thread::sleep(Duration::from_millis(30));
+    scheduler.stop();
+
+    let log = log.lock().unwrap();
+    let kinds: Vec<ReminderKind> = log.reminders().iter().map(|r| r.kind).collect();
+    assert_eq!(kinds, vec![ReminderKind::DueSoon, ReminderKind::Overdue]);
+}
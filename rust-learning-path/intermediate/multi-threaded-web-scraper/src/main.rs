@@ -3,6 +3,7 @@ mod scraper;
 mod worker;
 
 use clap::{Arg, Command};
+use progress::Progress;
 use result::{ScrapeResults, ScraperError};
 use std::time::{Duration, Instant};
 use worker::{ThreadPoolConfig, ThreadPoolScraper, WorkerResult};
@@ -64,7 +65,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Run the scraper
-    let results = run_scraper(urls, num_threads, timeout_secs)?;
+    let progress = progress::for_terminal("URLs");
+    let results = run_scraper(urls, num_threads, timeout_secs, progress.as_ref())?;
     
     // Print results
     results.print_summary();
@@ -76,6 +78,7 @@ fn run_scraper(
     urls: Vec<String>,
     num_threads: usize,
     timeout_secs: u64,
+    progress: &dyn Progress,
 ) -> Result<ScrapeResults, ScraperError> {
     let start_time = Instant::now();
     
@@ -87,9 +90,11 @@ fn run_scraper(
     // Create the thread pool scraper
     let scraper = ThreadPoolScraper::new(config)?;
 
-    // Submit all URLs for processing
+    // Submit all URLs for processing; workers exit once they have drained them
     let total_urls = urls.len();
+    progress.set_total(total_urls as u64);
     scraper.submit_urls(urls)?;
+    scraper.close();
 
     // Collect results
     let mut results = ScrapeResults::new();
@@ -99,36 +104,47 @@ fn run_scraper(
     let mut workers_finished = 0;
     let expected_workers = num_threads;
 
-    println!("Processing URLs...");
-
-    // Process results as they come in
-    while completed < total_urls || workers_finished < expected_workers {
-        match scraper.receive_result_timeout(Duration::from_secs(1))? {
+    progress.println("Processing URLs...");
+
+    // Process results as they come in, until every URL has a result or
+    // every worker has exited. The channel disconnects once the last worker
+    // is gone, including one that panicked without saying it finished, so a
+    // dead worker can't leave this waiting forever. Lines go through the
+    // progress so they don't break up its bar.
+    while completed < total_urls && workers_finished < expected_workers {
+        let message = match scraper.receive_result_timeout(Duration::from_secs(1)) {
+            Ok(message) => message,
+            Err(_) => break,
+        };
+        match message {
             Some(WorkerResult::Success(result)) => {
                 completed += 1;
-                println!("✓ Completed {}/{}: {} ({}ms)", 
-                    completed, total_urls, result.url, result.response_time.as_millis());
+                progress.println(&format!("✓ Completed {}/{}: {} ({}ms)",
+                    completed, total_urls, result.url, result.response_time.as_millis()));
                 results.add_success(result);
+                progress.inc(1);
             }
             Some(WorkerResult::Error(error)) => {
                 completed += 1;
-                println!("✗ Failed {}/{}: {} - {}", 
-                    completed, total_urls, error.url, error.error);
+                progress.println(&format!("✗ Failed {}/{}: {} - {}",
+                    completed, total_urls, error.url, error.error));
                 results.add_failure(error);
+                progress.inc(1);
             }
             Some(WorkerResult::WorkerFinished) => {
                 workers_finished += 1;
-                println!("Worker finished ({}/{})", workers_finished, expected_workers);
+                progress.println(&format!("Worker finished ({}/{})", workers_finished, expected_workers));
             }
             None => {
-                // Timeout - print progress
-                if completed < total_urls {
-                    println!("Progress: {}/{} URLs completed...", completed, total_urls);
-                }
+                // Timeout - nothing arrived yet, keep waiting
             }
         }
     }
 
+    progress.finish();
+    if completed < total_urls {
+        eprintln!("Warning: {} URLs got no result because every worker exited", total_urls - completed);
+    }
     results.total_time = start_time.elapsed();
 
     // Shutdown the thread pool
@@ -140,11 +156,12 @@ fn run_scraper(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use progress::SilentProgress;
 
     #[test]
     fn test_run_scraper_with_invalid_urls() {
         let urls = vec!["not-a-valid-url".to_string()];
-        let result = run_scraper(urls, 1, 5);
+        let result = run_scraper(urls, 1, 5, &SilentProgress::new());
         
         // Should not panic, but may return errors for invalid URLs
         assert!(result.is_ok());
@@ -158,10 +175,22 @@ mod tests {
     #[test]
     fn test_run_scraper_empty_urls() {
         let urls = vec![];
-        let result = run_scraper(urls, 1, 5);
+        let result = run_scraper(urls, 1, 5, &SilentProgress::new());
         
         assert!(result.is_ok());
         let results = result.unwrap();
         assert_eq!(results.total_urls, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_scraper_reports_progress() {
+        let urls = vec!["not-a-valid-url".to_string(), "also-not-a-url".to_string()];
+        let progress = SilentProgress::new();
+        let results = run_scraper(urls, 2, 5, &progress).unwrap();
+
+        assert_eq!(results.failed.len(), 2);
+        assert_eq!(progress.total(), 2);
+        assert_eq!(progress.position(), 2);
+        assert!(progress.is_finished());
+    }
+}
@@ -1,18 +1,10 @@
 use crate::result::{ScrapeError, ScrapeResult, ScraperError};
 use crate::scraper::{ScraperConfig, WebScraper};
 use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
-
-/// Message types for communication between main thread and workers
-#[derive(Debug)]
-pub enum WorkerMessage {
-    /// A URL to scrape
-    ScrapeUrl(String),
-    /// Signal to shutdown the worker
-    Shutdown,
-}
+use work_queue::WorkQueue;
 
 /// Result messages sent back from workers to main thread
 #[derive(Debug)]
@@ -44,26 +36,24 @@ impl Default for ThreadPoolConfig {
 /// Multi-threaded web scraper using a thread pool
 pub struct ThreadPoolScraper {
     workers: Vec<Worker>,
-    sender: Option<Sender<WorkerMessage>>,
+    /// URLs waiting for a worker
+    queue: Arc<WorkQueue<String>>,
     result_receiver: Receiver<WorkerResult>,
 }
 
 impl ThreadPoolScraper {
     /// Create a new thread pool scraper
     pub fn new(config: ThreadPoolConfig) -> Result<Self, ScraperError> {
-        let (work_sender, work_receiver) = mpsc::channel();
+        let queue = Arc::new(WorkQueue::unbounded());
         let (result_sender, result_receiver) = mpsc::channel();
         
-        // Wrap the work receiver in Arc<Mutex<>> so it can be shared among workers
-        let work_receiver = Arc::new(Mutex::new(work_receiver));
-        
         let mut workers = Vec::with_capacity(config.num_threads);
         
         // Create worker threads
         for id in 0..config.num_threads {
             let worker = Worker::new(
                 id,
-                Arc::clone(&work_receiver),
+                Arc::clone(&queue),
                 result_sender.clone(),
                 config.scraper_config.clone(),
             )?;
@@ -72,19 +62,16 @@ impl ThreadPoolScraper {
         
         Ok(Self {
             workers,
-            sender: Some(work_sender),
+            queue,
             result_receiver,
         })
     }
 
     /// Submit a URL for scraping
     pub fn submit_url(&self, url: String) -> Result<(), ScraperError> {
-        if let Some(sender) = &self.sender {
-            sender
-                .send(WorkerMessage::ScrapeUrl(url))
-                .map_err(|e| ScraperError::ChannelError(e.to_string()))?;
-        }
-        Ok(())
+        self.queue
+            .push(url)
+            .map_err(|_| ScraperError::ChannelError("Scraper has been shut down".to_string()))
     }
 
     /// Submit multiple URLs for scraping
@@ -113,16 +100,19 @@ impl ThreadPoolScraper {
         }
     }
 
+    /// Stop accepting URLs
+    ///
+    /// Workers scrape the URLs already queued and then exit, each sending
+    /// `WorkerFinished`. Once every worker is gone, receiving a result fails.
+    pub fn close(&self) {
+        self.queue.close();
+    }
+
     /// Shutdown all workers and wait for them to finish
-    pub fn shutdown(mut self) -> Result<(), ScraperError> {
-        // Send shutdown signal to all workers
-        if let Some(sender) = self.sender.take() {
-            for _ in 0..self.workers.len() {
-                sender
-                    .send(WorkerMessage::Shutdown)
-                    .map_err(|e| ScraperError::ChannelError(e.to_string()))?;
-            }
-        }
+    ///
+    /// Workers scrape any URLs still queued before they stop.
+    pub fn shutdown(self) -> Result<(), ScraperError> {
+        self.queue.close();
 
         // Wait for all workers to finish
         for worker in self.workers {
@@ -146,7 +136,7 @@ struct Worker {
 impl Worker {
     fn new(
         id: usize,
-        receiver: Arc<Mutex<Receiver<WorkerMessage>>>,
+        queue: Arc<WorkQueue<String>>,
         sender: Sender<WorkerResult>,
         config: ScraperConfig,
     ) -> Result<Self, ScraperError> {
@@ -160,46 +150,25 @@ impl Worker {
                 }
             };
 
-            println!("Worker {} started", id);
-
-            loop {
-                // Receive work from the shared channel
-                let message = {
-                    let receiver = receiver.lock().unwrap();
-                    receiver.recv()
-                };
-
-                match message {
-                    Ok(WorkerMessage::ScrapeUrl(url)) => {
-                        println!("Worker {} processing: {}", id, url);
-                        
-                        // Perform the scraping
-                        match scraper.scrape_url(&url) {
-                            Ok(result) => {
-                                if let Err(e) = sender.send(WorkerResult::Success(result)) {
-                                    eprintln!("Worker {}: Failed to send result: {}", id, e);
-                                    break;
-                                }
-                            }
-                            Err(e) => {
-                                let error = ScrapeError {
-                                    url: url.clone(),
-                                    error: e.to_string(),
-                                };
-                                if let Err(e) = sender.send(WorkerResult::Error(error)) {
-                                    eprintln!("Worker {}: Failed to send error: {}", id, e);
-                                    break;
-                                }
-                            }
+            // Take URLs from the shared queue until it's closed and empty
+            while let Some(url) = queue.pop() {
+                // Perform the scraping
+                match scraper.scrape_url(&url) {
+                    Ok(result) => {
+                        if let Err(e) = sender.send(WorkerResult::Success(result)) {
+                            eprintln!("Worker {}: Failed to send result: {}", id, e);
+                            break;
                         }
                     }
-                    Ok(WorkerMessage::Shutdown) => {
-                        println!("Worker {} shutting down", id);
-                        break;
-                    }
                     Err(e) => {
-                        eprintln!("Worker {}: Channel error: {}", id, e);
-                        break;
+                        let error = ScrapeError {
+                            url: url.clone(),
+                            error: e.to_string(),
+                        };
+                        if let Err(e) = sender.send(WorkerResult::Error(error)) {
+                            eprintln!("Worker {}: Failed to send error: {}", id, e);
+                            break;
+                        }
                     }
                 }
             }
@@ -208,8 +177,6 @@ impl Worker {
             if let Err(e) = sender.send(WorkerResult::WorkerFinished) {
                 eprintln!("Worker {}: Failed to send finish signal: {}", id, e);
             }
-
-            println!("Worker {} finished", id);
         });
 
         Ok(Worker {
@@ -252,19 +219,41 @@ mod tests {
     }
 
     #[test]
-    fn test_worker_message_types() {
-        let url_message = WorkerMessage::ScrapeUrl("https://example.com".to_string());
-        let shutdown_message = WorkerMessage::Shutdown;
-        
-        match url_message {
-            WorkerMessage::ScrapeUrl(url) => assert_eq!(url, "https://example.com"),
-            _ => panic!("Wrong message type"),
-        }
-        
-        match shutdown_message {
-            WorkerMessage::Shutdown => (),
-            _ => panic!("Wrong message type"),
+    fn test_shutdown_closes_queue() {
+        let config = ThreadPoolConfig {
+            num_threads: 1,
+            scraper_config: ScraperConfig::default(),
+        };
+        let pool = ThreadPoolScraper::new(config).unwrap();
+        let queue = Arc::clone(&pool.queue);
+        pool.shutdown().unwrap();
+
+        assert!(queue.push("https://example.com".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_results_end_once_workers_drain_a_closed_queue() {
+        let config = ThreadPoolConfig {
+            num_threads: 2,
+            scraper_config: ScraperConfig::default(),
+        };
+        let pool = ThreadPoolScraper::new(config).unwrap();
+        pool.submit_url("not-a-valid-url".to_string()).unwrap();
+        pool.close();
+
+        let mut errors = 0;
+        let mut finished = 0;
+        while let Ok(message) = pool.receive_result_timeout(Duration::from_secs(5)) {
+            match message {
+                Some(WorkerResult::Error(_)) => errors += 1,
+                Some(WorkerResult::WorkerFinished) => finished += 1,
+                other => panic!("unexpected message: {:?}", other),
+            }
         }
+
+        assert_eq!(errors, 1);
+        assert_eq!(finished, 2);
+        pool.shutdown().unwrap();
     }
 
     #[test]
@@ -0,0 +1,148 @@
+//! Progress reporting for long-running operations
+//!
+//! Processing loops report their progress through the `Progress` trait
+//! rather than drawing anything themselves, so the same code can show a
+//! progress bar in a terminal and stay silent in tests or when output is
+//! redirected. Shared by the data processor and the web scraper.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Receives progress updates from a processing loop
+///
+/// Implementations take `&self` and must be `Send + Sync`, so parallel
+/// workers can report through the same instance.
+pub trait Progress: Send + Sync {
+    /// Start a new run of `total` units, with none of them done yet
+    fn set_total(&self, total: u64);
+
+    /// Record that `delta` more units are done
+    fn inc(&self, delta: u64);
+
+    /// Record that the run is over
+    fn finish(&self);
+
+    /// Print a line to stdout without breaking up a bar being drawn
+    fn println(&self, line: &str) {
+        println!("{}", line);
+    }
+}
+
+/// Progress that draws nothing, for tests and non-terminal output
+///
+/// It still keeps count, so tests can check what a loop reported.
+#[derive(Debug, Default)]
+pub struct SilentProgress {
+    total: AtomicU64,
+    position: AtomicU64,
+    finished: AtomicBool,
+}
+
+impl SilentProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the total set by the last `set_total`
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// Get the sum of the increments since the last `set_total`
+    pub fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    /// Check whether the current run has finished
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+impl Progress for SilentProgress {
+    fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+        self.position.store(0, Ordering::Relaxed);
+        self.finished.store(false, Ordering::Relaxed);
+    }
+
+    fn inc(&self, delta: u64) {
+        self.position.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    fn finish(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Progress drawn as a bar on stderr
+pub struct BarProgress {
+    bar: ProgressBar,
+}
+
+impl BarProgress {
+    /// Create a bar labelled with `message`, e.g. "records" or "bytes"
+    pub fn new(message: &'static str) -> Self {
+        let bar = ProgressBar::new(0);
+        bar.set_style(
+            ProgressStyle::with_template("{bar:40} {pos}/{len} {msg} ({elapsed}, eta {eta})")
+                .expect("progress bar template is valid"),
+        );
+        bar.set_message(message);
+        Self { bar }
+    }
+}
+
+impl Progress for BarProgress {
+    fn set_total(&self, total: u64) {
+        self.bar.reset();
+        self.bar.set_length(total);
+    }
+
+    fn inc(&self, delta: u64) {
+        self.bar.inc(delta);
+    }
+
+    fn finish(&self) {
+        self.bar.finish();
+    }
+
+    fn println(&self, line: &str) {
+        // Hide the bar while the line is printed, then draw it again below
+        self.bar.suspend(|| println!("{}", line));
+    }
+}
+
+/// Get a progress bar if stderr is a terminal, or silent progress otherwise
+pub fn for_terminal(message: &'static str) -> Arc<dyn Progress> {
+    if std::io::stderr().is_terminal() {
+        Arc::new(BarProgress::new(message))
+    } else {
+        Arc::new(SilentProgress::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silent_progress_counts() {
+        let progress = SilentProgress::new();
+        progress.set_total(10);
+        progress.inc(4);
+        progress.inc(6);
+        progress.finish();
+
+        assert_eq!(progress.total(), 10);
+        assert_eq!(progress.position(), 10);
+        assert!(progress.is_finished());
+
+        // A new run starts from zero
+        progress.set_total(3);
+        assert_eq!(progress.position(), 0);
+        assert!(!progress.is_finished());
+    }
+}
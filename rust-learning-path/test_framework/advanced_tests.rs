@@ -1,15 +1,15 @@
 // Comprehensive unit tests for Advanced Level code examples
-use crate::framework::{TestResult, TestSuite};
-use crate::{test_case, assert_with_msg};
+use crate::framework::TestSuite;
+use crate::assert_with_msg;
 use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
 use std::time::Duration;
 
 /// Test concurrency concepts
 pub fn test_concurrency_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Advanced Level - Concurrency Concepts".to_string());
+    let mut suite = TestSuite::for_level("Advanced Level - Concurrency Concepts".to_string(), "advanced");
     
-    suite.add_test(test_case!("Concurrency - Basic Threading", || {
+    suite.add_case("Concurrency - Basic Threading", || {
         let handle = thread::spawn(|| {
             for i in 1..10 {
                 println!("hi number {} from the spawned thread!", i);
@@ -24,9 +24,9 @@ pub fn test_concurrency_concepts() -> TestSuite {
         
         handle.join().unwrap();
         assert_with_msg!(true, "Thread should complete successfully");
-    }));
+    });
     
-    suite.add_test(test_case!("Concurrency - Move Closures", || {
+    suite.add_case("Concurrency - Move Closures", || {
         let v = vec![1, 2, 3];
         
         let handle = thread::spawn(move || {
@@ -36,9 +36,9 @@ pub fn test_concurrency_concepts() -> TestSuite {
         
         let result = handle.join().unwrap();
         assert_with_msg!(result == 3, "Move closure should transfer ownership to thread");
-    }));
+    });
     
-    suite.add_test(test_case!("Concurrency - Message Passing", || {
+    suite.add_case("Concurrency - Message Passing", || {
         let (tx, rx) = mpsc::channel();
         
         thread::spawn(move || {
@@ -48,9 +48,9 @@ pub fn test_concurrency_concepts() -> TestSuite {
         
         let received = rx.recv().unwrap();
         assert_with_msg!(received == "hi", "Message passing should work between threads");
-    }));
+    });
     
-    suite.add_test(test_case!("Concurrency - Multiple Producers", || {
+    suite.add_case("Concurrency - Multiple Producers", || {
         let (tx, rx) = mpsc::channel();
         
         let tx1 = tx.clone();
@@ -92,9 +92,9 @@ pub fn test_concurrency_concepts() -> TestSuite {
         }
         
         assert_with_msg!(received_count == 8, "Should receive messages from multiple producers");
-    }));
+    });
     
-    suite.add_test(test_case!("Concurrency - Shared State with Mutex", || {
+    suite.add_case("Concurrency - Shared State with Mutex", || {
         let counter = Arc::new(Mutex::new(0));
         let mut handles = vec![];
         
@@ -113,16 +113,16 @@ pub fn test_concurrency_concepts() -> TestSuite {
         
         let result = *counter.lock().unwrap();
         assert_with_msg!(result == 10, "Mutex should protect shared state across threads");
-    }));
+    });
     
     suite
 }
 
 /// Test unsafe Rust concepts
 pub fn test_unsafe_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Advanced Level - Unsafe Rust Concepts".to_string());
+    let mut suite = TestSuite::for_level("Advanced Level - Unsafe Rust Concepts".to_string(), "advanced");
     
-    suite.add_test(test_case!("Unsafe - Raw Pointers", || {
+    suite.add_case("Unsafe - Raw Pointers", || {
         let mut num = 5;
         
         let r1 = &num as *const i32;
@@ -133,9 +133,9 @@ pub fn test_unsafe_concepts() -> TestSuite {
             *r2 = 10;
             assert_with_msg!(*r1 == 10, "Mutable raw pointer should allow modification");
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Unsafe - Unsafe Functions", || {
+    suite.add_case("Unsafe - Unsafe Functions", || {
         unsafe fn dangerous() -> i32 {
             42
         }
@@ -144,9 +144,9 @@ pub fn test_unsafe_concepts() -> TestSuite {
             let result = dangerous();
             assert_with_msg!(result == 42, "Unsafe function should execute correctly");
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Unsafe - Safe Abstraction", || {
+    suite.add_case("Unsafe - Safe Abstraction", || {
         fn split_at_mut(slice: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
             let len = slice.len();
             let ptr = slice.as_mut_ptr();
@@ -168,9 +168,9 @@ pub fn test_unsafe_concepts() -> TestSuite {
         assert_with_msg!(right.len() == 3, "Right slice should have correct length");
         assert_with_msg!(left[0] == 1, "Left slice should contain correct elements");
         assert_with_msg!(right[0] == 4, "Right slice should contain correct elements");
-    }));
+    });
     
-    suite.add_test(test_case!("Unsafe - Static Variables", || {
+    suite.add_case("Unsafe - Static Variables", || {
         static mut COUNTER: usize = 0;
         
         fn add_to_count(inc: usize) {
@@ -184,16 +184,16 @@ pub fn test_unsafe_concepts() -> TestSuite {
         unsafe {
             assert_with_msg!(COUNTER == 3, "Static mutable variable should be modifiable");
         }
-    }));
+    });
     
     suite
 }
 
 /// Test advanced trait concepts
 pub fn test_advanced_trait_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Advanced Level - Advanced Trait Concepts".to_string());
+    let mut suite = TestSuite::for_level("Advanced Level - Advanced Trait Concepts".to_string(), "advanced");
     
-    suite.add_test(test_case!("Advanced Traits - Associated Types", || {
+    suite.add_case("Advanced Traits - Associated Types", || {
         trait Iterator {
             type Item;
             
@@ -230,9 +230,9 @@ pub fn test_advanced_trait_concepts() -> TestSuite {
         assert_with_msg!(counter.next() == Some(1), "Iterator should return second item");
         assert_with_msg!(counter.next() == Some(2), "Iterator should return third item");
         assert_with_msg!(counter.next() == None, "Iterator should return None when exhausted");
-    }));
+    });
     
-    suite.add_test(test_case!("Advanced Traits - Operator Overloading", || {
+    suite.add_case("Advanced Traits - Operator Overloading", || {
         use std::ops::Add;
         
         #[derive(Debug, PartialEq)]
@@ -257,9 +257,9 @@ pub fn test_advanced_trait_concepts() -> TestSuite {
         let p3 = p1 + p2;
         
         assert_with_msg!(p3 == Point { x: 3, y: 3 }, "Operator overloading should work");
-    }));
+    });
     
-    suite.add_test(test_case!("Advanced Traits - Trait Objects", || {
+    suite.add_case("Advanced Traits - Trait Objects", || {
         trait Draw {
             fn draw(&self) -> String;
         }
@@ -321,16 +321,16 @@ pub fn test_advanced_trait_concepts() -> TestSuite {
         assert_with_msg!(results.len() == 2, "Trait objects should allow heterogeneous collections");
         assert_with_msg!(results[0] == "SelectBox with 3 options", "First trait object should work");
         assert_with_msg!(results[1] == "Button: OK", "Second trait object should work");
-    }));
+    });
     
     suite
 }
 
 /// Test macro concepts
 pub fn test_macro_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Advanced Level - Macro Concepts".to_string());
+    let mut suite = TestSuite::for_level("Advanced Level - Macro Concepts".to_string(), "advanced");
     
-    suite.add_test(test_case!("Macros - Declarative Macros", || {
+    suite.add_case("Macros - Declarative Macros", || {
         macro_rules! vec_custom {
             ( $( $x:expr ),* ) => {
                 {
@@ -349,9 +349,9 @@ pub fn test_macro_concepts() -> TestSuite {
         assert_with_msg!(v1.len() == 3, "Custom vec macro should create vector with correct length");
         assert_with_msg!(v1[0] == 1, "Custom vec macro should store elements correctly");
         assert_with_msg!(v2.len() == 5, "Custom vec macro should handle variable arguments");
-    }));
+    });
     
-    suite.add_test(test_case!("Macros - Pattern Matching in Macros", || {
+    suite.add_case("Macros - Pattern Matching in Macros", || {
         macro_rules! calculate {
             (eval $e:expr) => {{
                 {
@@ -367,9 +367,9 @@ pub fn test_macro_concepts() -> TestSuite {
         };
         
         assert_with_msg!(result == 7, "Macro should evaluate expression correctly");
-    }));
+    });
     
-    suite.add_test(test_case!("Macros - Repetition in Macros", || {
+    suite.add_case("Macros - Repetition in Macros", || {
         macro_rules! find_min {
             ($x:expr) => ($x);
             ($x:expr, $($y:expr),+) => (
@@ -382,16 +382,16 @@ pub fn test_macro_concepts() -> TestSuite {
         
         let min = find_min!(5u32, 2, 8, 1, 9);
         assert_with_msg!(min == 1, "Recursive macro should handle multiple values");
-    }));
+    });
     
     suite
 }
 
 /// Test error handling patterns
 pub fn test_error_handling_patterns() -> TestSuite {
-    let mut suite = TestSuite::new("Advanced Level - Error Handling Patterns".to_string());
+    let mut suite = TestSuite::for_level("Advanced Level - Error Handling Patterns".to_string(), "advanced");
     
-    suite.add_test(test_case!("Error Handling - Custom Error Types", || {
+    suite.add_case("Error Handling - Custom Error Types", || {
         use std::fmt;
         use std::error::Error;
         
@@ -441,9 +441,9 @@ pub fn test_error_handling_patterns() -> TestSuite {
         
         let result = sqrt(-1.0);
         assert_with_msg!(result.is_err(), "Negative square root should fail");
-    }));
+    });
     
-    suite.add_test(test_case!("Error Handling - Error Propagation", || {
+    suite.add_case("Error Handling - Error Propagation", || {
         use std::fs::File;
         use std::io::{self, Read};
         
@@ -468,7 +468,7 @@ pub fn test_error_handling_patterns() -> TestSuite {
         
         let result = last_char_of_first_line("");
         assert_with_msg!(result == None, "Empty string should return None");
-    }));
+    });
     
     suite
 }
\ No newline at end of file
@@ -0,0 +1,200 @@
+// Function-level coverage tracking for the Rust Learning Path examples
+// Level suites register each of their cases as an example under the level,
+// running a case marks it exercised, and the report lists the examples no
+// test touched.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Records which example functions exist and which ones tests have exercised
+#[derive(Debug, Default)]
+pub struct CoverageTracker {
+    /// level -> example function -> number of times a test exercised it
+    levels: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl CoverageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Register an example function under a level ("basic", "intermediate", ...).
+    /// Registering the same function twice keeps its hit count.
+    pub fn register(&mut self, level: &str, function: &str) {
+        self.levels
+            .entry(level.to_string())
+            .or_default()
+            .entry(function.to_string())
+            .or_insert(0);
+    }
+    
+    /// Mark an example function as exercised by a test.
+    /// Returns false if no level has registered a function by that name.
+    pub fn exercise(&mut self, function: &str) -> bool {
+        let mut found = false;
+        for functions in self.levels.values_mut() {
+            if let Some(hits) = functions.get_mut(function) {
+                *hits += 1;
+                found = true;
+            }
+        }
+        found
+    }
+    
+    /// Build the per-level report of exercised and unexercised functions
+    pub fn report(&self) -> CoverageReport {
+        let levels = self.levels.iter()
+            .map(|(level, functions)| {
+                let (exercised, unexercised): (Vec<_>, Vec<_>) = functions.iter()
+                    .partition(|(_, &hits)| hits > 0);
+                LevelCoverage {
+                    level: level.clone(),
+                    exercised: exercised.into_iter().map(|(name, _)| name.clone()).collect(),
+                    unexercised: unexercised.into_iter().map(|(name, _)| name.clone()).collect(),
+                }
+            })
+            .collect();
+        
+        CoverageReport { levels }
+    }
+    
+    /// Forget every registration and hit
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+}
+
+/// Coverage of one level's example functions, each list sorted by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelCoverage {
+    pub level: String,
+    pub exercised: Vec<String>,
+    pub unexercised: Vec<String>,
+}
+
+impl LevelCoverage {
+    pub fn total_count(&self) -> usize {
+        self.exercised.len() + self.unexercised.len()
+    }
+    
+    pub fn coverage_percent(&self) -> f64 {
+        if self.total_count() == 0 {
+            0.0
+        } else {
+            self.exercised.len() as f64 / self.total_count() as f64 * 100.0
+        }
+    }
+}
+
+/// Coverage of every registered level, in level name order
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    pub levels: Vec<LevelCoverage>,
+}
+
+impl CoverageReport {
+    pub fn level(&self, level: &str) -> Option<&LevelCoverage> {
+        self.levels.iter().find(|coverage| coverage.level == level)
+    }
+    
+    /// Every unexercised function across all levels
+    pub fn unexercised(&self) -> Vec<&str> {
+        self.levels.iter()
+            .flat_map(|coverage| coverage.unexercised.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n========== EXAMPLE COVERAGE ==========")?;
+        if self.levels.is_empty() {
+            return writeln!(f, "No example functions registered");
+        }
+        
+        for coverage in &self.levels {
+            writeln!(f, "{}: {}/{} examples exercised ({:.1}%)",
+                     coverage.level, coverage.exercised.len(), coverage.total_count(), coverage.coverage_percent())?;
+            for function in &coverage.unexercised {
+                writeln!(f, "  not exercised: {}", function)?;
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// The tracker shared by the whole test run
+pub fn global() -> MutexGuard<'static, CoverageTracker> {
+    static TRACKER: OnceLock<Mutex<CoverageTracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| Mutex::new(CoverageTracker::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Register an example function with the shared tracker
+pub fn register(level: &str, function: &str) {
+    global().register(level, function);
+}
+
+/// Mark an example function as exercised in the shared tracker
+pub fn exercise(function: &str) -> bool {
+    global().exercise(function)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn tracker() -> CoverageTracker {
+        let mut tracker = CoverageTracker::new();
+        tracker.register("basic", "variables_example");
+        tracker.register("basic", "shadowing_example");
+        tracker.register("basic", "loops_example");
+        tracker.register("advanced", "thread_pool_example");
+        tracker.register("advanced", "unsafe_example");
+        tracker
+    }
+    
+    #[test]
+    fn test_report_lists_unexercised_functions() {
+        let mut tracker = tracker();
+        assert!(tracker.exercise("variables_example"));
+        assert!(tracker.exercise("loops_example"));
+        assert!(tracker.exercise("loops_example"));
+        assert!(tracker.exercise("thread_pool_example"));
+        
+        let report = tracker.report();
+        
+        let basic = report.level("basic").unwrap();
+        assert_eq!(basic.exercised, ["loops_example", "variables_example"]);
+        assert_eq!(basic.unexercised, ["shadowing_example"]);
+        assert!((basic.coverage_percent() - 66.7).abs() < 0.1);
+        
+        assert_eq!(report.unexercised(), ["unsafe_example", "shadowing_example"]);
+        
+        let text = report.to_string();
+        assert!(text.contains("basic: 2/3 examples exercised"));
+        assert!(text.contains("not exercised: unsafe_example"));
+        assert!(!text.contains("not exercised: loops_example"));
+    }
+    
+    #[test]
+    fn test_unknown_function_is_not_counted() {
+        let mut tracker = tracker();
+        
+        assert!(!tracker.exercise("no_such_example"));
+        assert_eq!(tracker.report().unexercised().len(), 5);
+    }
+    
+    #[test]
+    fn test_registering_twice_keeps_hits() {
+        let mut tracker = tracker();
+        tracker.exercise("unsafe_example");
+        tracker.register("advanced", "unsafe_example");
+        
+        let report = tracker.report();
+        assert_eq!(report.level("advanced").unwrap().exercised, ["unsafe_example"]);
+    }
+}
@@ -1,15 +1,15 @@
 // Comprehensive unit tests for Expert Level code examples
-use crate::framework::{TestResult, TestSuite};
-use crate::{test_case, assert_with_msg};
+use crate::framework::TestSuite;
+use crate::assert_with_msg;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// Test async programming concepts
 pub fn test_async_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Expert Level - Async Programming Concepts".to_string());
+    let mut suite = TestSuite::for_level("Expert Level - Async Programming Concepts".to_string(), "expert");
     
-    suite.add_test(test_case!("Async - Basic Future", || {
+    suite.add_case("Async - Basic Future", || {
         struct SimpleFuture {
             completed: bool,
         }
@@ -42,9 +42,9 @@ pub fn test_async_concepts() -> TestSuite {
             Poll::Ready(value) => assert_with_msg!(value == 42, "Second poll should return Ready with value"),
             Poll::Pending => panic!("Second poll should be ready"),
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Async - Timer Future", || {
+    suite.add_case("Async - Timer Future", || {
         use std::time::{Duration, Instant};
         
         struct TimerFuture {
@@ -74,7 +74,7 @@ pub fn test_async_concepts() -> TestSuite {
             }
         }
         
-        let timer = TimerFuture::new(Duration::from_millis(1));
+        let mut timer = TimerFuture::new(Duration::from_millis(1));
         let waker = futures::task::noop_waker();
         let mut context = Context::from_waker(&waker);
         
@@ -85,9 +85,9 @@ pub fn test_async_concepts() -> TestSuite {
             Poll::Ready(()) => assert_with_msg!(true, "Timer should be ready after duration"),
             Poll::Pending => panic!("Timer should be ready"),
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Async - Executor Basics", || {
+    suite.add_case("Async - Executor Basics", || {
         use std::sync::{Arc, Mutex};
         use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
         use std::task::{Waker, RawWaker, RawWakerVTable};
@@ -160,16 +160,16 @@ pub fn test_async_concepts() -> TestSuite {
         
         executor.run();
         assert_with_msg!(true, "Simple executor should run async tasks");
-    }));
+    });
     
     suite
 }
 
 /// Test advanced memory management concepts
 pub fn test_memory_management_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Expert Level - Memory Management Concepts".to_string());
+    let mut suite = TestSuite::for_level("Expert Level - Memory Management Concepts".to_string(), "expert");
     
-    suite.add_test(test_case!("Memory - Custom Allocator Basics", || {
+    suite.add_case("Memory - Custom Allocator Basics", || {
         use std::alloc::{GlobalAlloc, Layout, System};
         use std::sync::atomic::{AtomicUsize, Ordering};
         
@@ -204,9 +204,9 @@ pub fn test_memory_management_concepts() -> TestSuite {
         
         // Note: This might not be exactly equal due to other allocations
         assert_with_msg!(after_dealloc <= after_alloc, "Deallocation should decrease or maintain memory usage");
-    }));
+    });
     
-    suite.add_test(test_case!("Memory - Memory Layout", || {
+    suite.add_case("Memory - Memory Layout", || {
         use std::alloc::Layout;
         use std::mem;
         
@@ -236,9 +236,9 @@ pub fn test_memory_management_concepts() -> TestSuite {
         
         assert_with_msg!(i32_layout.align() == 4, "i32 should be 4-byte aligned");
         assert_with_msg!(i64_layout.align() == 8, "i64 should be 8-byte aligned");
-    }));
+    });
     
-    suite.add_test(test_case!("Memory - Manual Memory Management", || {
+    suite.add_case("Memory - Manual Memory Management", || {
         use std::alloc::{alloc, dealloc, Layout};
         use std::ptr;
         
@@ -258,16 +258,16 @@ pub fn test_memory_management_concepts() -> TestSuite {
             // Clean up
             dealloc(ptr as *mut u8, layout);
         }
-    }));
+    });
     
     suite
 }
 
 /// Test performance optimization concepts
 pub fn test_performance_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Expert Level - Performance Optimization Concepts".to_string());
+    let mut suite = TestSuite::for_level("Expert Level - Performance Optimization Concepts".to_string(), "expert");
     
-    suite.add_test(test_case!("Performance - SIMD Operations", || {
+    suite.add_case("Performance - SIMD Operations", || {
         // Note: This is a simplified example. Real SIMD would use platform-specific intrinsics
         fn add_arrays_scalar(a: &[f32], b: &[f32], result: &mut [f32]) {
             for i in 0..a.len() {
@@ -293,9 +293,9 @@ pub fn test_performance_concepts() -> TestSuite {
         assert_with_msg!(result1 == vec![6.0, 8.0, 10.0, 12.0], "Scalar addition should work correctly");
         assert_with_msg!(result2 == vec![6.0, 8.0, 10.0, 12.0], "Vectorized addition should work correctly");
         assert_with_msg!(result1 == result2, "Both methods should produce same results");
-    }));
+    });
     
-    suite.add_test(test_case!("Performance - Cache-Friendly Data Structures", || {
+    suite.add_case("Performance - Cache-Friendly Data Structures", || {
         // Array of Structures (AoS) - less cache friendly
         #[derive(Clone)]
         struct Point3D {
@@ -356,9 +356,9 @@ pub fn test_performance_concepts() -> TestSuite {
         
         assert_with_msg!((aos_sum - soa_sum).abs() < f32::EPSILON, "Both approaches should give same result");
         assert_with_msg!(soa_points.len() == 1000, "SoA should store all points");
-    }));
+    });
     
-    suite.add_test(test_case!("Performance - Branch Prediction", || {
+    suite.add_case("Performance - Branch Prediction", || {
         // Demonstrate the impact of predictable vs unpredictable branches
         fn sum_if_positive_predictable(data: &[i32]) -> i32 {
             let mut sum = 0;
@@ -388,16 +388,16 @@ pub fn test_performance_concepts() -> TestSuite {
         assert_with_msg!(sorted_sum1 == sorted_sum2, "Both methods should give same result for sorted data");
         assert_with_msg!(mixed_sum1 == mixed_sum2, "Both methods should give same result for mixed data");
         assert_with_msg!(sorted_sum1 > 0, "Sum of positive numbers should be positive");
-    }));
+    });
     
     suite
 }
 
 /// Test compiler internals concepts
 pub fn test_compiler_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Expert Level - Compiler Internals Concepts".to_string());
+    let mut suite = TestSuite::for_level("Expert Level - Compiler Internals Concepts".to_string(), "expert");
     
-    suite.add_test(test_case!("Compiler - Procedural Macros Basics", || {
+    suite.add_case("Compiler - Procedural Macros Basics", || {
         // This is a conceptual test - real proc macros require separate crates
         // We'll test the concepts using function-like behavior
         
@@ -420,9 +420,9 @@ pub fn test_compiler_concepts() -> TestSuite {
         assert_with_msg!(generated.contains("impl Debug for Point"), "Generated code should implement Debug");
         assert_with_msg!(generated.contains("self.x"), "Generated code should access x field");
         assert_with_msg!(generated.contains("self.y"), "Generated code should access y field");
-    }));
+    });
     
-    suite.add_test(test_case!("Compiler - Attribute Macros Concept", || {
+    suite.add_case("Compiler - Attribute Macros Concept", || {
         // Simulate what an attribute macro might do
         fn benchmark_wrapper(function_name: &str, function_body: &str) -> String {
             format!(
@@ -438,9 +438,9 @@ pub fn test_compiler_concepts() -> TestSuite {
         assert_with_msg!(wrapped.contains("Instant::now()"), "Wrapper should add timing");
         assert_with_msg!(wrapped.contains("println!(\"Hello, world!\");"), "Wrapper should preserve original code");
         assert_with_msg!(wrapped.contains("duration"), "Wrapper should measure duration");
-    }));
+    });
     
-    suite.add_test(test_case!("Compiler - Const Evaluation", || {
+    suite.add_case("Compiler - Const Evaluation", || {
         const fn fibonacci(n: usize) -> usize {
             match n {
                 0 => 0,
@@ -463,7 +463,7 @@ pub fn test_compiler_concepts() -> TestSuite {
         
         assert_with_msg!(arr5.len() == 5, "Const generic should create array of correct size");
         assert_with_msg!(arr10.len() == 10, "Const generic should work with different values");
-    }));
+    });
     
     suite
 }
\ No newline at end of file
@@ -2,7 +2,14 @@
 // This module provides utilities for testing all code examples
 
 use std::collections::HashMap;
-use std::fmt;
+use std::fmt::{self, Debug};
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe, RefUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use crate::coverage;
 
 /// Test result for individual test cases
 #[derive(Debug, Clone)]
@@ -39,11 +46,121 @@ impl fmt::Display for TestResult {
     }
 }
 
+/// A test that is run by the `TestRunner` instead of when the suite is built
+pub struct TestCase {
+    pub name: String,
+    /// Level whose example this case covers, marked exercised when it runs
+    level: Option<String>,
+    body: Box<dyn Fn() + Send + Sync + RefUnwindSafe>,
+}
+
+impl TestCase {
+    pub fn new<F>(name: &str, body: F) -> Self
+    where
+        F: Fn() + Send + Sync + RefUnwindSafe + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            level: None,
+            body: Box::new(body),
+        }
+    }
+    
+    /// Register this case as an example of `level` with the shared coverage
+    /// tracker, so running it counts as exercising that example
+    pub fn covering(mut self, level: &str) -> Self {
+        coverage::register(level, &self.name);
+        self.level = Some(level.to_string());
+        self
+    }
+    
+    /// Run the test, turning a panic into a failed result
+    pub fn run(&self) -> TestResult {
+        if self.level.is_some() {
+            coverage::exercise(&self.name);
+        }
+        
+        let start = Instant::now();
+        let result = panic::catch_unwind(|| (self.body)());
+        let duration = start.elapsed().as_millis();
+        
+        match result {
+            Ok(()) => TestResult::success(self.name.clone(), duration),
+            Err(panic_info) => {
+                let message = if let Some(s) = panic_info.downcast_ref::<&str>() {
+                    format!("Panic: {}", s)
+                } else if let Some(s) = panic_info.downcast_ref::<String>() {
+                    format!("Panic: {}", s)
+                } else {
+                    "Test panicked with unknown error".to_string()
+                };
+                TestResult::failure(self.name.clone(), message, duration)
+            }
+        }
+    }
+}
+
+/// Run `f` on the input of every `(input, expected)` case and compare the
+/// output with the expected value
+/// 
+/// Each case becomes its own `TestResult` named `"{name} [{input:?}]"`, so a
+/// failure points at the input that caused it. A panic in `f` fails only
+/// that case.
+pub fn run_cases<I, E, F>(name: &str, cases: &[(I, E)], f: F) -> Vec<TestResult>
+where
+    I: Debug,
+    E: Debug + PartialEq,
+    F: Fn(&I) -> E,
+{
+    cases.iter()
+        .map(|(input, expected)| {
+            let case_name = format!("{} [{:?}]", name, input);
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(input)));
+            let duration = start.elapsed().as_millis();
+            
+            match result {
+                Ok(actual) if actual == *expected => TestResult::success(case_name, duration),
+                Ok(actual) => TestResult::failure(
+                    case_name,
+                    format!("expected {:?}, got {:?}", expected, actual),
+                    duration,
+                ),
+                Err(panic_info) => {
+                    let message = if let Some(s) = panic_info.downcast_ref::<&str>() {
+                        format!("Panic: {}", s)
+                    } else if let Some(s) = panic_info.downcast_ref::<String>() {
+                        format!("Panic: {}", s)
+                    } else {
+                        "Test panicked with unknown error".to_string()
+                    };
+                    TestResult::failure(case_name, message, duration)
+                }
+            }
+        })
+        .collect()
+}
+
+impl fmt::Debug for TestCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestCase")
+            .field("name", &self.name)
+            .field("level", &self.level)
+            .finish()
+    }
+}
+
 /// Test suite for organizing related tests
+/// 
+/// `tests` holds results recorded while the suite was built; `cases` holds
+/// tests that only run when the runner gets to them.
 #[derive(Debug)]
 pub struct TestSuite {
     pub name: String,
     pub tests: Vec<TestResult>,
+    pub cases: Vec<TestCase>,
+    /// Learning path level the cases belong to, used for coverage
+    pub level: Option<String>,
 }
 
 impl TestSuite {
@@ -51,6 +168,17 @@ impl TestSuite {
         Self {
             name,
             tests: Vec::new(),
+            cases: Vec::new(),
+            level: None,
+        }
+    }
+    
+    /// A suite whose cases are the examples of `level` ("basic", ...) in the
+    /// coverage report
+    pub fn for_level(name: String, level: &str) -> Self {
+        Self {
+            level: Some(level.to_string()),
+            ..Self::new(name)
         }
     }
     
@@ -58,6 +186,62 @@ impl TestSuite {
         self.tests.push(test);
     }
     
+    /// Add several recorded results, such as the cases from `test_table!`
+    pub fn add_tests(&mut self, tests: impl IntoIterator<Item = TestResult>) {
+        self.tests.extend(tests);
+    }
+    
+    /// Add a test to be run by the runner, after any recorded results
+    pub fn add_case<F>(&mut self, name: &str, body: F)
+    where
+        F: Fn() + Send + Sync + RefUnwindSafe + 'static,
+    {
+        let case = TestCase::new(name, body);
+        self.cases.push(match &self.level {
+            Some(level) => case.covering(level),
+            None => case,
+        });
+    }
+    
+    /// Names of the recorded results followed by the pending cases
+    pub fn test_names(&self) -> impl Iterator<Item = &str> {
+        self.tests.iter().map(|t| t.name.as_str())
+            .chain(self.cases.iter().map(|c| c.name.as_str()))
+    }
+    
+    /// Collect the recorded results and run the pending cases in order,
+    /// skipping tests the filter doesn't select. Stops before the next test
+    /// once `stop` is set, and sets it on the first failure under fail-fast.
+    fn execute(&self, options: &RunOptions, stop: &AtomicBool) -> TestSuite {
+        let mut executed = TestSuite::new(self.name.clone());
+        
+        let recorded = self.tests.iter().map(Ok);
+        let pending = self.cases.iter().map(Err);
+        for test in recorded.chain(pending) {
+            let name = match test {
+                Ok(result) => &result.name,
+                Err(case) => &case.name,
+            };
+            if !options.selects(name) {
+                continue;
+            }
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            
+            let result = match test {
+                Ok(result) => result.clone(),
+                Err(case) => case.run(),
+            };
+            if options.fail_fast && !result.passed {
+                stop.store(true, Ordering::SeqCst);
+            }
+            executed.add_test(result);
+        }
+        
+        executed
+    }
+    
     pub fn passed_count(&self) -> usize {
         self.tests.iter().filter(|t| t.passed).count()
     }
@@ -95,19 +279,72 @@ impl fmt::Display for TestSuite {
     }
 }
 
+/// How much the test runner prints while reporting results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Print nothing; callers show the final summary themselves
+    Quiet,
+    /// Print one line per suite and details for failed tests only
+    #[default]
+    Normal,
+    /// Print every test with its result and duration
+    Verbose,
+}
+
+/// Settings that control how a `TestRunner` runs and reports tests
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RunOptions {
+    pub verbosity: Verbosity,
+    /// Stop at the first failing test and skip everything after it
+    pub fail_fast: bool,
+    /// Run suites on separate threads
+    pub parallel: bool,
+    /// Only run tests whose name contains this text
+    pub filter: Option<String>,
+    /// Require the filter to match the whole test name
+    pub exact: bool,
+}
+
+impl RunOptions {
+    /// Check whether the filter selects a test, like `cargo test <name>`
+    pub fn selects(&self, test_name: &str) -> bool {
+        match &self.filter {
+            None => true,
+            Some(filter) if self.exact => test_name == filter,
+            Some(filter) => test_name.contains(filter.as_str()),
+        }
+    }
+}
+
 /// Main test runner for the entire learning path
 #[derive(Debug)]
 pub struct TestRunner {
     pub suites: HashMap<String, TestSuite>,
+    pub options: RunOptions,
 }
 
 impl TestRunner {
     pub fn new() -> Self {
         Self {
             suites: HashMap::new(),
+            options: RunOptions::default(),
         }
     }
     
+    pub fn with_options(options: RunOptions) -> Self {
+        Self {
+            options,
+            ..Self::new()
+        }
+    }
+    
+    pub fn with_verbosity(verbosity: Verbosity) -> Self {
+        Self::with_options(RunOptions {
+            verbosity,
+            ..RunOptions::default()
+        })
+    }
+    
     pub fn add_suite(&mut self, suite: TestSuite) {
         self.suites.insert(suite.name.clone(), suite);
     }
@@ -116,26 +353,91 @@ impl TestRunner {
         self.suites.get_mut(name)
     }
     
+    /// Report every suite on stdout and summarize the results.
+    /// Stdout stays unlocked while tests run, since test bodies print from
+    /// threads they spawn and join.
     pub fn run_all(&self) -> TestSummary {
+        self.run_all_to(&mut io::stdout())
+            .expect("failed to write test output")
+    }
+    
+    /// Run every suite, report it to `out` at the runner's verbosity, and summarize the results.
+    /// The summary is the same at every verbosity level.
+    pub fn run_all_to<W: Write>(&self, out: &mut W) -> io::Result<TestSummary> {
         let mut total_passed = 0;
         let mut total_failed = 0;
         let mut total_tests = 0;
         
-        println!("Running all tests for Rust Learning Path...\n");
+        let filtered_out = self.suites.values()
+            .flat_map(|suite| suite.test_names())
+            .filter(|name| !self.options.selects(name))
+            .count();
+        
+        if self.options.verbosity != Verbosity::Quiet {
+            writeln!(out, "Running all tests for Rust Learning Path...\n")?;
+            if let Some(filter) = &self.options.filter {
+                writeln!(out, "Filter '{}' skipped {} test(s)\n", filter, filtered_out)?;
+            }
+        }
+        
+        let stop = AtomicBool::new(false);
+        let executed = self.execute_suites(&stop);
         
-        for suite in self.suites.values() {
-            println!("{}", suite);
+        for suite in &executed {
+            match self.options.verbosity {
+                Verbosity::Quiet => {}
+                Verbosity::Normal => {
+                    writeln!(out, "{}: {} passed, {} failed, {} total",
+                             suite.name, suite.passed_count(), suite.failed_count(), suite.total_count())?;
+                    for test in suite.tests.iter().filter(|t| !t.passed) {
+                        writeln!(out, "  {}", test)?;
+                    }
+                }
+                Verbosity::Verbose => writeln!(out, "{}", suite)?,
+            }
             total_passed += suite.passed_count();
             total_failed += suite.failed_count();
             total_tests += suite.total_count();
         }
         
-        TestSummary {
+        Ok(TestSummary {
             total_tests,
             total_passed,
             total_failed,
             success_rate: if total_tests == 0 { 0.0 } else { total_passed as f64 / total_tests as f64 * 100.0 },
+            stopped_early: stop.load(Ordering::SeqCst),
+            filtered_out,
+        })
+    }
+    
+    /// Run the suites in name order, sequentially or one thread per suite.
+    /// In parallel mode a failure under fail-fast stops the other threads
+    /// before their next test.
+    fn execute_suites(&self, stop: &AtomicBool) -> Vec<TestSuite> {
+        let mut suites: Vec<&TestSuite> = self.suites.values().collect();
+        suites.sort_by(|a, b| a.name.cmp(&b.name));
+        let options = &self.options;
+        
+        let mut executed: Vec<TestSuite> = if options.parallel {
+            thread::scope(|scope| {
+                let handles: Vec<_> = suites.into_iter()
+                    .map(|suite| scope.spawn(move || suite.execute(options, stop)))
+                    .collect();
+                handles.into_iter()
+                    .map(|handle| handle.join().expect("test suite thread panicked"))
+                    .collect()
+            })
+        } else {
+            suites.into_iter()
+                .map(|suite| suite.execute(options, stop))
+                .collect()
+        };
+        
+        // Leave out suites that fail-fast or the filter skipped entirely
+        if stop.load(Ordering::SeqCst) || options.filter.is_some() {
+            executed.retain(|suite| suite.total_count() > 0);
         }
+        executed
     }
 }
 
@@ -146,6 +448,10 @@ pub struct TestSummary {
     pub total_passed: usize,
     pub total_failed: usize,
     pub success_rate: f64,
+    /// Fail-fast stopped the run, so some tests were never run
+    pub stopped_early: bool,
+    /// Tests the name filter did not select
+    pub filtered_out: usize,
 }
 
 impl fmt::Display for TestSummary {
@@ -156,6 +462,14 @@ impl fmt::Display for TestSummary {
         writeln!(f, "Failed: {}", self.total_failed)?;
         writeln!(f, "Success rate: {:.1}%", self.success_rate)?;
         
+        if self.filtered_out > 0 {
+            writeln!(f, "Filtered out: {}", self.filtered_out)?;
+        }
+        
+        if self.stopped_early {
+            writeln!(f, "Stopped at the first failure (fail-fast); remaining tests were not run")?;
+        }
+        
         if self.total_failed == 0 {
             writeln!(f, "\n🎉 All tests passed!")?;
         } else {
@@ -166,28 +480,21 @@ impl fmt::Display for TestSummary {
     }
 }
 
-/// Macro for creating test cases with timing
+/// Macro for creating a test case that only runs when the runner reaches it
 #[macro_export]
 macro_rules! test_case {
-    ($name:expr, $test_fn:expr) => {{
-        let start = std::time::Instant::now();
-        let result = std::panic::catch_unwind(|| $test_fn);
-        let duration = start.elapsed().as_millis();
-        
-        match result {
-            Ok(_) => TestResult::success($name.to_string(), duration),
-            Err(panic_info) => {
-                let message = if let Some(s) = panic_info.downcast_ref::<&str>() {
-                    format!("Panic: {}", s)
-                } else if let Some(s) = panic_info.downcast_ref::<String>() {
-                    format!("Panic: {}", s)
-                } else {
-                    "Test panicked with unknown error".to_string()
-                };
-                TestResult::failure($name.to_string(), message, duration)
-            }
-        }
-    }};
+    ($name:expr, $test_fn:expr) => {
+        $crate::framework::TestCase::new($name, $test_fn)
+    };
+}
+
+/// Macro for table-driven tests: runs the function over `(input, expected)`
+/// cases and returns one `TestResult` per case (see `run_cases`)
+#[macro_export]
+macro_rules! test_table {
+    ($name:expr, [$(($input:expr, $expected:expr)),* $(,)?], $f:expr) => {
+        $crate::framework::run_cases($name, &[$(($input, $expected)),*], $f)
+    };
 }
 
 /// Macro for asserting with custom error messages
@@ -203,4 +510,265 @@ macro_rules! assert_with_msg {
             panic!($msg, $($arg)*);
         }
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    
+    fn sample_runner(verbosity: Verbosity) -> TestRunner {
+        let mut suite = TestSuite::new("Sample Suite".to_string());
+        suite.add_test(TestResult::success("passing test".to_string(), 3));
+        suite.add_test(TestResult::failure("failing test".to_string(), "boom".to_string(), 7));
+        
+        let mut runner = TestRunner::with_verbosity(verbosity);
+        runner.add_suite(suite);
+        runner
+    }
+    
+    #[test]
+    fn test_table_reports_each_case() {
+        let results = crate::test_table!("Square", [(2, 4), (3, 10), (-4, 16)], |x: &i32| x * x);
+        
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["Square [2]", "Square [3]", "Square [-4]"]);
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+        assert_eq!(results[1].message, "expected 10, got 9");
+        assert!(results[2].passed);
+    }
+    
+    #[test]
+    fn test_table_panic_fails_only_that_case() {
+        let cases = [("7", 7), ("x", 0)];
+        let results = run_cases("Parse", &cases, |input: &&str| input.parse::<i32>().unwrap());
+        
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+        assert_eq!(results[1].name, "Parse [\"x\"]");
+        assert!(results[1].message.starts_with("Panic:"));
+        
+        let mut suite = TestSuite::new("Table".to_string());
+        suite.add_tests(results);
+        assert_eq!((suite.passed_count(), suite.failed_count()), (1, 1));
+    }
+    
+    fn capture(verbosity: Verbosity) -> (String, TestSummary) {
+        let mut out = Vec::new();
+        let summary = sample_runner(verbosity).run_all_to(&mut out).unwrap();
+        (String::from_utf8(out).unwrap(), summary)
+    }
+    
+    #[test]
+    fn test_quiet_prints_nothing() {
+        let (output, summary) = capture(Verbosity::Quiet);
+        
+        assert!(output.is_empty());
+        assert_eq!(summary.total_tests, 2);
+        assert_eq!(summary.total_failed, 1);
+    }
+    
+    #[test]
+    fn test_normal_shows_suites_and_failures() {
+        let (output, _) = capture(Verbosity::Normal);
+        
+        assert!(output.contains("Running all tests"));
+        assert!(output.contains("Sample Suite: 1 passed, 1 failed, 2 total"));
+        assert!(output.contains("✗ FAIL failing test (7 ms) - boom"));
+        assert!(!output.contains("passing test"));
+    }
+    
+    #[test]
+    fn test_verbose_shows_every_test() {
+        let (output, _) = capture(Verbosity::Verbose);
+        
+        assert!(output.contains("=== Sample Suite ==="));
+        assert!(output.contains("✓ PASS passing test (3 ms)"));
+        assert!(output.contains("✗ FAIL failing test (7 ms) - boom"));
+    }
+    
+    #[test]
+    fn test_verbosity_does_not_change_results() {
+        let (_, quiet) = capture(Verbosity::Quiet);
+        let (_, verbose) = capture(Verbosity::Verbose);
+        
+        assert_eq!(quiet.total_tests, verbose.total_tests);
+        assert_eq!(quiet.total_passed, verbose.total_passed);
+        assert_eq!(quiet.total_failed, verbose.total_failed);
+    }
+    
+    /// Suite whose second test fails, followed by a test that records whether it ran
+    fn suite_with_early_failure(name: &str, ran: &Arc<AtomicBool>) -> TestSuite {
+        let mut suite = TestSuite::new(name.to_string());
+        suite.add_case("first", || {});
+        suite.add_case("second", || panic!("boom"));
+        let ran = Arc::clone(ran);
+        suite.add_case("third", move || ran.store(true, Ordering::SeqCst));
+        suite
+    }
+    
+    fn run_quietly(runner: &TestRunner) -> TestSummary {
+        runner.run_all_to(&mut io::sink()).unwrap()
+    }
+    
+    #[test]
+    fn test_fail_fast_skips_remaining_tests() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let mut runner = TestRunner::with_options(RunOptions {
+            fail_fast: true,
+            ..RunOptions::default()
+        });
+        runner.add_suite(suite_with_early_failure("A suite", &ran));
+        let later_ran = Arc::new(AtomicBool::new(false));
+        let mut later = TestSuite::new("B suite".to_string());
+        let flag = Arc::clone(&later_ran);
+        later.add_case("later", move || flag.store(true, Ordering::SeqCst));
+        runner.add_suite(later);
+        
+        let summary = run_quietly(&runner);
+        
+        assert!(summary.stopped_early);
+        assert_eq!(summary.total_tests, 2);
+        assert_eq!(summary.total_failed, 1);
+        assert!(!ran.load(Ordering::SeqCst));
+        assert!(!later_ran.load(Ordering::SeqCst));
+        assert!(summary.to_string().contains("fail-fast"));
+    }
+    
+    #[test]
+    fn test_without_fail_fast_everything_runs() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let mut runner = TestRunner::new();
+        runner.add_suite(suite_with_early_failure("A suite", &ran));
+        
+        let summary = run_quietly(&runner);
+        
+        assert!(!summary.stopped_early);
+        assert_eq!(summary.total_tests, 3);
+        assert_eq!(summary.total_failed, 1);
+        assert!(ran.load(Ordering::SeqCst));
+    }
+    
+    #[test]
+    fn test_parallel_fail_fast_cancels_other_suites() {
+        let mut runner = TestRunner::with_options(RunOptions {
+            fail_fast: true,
+            parallel: true,
+            ..RunOptions::default()
+        });
+        
+        let mut failing = TestSuite::new("failing".to_string());
+        failing.add_case("fails immediately", || panic!("boom"));
+        runner.add_suite(failing);
+        
+        // The slow test is already running when the failure happens, so it
+        // finishes, but the test after it is cancelled
+        let ran = Arc::new(AtomicBool::new(false));
+        let mut slow = TestSuite::new("slow".to_string());
+        slow.add_case("slow", || thread::sleep(std::time::Duration::from_millis(200)));
+        let flag = Arc::clone(&ran);
+        slow.add_case("after slow", move || flag.store(true, Ordering::SeqCst));
+        runner.add_suite(slow);
+        
+        let summary = run_quietly(&runner);
+        
+        assert!(summary.stopped_early);
+        assert_eq!(summary.total_failed, 1);
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+    
+    /// Runner with two suites of named cases that each record when they run
+    fn named_runner(options: RunOptions, ran: &Arc<Mutex<Vec<String>>>) -> TestRunner {
+        let mut runner = TestRunner::with_options(options);
+        for (suite_name, names) in [
+            ("Parsing", ["parse_number", "parse_number_negative", "parse_string"]),
+            ("Formatting", ["format_number", "format_string", "format_padding"]),
+        ] {
+            let mut suite = TestSuite::new(suite_name.to_string());
+            for name in names {
+                let ran = Arc::clone(ran);
+                suite.add_case(name, move || ran.lock().unwrap().push(name.to_string()));
+            }
+            runner.add_suite(suite);
+        }
+        runner
+    }
+    
+    #[test]
+    fn test_filter_selects_matching_tests() {
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let runner = named_runner(RunOptions {
+            filter: Some("number".to_string()),
+            ..RunOptions::default()
+        }, &ran);
+        
+        let mut out = Vec::new();
+        let summary = runner.run_all_to(&mut out).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        
+        let mut ran = ran.lock().unwrap().clone();
+        ran.sort();
+        assert_eq!(ran, ["format_number", "parse_number", "parse_number_negative"]);
+        assert_eq!(summary.total_tests, 3);
+        assert_eq!(summary.filtered_out, 3);
+        assert!(output.contains("Filter 'number' skipped 3 test(s)"));
+        assert!(summary.to_string().contains("Filtered out: 3"));
+    }
+    
+    #[test]
+    fn test_exact_filter_selects_one_test() {
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let runner = named_runner(RunOptions {
+            filter: Some("parse_number".to_string()),
+            exact: true,
+            verbosity: Verbosity::Verbose,
+            ..RunOptions::default()
+        }, &ran);
+        
+        let mut out = Vec::new();
+        let summary = runner.run_all_to(&mut out).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        
+        assert_eq!(*ran.lock().unwrap(), ["parse_number"]);
+        assert_eq!(summary.total_tests, 1);
+        assert_eq!(summary.filtered_out, 5);
+        // Suites with nothing selected are left out of the report
+        assert!(!output.contains("Formatting"));
+    }
+    
+    #[test]
+    fn test_filtered_out_cases_are_never_called() {
+        for parallel in [false, true] {
+            let skipped_ran = Arc::new(AtomicBool::new(false));
+            let mut runner = TestRunner::with_options(RunOptions {
+                filter: Some("selected".to_string()),
+                parallel,
+                ..RunOptions::default()
+            });
+            for suite_name in ["First", "Second"] {
+                let mut suite = TestSuite::new(suite_name.to_string());
+                suite.add_case("selected", || {});
+                let flag = Arc::clone(&skipped_ran);
+                suite.add_case("skipped", move || flag.store(true, Ordering::SeqCst));
+                runner.add_suite(suite);
+            }
+            
+            let summary = run_quietly(&runner);
+            
+            assert!(!skipped_ran.load(Ordering::SeqCst), "parallel: {}", parallel);
+            assert_eq!(summary.total_tests, 2);
+            assert_eq!(summary.filtered_out, 2);
+        }
+    }
+    
+    #[test]
+    fn test_no_filter_runs_everything() {
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let summary = run_quietly(&named_runner(RunOptions::default(), &ran));
+        
+        assert_eq!(ran.lock().unwrap().len(), 6);
+        assert_eq!(summary.total_tests, 6);
+        assert_eq!(summary.filtered_out, 0);
+    }
+}
@@ -1,13 +1,13 @@
 // Comprehensive unit tests for Intermediate Level code examples
-use crate::framework::{TestResult, TestSuite};
-use crate::{test_case, assert_with_msg};
+use crate::framework::TestSuite;
+use crate::assert_with_msg;
 use std::collections::HashMap;
 
 /// Test advanced ownership concepts
 pub fn test_ownership_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Intermediate Level - Ownership Concepts".to_string());
+    let mut suite = TestSuite::for_level("Intermediate Level - Ownership Concepts".to_string(), "intermediate");
     
-    suite.add_test(test_case!("Ownership - Borrowing", || {
+    suite.add_case("Ownership - Borrowing", || {
         fn calculate_length(s: &String) -> usize {
             s.len()
         }
@@ -17,9 +17,9 @@ pub fn test_ownership_concepts() -> TestSuite {
         
         assert_with_msg!(len == 5, "Borrowing should allow reading without taking ownership");
         assert_with_msg!(s1 == "hello", "Original string should still be accessible after borrowing");
-    }));
+    });
     
-    suite.add_test(test_case!("Ownership - Mutable Borrowing", || {
+    suite.add_case("Ownership - Mutable Borrowing", || {
         fn change(s: &mut String) {
             s.push_str(", world");
         }
@@ -28,9 +28,9 @@ pub fn test_ownership_concepts() -> TestSuite {
         change(&mut s);
         
         assert_with_msg!(s == "hello, world", "Mutable borrowing should allow modification");
-    }));
+    });
     
-    suite.add_test(test_case!("Ownership - Multiple Immutable Borrows", || {
+    suite.add_case("Ownership - Multiple Immutable Borrows", || {
         let s1 = String::from("hello");
         let r1 = &s1;
         let r2 = &s1;
@@ -38,9 +38,9 @@ pub fn test_ownership_concepts() -> TestSuite {
         assert_with_msg!(*r1 == "hello", "First immutable borrow should work");
         assert_with_msg!(*r2 == "hello", "Second immutable borrow should work");
         assert_with_msg!(s1 == "hello", "Original should still be accessible");
-    }));
+    });
     
-    suite.add_test(test_case!("Ownership - Slice References", || {
+    suite.add_case("Ownership - Slice References", || {
         fn first_word(s: &str) -> &str {
             let bytes = s.as_bytes();
             
@@ -61,9 +61,9 @@ pub fn test_ownership_concepts() -> TestSuite {
         let s = String::from("hello");
         let word = first_word(&s);
         assert_with_msg!(word == "hello", "String slice should return entire string if no space");
-    }));
+    });
     
-    suite.add_test(test_case!("Ownership - Lifetimes", || {
+    suite.add_case("Ownership - Lifetimes", || {
         fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
             if x.len() > y.len() {
                 x
@@ -77,16 +77,16 @@ pub fn test_ownership_concepts() -> TestSuite {
         
         let result = longest(string1.as_str(), string2);
         assert_with_msg!(result == "long string is long", "Lifetime annotation should allow returning longer string");
-    }));
+    });
     
     suite
 }
 
 /// Test struct and enum concepts
 pub fn test_struct_enum_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Intermediate Level - Structs and Enums".to_string());
+    let mut suite = TestSuite::for_level("Intermediate Level - Structs and Enums".to_string(), "intermediate");
     
-    suite.add_test(test_case!("Structs - Basic Struct", || {
+    suite.add_case("Structs - Basic Struct", || {
         #[derive(Debug, PartialEq)]
         struct User {
             username: String,
@@ -106,9 +106,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         assert_with_msg!(user1.email == "someone@example.com", "Struct should store all fields correctly");
         assert_with_msg!(user1.active == true, "Boolean fields should work in structs");
         assert_with_msg!(user1.sign_in_count == 1, "Numeric fields should work in structs");
-    }));
+    });
     
-    suite.add_test(test_case!("Structs - Struct Update Syntax", || {
+    suite.add_case("Structs - Struct Update Syntax", || {
         #[derive(Debug, PartialEq)]
         struct User {
             username: String,
@@ -134,9 +134,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         assert_with_msg!(user2.username == "anotherusername567", "Updated fields should override");
         assert_with_msg!(user2.active == true, "Struct update syntax should copy remaining fields");
         assert_with_msg!(user2.sign_in_count == 1, "Struct update syntax should preserve other fields");
-    }));
+    });
     
-    suite.add_test(test_case!("Structs - Tuple Structs", || {
+    suite.add_case("Structs - Tuple Structs", || {
         #[derive(Debug, PartialEq)]
         struct Color(i32, i32, i32);
         
@@ -153,9 +153,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         // Different types even with same structure
         // This would not compile: assert_with_msg!(black == origin, "Different tuple struct types");
         assert_with_msg!(black == Color(0, 0, 0), "Same tuple struct types should be equal");
-    }));
+    });
     
-    suite.add_test(test_case!("Structs - Methods", || {
+    suite.add_case("Structs - Methods", || {
         #[derive(Debug)]
         struct Rectangle {
             width: u32,
@@ -196,9 +196,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         assert_with_msg!(!rect2.can_hold(&rect1), "Method should handle reverse comparison");
         assert_with_msg!(square.width == 25, "Associated function should create square");
         assert_with_msg!(square.height == 25, "Associated function should set both dimensions");
-    }));
+    });
     
-    suite.add_test(test_case!("Enums - Basic Enum", || {
+    suite.add_case("Enums - Basic Enum", || {
         #[derive(Debug, PartialEq)]
         enum IpAddrKind {
             V4,
@@ -210,9 +210,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         
         assert_with_msg!(four == IpAddrKind::V4, "Enum variants should be comparable");
         assert_with_msg!(six == IpAddrKind::V6, "Enum should support multiple variants");
-    }));
+    });
     
-    suite.add_test(test_case!("Enums - Enum with Data", || {
+    suite.add_case("Enums - Enum with Data", || {
         #[derive(Debug, PartialEq)]
         enum IpAddr {
             V4(u8, u8, u8, u8),
@@ -238,9 +238,9 @@ pub fn test_struct_enum_concepts() -> TestSuite {
             }
             _ => panic!("Should match V6 variant"),
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Enums - Option Enum", || {
+    suite.add_case("Enums - Option Enum", || {
         let some_number = Some(5);
         let some_string = Some("a string");
         let absent_number: Option<i32> = None;
@@ -259,16 +259,16 @@ pub fn test_struct_enum_concepts() -> TestSuite {
         
         let value = absent_number.unwrap_or(0);
         assert_with_msg!(value == 0, "unwrap_or should return default for None");
-    }));
+    });
     
     suite
 }
 
 /// Test trait concepts
 pub fn test_trait_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Intermediate Level - Trait Concepts".to_string());
+    let mut suite = TestSuite::for_level("Intermediate Level - Trait Concepts".to_string(), "intermediate");
     
-    suite.add_test(test_case!("Traits - Basic Trait", || {
+    suite.add_case("Traits - Basic Trait", || {
         trait Summary {
             fn summarize(&self) -> String;
         }
@@ -322,9 +322,9 @@ pub fn test_trait_concepts() -> TestSuite {
             tweet.summarize() == "horse_ebooks: of course, as you probably already know, people",
             "Trait implementation should work for Tweet"
         );
-    }));
+    });
     
-    suite.add_test(test_case!("Traits - Default Implementation", || {
+    suite.add_case("Traits - Default Implementation", || {
         trait Summary {
             fn summarize_author(&self) -> String;
             
@@ -357,9 +357,9 @@ pub fn test_trait_concepts() -> TestSuite {
             tweet.summarize() == "(Read more from @horse_ebooks...)",
             "Default trait implementation should work"
         );
-    }));
+    });
     
-    suite.add_test(test_case!("Traits - Trait Bounds", || {
+    suite.add_case("Traits - Trait Bounds", || {
         trait Display {
             fn fmt(&self) -> String;
         }
@@ -403,16 +403,16 @@ pub fn test_trait_concepts() -> TestSuite {
             result.contains("Debug: Article { title: Breaking News"),
             "Trait bound function should use Debug trait"
         );
-    }));
+    });
     
     suite
 }
 
 /// Test generic concepts
 pub fn test_generic_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Intermediate Level - Generic Concepts".to_string());
+    let mut suite = TestSuite::for_level("Intermediate Level - Generic Concepts".to_string(), "intermediate");
     
-    suite.add_test(test_case!("Generics - Generic Functions", || {
+    suite.add_case("Generics - Generic Functions", || {
         fn largest<T: PartialOrd + Copy>(list: &[T]) -> T {
             let mut largest = list[0];
             
@@ -432,9 +432,9 @@ pub fn test_generic_concepts() -> TestSuite {
         let char_list = vec!['y', 'm', 'a', 'q'];
         let result = largest(&char_list);
         assert_with_msg!(result == 'y', "Generic function should work with different types");
-    }));
+    });
     
-    suite.add_test(test_case!("Generics - Generic Structs", || {
+    suite.add_case("Generics - Generic Structs", || {
         #[derive(Debug, PartialEq)]
         struct Point<T> {
             x: T,
@@ -461,9 +461,9 @@ pub fn test_generic_concepts() -> TestSuite {
         
         let distance = float_point.distance_from_origin();
         assert_with_msg!((distance - 4.123).abs() < 0.01, "Specific implementation should work");
-    }));
+    });
     
-    suite.add_test(test_case!("Generics - Generic Enums", || {
+    suite.add_case("Generics - Generic Enums", || {
         #[derive(Debug, PartialEq)]
         enum Result<T, E> {
             Ok(T),
@@ -482,16 +482,16 @@ pub fn test_generic_concepts() -> TestSuite {
             Result::Ok(_) => panic!("Should be Err variant"),
             Result::Err(msg) => assert_with_msg!(msg == "Something went wrong", "Generic enum should store error value"),
         }
-    }));
+    });
     
     suite
 }
 
 /// Test collection concepts
 pub fn test_collection_concepts() -> TestSuite {
-    let mut suite = TestSuite::new("Intermediate Level - Collection Concepts".to_string());
+    let mut suite = TestSuite::for_level("Intermediate Level - Collection Concepts".to_string(), "intermediate");
     
-    suite.add_test(test_case!("Collections - Vector", || {
+    suite.add_case("Collections - Vector", || {
         let mut v = Vec::new();
         v.push(5);
         v.push(6);
@@ -514,9 +514,9 @@ pub fn test_collection_concepts() -> TestSuite {
             Some(_) => panic!("Should not find element at index 100"),
             None => assert_with_msg!(true, "Vector get method should return None for invalid index"),
         }
-    }));
+    });
     
-    suite.add_test(test_case!("Collections - HashMap", || {
+    suite.add_case("Collections - HashMap", || {
         let mut scores = HashMap::new();
         
         scores.insert(String::from("Blue"), 10);
@@ -542,9 +542,9 @@ pub fn test_collection_concepts() -> TestSuite {
         
         assert_with_msg!(scores[&String::from("Red")] == 30, "Entry API should insert new values");
         assert_with_msg!(scores[&String::from("Blue")] == 25, "Entry API should not overwrite existing values");
-    }));
+    });
     
-    suite.add_test(test_case!("Collections - String", || {
+    suite.add_case("Collections - String", || {
         let mut s = String::new();
         s.push_str("hello");
         s.push(' ');
@@ -569,7 +569,7 @@ pub fn test_collection_concepts() -> TestSuite {
         let hello = "Здравствуйте";
         let s = &hello[0..4];
         assert_with_msg!(s == "Зд", "String slicing should work with Unicode");
-    }));
+    });
     
     suite
 }
\ No newline at end of file
@@ -2,6 +2,8 @@
 // This provides comprehensive unit testing for all code examples
 
 pub mod framework;
+pub mod coverage;
+pub mod snapshot;
 pub mod basic_tests;
 pub mod intermediate_tests;
 pub mod advanced_tests;
@@ -15,11 +17,13 @@ pub mod project_validator_cli;
 #[cfg(test)]
 mod project_validation_tests;
 
-pub use crate::framework::{TestResult, TestSuite, TestRunner, TestSummary, test_case, assert_with_msg};
+pub use crate::framework::{TestResult, TestSuite, TestCase, TestRunner, TestSummary, RunOptions, Verbosity, run_cases};
+pub use crate::coverage::{CoverageTracker, CoverageReport};
+pub use crate::snapshot::assert_snapshot;
 
 /// Run all tests for the entire Rust Learning Path
-pub fn run_all_tests() -> TestSummary {
-    let mut runner = TestRunner::new();
+pub fn run_all_tests(options: RunOptions) -> TestSummary {
+    let mut runner = TestRunner::with_options(options);
     
     // Add Basic Level tests
     runner.add_suite(basic_tests::test_basic_concepts());
@@ -50,8 +54,8 @@ pub fn run_all_tests() -> TestSummary {
 }
 
 /// Run tests for a specific level
-pub fn run_level_tests(level: &str) -> TestSummary {
-    let mut runner = TestRunner::new();
+pub fn run_level_tests(level: &str, options: RunOptions) -> TestSummary {
+    let mut runner = TestRunner::with_options(options);
     
     match level.to_lowercase().as_str() {
         "basic" => {
@@ -86,6 +90,8 @@ pub fn run_level_tests(level: &str) -> TestSummary {
                 total_passed: 0,
                 total_failed: 0,
                 success_rate: 0.0,
+                stopped_early: false,
+                filtered_out: 0,
             };
         }
     }
@@ -94,8 +100,8 @@ pub fn run_level_tests(level: &str) -> TestSummary {
 }
 
 /// Run tests for a specific concept area
-pub fn run_concept_tests(concept: &str) -> TestSummary {
-    let mut runner = TestRunner::new();
+pub fn run_concept_tests(concept: &str, options: RunOptions) -> TestSummary {
+    let mut runner = TestRunner::with_options(options);
     
     match concept.to_lowercase().as_str() {
         "variables" | "data_types" | "control_flow" | "functions" => {
@@ -152,6 +158,8 @@ pub fn run_concept_tests(concept: &str) -> TestSummary {
                 total_passed: 0,
                 total_failed: 0,
                 success_rate: 0.0,
+                stopped_early: false,
+                filtered_out: 0,
             };
         }
     }
@@ -178,14 +186,60 @@ mod tests {
     
     #[test]
     fn test_macro_functionality() {
-        let result = test_case!("Macro Test", || {
+        let case = test_case!("Macro Test", || {
             assert_eq!(2 + 2, 4);
         });
         
+        let result = case.run();
         assert!(result.passed);
         assert_eq!(result.name, "Macro Test");
     }
     
+    #[test]
+    fn test_suites_defer_their_cases() {
+        let suite = basic_tests::test_string_concepts();
+        
+        assert_eq!(suite.total_count(), 0);
+        assert_eq!(suite.cases.len(), 3);
+    }
+    
+    #[test]
+    fn test_fail_fast_never_runs_later_cases() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        
+        let mut suite = intermediate_tests::test_ownership_concepts();
+        suite.cases.insert(0, test_case!("fails first", || panic!("boom")));
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        suite.cases.push(test_case!("runs last", move || flag.store(true, Ordering::SeqCst)));
+        
+        let mut runner = TestRunner::with_options(RunOptions {
+            fail_fast: true,
+            verbosity: Verbosity::Quiet,
+            ..RunOptions::default()
+        });
+        runner.add_suite(suite);
+        let summary = runner.run_all();
+        
+        assert!(summary.stopped_early);
+        assert_eq!(summary.total_tests, 1);
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+    
+    #[test]
+    fn test_running_a_suite_fills_the_coverage_report() {
+        let mut runner = TestRunner::with_verbosity(Verbosity::Quiet);
+        runner.add_suite(basic_tests::test_string_concepts());
+        runner.run_all();
+        
+        let report = coverage::global().report();
+        let basic = report.level("basic").unwrap();
+        assert!(basic.exercised.iter().any(|name| name == "Strings - String Literals"));
+        assert!(basic.coverage_percent() > 0.0);
+        assert!(!report.to_string().contains("No example functions registered"));
+    }
+    
     #[test]
     fn test_assert_with_msg_macro() {
         let result = std::panic::catch_unwind(|| {
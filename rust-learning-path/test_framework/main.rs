@@ -2,7 +2,7 @@
 use std::env;
 use std::process;
 
-use rust_learning_path_tests::{run_all_tests, run_level_tests, run_concept_tests};
+use rust_learning_path_tests::{run_all_tests, run_level_tests, run_concept_tests, coverage, RunOptions, Verbosity};
 use rust_learning_path_tests::quiz_framework::{run_interactive_quiz_session, QuizBank};
 use rust_learning_path_tests::project_validation_runner::{
     validate_single_project, validate_level_projects, validate_all_learning_path_projects,
@@ -10,13 +10,32 @@ use rust_learning_path_tests::project_validation_runner::{
 };
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let options = RunOptions {
+        verbosity: take_verbosity(&mut args),
+        fail_fast: take_flag(&mut args, "--fail-fast"),
+        parallel: take_flag(&mut args, "--parallel"),
+        exact: take_flag(&mut args, "--exact"),
+        filter: match take_value(&mut args, "--filter") {
+            Ok(filter) => filter,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                print_help();
+                process::exit(1);
+            }
+        },
+    };
+    let quiet = options.verbosity == Verbosity::Quiet;
+    let show_coverage = take_flag(&mut args, "--coverage");
     
     if args.len() == 1 {
         // Run all tests by default
-        println!("Running all tests for Rust Learning Path...\n");
-        let summary = run_all_tests();
+        if !quiet {
+            println!("Running all tests for Rust Learning Path...\n");
+        }
+        let summary = run_all_tests(options);
         println!("{}", summary);
+        print_coverage(show_coverage);
         
         if summary.total_failed > 0 {
             process::exit(1);
@@ -36,9 +55,12 @@ fn main() {
             }
             
             let level = &args[2];
-            println!("Running tests for {} level...\n", level);
-            let summary = run_level_tests(level);
+            if !quiet {
+                println!("Running tests for {} level...\n", level);
+            }
+            let summary = run_level_tests(level, options);
             println!("{}", summary);
+            print_coverage(show_coverage);
             
             if summary.total_failed > 0 {
                 process::exit(1);
@@ -52,17 +74,22 @@ fn main() {
             }
             
             let concept = &args[2];
-            println!("Running tests for {} concept...\n", concept);
-            let summary = run_concept_tests(concept);
+            if !quiet {
+                println!("Running tests for {} concept...\n", concept);
+            }
+            let summary = run_concept_tests(concept, options);
             println!("{}", summary);
+            print_coverage(show_coverage);
             
             if summary.total_failed > 0 {
                 process::exit(1);
             }
         }
         "--validate" | "-v" => {
-            println!("Validating all code examples...\n");
-            let summary = run_all_tests();
+            if !quiet {
+                println!("Validating all code examples...\n");
+            }
+            let summary = run_all_tests(options);
             
             if summary.total_failed == 0 {
                 println!("✅ All code examples are valid and working correctly!");
@@ -75,8 +102,10 @@ fn main() {
             }
         }
         "--stats" | "-s" => {
-            println!("Generating test statistics...\n");
-            let summary = run_all_tests();
+            if !quiet {
+                println!("Generating test statistics...\n");
+            }
+            let summary = run_all_tests(options);
             
             println!("📊 Test Statistics:");
             println!("==================");
@@ -253,6 +282,51 @@ fn main() {
     }
 }
 
+/// Removes every occurrence of `flag` from the arguments and reports whether it was present
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// Removes `flag` and the value after it from the arguments and returns the value
+fn take_value(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(format!("{} requires a value", flag));
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
+
+/// Prints which registered example functions the run exercised
+fn print_coverage(show: bool) {
+    if show {
+        println!("{}", coverage::global().report());
+    }
+}
+
+/// Removes `--quiet` and `--verbose` from the arguments and returns the
+/// requested verbosity. If both are given, the last one wins.
+fn take_verbosity(args: &mut Vec<String>) -> Verbosity {
+    let mut verbosity = Verbosity::Normal;
+    args.retain(|arg| match arg.as_str() {
+        "--quiet" => {
+            verbosity = Verbosity::Quiet;
+            false
+        }
+        "--verbose" => {
+            verbosity = Verbosity::Verbose;
+            false
+        }
+        _ => true,
+    });
+    verbosity
+}
+
 fn print_help() {
     println!("Rust Learning Path Test Runner");
     println!("==============================");
@@ -269,6 +343,16 @@ fn print_help() {
     println!("    -s, --stats                  Show test statistics");
     println!("    -q, --quiz [QUIZ_ID]         Run interactive quizzes (or specific quiz)");
     println!();
+    println!("OUTPUT OPTIONS (combine with any of the above):");
+    println!("    --quiet                      Only print the final summary");
+    println!("    --verbose                    Print every test with its result and duration");
+    println!("                                 (default: one line per suite plus failures)");
+    println!("    --fail-fast                  Stop at the first failing test");
+    println!("    --parallel                   Run test suites on separate threads");
+    println!("    --filter <TEXT>              Only run tests whose name contains TEXT");
+    println!("    --exact                      Only run the test named exactly as the filter");
+    println!("    --coverage                   List exercised and unexercised example functions");
+    println!();
     println!("PROJECT VALIDATION OPTIONS:");
     println!("    -vp, --validate-project <PROJECT>   Validate specific project");
     println!("    -vl, --validate-level <LEVEL>       Validate all projects in level");
@@ -309,6 +393,11 @@ fn print_help() {
     println!("    cargo run -- --concept ownership            # Run ownership tests");
     println!("    cargo run -- --validate                     # Validate all examples");
     println!("    cargo run -- --stats                        # Show statistics");
+    println!("    cargo run -- --level basic --quiet          # Only show the summary");
+    println!("    cargo run -- --verbose                      # Show every test result");
+    println!("    cargo run -- --level basic --fail-fast      # Stop at the first failure");
+    println!("    cargo run -- --filter ownership             # Only run matching tests");
+    println!("    cargo run -- --level basic --coverage       # Show example coverage");
     println!("    cargo run -- --quiz                         # Run interactive quizzes");
     println!("    cargo run -- --quiz basic_variables         # Run specific quiz");
     println!();
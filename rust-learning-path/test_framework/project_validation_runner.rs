@@ -204,6 +204,8 @@ impl ProjectValidationRunner {
                         total_passed: 0,
                         total_failed: 1,
                         success_rate: 0.0,
+                        stopped_early: false,
+                        filtered_out: 0,
                     },
                     error_message: Some(format!("Unknown level: {}. Available levels: basic, intermediate, advanced, expert", level)),
                 };
@@ -239,6 +241,8 @@ impl ProjectValidationRunner {
                 total_passed,
                 total_failed,
                 success_rate,
+                stopped_early: false,
+                filtered_out: 0,
             },
             error_message: None,
         }
@@ -275,6 +279,8 @@ impl ProjectValidationRunner {
                 total_passed,
                 total_failed,
                 success_rate,
+                stopped_early: false,
+                filtered_out: 0,
             },
         }
     }
@@ -0,0 +1,231 @@
+// Snapshot testing for example output
+// The first run of `assert_snapshot` records the output in a snapshot file;
+// later runs compare against it and fail with a line diff when it changes.
+// Set UPDATE_SNAPSHOTS=1 to rewrite the stored snapshots instead.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that makes every snapshot assertion overwrite its file
+pub const UPDATE_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// What happened when a value was checked against its snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotOutcome {
+    /// No snapshot existed, so the value was written as the new snapshot
+    Created,
+    /// The value matched the stored snapshot
+    Matched,
+    /// The snapshot was rewritten because updates are enabled
+    Updated,
+    /// The value differs from the stored snapshot
+    Mismatch { diff: String },
+}
+
+/// A directory of `<name>.snap` files
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+    update: bool,
+}
+
+impl SnapshotStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            update: false,
+        }
+    }
+    
+    /// The `snapshots` directory of this crate, updating when UPDATE_SNAPSHOTS is set
+    pub fn from_env() -> Self {
+        let update = env::var(UPDATE_ENV_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+        Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots"))
+            .with_update(update)
+    }
+    
+    /// Overwrite snapshots instead of comparing against them
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+    
+    /// Path of the file that stores the snapshot called `name`.
+    /// Characters that can't go in a file name become `_`; when that changes
+    /// the name, a hash of the original is appended so `a/b` and `a_b` get
+    /// different files.
+    pub fn path(&self, name: &str) -> PathBuf {
+        let file_name: String = name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        if file_name == name {
+            self.dir.join(format!("{}.snap", file_name))
+        } else {
+            self.dir.join(format!("{}-{:08x}.snap", file_name, fnv1a(name)))
+        }
+    }
+    
+    /// Compare `actual` with the snapshot called `name`, creating or
+    /// updating the file when appropriate
+    pub fn check(&self, name: &str, actual: &str) -> io::Result<SnapshotOutcome> {
+        let path = self.path(name);
+        
+        let expected = match fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.write(&path, actual)?;
+                return Ok(SnapshotOutcome::Created);
+            }
+            Err(e) => return Err(e),
+        };
+        
+        if expected == actual {
+            Ok(SnapshotOutcome::Matched)
+        } else if self.update {
+            self.write(&path, actual)?;
+            Ok(SnapshotOutcome::Updated)
+        } else {
+            Ok(SnapshotOutcome::Mismatch { diff: line_diff(&expected, actual) })
+        }
+    }
+    
+    /// Like `check`, but panics with a readable diff on a mismatch
+    pub fn assert(&self, name: &str, actual: &str) {
+        match self.check(name, actual) {
+            Ok(SnapshotOutcome::Mismatch { diff }) => panic!(
+                "Snapshot '{}' does not match {}\n{}\nRe-run with {}=1 to accept the new output",
+                name,
+                self.path(name).display(),
+                diff,
+                UPDATE_ENV_VAR
+            ),
+            Ok(_) => {}
+            Err(e) => panic!("Could not access snapshot '{}': {}", name, e),
+        }
+    }
+    
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, contents)
+    }
+}
+
+/// Check `actual` against the snapshot called `name` in this crate's
+/// `snapshots` directory, panicking with a diff if it changed
+pub fn assert_snapshot(name: &str, actual: &str) {
+    SnapshotStore::from_env().assert(name, actual);
+}
+
+/// 32-bit FNV-1a hash, which stays the same across runs and Rust versions
+fn fnv1a(text: &str) -> u32 {
+    text.bytes().fold(0x811c9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
+
+/// Line-by-line diff of two texts: `-` lines are only in `expected`,
+/// `+` lines are only in `actual`, and unchanged lines are indented
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    
+    // lcs[i][j] is the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_first_run_creates_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        
+        assert_eq!(store.check("greeting", "Hello\nWorld\n").unwrap(), SnapshotOutcome::Created);
+        assert_eq!(fs::read_to_string(store.path("greeting")).unwrap(), "Hello\nWorld\n");
+    }
+    
+    #[test]
+    fn test_matching_value_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        
+        store.assert("greeting", "Hello\nWorld\n");
+        assert_eq!(store.check("greeting", "Hello\nWorld\n").unwrap(), SnapshotOutcome::Matched);
+        store.assert("greeting", "Hello\nWorld\n");
+    }
+    
+    #[test]
+    fn test_changed_value_fails_with_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        store.check("greeting", "Hello\nWorld\n!\n").unwrap();
+        
+        let outcome = store.check("greeting", "Hello\nRust\n!\n").unwrap();
+        assert_eq!(outcome, SnapshotOutcome::Mismatch {
+            diff: "  Hello\n- World\n+ Rust\n  !\n".to_string(),
+        });
+        
+        let panic = std::panic::catch_unwind(|| store.assert("greeting", "Hello\nRust\n!\n")).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("Snapshot 'greeting' does not match"));
+        assert!(message.contains("- World\n+ Rust"));
+        assert!(message.contains(UPDATE_ENV_VAR));
+        
+        // A failed comparison leaves the stored snapshot alone
+        assert_eq!(fs::read_to_string(store.path("greeting")).unwrap(), "Hello\nWorld\n!\n");
+    }
+    
+    #[test]
+    fn test_sanitized_names_do_not_collide() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        
+        assert_eq!(store.path("a_b"), dir.path().join("a_b.snap"));
+        assert_ne!(store.path("a/b"), store.path("a_b"));
+        assert_ne!(store.path("a/b"), store.path("a b"));
+        assert_eq!(store.path("a/b"), store.path("a/b"));
+        
+        store.check("a/b", "slash\n").unwrap();
+        assert_eq!(store.check("a_b", "underscore\n").unwrap(), SnapshotOutcome::Created);
+        assert_eq!(store.check("a/b", "slash\n").unwrap(), SnapshotOutcome::Matched);
+    }
+    
+    #[test]
+    fn test_update_mode_rewrites_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        SnapshotStore::new(dir.path()).check("greeting", "old\n").unwrap();
+        
+        let store = SnapshotStore::new(dir.path()).with_update(true);
+        assert_eq!(store.check("greeting", "new\n").unwrap(), SnapshotOutcome::Updated);
+        assert_eq!(fs::read_to_string(store.path("greeting")).unwrap(), "new\n");
+    }
+}
@@ -0,0 +1,305 @@
+//! A bounded multi-producer, multi-consumer work queue
+//!
+//! Shared by the thread pool and the web scraper, whose workers all take
+//! jobs from a `WorkQueue` until it's closed and empty.
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// A FIFO queue that any number of threads can push to and pop from
+///
+/// A bounded queue makes producers wait while it's full, so a slow pool
+/// slows down whoever is feeding it instead of buffering without limit.
+/// Closing the queue stops new items from being pushed; consumers keep
+/// popping what's left and then get `None`, so nothing queued before the
+/// close is lost.
+///
+/// # Examples
+///
+/// ```
+/// use work_queue::WorkQueue;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let queue = Arc::new(WorkQueue::bounded(2));
+///
+/// let consumer = {
+///     let queue = Arc::clone(&queue);
+///     thread::spawn(move || {
+///         let mut total = 0;
+///         while let Some(n) = queue.pop() {
+///             total += n;
+///         }
+///         total
+///     })
+/// };
+///
+/// for n in 1..=10 {
+///     queue.push(n).unwrap();
+/// }
+/// queue.close();
+///
+/// assert_eq!(consumer.join().unwrap(), 55);
+/// ```
+pub struct WorkQueue<T> {
+    state: Mutex<QueueState<T>>,
+    /// Signalled when an item is pushed or the queue is closed
+    not_empty: Condvar,
+    /// Signalled when an item is popped or the queue is closed
+    not_full: Condvar,
+    capacity: usize,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+/// Why an item couldn't be pushed; the item is handed back either way
+#[derive(Debug, PartialEq, Eq)]
+pub enum PushError<T> {
+    /// The queue is at capacity (only from `try_push`)
+    Full(T),
+    /// The queue has been closed
+    Closed(T),
+}
+
+impl<T> PushError<T> {
+    /// Get back the item that wasn't pushed
+    pub fn into_inner(self) -> T {
+        match self {
+            PushError::Full(item) | PushError::Closed(item) => item,
+        }
+    }
+}
+
+impl<T> std::fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::Full(_) => write!(f, "Work queue is full"),
+            PushError::Closed(_) => write!(f, "Work queue is closed"),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for PushError<T> {}
+
+impl<T> WorkQueue<T> {
+    /// Create a queue that holds at most `capacity` items
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0, as nothing could ever be pushed.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "Work queue capacity must be greater than 0");
+        WorkQueue {
+            state: Mutex::new(QueueState {
+                items: VecDeque::new(),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// Create a queue with no limit on how many items it holds
+    pub fn unbounded() -> Self {
+        Self::bounded(usize::MAX)
+    }
+
+    /// Add an item to the back of the queue, waiting while it's full
+    ///
+    /// Fails with `PushError::Closed` if the queue is closed, including
+    /// while waiting for room.
+    pub fn push(&self, item: T) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        while !state.closed && state.items.len() >= self.capacity {
+            state = self.not_full.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        self.push_locked(state, item)
+    }
+
+    /// Add an item to the back of the queue if there's room right now
+    pub fn try_push(&self, item: T) -> Result<(), PushError<T>> {
+        let state = self.lock();
+        if !state.closed && state.items.len() >= self.capacity {
+            return Err(PushError::Full(item));
+        }
+        self.push_locked(state, item)
+    }
+
+    fn push_locked(&self, mut state: MutexGuard<'_, QueueState<T>>, item: T) -> Result<(), PushError<T>> {
+        if state.closed {
+            return Err(PushError::Closed(item));
+        }
+        state.items.push_back(item);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Take the item at the front of the queue, waiting while it's empty
+    ///
+    /// Returns `None` once the queue is closed and every item pushed before
+    /// the close has been taken.
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.not_full.notify_one();
+                return Some(item);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.not_empty.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Stop accepting items and wake everyone waiting on the queue
+    ///
+    /// Items already in the queue stay there for consumers to pop.
+    /// Closing an already closed queue does nothing.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Check whether the queue has been closed
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Get the number of items waiting in the queue
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    /// Check whether no items are waiting in the queue
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the most items the queue holds at once
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// No user code runs with the lock held, so a poisoned lock still
+    /// guards a consistent queue
+    fn lock(&self) -> MutexGuard<'_, QueueState<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_close_drains_remaining_items() {
+        let queue = WorkQueue::unbounded();
+        for n in 0..5 {
+            queue.push(n).unwrap();
+        }
+        queue.close();
+
+        assert_eq!(queue.push(5), Err(PushError::Closed(5)));
+        for n in 0..5 {
+            assert_eq!(queue.pop(), Some(n));
+        }
+        // Then EOF, every time
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_bounded_queue_applies_backpressure() {
+        let queue = Arc::new(WorkQueue::bounded(2));
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        assert_eq!(queue.try_push(3), Err(PushError::Full(3)));
+
+        let pushed = Arc::new(AtomicBool::new(false));
+        let producer = {
+            let (queue, pushed) = (Arc::clone(&queue), Arc::clone(&pushed));
+            thread::spawn(move || {
+                queue.push(3).unwrap();
+                pushed.store(true, Ordering::SeqCst);
+            })
+        };
+
+        // The producer stays blocked until there's room
+        thread::sleep(Duration::from_millis(50));
+        assert!(!pushed.load(Ordering::SeqCst));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.pop(), Some(1));
+        producer.join().unwrap();
+        assert!(pushed.load(Ordering::SeqCst));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_close_wakes_blocked_producer() {
+        let queue = Arc::new(WorkQueue::bounded(1));
+        queue.push(1).unwrap();
+
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push(2))
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.close();
+
+        assert_eq!(producer.join().unwrap(), Err(PushError::Closed(2)));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_consumers_exit_after_close_without_losing_items() {
+        let queue = Arc::new(WorkQueue::bounded(8));
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut taken = Vec::new();
+                    while let Some(item) = queue.pop() {
+                        taken.push(item);
+                    }
+                    taken
+                })
+            })
+            .collect();
+
+        let producers: Vec<_> = (0..3)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for n in 0..1000 {
+                        queue.push(p * 1000 + n).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+
+        let mut taken: Vec<i32> = consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect();
+        taken.sort_unstable();
+        assert_eq!(taken, (0..3000).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+}